@@ -0,0 +1,248 @@
+//! Minimal parser for IPFS content identifiers (CIDs).
+//!
+//! Both CIDv0 (base58btc encoded sha2-256 multihashes, starting with `Qm`) and
+//! CIDv1 (multibase prefixed `<version><codec><multihash>`) are understood. The
+//! parser only validates the structure of a CID, it does not fetch anything.
+
+use crate::traits::ReferenceValidator;
+use sp_std::vec;
+use sp_std::vec::Vec;
+
+/// Prefix that may be put in front of an IPFS reference.
+pub const IPFS_SCHEME: &[u8] = b"ipfs://";
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE36_ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE32_UPPER_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE16_ALPHABET: &[u8] = b"0123456789abcdef";
+const BASE16_UPPER_ALPHABET: &[u8] = b"0123456789ABCDEF";
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Multihash code of sha2-256, the only hash function CIDv0 can use.
+pub const SHA2_256: u64 = 0x12;
+
+/// Hash functions that are accepted along with the size of their digest.
+const HASH_FUNCTIONS: &[(u64, usize)] = &[
+    (0x11, 20), // sha1
+    (SHA2_256, 32),
+    (0x13, 64),   // sha2-512
+    (0x14, 64),   // sha3-512
+    (0x15, 48),   // sha3-384
+    (0x16, 32),   // sha3-256
+    (0x1b, 32),   // keccak-256
+    (0x1e, 32),   // blake3
+    (0xb220, 32), // blake2b-256
+    (0xb240, 64), // blake2b-512
+    (0xb260, 32), // blake2s-256
+];
+
+/// Multicodec of the content of a CIDv0.
+pub const DAG_PB: u64 = 0x70;
+
+/// The parts of a CID that were checked by the parser.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cid {
+    pub version: u8,
+    pub codec: u64,
+    pub hash_function: u64,
+    pub digest_len: usize,
+}
+
+/// Why a CID was rejected.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CidError {
+    /// The CID is empty.
+    Empty,
+    /// The multibase prefix is not supported.
+    UnknownMultibase,
+    /// The CID contains characters that are not part of its base.
+    InvalidEncoding,
+    /// A varint is malformed or too large.
+    InvalidVarint,
+    /// The CID version is neither 0 nor 1.
+    UnknownVersion,
+    /// The hash function is not supported.
+    UnknownHashFunction,
+    /// The digest does not have the size of the hash function.
+    InvalidDigestLength,
+}
+
+/// Parse a raw CID, without any scheme or path.
+pub fn parse_cid(cid: &[u8]) -> Result<Cid, CidError> {
+    if cid.is_empty() {
+        return Err(CidError::Empty);
+    }
+
+    // A CIDv0 is always 46 base58btc characters starting with "Qm".
+    if cid.len() == 46 && cid.starts_with(b"Qm") {
+        let bytes = decode_base_n(cid, BASE58_ALPHABET)?;
+        let (hash_function, digest_len) = parse_multihash(&bytes)?;
+        if hash_function != SHA2_256 {
+            return Err(CidError::UnknownHashFunction);
+        }
+        return Ok(Cid {
+            version: 0,
+            codec: DAG_PB,
+            hash_function,
+            digest_len,
+        });
+    }
+
+    let bytes = decode_multibase(cid)?;
+    let (version, rest) = read_varint(&bytes)?;
+    if version != 1 {
+        return Err(CidError::UnknownVersion);
+    }
+    let (codec, rest) = read_varint(rest)?;
+    let (hash_function, digest_len) = parse_multihash(rest)?;
+
+    Ok(Cid {
+        version: 1,
+        codec,
+        hash_function,
+        digest_len,
+    })
+}
+
+/// Parse an IPFS reference. The CID can be preceded by `ipfs://` and followed
+/// by a path.
+pub fn parse_ipfs_reference(reference: &[u8]) -> Result<Cid, CidError> {
+    let reference = reference.strip_prefix(IPFS_SCHEME).unwrap_or(reference);
+    let cid = match reference.iter().position(|x| *x == b'/') {
+        Some(end) => &reference[..end],
+        None => reference,
+    };
+
+    parse_cid(cid)
+}
+
+/// Accept only well-formed IPFS references.
+pub struct IPFSReference;
+
+impl ReferenceValidator for IPFSReference {
+    fn is_valid(reference: &[u8]) -> bool {
+        parse_ipfs_reference(reference).is_ok()
+    }
+
+    fn benchmark_reference() -> Vec<u8> {
+        b"QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n".to_vec()
+    }
+}
+
+/// Check the `<hash function><digest length><digest>` layout of a multihash.
+fn parse_multihash(bytes: &[u8]) -> Result<(u64, usize), CidError> {
+    let (hash_function, rest) = read_varint(bytes)?;
+    let (digest_len, digest) = read_varint(rest)?;
+
+    let expected = HASH_FUNCTIONS
+        .iter()
+        .find(|(code, _)| *code == hash_function)
+        .map(|(_, len)| *len)
+        .ok_or(CidError::UnknownHashFunction)?;
+
+    if digest_len != expected as u64 || digest.len() != expected {
+        return Err(CidError::InvalidDigestLength);
+    }
+
+    Ok((hash_function, expected))
+}
+
+/// Read an unsigned LEB128 varint as used by multiformats. Return the value
+/// and what is left of the input.
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), CidError> {
+    let mut value: u64 = 0;
+    // Multiformats limit varints to 9 bytes.
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            // A varint must use the fewest bytes possible.
+            if *byte == 0 && i > 0 {
+                return Err(CidError::InvalidVarint);
+            }
+            return Ok((value, &bytes[i + 1..]));
+        }
+    }
+
+    Err(CidError::InvalidVarint)
+}
+
+/// Decode a multibase string, which starts with a character naming its base.
+fn decode_multibase(text: &[u8]) -> Result<Vec<u8>, CidError> {
+    let (prefix, data) = text.split_first().ok_or(CidError::Empty)?;
+    if data.is_empty() {
+        return Err(CidError::Empty);
+    }
+
+    match prefix {
+        b'b' => decode_bits(data, BASE32_ALPHABET, 5),
+        b'B' => decode_bits(data, BASE32_UPPER_ALPHABET, 5),
+        b'f' => decode_bits(data, BASE16_ALPHABET, 4),
+        b'F' => decode_bits(data, BASE16_UPPER_ALPHABET, 4),
+        b'm' => decode_bits(data, BASE64_ALPHABET, 6),
+        b'u' => decode_bits(data, BASE64_URL_ALPHABET, 6),
+        b'z' => decode_base_n(data, BASE58_ALPHABET),
+        b'k' => decode_base_n(data, BASE36_ALPHABET),
+        _ => Err(CidError::UnknownMultibase),
+    }
+}
+
+fn digit(alphabet: &[u8], c: u8) -> Result<u32, CidError> {
+    alphabet
+        .iter()
+        .position(|x| *x == c)
+        .map(|x| x as u32)
+        .ok_or(CidError::InvalidEncoding)
+}
+
+/// Decode an unpadded power of two base where each character holds `bits` bits.
+fn decode_bits(text: &[u8], alphabet: &[u8], bits: u32) -> Result<Vec<u8>, CidError> {
+    let mut out = Vec::with_capacity(text.len() * bits as usize / 8);
+    let mut buffer: u32 = 0;
+    let mut buffered: u32 = 0;
+
+    for c in text {
+        buffer = (buffer << bits) | digit(alphabet, *c)?;
+        buffered += bits;
+        if buffered >= 8 {
+            buffered -= 8;
+            out.push((buffer >> buffered) as u8);
+            buffer &= (1 << buffered) - 1;
+        }
+    }
+
+    // Leftovers are only allowed to be the zero padding of the last byte.
+    if buffered >= bits || buffer != 0 {
+        return Err(CidError::InvalidEncoding);
+    }
+
+    Ok(out)
+}
+
+/// Decode a base that is not a power of two (base58btc, base36). Leading
+/// zero digits stand for leading zero bytes.
+fn decode_base_n(text: &[u8], alphabet: &[u8]) -> Result<Vec<u8>, CidError> {
+    let base = alphabet.len() as u32;
+    let zeros = text.iter().take_while(|c| **c == alphabet[0]).count();
+    // Big endian number, without the leading zero bytes.
+    let mut number: Vec<u8> = Vec::with_capacity(text.len());
+
+    for c in &text[zeros..] {
+        let mut carry = digit(alphabet, *c)?;
+        for byte in number.iter_mut().rev() {
+            carry += (*byte as u32) * base;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            number.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut out = vec![0u8; zeros];
+    out.extend(number);
+    Ok(out)
+}
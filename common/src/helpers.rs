@@ -1,3 +1,14 @@
+use frame_support::dispatch::DispatchResult;
+use frame_support::traits::tokens::fungibles;
+use frame_support::traits::{Currency, ExistenceRequirement, Get};
+use frame_support::BoundedVec;
+use sp_runtime::traits::{AtLeast32BitUnsigned, Zero};
+use sp_std::convert::TryInto;
+use sp_std::marker::PhantomData;
+use sp_std::vec::Vec;
+use ternoa_primitives::marketplace::{MarketplaceCommission, PaymentAsset};
+use ternoa_primitives::TextFormat;
+
 pub fn check_bounds<T>(src_len: usize, min_len: (u16, T), max_len: (u16, T)) -> Result<(), T> {
     if src_len < min_len.0 as usize {
         return Err(min_len.1);
@@ -8,3 +19,102 @@ pub fn check_bounds<T>(src_len: usize, min_len: (u16, T), max_len: (u16, T)) ->
 
     Ok(())
 }
+
+/// Check the length of a text like `check_bounds` does and turn it into the
+/// bounded vector that is kept in storage.
+pub fn bounded_text<E, S: Get<u32>>(
+    text: TextFormat,
+    min_len: (u16, E),
+    max_len: (u16, E),
+) -> Result<BoundedVec<u8, S>, E> {
+    if text.len() < min_len.0 as usize {
+        return Err(min_len.1);
+    }
+    if text.len() > max_len.0 as usize {
+        return Err(max_len.1);
+    }
+
+    text.try_into().map_err(|_| max_len.1)
+}
+
+/// Turn one of our `Get<u16>` length limits into the `Get<u32>` that
+/// `BoundedVec` expects.
+pub struct U16ToU32<G>(PhantomData<G>);
+
+impl<G: Get<u16>> Get<u32> for U16ToU32<G> {
+    fn get() -> u32 {
+        G::get().into()
+    }
+}
+
+/// Cut a vector down to its bound. This is meant for migrations of data that
+/// was stored before the bound existed.
+pub fn truncate_to_bound<T, S: Get<u32>>(mut value: Vec<T>) -> BoundedVec<T, S> {
+    value.truncate(S::get() as usize);
+    value
+        .try_into()
+        .expect("The vector has been truncated to its bound; qed")
+}
+
+/// Whether a vector fits in its bound. Migrations use it to find the data that
+/// cannot be bounded before anything gets changed.
+pub fn fits_bound<T, S: Get<u32>>(value: &[T]) -> bool {
+    value.len() <= S::get() as usize
+}
+
+/// Amount a marketplace takes out of a sale made at `price`. The rate is
+/// rounded down and the flat fee is added on top, but the commission never
+/// exceeds the price itself.
+pub fn marketplace_commission<Balance: AtLeast32BitUnsigned + Copy>(
+    commission: &MarketplaceCommission<Balance>,
+    price: Balance,
+) -> Balance {
+    commission
+        .rate
+        .mul_floor(price)
+        .saturating_add(commission.flat)
+        .min(price)
+}
+
+/// Whether `dest` can receive `amount` of `asset`. An account that holds none
+/// of the asset cannot be paid less than its minimum balance.
+pub fn can_receive_payment<AccountId, Native, Assets>(
+    asset: &PaymentAsset<Assets::AssetId>,
+    dest: &AccountId,
+    amount: Native::Balance,
+) -> bool
+where
+    Native: Currency<AccountId>,
+    Assets: fungibles::Inspect<AccountId, Balance = Native::Balance>,
+{
+    match asset {
+        PaymentAsset::Native => {
+            amount >= Native::minimum_balance() || !Native::total_balance(dest).is_zero()
+        }
+        PaymentAsset::Asset(id) => {
+            amount >= Assets::minimum_balance(*id) || !Assets::balance(*id, dest).is_zero()
+        }
+    }
+}
+
+/// Move `amount` of `asset` from `source` to `dest`. The native token goes
+/// through `Native` and every other asset through `Assets`.
+pub fn transfer_payment<AccountId, Native, Assets>(
+    asset: &PaymentAsset<Assets::AssetId>,
+    source: &AccountId,
+    dest: &AccountId,
+    amount: Native::Balance,
+    existence: ExistenceRequirement,
+) -> DispatchResult
+where
+    Native: Currency<AccountId>,
+    Assets: fungibles::Transfer<AccountId, Balance = Native::Balance>,
+{
+    match asset {
+        PaymentAsset::Native => Native::transfer(source, dest, amount, existence),
+        PaymentAsset::Asset(id) => {
+            let keep_alive = existence == ExistenceRequirement::KeepAlive;
+            Assets::transfer(*id, source, dest, amount, keep_alive).map(|_| ())
+        }
+    }
+}
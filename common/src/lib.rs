@@ -2,5 +2,9 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod cid;
 pub mod helpers;
 pub mod traits;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,144 @@
+use crate::cid::{parse_cid, parse_ipfs_reference, Cid, CidError, IPFSReference, DAG_PB, SHA2_256};
+use crate::traits::ReferenceValidator;
+
+/// The same sha2-256 raw CIDv1 written in every supported multibase.
+const RAW_CIDS: &[&str] = &[
+    "bafkreicjyjnm2rfq3rjb5bv6uwkutqwmvvp7ld7pfl6caaefa57gwmdwpq",
+    "BAFKREICJYJNM2RFQ3RJB5BV6UWKUTQWMVVP7LD7PFL6CAAEFA57GWMDWPQ",
+    "f0155122049c25acd44b0dc521e86bea59549c2ccad5ff58fef2afc200085077e6b30767c",
+    "F0155122049C25ACD44B0DC521E86BEA59549C2CCAD5FF58FEF2AFC200085077E6B30767C",
+    "zb2rhbcCqsfjwAF3WgtQNTnrgGLZ7kEeRodUbrgg8qWWo9rYK",
+    "mAVUSIEnCWs1EsNxSHoa+pZVJwsytX/WP7yr8IACFB35rMHZ8",
+    "uAVUSIEnCWs1EsNxSHoa-pZVJwsytX_WP7yr8IACFB35rMHZ8",
+    "k2cwueahl2trc7vffizt2mz99eyoo4r4vlayhn72ct28dptsvjka4t4s",
+];
+
+const RAW: u64 = 0x55;
+
+fn cid_v1(codec: u64, hash_function: u64) -> Cid {
+    Cid {
+        version: 1,
+        codec,
+        hash_function,
+        digest_len: 32,
+    }
+}
+
+#[test]
+fn cid_v0() {
+    let expected = Cid {
+        version: 0,
+        codec: DAG_PB,
+        hash_function: SHA2_256,
+        digest_len: 32,
+    };
+
+    let ok = parse_cid(b"QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n");
+    assert_eq!(ok, Ok(expected));
+    let ok = parse_cid(b"QmTJaMpTW5uE6KqLRGhWAmssnr2jvwUtZq4cNTwvkXBgLb");
+    assert_eq!(ok, Ok(expected));
+}
+
+#[test]
+fn cid_v1_multibases() {
+    for cid in RAW_CIDS {
+        assert_eq!(
+            parse_cid(cid.as_bytes()),
+            Ok(cid_v1(RAW, SHA2_256)),
+            "{}",
+            cid
+        );
+    }
+}
+
+#[test]
+fn cid_v1_codecs_and_hashes() {
+    let cid = b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    assert_eq!(parse_cid(cid), Ok(cid_v1(DAG_PB, SHA2_256)));
+
+    // dag-json, whose code takes two bytes
+    let cid = b"baguqeerajhbfvtkewdofehugx2szksoczswv75mp54vpyiaaqudx42zqoz6a";
+    assert_eq!(parse_cid(cid), Ok(cid_v1(0x0129, SHA2_256)));
+
+    // dag-cbor hashed with blake2b-256
+    let cid = b"bafy2bzacedpgedtjfrxj7w54rwsy4lrylz23qikawm75ph3qhdvrfl3wrsiwi";
+    assert_eq!(parse_cid(cid), Ok(cid_v1(0x71, 0xb220)));
+}
+
+#[test]
+fn ipfs_references() {
+    let cid = "bafkreicjyjnm2rfq3rjb5bv6uwkutqwmvvp7ld7pfl6caaefa57gwmdwpq";
+    let expected = Ok(cid_v1(RAW, SHA2_256));
+
+    assert_eq!(parse_ipfs_reference(cid.as_bytes()), expected);
+    let reference = format!("ipfs://{}", cid);
+    assert_eq!(parse_ipfs_reference(reference.as_bytes()), expected);
+    let reference = format!("ipfs://{}/metadata.json", cid);
+    assert_eq!(parse_ipfs_reference(reference.as_bytes()), expected);
+
+    assert!(IPFSReference::is_valid(
+        b"QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n"
+    ));
+    assert!(!IPFSReference::is_valid(b"https://ternoa.com"));
+    assert!(<() as ReferenceValidator>::is_valid(b"https://ternoa.com"));
+}
+
+#[test]
+fn invalid_cids() {
+    let cases: &[(&[u8], CidError)] = &[
+        (b"", CidError::Empty),
+        (b"b", CidError::Empty),
+        (b"ipfs://", CidError::Empty),
+        (b"hello world", CidError::UnknownMultibase),
+        // CIDv0 with a character that base58 does not have
+        (
+            b"QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR10",
+            CidError::InvalidEncoding,
+        ),
+        // CIDv0 that is too short
+        (
+            b"QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1",
+            CidError::UnknownMultibase,
+        ),
+        // Uppercase characters in a lowercase base32 CID
+        (
+            b"bAFKREICJYJNM2RFQ3RJB5BV6UWKUTQWMVVP7LD7PFL6CAAEFA57GWMDWPQ",
+            CidError::InvalidEncoding,
+        ),
+        // base16 CID with an odd number of characters
+        (
+            b"f0155122049c25acd44b0dc521e86bea59549c2ccad5ff58fef2afc200085077e6b30767",
+            CidError::InvalidEncoding,
+        ),
+        // CIDv2
+        (
+            b"bajkreicjyjnm2rfq3rjb5bv6uwkutqwmvvp7ld7pfl6caaefa57gwmdwpq",
+            CidError::UnknownVersion,
+        ),
+        // Version 1 encoded with a needlessly long varint
+        (
+            b"bqeafkerajhbfvtkewdofehugx2szksoczswv75mp54vpyiaaqudx42zqoz6a",
+            CidError::InvalidVarint,
+        ),
+        // Unknown hash function
+        (
+            b"bafkzsajajhbfvtkewdofehugx2szksoczswv75mp54vpyiaaqudx42zqoz6a",
+            CidError::UnknownHashFunction,
+        ),
+        // Digest that is one byte short
+        (
+            b"bafkreicjyjnm2rfq3rjb5bv6uwkutqwmvvp7ld7pfl6caaefa57gwmdw",
+            CidError::InvalidDigestLength,
+        ),
+        // Digest length that does not match sha2-256
+        (
+            b"bafkreh2jyjnm2rfq3rjb5bv6uwkutqwmvvp7ld7pfl6caaefa57gwmdw",
+            CidError::InvalidDigestLength,
+        ),
+    ];
+
+    for (cid, error) in cases {
+        let cid_text = String::from_utf8_lossy(cid);
+        assert_eq!(parse_ipfs_reference(cid), Err(*error), "{}", cid_text);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::helpers::marketplace_commission;
+use sp_runtime::Permill;
+use ternoa_primitives::marketplace::MarketplaceCommission;
+
+#[test]
+fn commission_rate_is_exact() {
+    let commission = MarketplaceCommission::new(Permill::from_percent(30), 0u128);
+    assert_eq!(marketplace_commission(&commission, 100), 30);
+
+    let commission = MarketplaceCommission::new(Permill::from_percent(70), 0u128);
+    assert_eq!(marketplace_commission(&commission, 100), 70);
+
+    let commission = MarketplaceCommission::new(Permill::from_parts(12_345), 0u128);
+    assert_eq!(marketplace_commission(&commission, 1_000_000), 12_345);
+}
+
+#[test]
+fn commission_rate_rounds_down() {
+    let commission = MarketplaceCommission::new(Permill::from_percent(10), 0u128);
+    assert_eq!(marketplace_commission(&commission, 99), 9);
+}
+
+#[test]
+fn commission_adds_flat_fee() {
+    let commission = MarketplaceCommission::new(Permill::from_percent(10), 5u128);
+    assert_eq!(marketplace_commission(&commission, 100), 15);
+
+    let commission = MarketplaceCommission::new(Permill::zero(), 5u128);
+    assert_eq!(marketplace_commission(&commission, 100), 5);
+}
+
+#[test]
+fn commission_is_capped_by_price() {
+    let commission = MarketplaceCommission::new(Permill::from_percent(50), 80u128);
+    assert_eq!(marketplace_commission(&commission, 100), 100);
+
+    let commission = MarketplaceCommission::new(Permill::one(), u128::MAX);
+    assert_eq!(marketplace_commission(&commission, 100), 100);
+}
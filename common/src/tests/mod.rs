@@ -0,0 +1,2 @@
+mod cid;
+mod helpers;
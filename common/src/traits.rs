@@ -1,10 +1,28 @@
-use frame_support::dispatch::{DispatchErrorWithPostInfo, DispatchResult};
-use ternoa_primitives::marketplace::{MarketplaceId, MarketplaceInformation, MarketplaceType};
-use ternoa_primitives::nfts::{NFTData, NFTId, NFTSeriesId};
+use frame_support::dispatch::{DispatchError, DispatchErrorWithPostInfo, DispatchResult};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use sp_runtime::Permill;
+use sp_std::fmt::Debug;
+use sp_std::vec;
+use sp_std::vec::Vec;
+use ternoa_primitives::marketplace::{
+    MarketplaceCommission, MarketplaceId, MarketplaceInformation, MarketplaceRole, MarketplaceType,
+    PaymentAsset,
+};
+use ternoa_primitives::nfts::{NFTData, NFTId, NFTLockReason, NFTSeriesId};
 use ternoa_primitives::TextFormat;
 
 pub trait NFTTrait {
-    type AccountId: Clone;
+    type AccountId: Clone + PartialEq + Debug;
+
+    /// Balance the mint fee is paid in.
+    type Balance;
+
+    /// Limit on the length of the stored IPFS references.
+    type IPFSLengthLimit: Get<u32>;
+
+    /// Limit on the length of the stored series ids.
+    type SeriesIdLengthLimit: Get<u32>;
 
     /// Change the owner of an NFT.
     fn set_owner(id: NFTId, owner: &Self::AccountId) -> DispatchResult;
@@ -12,6 +30,9 @@ pub trait NFTTrait {
     /// Return the owner of an NFT.
     fn owner(id: NFTId) -> Option<Self::AccountId>;
 
+    /// Change the share of the secondary sales of an NFT paid to its creator.
+    fn set_royalty(id: NFTId, royalty: Permill) -> DispatchResult;
+
     /// Is series completed(locked)
     fn is_nft_in_completed_series(id: NFTId) -> Option<bool>;
 
@@ -22,51 +43,217 @@ pub trait NFTTrait {
         series_id: Option<NFTSeriesId>,
     ) -> Result<NFTId, DispatchErrorWithPostInfo>;
 
+    /// Fee withdrawn from the owner by `create_nft`.
+    fn mint_fee() -> Self::Balance;
+
     /// Get NFT data
-    fn get_nft(id: NFTId) -> Option<NFTData<Self::AccountId>>;
+    fn get_nft(
+        id: NFTId,
+    ) -> Option<NFTData<Self::AccountId, Self::IPFSLengthLimit, Self::SeriesIdLengthLimit>>;
 
     /// Lock series WARNING: Only for benchmark purposes!
     fn benchmark_lock_series(series_id: NFTSeriesId);
 
-    /// TODO!
-    fn set_listed_for_sale(id: NFTId, value: bool) -> DispatchResult;
-
-    /// TODO!
-    fn is_listed_for_sale(id: NFTId) -> Option<bool>;
+    /// Create an NFT without billing the mint fee WARNING: Only for benchmark purposes!
+    fn benchmark_create_nft(owner: &Self::AccountId) -> NFTId;
 
-    /// TODO!
-    fn set_in_transmission(id: NFTId, value: bool) -> DispatchResult;
+    /// Lock an NFT for the given reason. Fails if the NFT is already locked.
+    fn lock(id: NFTId, reason: NFTLockReason) -> DispatchResult;
 
-    /// TODO!
-    fn is_in_transmission(id: NFTId) -> Option<bool>;
+    /// Remove the lock of an NFT. Fails if the NFT is not locked for the given reason.
+    fn unlock(id: NFTId, reason: NFTLockReason) -> DispatchResult;
 
-    /// TODO!
-    fn set_converted_to_capsule(id: NFTId, value: bool) -> DispatchResult;
-
-    /// TODO!
-    fn is_converted_to_capsule(id: NFTId) -> Option<bool>;
+    /// Return whether an NFT is locked for the given reason.
+    fn is_locked(id: NFTId, reason: NFTLockReason) -> Option<bool>;
 
     /// Set a series to be either completed or not-completed.
     fn set_series_completion(series_id: &NFTSeriesId, value: bool) -> DispatchResult;
+}
+
+/// Lets other pallets hand capsules over to someone else.
+pub trait CapsuleTrait<AccountId> {
+    /// Give a capsule and the funds it holds to a new owner, along with its NFT.
+    fn set_capsule_owner(id: NFTId, owner: &AccountId) -> DispatchResult;
+}
+
+/// There are no capsules.
+impl<AccountId> CapsuleTrait<AccountId> for () {
+    fn set_capsule_owner(_id: NFTId, _owner: &AccountId) -> DispatchResult {
+        Err("Capsules are not supported".into())
+    }
+}
+
+/// Check applied to the references (IPFS references, URIs) that pallets store.
+pub trait ReferenceValidator {
+    /// Return whether the reference can be stored.
+    fn is_valid(reference: &[u8]) -> bool;
+
+    /// Return a reference that passes the check WARNING: Only for benchmark purposes!
+    fn benchmark_reference() -> Vec<u8>;
+}
+
+/// Accept every reference.
+impl ReferenceValidator for () {
+    fn is_valid(_reference: &[u8]) -> bool {
+        true
+    }
+
+    fn benchmark_reference() -> Vec<u8> {
+        vec![1]
+    }
+}
+
+/// Implemented by the pallets that lock NFTs. When governance takes over an
+/// NFT, every handler gets a chance to drop what it knows about the NFT and to
+/// refund the funds tied to it. The NFTs pallet removes the lock afterwards.
+pub trait NFTReleaseHandler {
+    /// Release an NFT that is locked for `reason` and return the weight that was
+    /// used. Handlers that do not own the lock must do nothing.
+    fn release_nft(id: NFTId, reason: NFTLockReason) -> Result<Weight, DispatchError>;
+
+    /// Most weight that `release_nft` can use, charged before it is called.
+    fn max_release_weight() -> Weight;
+}
 
-    /// Set the NFT viewer to a value.
-    fn set_viewer(id: NFTId, value: Option<Self::AccountId>) -> DispatchResult;
+/// No pallet locks NFTs.
+impl NFTReleaseHandler for () {
+    fn release_nft(_id: NFTId, _reason: NFTLockReason) -> Result<Weight, DispatchError> {
+        Ok(0)
+    }
+
+    fn max_release_weight() -> Weight {
+        0
+    }
+}
+
+macro_rules! impl_nft_release_handler_for_tuples {
+    ($($handler:ident),+) => {
+        impl<$($handler: NFTReleaseHandler),+> NFTReleaseHandler for ($($handler,)+) {
+            fn release_nft(id: NFTId, reason: NFTLockReason) -> Result<Weight, DispatchError> {
+                let mut weight: Weight = 0;
+                $(weight = weight.saturating_add($handler::release_nft(id, reason)?);)+
+                Ok(weight)
+            }
+
+            fn max_release_weight() -> Weight {
+                let mut weight: Weight = 0;
+                $(weight = weight.saturating_add($handler::max_release_weight());)+
+                weight
+            }
+        }
+    };
+}
+
+impl_nft_release_handler_for_tuples!(A);
+impl_nft_release_handler_for_tuples!(A, B);
+impl_nft_release_handler_for_tuples!(A, B, C);
+impl_nft_release_handler_for_tuples!(A, B, C, D);
+impl_nft_release_handler_for_tuples!(A, B, C, D, E);
+impl_nft_release_handler_for_tuples!(A, B, C, D, E, F);
+
+/// Implemented by the pallets that host NFTs on marketplaces. When a marketplace
+/// closes, every handler takes down what it hosts there, a page at a time.
+pub trait MarketplaceCloseHandler {
+    /// Settle or cancel up to `limit` items hosted on a closing marketplace and
+    /// return how many were handled.
+    fn close_marketplace(marketplace_id: MarketplaceId, limit: u32) -> Result<u32, DispatchError>;
+
+    /// Weight of handling up to `limit` items of a closing marketplace.
+    fn close_marketplace_weight(limit: u32) -> Weight;
+}
+
+/// No pallet hosts anything on marketplaces.
+impl MarketplaceCloseHandler for () {
+    fn close_marketplace(
+        _marketplace_id: MarketplaceId,
+        _limit: u32,
+    ) -> Result<u32, DispatchError> {
+        Ok(0)
+    }
+
+    fn close_marketplace_weight(_limit: u32) -> Weight {
+        0
+    }
 }
 
+macro_rules! impl_marketplace_close_handler_for_tuples {
+    ($($handler:ident),+) => {
+        impl<$($handler: MarketplaceCloseHandler),+> MarketplaceCloseHandler for ($($handler,)+) {
+            fn close_marketplace(
+                marketplace_id: MarketplaceId,
+                limit: u32,
+            ) -> Result<u32, DispatchError> {
+                let mut handled = 0u32;
+                $(handled = handled.saturating_add(
+                    $handler::close_marketplace(marketplace_id, limit.saturating_sub(handled))?,
+                );)+
+                Ok(handled)
+            }
+
+            fn close_marketplace_weight(limit: u32) -> Weight {
+                let mut weight: Weight = 0;
+                $(weight = weight.saturating_add($handler::close_marketplace_weight(limit));)+
+                weight
+            }
+        }
+    };
+}
+
+impl_marketplace_close_handler_for_tuples!(A);
+impl_marketplace_close_handler_for_tuples!(A, B);
+impl_marketplace_close_handler_for_tuples!(A, B, C);
+
 /// Trait that implements basic functionalities related to Ternoa Marketplace
 /// TODO: Expand trait with more useful functions
-pub trait MarketplaceTrait<AccountId> {
+pub trait MarketplaceTrait<AccountId: Clone + PartialEq + Debug, Balance: Clone + PartialEq + Debug>
+{
+    /// Limit on the length of a marketplace name.
+    type NameLengthLimit: Get<u32>;
+
+    /// Limit on the length of a marketplace uri.
+    type URILengthLimit: Get<u32>;
+
+    /// Limit on the length of a marketplace description.
+    type DescriptionLengthLimit: Get<u32>;
+
+    /// Id of the assets that can be used as payment.
+    type AssetId: Clone + PartialEq + Debug;
+
     /// Return if an account is permitted to list on given marketplace
     fn is_allowed_to_list(marketplace_id: MarketplaceId, account_id: AccountId) -> DispatchResult;
 
+    /// Return whether an account is the owner of a marketplace or was granted
+    /// at least `role` on it.
+    fn has_role(
+        marketplace_id: MarketplaceId,
+        account_id: &AccountId,
+        role: MarketplaceRole,
+    ) -> bool;
+
+    /// Return an error if the marketplace does not accept payments with `asset`.
+    fn is_asset_accepted(
+        marketplace_id: MarketplaceId,
+        asset: &PaymentAsset<Self::AssetId>,
+    ) -> DispatchResult;
+
     /// Return marketplace
-    fn get_marketplace(marketplace_id: MarketplaceId) -> Option<MarketplaceInformation<AccountId>>;
+    fn get_marketplace(
+        marketplace_id: MarketplaceId,
+    ) -> Option<
+        MarketplaceInformation<
+            AccountId,
+            Balance,
+            Self::NameLengthLimit,
+            Self::URILengthLimit,
+            Self::DescriptionLengthLimit,
+        >,
+    >;
 
     /// create a new marketplace
     fn create(
         origin: AccountId,
         kind: MarketplaceType,
-        commission_fee: u8,
+        commission_fee: MarketplaceCommission<Balance>,
         name: TextFormat,
         uri: Option<TextFormat>,
         logo_uri: Option<TextFormat>,
@@ -1,9 +1,13 @@
 #![cfg(feature = "runtime-benchmarks")]
 
-use crate::{Account, Call, Config, Pallet, SupportedAccount, SupportedAccounts, Users};
+use crate::{
+    Account, AccountOf, Call, Config, Pallet, SupportedAccount, SupportedAccountOf,
+    SupportedAccounts, Users,
+};
 use frame_benchmarking::{account, benchmarks};
 use frame_support::assert_ok;
 use frame_system::RawOrigin;
+use sp_std::convert::TryInto;
 use sp_std::prelude::*;
 
 use crate::Pallet as AAcounts;
@@ -13,29 +17,29 @@ benchmarks! {
         let alice: T::AccountId = account("ALICE", 0, 0);
 
         // Add supported account
-        let supp = SupportedAccount::new(vec![20], 1, 20, true);
-        assert_ok!(AAcounts::<T>::add_new_supported_account(RawOrigin::Root.into(), supp.key.clone(), supp.min_length, supp.max_length, supp.initial_set_fee));
+        let supp: SupportedAccountOf<T> = SupportedAccount::new(vec![20].try_into().unwrap(), 1, 20, true);
+        assert_ok!(AAcounts::<T>::add_new_supported_account(RawOrigin::Root.into(), supp.key.to_vec(), supp.min_length, supp.max_length, supp.initial_set_fee));
 
-        let acc = Account {key: supp.key.clone(), value: vec![50]};
-    }: _(RawOrigin::Signed(alice.clone()), acc.key.clone(), acc.value.clone())
+        let acc: AccountOf<T> = Account::new(supp.key.clone(), vec![50].try_into().unwrap());
+    }: _(RawOrigin::Signed(alice.clone()), acc.key.to_vec(), acc.value.to_vec())
     verify {
-        assert_eq!(Users::<T>::get(&alice), Some(vec![acc]));
+        assert_eq!(Users::<T>::get(&alice), Some(vec![acc].try_into().unwrap()));
     }
 
     add_new_supported_account {
-        let supp = SupportedAccount::new(vec![20], 1, 20, true);
-    }: _(RawOrigin::Root, supp.key.clone(), supp.min_length, supp.max_length, supp.initial_set_fee)
+        let supp: SupportedAccountOf<T> = SupportedAccount::new(vec![20].try_into().unwrap(), 1, 20, true);
+    }: _(RawOrigin::Root, supp.key.to_vec(), supp.min_length, supp.max_length, supp.initial_set_fee)
     verify {
-        assert_eq!(SupportedAccounts::<T>::get(), vec![supp]);
+        assert_eq!(SupportedAccounts::<T>::get().to_vec(), vec![supp]);
     }
 
     remove_supported_account {
         // Add supported account
-        let supp = SupportedAccount::new(vec![20], 1, 20, true);
-        assert_ok!(AAcounts::<T>::add_new_supported_account(RawOrigin::Root.into(), supp.key.clone(), supp.min_length, supp.max_length, supp.initial_set_fee));
+        let supp: SupportedAccountOf<T> = SupportedAccount::new(vec![20].try_into().unwrap(), 1, 20, true);
+        assert_ok!(AAcounts::<T>::add_new_supported_account(RawOrigin::Root.into(), supp.key.to_vec(), supp.min_length, supp.max_length, supp.initial_set_fee));
 
-    }: _(RawOrigin::Root, supp.key)
+    }: _(RawOrigin::Root, supp.key.to_vec())
     verify {
-        assert_eq!(SupportedAccounts::<T>::get(), vec![]);
+        assert!(SupportedAccounts::<T>::get().is_empty());
     }
 }
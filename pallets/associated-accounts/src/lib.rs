@@ -7,6 +7,7 @@ mod benchmarking;
 mod tests;
 
 mod default_weights;
+mod migrations;
 mod types;
 
 pub use default_weights::WeightInfo;
@@ -14,34 +15,55 @@ use frame_support::traits::StorageVersion;
 pub use pallet::*;
 pub use types::*;
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
-    use sp_std::vec;
+    use sp_std::convert::TryInto;
     use sp_std::vec::Vec;
-    use ternoa_common::helpers::check_bounds;
+    use ternoa_common::helpers::{bounded_text, U16ToU32};
     use ternoa_primitives::TextFormat;
 
+    pub type KeyLimitOf<T> = U16ToU32<<T as Config>::MaxKeyLen>;
+    pub type ValueLimitOf<T> = U16ToU32<<T as Config>::MaxValueLen>;
+    pub type SupportedAccountOf<T> = SupportedAccount<KeyLimitOf<T>>;
+    pub type AccountOf<T> = Account<KeyLimitOf<T>, ValueLimitOf<T>>;
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// Because this pallet emits events, it depends on the runtime's definition of an event.
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
         /// Weight
         type WeightInfo: WeightInfo;
+
+        /// Max length of an account key
+        #[pallet::constant]
+        type MaxKeyLen: Get<u16>;
+
+        /// Max length of an account value
+        #[pallet::constant]
+        type MaxValueLen: Get<u16>;
+
+        /// Max number of supported accounts. This also limits how many
+        /// accounts a single user can set.
+        #[pallet::constant]
+        type MaxSupportedAccounts: Get<u32>;
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -58,30 +80,29 @@ pub mod pallet {
             let supported_account = supported_accounts.iter().find(|x| x.key == account_key);
             let supported_account = supported_account.ok_or(Error::<T>::UnknownAccountKey)?;
 
-            check_bounds(
-                account_value.len(),
+            let value = bounded_text(
+                account_value.clone(),
                 (supported_account.min_length, Error::<T>::ValueIsTooShort),
                 (supported_account.max_length, Error::<T>::ValueIsTooLong),
             )?;
 
             let mut pays_fee = true;
-            Users::<T>::mutate(&who, |maybe_accounts| {
-                if let Some(accounts) = maybe_accounts {
-                    let pos = accounts
-                        .iter()
-                        .position(|account| account.key == account_key);
-                    if let Some(pos) = pos {
-                        accounts[pos].value = account_value.clone();
-                    } else {
-                        accounts.push(Account::new(account_key.clone(), account_value.clone()));
-                        pays_fee = supported_account.initial_set_fee;
-                    }
+            Users::<T>::try_mutate(&who, |maybe_accounts| -> DispatchResult {
+                let accounts = maybe_accounts.get_or_insert_with(Default::default);
+                let account = accounts
+                    .iter_mut()
+                    .find(|account| account.key == account_key);
+                if let Some(account) = account {
+                    account.value = value;
                 } else {
-                    let account = Account::new(account_key.clone(), account_value.clone());
-                    *maybe_accounts = Some(vec![account]);
+                    let account = Account::new(supported_account.key.clone(), value);
+                    accounts
+                        .try_push(account)
+                        .map_err(|_| Error::<T>::TooManyAccounts)?;
                     pays_fee = supported_account.initial_set_fee;
                 }
-            });
+                Ok(())
+            })?;
 
             let event = Event::UserAccountAdded {
                 user: who,
@@ -107,14 +128,15 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             ensure_root(origin)?;
 
-            SupportedAccounts::<T>::mutate(|x| {
-                x.push(SupportedAccount {
-                    key: key.clone(),
-                    min_length,
-                    max_length,
-                    initial_set_fee,
-                });
-            });
+            let bounded_key: BoundedVec<u8, KeyLimitOf<T>> = key
+                .clone()
+                .try_into()
+                .map_err(|_| Error::<T>::KeyIsTooLong)?;
+            let supported_account =
+                SupportedAccount::new(bounded_key, min_length, max_length, initial_set_fee);
+
+            SupportedAccounts::<T>::try_mutate(|x| x.try_push(supported_account))
+                .map_err(|_| Error::<T>::TooManySupportedAccounts)?;
             let event = Event::SupportedAccountAdded {
                 key,
                 min_length,
@@ -174,22 +196,36 @@ pub mod pallet {
         ValueIsTooLong,
         /// That account key was not found.
         UnknownAccountKey,
+        /// Account key length is higher than the maximal allowed length.
+        KeyIsTooLong,
+        /// The maximum number of supported accounts has been reached.
+        TooManySupportedAccounts,
+        /// The user has already set the maximum number of accounts.
+        TooManyAccounts,
     }
 
     /// List of Altvr datas create.
     #[pallet::storage]
     #[pallet::getter(fn users)]
-    pub type Users<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::AccountId, Vec<Account>, OptionQuery>;
+    pub type Users<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<AccountOf<T>, T::MaxSupportedAccounts>,
+        OptionQuery,
+    >;
 
     #[pallet::storage]
     #[pallet::getter(fn supported_accounts)]
-    pub type SupportedAccounts<T: Config> = StorageValue<_, Vec<SupportedAccount>, ValueQuery>;
+    pub type SupportedAccounts<T: Config> =
+        StorageValue<_, BoundedVec<SupportedAccountOf<T>, T::MaxSupportedAccounts>, ValueQuery>;
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
-        pub users: Vec<(T::AccountId, Vec<Account>)>,
-        pub supported_accounts: Vec<SupportedAccount>,
+        /// Owner and its (key, value) accounts
+        pub users: Vec<(T::AccountId, Vec<(TextFormat, TextFormat)>)>,
+        /// Key, min length, max length and initial set fee
+        pub supported_accounts: Vec<(TextFormat, u16, u16, bool)>,
     }
 
     #[cfg(feature = "std")]
@@ -209,10 +245,31 @@ pub mod pallet {
                 .clone()
                 .into_iter()
                 .for_each(|(owner, accounts)| {
-                    Users::<T>::insert(owner.clone(), accounts);
+                    let accounts: Vec<AccountOf<T>> = accounts
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let key = key.try_into().expect("Account key is too long");
+                            let value = value.try_into().expect("Account value is too long");
+                            Account::new(key, value)
+                        })
+                        .collect();
+                    let accounts = accounts.try_into().expect("Too many accounts");
+                    Users::<T>::insert(owner, accounts);
                 });
 
-            SupportedAccounts::<T>::put(self.supported_accounts.clone());
+            let supported_accounts: Vec<SupportedAccountOf<T>> = self
+                .supported_accounts
+                .clone()
+                .into_iter()
+                .map(|(key, min_length, max_length, initial_set_fee)| {
+                    let key = key.try_into().expect("Supported account key is too long");
+                    SupportedAccount::new(key, min_length, max_length, initial_set_fee)
+                })
+                .collect();
+            let supported_accounts = supported_accounts
+                .try_into()
+                .expect("Too many supported accounts");
+            SupportedAccounts::<T>::put(supported_accounts);
         }
     }
 }
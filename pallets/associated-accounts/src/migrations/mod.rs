@@ -0,0 +1,20 @@
+pub mod v2;
+
+use crate::{Config, Pallet};
+use frame_support::traits::StorageVersion;
+use frame_support::weights::Weight;
+
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get::<Pallet<T>>() == 1 {
+        log::info!(target: "runtime::associated-accounts", "Associated Accounts pallet: migrating to StorageVersion V2");
+
+        weight = weight.saturating_add(v2::migrate::<T>());
+        StorageVersion::new(2).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::associated-accounts", "Associated Accounts pallet: migration to StorageVersion V2 done");
+    }
+
+    weight
+}
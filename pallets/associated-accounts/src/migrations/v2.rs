@@ -0,0 +1,84 @@
+use crate::{
+    Account, AccountOf, Config, SupportedAccount, SupportedAccountOf, SupportedAccounts, Users,
+};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use sp_std::vec::Vec;
+use ternoa_common::helpers::truncate_to_bound;
+
+pub mod v1 {
+    use codec::{Decode, Encode};
+    use sp_runtime::RuntimeDebug;
+    use ternoa_primitives::TextFormat;
+
+    // SupportedAccount as it was stored before its key was bounded
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct SupportedAccount {
+        pub key: TextFormat,
+        pub min_length: u16,
+        pub max_length: u16,
+        pub initial_set_fee: bool,
+    }
+
+    // Account as it was stored before its key and value were bounded
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Account {
+        pub key: TextFormat,
+        pub value: TextFormat,
+    }
+}
+
+/// Bounds the supported account list and the accounts of every user. Keys and
+/// values that are too long are truncated and extra entries are dropped.
+pub fn migrate<T: Config>() -> Weight {
+    let mut reads: u64 = 1;
+    let mut writes: u64 = 1;
+
+    let key_limit = T::MaxKeyLen::get() as usize;
+    let value_limit = T::MaxValueLen::get() as usize;
+    let list_limit = T::MaxSupportedAccounts::get() as usize;
+
+    let _ = SupportedAccounts::<T>::translate::<Vec<v1::SupportedAccount>, _>(|old| {
+        let old = old.unwrap_or_default();
+        if old.len() > list_limit || old.iter().any(|x| x.key.len() > key_limit) {
+            log::warn!(target: "runtime::associated-accounts", "Supported accounts have been truncated");
+        }
+
+        let supported: Vec<SupportedAccountOf<T>> = old
+            .into_iter()
+            .take(list_limit)
+            .map(|x| {
+                SupportedAccount::new(
+                    truncate_to_bound(x.key),
+                    x.min_length,
+                    x.max_length,
+                    x.initial_set_fee,
+                )
+            })
+            .collect();
+
+        Some(truncate_to_bound(supported))
+    });
+
+    Users::<T>::translate::<Vec<v1::Account>, _>(|owner, old| {
+        reads += 1;
+        writes += 1;
+
+        let too_long = old
+            .iter()
+            .any(|x| x.key.len() > key_limit || x.value.len() > value_limit);
+        if old.len() > list_limit || too_long {
+            log::warn!(target: "runtime::associated-accounts", "Accounts of {:?} have been truncated", owner);
+        }
+
+        let accounts: Vec<AccountOf<T>> = old
+            .into_iter()
+            .take(list_limit)
+            .map(|x| Account::new(truncate_to_bound(x.key), truncate_to_bound(x.value)))
+            .collect();
+
+        Some(truncate_to_bound(accounts))
+    });
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
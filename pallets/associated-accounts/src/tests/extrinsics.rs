@@ -1,6 +1,9 @@
 use super::mock::*;
 use crate::tests::mock;
-use crate::{Account, Error, Event as AccountEvent, SupportedAccount, SupportedAccounts, Users};
+use crate::{
+    Account, AccountOf, Error, Event as AccountEvent, SupportedAccount, SupportedAccountOf,
+    SupportedAccounts, Users,
+};
 use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
 
@@ -19,19 +22,19 @@ mod set_account {
     fn set_account() {
         ExtBuilder::new_build().execute_with(|| {
             let service_name: Vec<u8> = SERVICE_NAME.into();
-            let acc = Account::new(service_name.clone(), "Marko".into());
+            let acc: AccountOf<Test> = Account::new(bounded(service_name), bounded("Marko".into()));
 
-            let ok = AAccounts::set_account(origin(ALICE), acc.key.clone(), acc.value.clone());
+            let ok = AAccounts::set_account(origin(ALICE), acc.key.to_vec(), acc.value.to_vec());
             assert_ok!(ok);
 
             // Storage
-            assert_eq!(Users::<Test>::get(ALICE), Some(vec![acc.clone()]));
+            assert_eq!(Users::<Test>::get(ALICE), Some(bounded(vec![acc.clone()])));
 
             // Events
             let event = AccountEvent::UserAccountAdded {
                 user: ALICE,
-                account_key: acc.key,
-                account_value: acc.value,
+                account_key: acc.key.into_inner(),
+                account_value: acc.value.into_inner(),
             };
             let event = Event::AAccounts(event);
             assert_eq!(System::events().last().unwrap().event, event);
@@ -42,27 +45,30 @@ mod set_account {
     fn updating_existing_account() {
         ExtBuilder::new_build().execute_with(|| {
             let service_name: Vec<u8> = SERVICE_NAME.into();
-            let acc = Account::new(service_name.clone(), vec![10]);
+            let acc: AccountOf<Test> = Account::new(bounded(service_name), bounded(vec![10]));
 
-            let ok = AAccounts::set_account(origin(ALICE), acc.key.clone(), acc.value.clone());
+            let ok = AAccounts::set_account(origin(ALICE), acc.key.to_vec(), acc.value.to_vec());
             assert_ok!(ok);
 
             let mut new_acc = acc.clone();
-            new_acc.value = vec![20];
+            new_acc.value = bounded(vec![20]);
 
             assert_ne!(acc.value, new_acc.value);
             let ok =
-                AAccounts::set_account(origin(ALICE), new_acc.key.clone(), new_acc.value.clone());
+                AAccounts::set_account(origin(ALICE), new_acc.key.to_vec(), new_acc.value.to_vec());
             assert_ok!(ok);
 
             // Storage
-            assert_eq!(Users::<Test>::get(ALICE), Some(vec![new_acc.clone()]));
+            assert_eq!(
+                Users::<Test>::get(ALICE),
+                Some(bounded(vec![new_acc.clone()]))
+            );
 
             // Events
             let event = AccountEvent::UserAccountAdded {
                 user: ALICE,
-                account_key: new_acc.key,
-                account_value: new_acc.value,
+                account_key: new_acc.key.into_inner(),
+                account_value: new_acc.value.into_inner(),
             };
             let event = Event::AAccounts(event);
             assert_eq!(System::events().last().unwrap().event, event);
@@ -88,7 +94,7 @@ mod set_account {
             let value: Vec<u8> = vec![];
             assert!(value.len() < supp.min_length as usize);
 
-            let ok = AAccounts::set_account(origin(ALICE), supp.key, value);
+            let ok = AAccounts::set_account(origin(ALICE), supp.key.into_inner(), value);
             assert_noop!(ok, Error::<Test>::ValueIsTooShort);
         })
     }
@@ -102,10 +108,35 @@ mod set_account {
             let value: Vec<u8> = "Lorem ipsum dolor sit amet".into();
             assert!(value.len() > supp.max_length as usize);
 
-            let ok = AAccounts::set_account(origin(ALICE), supp.key, value);
+            let ok = AAccounts::set_account(origin(ALICE), supp.key.into_inner(), value);
             assert_noop!(ok, Error::<Test>::ValueIsTooLong);
         })
     }
+
+    #[test]
+    fn too_many_accounts() {
+        ExtBuilder::new_build().execute_with(|| {
+            // MaxSupportedAccounts is 3
+            for key in [vec![1], vec![2]] {
+                let ok = AAccounts::add_new_supported_account(root(), key, 1, 10, true);
+                assert_ok!(ok);
+            }
+            let supports = SupportedAccounts::<Test>::get();
+            for supp in supports.iter() {
+                let ok = AAccounts::set_account(origin(ALICE), supp.key.to_vec(), vec![10]);
+                assert_ok!(ok);
+            }
+
+            // Swap one of the supported accounts for a new one
+            let ok = AAccounts::remove_supported_account(root(), vec![1]);
+            assert_ok!(ok);
+            let ok = AAccounts::add_new_supported_account(root(), vec![3], 1, 10, true);
+            assert_ok!(ok);
+
+            let ok = AAccounts::set_account(origin(ALICE), vec![3], vec![10]);
+            assert_noop!(ok, Error::<Test>::TooManyAccounts);
+        })
+    }
 }
 
 mod add_new_supported_account {
@@ -117,7 +148,7 @@ mod add_new_supported_account {
             let mut supports = SupportedAccounts::<Test>::get();
             let supp = supports.last().unwrap().clone();
 
-            let ok = AAccounts::remove_supported_account(root(), supp.key.clone());
+            let ok = AAccounts::remove_supported_account(root(), supp.key.to_vec());
             assert_ok!(ok);
 
             // Storage
@@ -126,7 +157,9 @@ mod add_new_supported_account {
             assert_eq!(SupportedAccounts::<Test>::get(), supports);
 
             // Events
-            let event = AccountEvent::SupportedAccountRemoved { key: supp.key };
+            let event = AccountEvent::SupportedAccountRemoved {
+                key: supp.key.into_inner(),
+            };
             let event = Event::AAccounts(event);
             assert_eq!(System::events().last().unwrap().event, event);
         })
@@ -139,11 +172,12 @@ mod remove_supported_account {
     #[test]
     fn add_new_supported_account() {
         ExtBuilder::new_build().execute_with(|| {
-            let supp = SupportedAccount::new(vec![65], 1, 10, true);
+            let supp: SupportedAccountOf<Test> =
+                SupportedAccount::new(bounded(vec![65]), 1, 10, true);
 
             let ok = AAccounts::add_new_supported_account(
                 root(),
-                supp.key.clone(),
+                supp.key.to_vec(),
                 supp.min_length,
                 supp.max_length,
                 supp.initial_set_fee,
@@ -156,7 +190,7 @@ mod remove_supported_account {
 
             // Events
             let event = AccountEvent::SupportedAccountAdded {
-                key: supp.key,
+                key: supp.key.into_inner(),
                 min_length: supp.min_length,
                 max_length: supp.max_length,
                 initial_set_fee: supp.initial_set_fee,
@@ -165,4 +199,21 @@ mod remove_supported_account {
             assert_eq!(System::events().last().unwrap().event, event);
         })
     }
+
+    #[test]
+    fn add_new_supported_account_unhappy() {
+        ExtBuilder::new_build().execute_with(|| {
+            // Unhappy key is too long
+            let ok = AAccounts::add_new_supported_account(root(), vec![65; 11], 1, 10, true);
+            assert_noop!(ok, Error::<Test>::KeyIsTooLong);
+
+            // Unhappy too many supported accounts
+            for key in [vec![1], vec![2]] {
+                let ok = AAccounts::add_new_supported_account(root(), key, 1, 10, true);
+                assert_ok!(ok);
+            }
+            let ok = AAccounts::add_new_supported_account(root(), vec![3], 1, 10, true);
+            assert_noop!(ok, Error::<Test>::TooManySupportedAccounts);
+        })
+    }
 }
@@ -1,6 +1,6 @@
 use super::mock::*;
 use crate::types::{Account, SupportedAccount};
-use crate::GenesisConfig;
+use crate::{AccountOf, GenesisConfig, SupportedAccountOf};
 use frame_support::traits::GenesisBuild;
 
 #[test]
@@ -10,20 +10,11 @@ fn genesis() {
         .unwrap();
 
     let users = vec![
-        (
-            ALICE,
-            vec![
-                Account::new(vec![10], vec![10]),
-                Account::new(vec![20], vec![10]),
-            ],
-        ),
-        (BOB, vec![Account::new(vec![10], vec![30])]),
+        (ALICE, vec![(vec![10], vec![10]), (vec![20], vec![10])]),
+        (BOB, vec![(vec![10], vec![30])]),
     ];
 
-    let supported_accounts = vec![
-        SupportedAccount::new(vec![10], 1, 10, true),
-        SupportedAccount::new(vec![20], 1, 10, true),
-    ];
+    let supported_accounts = vec![(vec![10], 1, 10, true), (vec![20], 1, 10, true)];
 
     GenesisConfig::<Test> {
         users: users.clone(),
@@ -34,10 +25,33 @@ fn genesis() {
 
     let mut ext = sp_io::TestExternalities::new(t);
     ext.execute_with(|| {
-        for user in users {
-            assert_eq!(AAccounts::users(user.0), Some(user.1));
+        for (owner, accounts) in users {
+            let accounts: Vec<AccountOf<Test>> = accounts
+                .into_iter()
+                .map(|(key, value)| Account::new(bounded(key), bounded(value)))
+                .collect();
+            assert_eq!(AAccounts::users(owner), Some(bounded(accounts)));
         }
 
-        assert_eq!(AAccounts::supported_accounts(), supported_accounts);
+        let supported_accounts: Vec<SupportedAccountOf<Test>> = supported_accounts
+            .into_iter()
+            .map(|(key, min, max, fee)| SupportedAccount::new(bounded(key), min, max, fee))
+            .collect();
+        assert_eq!(AAccounts::supported_accounts(), bounded(supported_accounts));
     });
 }
+
+#[test]
+#[should_panic(expected = "Supported account key is too long")]
+fn genesis_key_too_long() {
+    let mut t = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+
+    GenesisConfig::<Test> {
+        users: Default::default(),
+        supported_accounts: vec![(vec![10; 11], 1, 10, true)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+}
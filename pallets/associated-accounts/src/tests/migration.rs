@@ -0,0 +1,47 @@
+use super::mock::*;
+use crate::migrations::v2::v1;
+use crate::{SupportedAccounts, Users};
+use frame_support::storage::unhashed;
+use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+mod version_2 {
+    use super::*;
+
+    #[test]
+    fn upgrade_from_v1_to_v2() {
+        ExtBuilder::new_build().execute_with(|| {
+            // MaxSupportedAccounts is 3 and MaxKeyLen is 10
+            let supported: Vec<v1::SupportedAccount> = (0..4)
+                .map(|i| v1::SupportedAccount {
+                    key: vec![i; 12],
+                    min_length: 1,
+                    max_length: 30,
+                    initial_set_fee: true,
+                })
+                .collect();
+            unhashed::put(&SupportedAccounts::<Test>::hashed_key(), &supported);
+
+            // MaxValueLen is 20
+            let accounts = vec![v1::Account {
+                key: vec![0; 12],
+                value: vec![1; 25],
+            }];
+            unhashed::put(&Users::<Test>::hashed_key_for(ALICE), &accounts);
+
+            StorageVersion::put::<AAccounts>(&StorageVersion::new(1));
+            let weight = <AAccounts as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<AAccounts>(), StorageVersion::new(2));
+
+            let supported = SupportedAccounts::<Test>::get();
+            assert_eq!(supported.len(), 3);
+            assert_eq!(supported[0].key, vec![0; 10]);
+            assert_eq!(supported[2].key, vec![2; 10]);
+
+            let accounts = Users::<Test>::get(ALICE).unwrap();
+            assert_eq!(accounts.len(), 1);
+            assert_eq!(accounts[0].key, vec![0; 10]);
+            assert_eq!(accounts[0].value, vec![1; 20]);
+        })
+    }
+}
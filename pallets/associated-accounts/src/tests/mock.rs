@@ -1,9 +1,12 @@
-use crate::{self as ternoa_associated_accounts, Config, SupportedAccount};
+use crate::{self as ternoa_associated_accounts, Config};
 use frame_support::parameter_types;
-use frame_support::traits::{ConstU32, Contains, GenesisBuild};
+use frame_support::traits::{ConstU32, Contains, GenesisBuild, Get};
+use frame_support::weights::constants::RocksDbWeight;
+use frame_support::BoundedVec;
 use sp_core::H256;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use std::convert::TryInto;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -45,7 +48,7 @@ impl frame_system::Config for Test {
     type BaseCallFilter = TestBaseCallFilter;
     type BlockWeights = ();
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Origin = Origin;
     type Index = u64;
     type BlockNumber = u64;
@@ -68,9 +71,17 @@ impl frame_system::Config for Test {
     type MaxConsumers = ConstU32<16>;
 }
 
+parameter_types! {
+    pub const MaxKeyLen: u16 = 10;
+    pub const MaxValueLen: u16 = 20;
+}
+
 impl Config for Test {
     type Event = Event;
     type WeightInfo = ();
+    type MaxKeyLen = MaxKeyLen;
+    type MaxValueLen = MaxValueLen;
+    type MaxSupportedAccounts = ConstU32<3>;
 }
 
 pub struct ExtBuilder {}
@@ -95,10 +106,9 @@ impl ExtBuilder {
             .build_storage::<Test>()
             .unwrap();
 
-        let supp = SupportedAccount::new(SERVICE_NAME.into(), 1, 10, true);
         ternoa_associated_accounts::GenesisConfig::<Test> {
             users: Default::default(),
-            supported_accounts: vec![supp],
+            supported_accounts: vec![(SERVICE_NAME.into(), 1, 10, true)],
         }
         .assimilate_storage(&mut t)
         .unwrap();
@@ -116,3 +126,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         .unwrap();
     t.into()
 }
+
+pub fn bounded<T, S: Get<u32>>(value: Vec<T>) -> BoundedVec<T, S> {
+    value.try_into().unwrap()
+}
@@ -1,3 +1,4 @@
 mod extrinsics;
 mod genesis;
+mod migration;
 pub mod mock;
@@ -1,22 +1,39 @@
-#[cfg(feature = "std")]
-use serde::{Deserialize, Serialize};
-
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use frame_support::{BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
-use ternoa_primitives::TextFormat;
 
-#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct SupportedAccount {
-    pub key: TextFormat,
+#[derive(
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    RuntimeDebugNoBound,
+    Encode,
+    Decode,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(KeyLimit))]
+pub struct SupportedAccount<KeyLimit>
+where
+    KeyLimit: Get<u32>,
+{
+    pub key: BoundedVec<u8, KeyLimit>,
     pub min_length: u16,
     pub max_length: u16,
     pub initial_set_fee: bool,
 }
 
-impl SupportedAccount {
-    pub fn new(key: TextFormat, min_length: u16, max_length: u16, initial_set_fee: bool) -> Self {
+impl<KeyLimit> SupportedAccount<KeyLimit>
+where
+    KeyLimit: Get<u32>,
+{
+    pub fn new(
+        key: BoundedVec<u8, KeyLimit>,
+        min_length: u16,
+        max_length: u16,
+        initial_set_fee: bool,
+    ) -> Self {
         Self {
             key,
             min_length,
@@ -26,15 +43,32 @@ impl SupportedAccount {
     }
 }
 
-#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct Account {
-    pub key: TextFormat,
-    pub value: TextFormat,
+#[derive(
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    RuntimeDebugNoBound,
+    Encode,
+    Decode,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(KeyLimit, ValueLimit))]
+pub struct Account<KeyLimit, ValueLimit>
+where
+    KeyLimit: Get<u32>,
+    ValueLimit: Get<u32>,
+{
+    pub key: BoundedVec<u8, KeyLimit>,
+    pub value: BoundedVec<u8, ValueLimit>,
 }
 
-impl Account {
-    pub fn new(key: TextFormat, value: TextFormat) -> Self {
+impl<KeyLimit, ValueLimit> Account<KeyLimit, ValueLimit>
+where
+    KeyLimit: Get<u32>,
+    ValueLimit: Get<u32>,
+{
+    pub fn new(key: BoundedVec<u8, KeyLimit>, value: BoundedVec<u8, ValueLimit>) -> Self {
         Self { key, value }
     }
 }
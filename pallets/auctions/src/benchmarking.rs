@@ -8,10 +8,13 @@ use frame_support::traits::{Currency, OnFinalize, OnInitialize};
 use frame_system::pallet_prelude::OriginFor;
 use frame_system::{Pallet as System, RawOrigin};
 use sp_runtime::traits::Bounded;
+use sp_runtime::Permill;
 use sp_std::prelude::*;
-use ternoa_common::traits::{MarketplaceTrait, NFTTrait};
-use ternoa_primitives::marketplace::{MarketplaceId, MarketplaceType};
-use ternoa_primitives::nfts::NFTId;
+use ternoa_common::traits::{MarketplaceTrait, NFTReleaseHandler, NFTTrait};
+use ternoa_primitives::marketplace::{
+    MarketplaceCommission, MarketplaceId, MarketplaceType, PaymentAsset,
+};
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
 
 pub enum AuctionState {
     Before,
@@ -42,7 +45,7 @@ pub fn prepare_benchmarks<T: Config>(state: Option<AuctionState>) -> BenchmarkDa
     let market_id = T::MarketplaceHandler::create(
         alice.clone(),
         MarketplaceType::Public,
-        10,
+        MarketplaceCommission::new(Permill::from_percent(10), 0u32.into()),
         vec![1],
         None,
         None,
@@ -54,8 +57,14 @@ pub fn prepare_benchmarks<T: Config>(state: Option<AuctionState>) -> BenchmarkDa
     let alice_nft_id = T::NFTHandler::create_nft(alice.clone(), vec![10], None).unwrap();
     let bob_nft_id = T::NFTHandler::create_nft(bob.clone(), vec![10], None).unwrap();
 
-    let alice_series = T::NFTHandler::get_nft(alice_nft_id).unwrap().series_id;
-    let bob_series = T::NFTHandler::get_nft(bob_nft_id).unwrap().series_id;
+    let alice_series = T::NFTHandler::get_nft(alice_nft_id)
+        .unwrap()
+        .series_id
+        .to_vec();
+    let bob_series = T::NFTHandler::get_nft(bob_nft_id)
+        .unwrap()
+        .series_id
+        .to_vec();
 
     assert_ok!(T::NFTHandler::set_series_completion(&alice_series, true));
     assert_ok!(T::NFTHandler::set_series_completion(&bob_series, true));
@@ -83,6 +92,7 @@ pub fn prepare_benchmarks<T: Config>(state: Option<AuctionState>) -> BenchmarkDa
             end_block,
             start_price,
             buy_it_price,
+            PaymentAsset::Native,
         );
         assert_ok!(ok);
 
@@ -127,7 +137,7 @@ pub fn run_to_block<T: Config>(n: T::BlockNumber) {
     // Create 10 000 additional auctions
     for _i in 0..10_000 {
         let nft_id = T::NFTHandler::create_nft(alice.clone(), vec![10], None).unwrap();
-        let series_id = T::NFTHandler::get_nft(nft_id).unwrap().series_id;
+        let series_id = T::NFTHandler::get_nft(nft_id).unwrap().series_id.to_vec();
         assert_ok!(T::NFTHandler::set_series_completion(&series_id, true));
 
         let start_block = System::<T>::block_number() + T::MaxAuctionDelay::get() - 1u16.into();
@@ -143,6 +153,7 @@ pub fn run_to_block<T: Config>(n: T::BlockNumber) {
             end_block,
             start_price,
             Some(buy_it_price),
+            PaymentAsset::Native,
         );
         assert_ok!(ok);
     }
@@ -162,9 +173,9 @@ benchmarks! {
         let start_price = BalanceOf::<T>::max_value() / 100u32.into();
         let buy_now_price = start_price.saturating_mul(2u16.into());
 
-    }: _(RawOrigin::Signed(alice.clone()), nft_id, market_id, start_block, end_block, start_price, Some(buy_now_price))
+    }: _(RawOrigin::Signed(alice.clone()), nft_id, market_id, start_block, end_block, start_price, Some(buy_now_price), PaymentAsset::Native)
     verify {
-        assert_eq!(T::NFTHandler::is_listed_for_sale(nft_id), Some(true));
+        assert_eq!(T::NFTHandler::is_locked(nft_id, NFTLockReason::ListedForSale), Some(true));
     }
 
      cancel_auction {
@@ -174,7 +185,7 @@ benchmarks! {
 
     }: _(RawOrigin::Signed(bob.clone()), nft_id)
     verify {
-        assert_eq!(T::NFTHandler::is_listed_for_sale(nft_id), Some(false));
+        assert_eq!(T::NFTHandler::is_locked(nft_id, NFTLockReason::ListedForSale), Some(false));
     }
 
     end_auction {
@@ -193,7 +204,7 @@ benchmarks! {
     verify {
         let eve: T::AccountId = get_account::<T>("EVE");
 
-        assert_eq!(T::NFTHandler::is_listed_for_sale(nft_id), Some(false));
+        assert_eq!(T::NFTHandler::is_locked(nft_id, NFTLockReason::ListedForSale), Some(false));
         assert_eq!(T::NFTHandler::owner(nft_id), Some(eve));
     }
 
@@ -222,7 +233,7 @@ benchmarks! {
 
     }: _(RawOrigin::Signed(charlie.clone()), nft_id)
     verify {
-        assert_eq!(auction.bidders.list, vec![]);
+        assert!(auction.bidders.list.is_empty());
     }
 
     buy_it_now {
@@ -232,7 +243,7 @@ benchmarks! {
 
     }: _(RawOrigin::Signed(charlie.clone()), nft_id)
     verify {
-        assert_eq!(T::NFTHandler::is_listed_for_sale(nft_id), Some(false));
+        assert_eq!(T::NFTHandler::is_locked(nft_id, NFTLockReason::ListedForSale), Some(false));
         assert_eq!(T::NFTHandler::owner(nft_id), Some(charlie));
     }
 
@@ -252,7 +263,7 @@ benchmarks! {
     verify {
         let eve: T::AccountId = get_account::<T>("EVE");
 
-        assert_eq!(T::NFTHandler::is_listed_for_sale(nft_id), Some(false));
+        assert_eq!(T::NFTHandler::is_locked(nft_id, NFTLockReason::ListedForSale), Some(false));
         assert_eq!(T::NFTHandler::owner(nft_id), Some(eve));
     }
 
@@ -269,9 +280,30 @@ benchmarks! {
         assert_ok!(TernoaAuctions::<T>::add_bid(origin::<T>("EVE"), nft_id, eve_bid));
         assert_ok!(TernoaAuctions::<T>::complete_auction(RawOrigin::Root.into(), nft_id));
 
-    }: _(RawOrigin::Signed(charlie.clone()))
+    }: _(RawOrigin::Signed(charlie.clone()), PaymentAsset::Native)
     verify {
-        assert_eq!(Claims::<T>::get(charlie.clone()), None);
+        assert_eq!(Claims::<T>::get(charlie.clone(), PaymentAsset::Native), None);
+    }
+
+    release_nft {
+        let b in 0 .. T::MaxBidHistorySize::get();
+        let bench_data = prepare_benchmarks::<T>(Some(AuctionState::InProgress));
+        let nft_id = bench_data.bob_nft_id;
+
+        AuctionsStorage::<T>::mutate(nft_id, |x| {
+            let x = x.as_mut().unwrap();
+            x.bidders.max_size = b as u16;
+            for i in 0 .. b {
+                let bidder: T::AccountId = benchmark_account("BIDDER", i, 0);
+                x.bidders.insert_new_bid(bidder, 1u32.into());
+            }
+        });
+
+    }: {
+        assert_ok!(<TernoaAuctions<T> as NFTReleaseHandler>::release_nft(nft_id, NFTLockReason::ListedForSale));
+    }
+    verify {
+        assert_eq!(AuctionsStorage::<T>::get(nft_id), None);
     }
 }
 
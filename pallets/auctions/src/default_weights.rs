@@ -9,6 +9,7 @@ pub trait WeightInfo {
     fn buy_it_now() -> Weight;
     fn complete_auction() -> Weight;
     fn claim() -> Weight;
+    fn release_nft(b: u32) -> Weight;
 }
 
 /// Weight functions for `ternoa_auctions`.
@@ -27,9 +28,11 @@ impl WeightInfo for () {
     // Storage: Auctions Auctions (r:1 w:1)
     // Storage: Nfts Data (r:1 w:1)
     // Storage: Auctions Deadlines (r:1 w:1)
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
     fn cancel_auction() -> Weight {
         (27_890_000 as Weight)
-            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().reads(5 as Weight))
             .saturating_add(DbWeight::get().writes(3 as Weight))
     }
     // Storage: Auctions Auctions (r:1 w:1)
@@ -85,4 +88,16 @@ impl WeightInfo for () {
             .saturating_add(DbWeight::get().reads(3 as Weight))
             .saturating_add(DbWeight::get().writes(3 as Weight))
     }
+    // Storage: Auctions Auctions (r:1 w:1)
+    // Storage: Auctions Deadlines (r:1 w:1)
+    // Storage: Auctions Claims (r:1 w:1)
+    fn release_nft(b: u32) -> Weight {
+        (24_000_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((3_500_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(b as Weight)))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
+    }
 }
@@ -9,19 +9,27 @@ mod tests;
 mod benchmarking;
 
 mod default_weights;
+mod migrations;
 mod types;
 
 pub use default_weights::WeightInfo;
 use frame_support::pallet_prelude::*;
-use frame_support::traits::ExistenceRequirement::{AllowDeath, KeepAlive};
+use frame_support::traits::tokens::fungibles;
+use frame_support::traits::ExistenceRequirement::{self, AllowDeath, KeepAlive};
 use frame_support::traits::{Currency, Get, StorageVersion};
 use frame_support::PalletId;
-use sp_runtime::traits::{AccountIdConversion, Saturating};
-use ternoa_common::traits::{MarketplaceTrait, NFTTrait};
-use ternoa_primitives::nfts::NFTId;
+use sp_runtime::traits::{AccountIdConversion, MaybeSerializeDeserialize, Saturating, Zero};
+use sp_runtime::PerThing;
+use sp_std::vec::Vec;
+use ternoa_common::helpers::{can_receive_payment, marketplace_commission, transfer_payment};
+use ternoa_common::traits::{
+    MarketplaceCloseHandler, MarketplaceTrait, NFTReleaseHandler, NFTTrait,
+};
+use ternoa_primitives::marketplace::{MarketplaceId, MarketplaceRole, PaymentAsset};
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
 use types::{AuctionData, BidderList, DeadlineList};
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -35,6 +43,16 @@ pub mod pallet {
 
     pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type AuctionDataOf<T> = AuctionData<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        BalanceOf<T>,
+        <T as Config>::AssetId,
+        <T as Config>::MaxBidHistorySize,
+    >;
+    pub type PaymentAssetOf<T> = PaymentAsset<<T as Config>::AssetId>;
+    pub type DeadlineListOf<T> =
+        DeadlineList<<T as frame_system::Config>::BlockNumber, <T as Config>::MaxAuctions>;
 
     /// Configure the pallet by specifying the parameters and types on which it depends.
     #[pallet::config]
@@ -45,11 +63,25 @@ pub mod pallet {
         /// Caps Currency
         type Currency: Currency<Self::AccountId>;
 
+        /// Id of the assets that auctions can be paid with besides Caps.
+        type AssetId: Member + Parameter + Copy + MaxEncodedLen + MaybeSerializeDeserialize;
+
+        /// Pallet managing the assets that auctions can be paid with besides Caps.
+        type Assets: fungibles::Transfer<
+            Self::AccountId,
+            AssetId = Self::AssetId,
+            Balance = BalanceOf<Self>,
+        >;
+
         /// Get information on nfts
         type NFTHandler: NFTTrait<AccountId = Self::AccountId>;
 
         /// Get information on marketplace
-        type MarketplaceHandler: MarketplaceTrait<Self::AccountId>;
+        type MarketplaceHandler: MarketplaceTrait<
+            Self::AccountId,
+            BalanceOf<Self>,
+            AssetId = Self::AssetId,
+        >;
 
         /// Minimum required length of auction
         #[pallet::constant]
@@ -75,6 +107,14 @@ pub mod pallet {
         #[pallet::constant]
         type PalletId: Get<PalletId>;
 
+        /// Maximum number of bids that can be kept in the bid history of an auction
+        #[pallet::constant]
+        type MaxBidHistorySize: Get<u32>;
+
+        /// Maximum number of auctions that can run at the same time
+        #[pallet::constant]
+        type MaxAuctions: Get<u32>;
+
         // weight information for pallet
         type WeightInfo: WeightInfo;
     }
@@ -82,11 +122,14 @@ pub mod pallet {
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+
         /// Weight: see `begin_block`
         fn on_initialize(now: T::BlockNumber) -> Weight {
             let mut read = 0;
@@ -97,8 +140,13 @@ pub mod pallet {
                 read += 1;
 
                 if let Some(nft_id) = deadlines.next(now) {
-                    let ok = Self::complete_auction(RawOrigin::Root.into(), nft_id);
-                    debug_assert_eq!(ok, Ok(().into()));
+                    // An auction that cannot be completed would stay in the
+                    // deadlines and be picked again, so it is cancelled instead.
+                    if let Err(err) = Self::complete_auction(RawOrigin::Root.into(), nft_id) {
+                        log::error!(target: "runtime::auctions", "Auction {} could not be completed: {:?}", nft_id, err.error);
+                        Self::abort_auction(nft_id);
+                        write += 1;
+                    }
                 } else {
                     break;
                 }
@@ -129,6 +177,7 @@ pub mod pallet {
             #[pallet::compact] end_block: T::BlockNumber,
             start_price: BalanceOf<T>,
             buy_it_price: Option<BalanceOf<T>>,
+            asset: PaymentAssetOf<T>,
         ) -> DispatchResultWithPostInfo {
             let creator = ensure_signed(origin)?;
             let current_block = frame_system::Pallet::<T>::block_number();
@@ -177,47 +226,39 @@ pub mod pallet {
                 Error::<T>::CannotAuctionNotOwnedNFTs
             );
 
-            ensure!(
-                nft_data.listed_for_sale == false,
-                Error::<T>::CannotAuctionNFTsListedForSale
-            );
-
-            ensure!(
-                nft_data.in_transmission == false,
-                Error::<T>::CannotAuctionNFTsInTransmission
-            );
-
-            ensure!(
-                nft_data.converted_to_capsule == false,
-                Error::<T>::CannotAuctionCapsules
-            );
-
-            ensure!(nft_data.viewer.is_none(), Error::<T>::CannotAuctionLentNFTs);
-
             ensure!(
                 is_nft_in_completed_series == Some(true),
                 Error::<T>::CannotAuctionNFTsInUncompletedSeries
             );
 
             T::MarketplaceHandler::is_allowed_to_list(marketplace_id, creator.clone())?;
-            T::NFTHandler::set_listed_for_sale(nft_id, true)?;
+            T::MarketplaceHandler::is_asset_accepted(marketplace_id, &asset)?;
+            let marketplace = T::MarketplaceHandler::get_marketplace(marketplace_id)
+                .ok_or(Error::<T>::UnknownMarketplace)?;
+            T::NFTHandler::lock(nft_id, NFTLockReason::ListedForSale)?;
 
             let bid_history_size = Pallet::<T>::bid_history_size();
-            let bidders: BidderList<T::AccountId, BalanceOf<T>> = BidderList::new(bid_history_size);
+            let bidders = BidderList::new(bid_history_size);
             let auction_data = AuctionData {
                 creator: creator.clone(),
                 start_block,
                 end_block,
                 start_price,
                 buy_it_price,
+                asset,
                 bidders,
                 marketplace_id,
+                commission_fee: marketplace.commission_fee,
                 is_extended: false,
             };
 
             // Add auction to storage and insert an entry to deadlines
+            ensure!(
+                Deadlines::<T>::mutate(|x| x.insert(nft_id, end_block)),
+                Error::<T>::MaximumAuctionsLimitReached
+            );
             Auctions::<T>::insert(nft_id, auction_data);
-            Deadlines::<T>::mutate(|x| x.insert(nft_id, end_block));
+            MarketplaceAuctions::<T>::insert(marketplace_id, nft_id, ());
 
             // Emit AuctionCreated event
             let event = Event::AuctionCreated {
@@ -226,6 +267,7 @@ pub mod pallet {
                 creator,
                 start_price,
                 buy_it_price,
+                asset,
                 start_block,
                 end_block,
             };
@@ -242,13 +284,21 @@ pub mod pallet {
 
             let auction = Auctions::<T>::get(nft_id).ok_or(Error::<T>::AuctionDoesNotExist)?;
 
-            ensure!(auction.creator == who, Error::<T>::NotTheAuctionCreator);
+            let is_curator = T::MarketplaceHandler::has_role(
+                auction.marketplace_id,
+                &who,
+                MarketplaceRole::Curator,
+            );
+            ensure!(
+                auction.creator == who || is_curator,
+                Error::<T>::NotTheAuctionCreator
+            );
             ensure!(
                 !Self::has_started(current_block, auction.start_block),
                 Error::<T>::CannotCancelAuctionInProgress
             );
 
-            T::NFTHandler::set_listed_for_sale(nft_id, false)?;
+            T::NFTHandler::unlock(nft_id, NFTLockReason::ListedForSale)?;
             Self::remove_auction(nft_id, &auction);
 
             Self::deposit_event(Event::AuctionCancelled { nft_id });
@@ -317,20 +367,21 @@ pub mod pallet {
                 }
                 let remaining_blocks = auction.end_block.saturating_sub(current_block);
 
+                let pot = Self::account_id();
                 if let Some(existing_bid) = auction.bidders.find_bid(who.clone()) {
                     let amount_difference = amount.saturating_sub(existing_bid.1);
-                    T::Currency::transfer(&who, &Self::account_id(), amount_difference, KeepAlive)?;
+                    Self::transfer(&auction.asset, &who, &pot, amount_difference, KeepAlive)?;
 
                     auction.bidders.remove_bid(who.clone());
                 } else {
                     // transfer funds from caller
-                    T::Currency::transfer(&who, &Self::account_id(), amount, KeepAlive)?;
+                    Self::transfer(&auction.asset, &who, &pot, amount, KeepAlive)?;
                 }
 
                 // replace top bidder with caller
                 // if bidder has been removed, refund removed user
                 if let Some(bid) = auction.bidders.insert_new_bid(who.clone(), amount) {
-                    Self::add_claim(&bid.0, bid.1);
+                    Self::add_claim(&bid.0, &auction.asset, bid.1);
                 }
 
                 let grace_period = T::AuctionGracePeriod::get();
@@ -383,7 +434,13 @@ pub mod pallet {
                     .ok_or(Error::<T>::BidDoesNotExist)?
                     .clone();
 
-                T::Currency::transfer(&Self::account_id(), &bid.0, bid.1, AllowDeath)?;
+                Self::transfer(
+                    &auction.asset,
+                    &Self::account_id(),
+                    &bid.0,
+                    bid.1,
+                    AllowDeath,
+                )?;
 
                 auction.bidders.remove_bid(who.clone());
 
@@ -463,17 +520,19 @@ pub mod pallet {
             Ok(().into())
         }
 
+        /// Get back the bids of `asset` that were outbid or whose auction was cancelled.
         #[pallet::weight(T::WeightInfo::claim())]
         #[transactional]
-        pub fn claim(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+        pub fn claim(origin: OriginFor<T>, asset: PaymentAssetOf<T>) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            let claim = Claims::<T>::get(&who).ok_or(Error::<T>::ClaimDoesNotExist)?;
+            let claim = Claims::<T>::get(&who, &asset).ok_or(Error::<T>::ClaimDoesNotExist)?;
 
-            T::Currency::transfer(&Self::account_id(), &who, claim, AllowDeath)?;
-            Claims::<T>::remove(&who);
+            Self::transfer(&asset, &Self::account_id(), &who, claim, AllowDeath)?;
+            Claims::<T>::remove(&who, &asset);
 
             let event = Event::BalanceClaimed {
                 account: who,
+                asset,
                 amount: claim,
             };
             Self::deposit_event(event);
@@ -492,6 +551,7 @@ pub mod pallet {
             creator: T::AccountId,
             start_price: BalanceOf<T>,
             buy_it_price: Option<BalanceOf<T>>,
+            asset: PaymentAssetOf<T>,
             start_block: T::BlockNumber,
             end_block: T::BlockNumber,
         },
@@ -503,6 +563,14 @@ pub mod pallet {
             new_owner: Option<T::AccountId>,
             amount: Option<BalanceOf<T>>,
         },
+        /// The price of a completed auction was split between the auction creator,
+        /// the marketplace owner and the creator of the nft.
+        AuctionPaidOut {
+            nft_id: NFTId,
+            seller_cut: BalanceOf<T>,
+            marketplace_cut: BalanceOf<T>,
+            royalty_cut: BalanceOf<T>,
+        },
         /// A new bid was created
         BidAdded {
             nft_id: NFTId,
@@ -524,6 +592,7 @@ pub mod pallet {
         /// Balance claimed
         BalanceClaimed {
             account: T::AccountId,
+            asset: PaymentAssetOf<T>,
             amount: BalanceOf<T>,
         },
     }
@@ -567,18 +636,12 @@ pub mod pallet {
         CannotRemoveBidAtTheEndOfAuction,
         /// Cannot end the auction if it was not extended.
         CannotEndAuctionThatWasNotExtended,
-        /// Cannot auction NFTs that are listed for sale.
-        CannotAuctionNFTsListedForSale,
-        /// Cannot auction NFTs that are in transmission.
-        CannotAuctionNFTsInTransmission,
-        /// Cannot auction capsules.
-        CannotAuctionCapsules,
         /// Cannot auction NFTs that are not owned by the caller.
         CannotAuctionNotOwnedNFTs,
-        /// Cannot auction lent NFTs.
-        CannotAuctionLentNFTs,
         /// Cannot claim if the claim does not exist.
         ClaimDoesNotExist,
+        /// Cannot create the auction because too many auctions are already running.
+        MaximumAuctionsLimitReached,
         /// Cannot auction NFTs that do not exit.
         NFTDoesNotExist,
         /// Operation not allowed because the caller is not the owner of the auction.
@@ -589,22 +652,37 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn auctions)]
-    pub type Auctions<T: Config> = StorageMap<
+    pub type Auctions<T: Config> =
+        StorageMap<_, Blake2_128Concat, NFTId, AuctionDataOf<T>, OptionQuery>;
+
+    /// Auctions hosted on each marketplace.
+    #[pallet::storage]
+    pub type MarketplaceAuctions<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
+        MarketplaceId,
+        Blake2_128Concat,
         NFTId,
-        AuctionData<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+        (),
         OptionQuery,
     >;
 
     #[pallet::storage]
     #[pallet::getter(fn deadlines)]
-    pub type Deadlines<T: Config> = StorageValue<_, DeadlineList<T::BlockNumber>, ValueQuery>;
+    pub type Deadlines<T: Config> = StorageValue<_, DeadlineListOf<T>, ValueQuery>;
 
+    /// Funds that bidders can get back, by asset.
     #[pallet::storage]
     #[pallet::getter(fn claims)]
-    pub type Claims<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
+    pub type Claims<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        PaymentAssetOf<T>,
+        BalanceOf<T>,
+        OptionQuery,
+    >;
 
     #[pallet::storage]
     #[pallet::getter(fn bid_history_size)]
@@ -612,9 +690,16 @@ pub mod pallet {
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
+        /// (nft id, creator, start block, end block, start price, buy it price, marketplace id, asset)
         pub auctions: Vec<(
             NFTId,
-            AuctionData<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+            T::AccountId,
+            T::BlockNumber,
+            T::BlockNumber,
+            BalanceOf<T>,
+            Option<BalanceOf<T>>,
+            MarketplaceId,
+            PaymentAssetOf<T>,
         )>,
         pub bid_history_size: u16,
     }
@@ -632,13 +717,43 @@ pub mod pallet {
     #[pallet::genesis_build]
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
-            self.auctions
-                .clone()
-                .into_iter()
-                .for_each(|(nft_id, auction)| {
-                    Deadlines::<T>::mutate(|x| x.insert(nft_id, auction.end_block));
+            assert!(
+                self.bid_history_size as u32 <= T::MaxBidHistorySize::get(),
+                "Bid history size is bigger than MaxBidHistorySize"
+            );
+
+            self.auctions.clone().into_iter().for_each(
+                |(
+                    nft_id,
+                    creator,
+                    start_block,
+                    end_block,
+                    start_price,
+                    buy_it_price,
+                    market,
+                    asset,
+                )| {
+                    let commission_fee = T::MarketplaceHandler::get_marketplace(market)
+                        .map(|x| x.commission_fee)
+                        .unwrap_or_default();
+                    let auction = AuctionData {
+                        creator,
+                        start_block,
+                        end_block,
+                        start_price,
+                        buy_it_price,
+                        asset,
+                        bidders: BidderList::new(self.bid_history_size),
+                        marketplace_id: market,
+                        commission_fee,
+                        is_extended: false,
+                    };
+                    let ok = Deadlines::<T>::mutate(|x| x.insert(nft_id, end_block));
+                    assert!(ok, "Too many auctions");
+                    MarketplaceAuctions::<T>::insert(market, nft_id, ());
                     Auctions::<T>::insert(nft_id, auction);
-                });
+                },
+            );
             BidHistorySize::<T>::set(self.bid_history_size);
         }
     }
@@ -653,18 +768,33 @@ impl<T: Config> Pallet<T> {
 
     pub fn close_auction(
         nft_id: NFTId,
-        auction: &AuctionData<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+        auction: &AuctionDataOf<T>,
         new_owner: &T::AccountId,
         price: BalanceOf<T>,
         balance_source: Option<T::AccountId>,
     ) -> DispatchResult {
+        let nft = T::NFTHandler::get_nft(nft_id).ok_or(Error::<T>::NFTDoesNotExist)?;
+
         // Handle marketplace fees
         let marketplace = T::MarketplaceHandler::get_marketplace(auction.marketplace_id)
             .ok_or(Error::<T>::UnknownMarketplace)?;
 
-        let to_marketplace =
-            price.saturating_mul(marketplace.commission_fee.into()) / 100u32.into();
-        let to_auctioneer = price.saturating_sub(to_marketplace);
+        // The creator is paid first. Royalties are only due on secondary sales
+        // and a royalty that is too small to be received is left to the seller.
+        let mut to_creator: BalanceOf<T> = Zero::zero();
+        if nft.creator != auction.creator {
+            let royalty = nft.royalty.mul_floor(price);
+            let asset = &auction.asset;
+            if can_receive_payment::<_, T::Currency, T::Assets>(asset, &nft.creator, royalty) {
+                to_creator = royalty;
+            }
+        }
+        let remaining = price.saturating_sub(to_creator);
+
+        // The commission is the one that applied when the auction was created.
+        let to_marketplace = marketplace_commission(&auction.commission_fee, price);
+        let to_marketplace = to_marketplace.min(remaining);
+        let to_auctioneer = remaining.saturating_sub(to_marketplace);
 
         let existence = if balance_source.is_none() {
             KeepAlive
@@ -673,47 +803,140 @@ impl<T: Config> Pallet<T> {
         };
         let balance_source = balance_source.unwrap_or_else(|| Self::account_id());
 
+        let transfer = |dest: &T::AccountId, amount: BalanceOf<T>| {
+            Self::transfer(&auction.asset, &balance_source, dest, amount, existence)
+        };
+
+        // Transfer royalty to creator
+        transfer(&nft.creator, to_creator)?;
+
         // Transfer fee to marketplace
-        T::Currency::transfer(
-            &balance_source,
-            &marketplace.owner,
-            to_marketplace,
-            existence,
-        )?;
+        transfer(&marketplace.owner, to_marketplace)?;
 
         // Transfer remaining to auction creator
-        T::Currency::transfer(&balance_source, &auction.creator, to_auctioneer, existence)?;
+        transfer(&auction.creator, to_auctioneer)?;
 
+        T::NFTHandler::unlock(nft_id, NFTLockReason::ListedForSale)?;
         T::NFTHandler::set_owner(nft_id, new_owner)?;
-        T::NFTHandler::set_listed_for_sale(nft_id, false)?;
+
+        Self::deposit_event(Event::AuctionPaidOut {
+            nft_id,
+            seller_cut: to_auctioneer,
+            marketplace_cut: to_marketplace,
+            royalty_cut: to_creator,
+        });
 
         Ok(())
     }
 
-    pub fn remove_auction(
-        nft_id: NFTId,
-        auction: &AuctionData<T::AccountId, T::BlockNumber, BalanceOf<T>>,
-    ) {
+    pub fn remove_auction(nft_id: NFTId, auction: &AuctionDataOf<T>) {
         Deadlines::<T>::mutate(|x| x.remove(nft_id));
 
         for bidder in &auction.bidders.list {
-            Self::add_claim(&bidder.0, bidder.1);
+            Self::add_claim(&bidder.0, &auction.asset, bidder.1);
         }
 
         Auctions::<T>::remove(nft_id);
+        MarketplaceAuctions::<T>::remove(auction.marketplace_id, nft_id);
     }
 
-    pub fn add_claim(account: &T::AccountId, amount: BalanceOf<T>) {
-        Claims::<T>::mutate(account, |x| {
+    /// Cancel an auction that could not be completed. The NFT goes back to the
+    /// auction creator and every bidder can claim their bid back.
+    fn abort_auction(nft_id: NFTId) {
+        match Auctions::<T>::get(nft_id) {
+            Some(auction) => {
+                let _ = T::NFTHandler::unlock(nft_id, NFTLockReason::ListedForSale);
+                Self::remove_auction(nft_id, &auction);
+                Self::deposit_event(Event::AuctionCancelled { nft_id });
+            }
+            None => {
+                Deadlines::<T>::mutate(|x| x.remove(nft_id));
+            }
+        }
+    }
+
+    pub fn add_claim(account: &T::AccountId, asset: &PaymentAssetOf<T>, amount: BalanceOf<T>) {
+        Claims::<T>::mutate(account, asset, |x| {
             if let Some(claim) = x {
-                claim.saturating_add(amount);
+                *claim = claim.saturating_add(amount);
             } else {
                 *x = Some(amount);
             }
         })
     }
 
+    /// Move funds of the asset an auction is paid with.
+    pub fn transfer(
+        asset: &PaymentAssetOf<T>,
+        source: &T::AccountId,
+        dest: &T::AccountId,
+        amount: BalanceOf<T>,
+        existence: ExistenceRequirement,
+    ) -> DispatchResult {
+        transfer_payment::<_, T::Currency, T::Assets>(asset, source, dest, amount, existence)
+    }
+
     pub fn has_started(now: T::BlockNumber, start_block: T::BlockNumber) -> bool {
         now >= start_block
     }
 }
+
+impl<T: Config> NFTReleaseHandler for Pallet<T> {
+    /// Cancel the auction of an NFT. The bidders can claim their bids back.
+    fn release_nft(nft_id: NFTId, reason: NFTLockReason) -> Result<Weight, DispatchError> {
+        if reason != NFTLockReason::ListedForSale {
+            return Ok(0);
+        }
+
+        match Auctions::<T>::get(nft_id) {
+            Some(auction) => {
+                Self::remove_auction(nft_id, &auction);
+                Self::deposit_event(Event::AuctionCancelled { nft_id });
+                Ok(T::WeightInfo::release_nft(auction.bidders.len() as u32))
+            }
+            None => Ok(T::DbWeight::get().reads(1)),
+        }
+    }
+
+    fn max_release_weight() -> Weight {
+        T::WeightInfo::release_nft(T::MaxBidHistorySize::get())
+    }
+}
+
+impl<T: Config> MarketplaceCloseHandler for Pallet<T> {
+    /// Settle the auctions hosted on a closing marketplace. Auctions that have a
+    /// bid go to their highest bidder, the others are cancelled.
+    fn close_marketplace(marketplace_id: MarketplaceId, limit: u32) -> Result<u32, DispatchError> {
+        let auctions: Vec<NFTId> = MarketplaceAuctions::<T>::iter_key_prefix(marketplace_id)
+            .take(limit as usize)
+            .collect();
+
+        for nft_id in &auctions {
+            let auction = Auctions::<T>::get(nft_id).ok_or(Error::<T>::AuctionDoesNotExist)?;
+            let mut settled = auction.clone();
+            if let Some((bidder, amount)) = settled.bidders.remove_highest_bid() {
+                Self::close_auction(*nft_id, &settled, &bidder, amount, None)?;
+                Self::remove_auction(*nft_id, &settled);
+                Self::deposit_event(Event::AuctionCompleted {
+                    nft_id: *nft_id,
+                    new_owner: Some(bidder),
+                    amount: Some(amount),
+                });
+            } else {
+                T::NFTHandler::unlock(*nft_id, NFTLockReason::ListedForSale)?;
+                Self::remove_auction(*nft_id, &auction);
+                Self::deposit_event(Event::AuctionCancelled { nft_id: *nft_id });
+            }
+        }
+
+        Ok(auctions.len() as u32)
+    }
+
+    fn close_marketplace_weight(limit: u32) -> Weight {
+        let settle = T::WeightInfo::complete_auction()
+            .saturating_add(T::WeightInfo::release_nft(T::MaxBidHistorySize::get()));
+        T::DbWeight::get()
+            .reads(1)
+            .saturating_add(settle.saturating_mul(limit as Weight))
+    }
+}
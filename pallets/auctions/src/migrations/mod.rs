@@ -0,0 +1,50 @@
+pub mod v2;
+pub mod v3;
+pub mod v4;
+pub mod v5;
+
+use crate::{Config, Pallet};
+use frame_support::traits::StorageVersion;
+use frame_support::weights::Weight;
+
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get::<Pallet<T>>() == 1 {
+        log::info!(target: "runtime::auctions", "Auctions pallet: migrating to StorageVersion V2");
+
+        weight = weight.saturating_add(v2::migrate::<T>());
+        StorageVersion::new(2).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::auctions", "Auctions pallet: migration to StorageVersion V2 done");
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 2 {
+        log::info!(target: "runtime::auctions", "Auctions pallet: migrating to StorageVersion V3");
+
+        weight = weight.saturating_add(v3::migrate::<T>());
+        StorageVersion::new(3).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::auctions", "Auctions pallet: migration to StorageVersion V3 done");
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 3 {
+        log::info!(target: "runtime::auctions", "Auctions pallet: migrating to StorageVersion V4");
+
+        weight = weight.saturating_add(v4::migrate::<T>());
+        StorageVersion::new(4).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::auctions", "Auctions pallet: migration to StorageVersion V4 done");
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 4 {
+        log::info!(target: "runtime::auctions", "Auctions pallet: migrating to StorageVersion V5");
+
+        weight = weight.saturating_add(v5::migrate::<T>());
+        StorageVersion::new(5).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::auctions", "Auctions pallet: migration to StorageVersion V5 done");
+    }
+
+    weight
+}
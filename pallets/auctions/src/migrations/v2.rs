@@ -0,0 +1,171 @@
+use crate::types::{BidderList, DeadlineList};
+use crate::{BalanceOf, Config, Deadlines};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use sp_runtime::traits::Saturating;
+use sp_std::vec::Vec;
+use ternoa_common::helpers::truncate_to_bound;
+use ternoa_common::traits::NFTTrait;
+use ternoa_primitives::nfts::NFTLockReason;
+
+pub mod v1 {
+    use codec::{Decode, Encode};
+    use sp_runtime::RuntimeDebug;
+    use sp_std::vec::Vec;
+    use ternoa_primitives::marketplace::MarketplaceId;
+    use ternoa_primitives::nfts::NFTId;
+
+    // AuctionData as it was stored before the bid history was bounded
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct AuctionData<AccountId, BlockNumber, Balance> {
+        pub creator: AccountId,
+        pub start_block: BlockNumber,
+        pub end_block: BlockNumber,
+        pub start_price: Balance,
+        pub buy_it_price: Option<Balance>,
+        pub bidders: BidderList<AccountId, Balance>,
+        pub marketplace_id: MarketplaceId,
+        pub is_extended: bool,
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct BidderList<AccountId, Balance> {
+        pub list: Vec<(AccountId, Balance)>,
+        pub max_size: u16,
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, Default)]
+    pub struct DeadlineList<BlockNumber>(pub Vec<(NFTId, BlockNumber)>);
+}
+
+pub mod v2 {
+    use crate::types::BidderList;
+    use crate::{BalanceOf, Config};
+    use codec::{Decode, Encode};
+    use frame_support::Blake2_128Concat;
+    use ternoa_primitives::marketplace::MarketplaceId;
+    use ternoa_primitives::nfts::NFTId;
+
+    // AuctionData as it was stored before the commission was recorded
+    #[derive(Encode, Decode)]
+    pub struct AuctionData<T: Config> {
+        pub creator: T::AccountId,
+        pub start_block: T::BlockNumber,
+        pub end_block: T::BlockNumber,
+        pub start_price: BalanceOf<T>,
+        pub buy_it_price: Option<BalanceOf<T>>,
+        pub bidders: BidderList<T::AccountId, BalanceOf<T>, T::MaxBidHistorySize>,
+        pub marketplace_id: MarketplaceId,
+        pub is_extended: bool,
+    }
+
+    frame_support::generate_storage_alias!(
+        Auctions, Auctions<T: Config> => Map<
+            (Blake2_128Concat, NFTId),
+            AuctionData<T>
+        >
+    );
+
+    // Claims as they were stored before auctions could be paid with assets
+    frame_support::generate_storage_alias!(
+        Auctions, Claims<T: Config> => Map<
+            (Blake2_128Concat, T::AccountId),
+            BalanceOf<T>
+        >
+    );
+}
+
+/// Bounds the bid history of every auction and the list of deadlines. The lowest
+/// bids that do not fit are turned into claims so that no funds get stuck. The
+/// auctions that end last are cancelled if there are too many of them, their
+/// bids are turned into claims and their NFTs are unlocked.
+pub fn migrate<T: Config>() -> Weight {
+    let mut reads: u64 = 0;
+    let mut writes: u64 = 0;
+    let limit = T::MaxBidHistorySize::get() as usize;
+
+    type OldAuctionData<T> = v1::AuctionData<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        BalanceOf<T>,
+    >;
+
+    v2::Auctions::<T>::translate::<OldAuctionData<T>, _>(|nft_id, old| {
+        reads += 1;
+        writes += 1;
+
+        let mut list = old.bidders.list;
+        if list.len() > limit {
+            let overflow = list.len() - limit;
+            log::warn!(target: "runtime::auctions", "Auction {} lost {} bids", nft_id, overflow);
+
+            // Bids are sorted from the lowest to the highest one.
+            for (account, amount) in list.drain(..overflow) {
+                add_claim::<T>(&account, amount);
+                reads += 1;
+                writes += 1;
+            }
+        }
+
+        let auction = v2::AuctionData::<T> {
+            creator: old.creator,
+            start_block: old.start_block,
+            end_block: old.end_block,
+            start_price: old.start_price,
+            buy_it_price: old.buy_it_price,
+            bidders: BidderList {
+                list: truncate_to_bound(list),
+                max_size: old.bidders.max_size,
+            },
+            marketplace_id: old.marketplace_id,
+            is_extended: old.is_extended,
+        };
+
+        Some(auction)
+    });
+
+    let old_deadlines = frame_support::storage::unhashed::get::<v1::DeadlineList<T::BlockNumber>>(
+        &Deadlines::<T>::hashed_key(),
+    );
+    reads += 1;
+
+    if let Some(old) = old_deadlines {
+        // Deadlines are sorted from the first one to the last one.
+        let mut deadlines = old.0;
+        let cancelled: Vec<_> = if deadlines.len() > T::MaxAuctions::get() as usize {
+            deadlines.split_off(T::MaxAuctions::get() as usize)
+        } else {
+            Vec::new()
+        };
+
+        for (nft_id, _) in cancelled {
+            log::warn!(target: "runtime::auctions", "Auction {} has been cancelled", nft_id);
+
+            if let Some(auction) = v2::Auctions::<T>::take(nft_id) {
+                for (account, amount) in auction.bidders.list.into_iter() {
+                    add_claim::<T>(&account, amount);
+                    reads += 1;
+                    writes += 1;
+                }
+            }
+            if T::NFTHandler::unlock(nft_id, NFTLockReason::ListedForSale).is_err() {
+                log::warn!(target: "runtime::auctions", "NFT {} could not be unlocked", nft_id);
+            }
+            reads += 2;
+            writes += 2;
+        }
+
+        // Nothing is dropped since the deadlines now fit.
+        Deadlines::<T>::put(DeadlineList(truncate_to_bound(deadlines)));
+        writes += 1;
+    }
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Give funds back to a bidder, through the claims as they were stored in V2.
+fn add_claim<T: Config>(account: &T::AccountId, amount: BalanceOf<T>) {
+    v2::Claims::<T>::mutate(account, |x| {
+        *x = Some(x.unwrap_or_default().saturating_add(amount));
+    });
+}
@@ -0,0 +1,66 @@
+use crate::migrations::v2::v2;
+use crate::Config;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use ternoa_common::traits::MarketplaceTrait;
+
+pub mod v3 {
+    use crate::types::BidderList;
+    use crate::{BalanceOf, Config};
+    use codec::{Decode, Encode};
+    use frame_support::Blake2_128Concat;
+    use ternoa_primitives::marketplace::{MarketplaceCommission, MarketplaceId};
+    use ternoa_primitives::nfts::NFTId;
+
+    // AuctionData as it was stored before auctions could be paid with assets
+    #[derive(Encode, Decode)]
+    pub struct AuctionData<T: Config> {
+        pub creator: T::AccountId,
+        pub start_block: T::BlockNumber,
+        pub end_block: T::BlockNumber,
+        pub start_price: BalanceOf<T>,
+        pub buy_it_price: Option<BalanceOf<T>>,
+        pub bidders: BidderList<T::AccountId, BalanceOf<T>, T::MaxBidHistorySize>,
+        pub marketplace_id: MarketplaceId,
+        pub commission_fee: MarketplaceCommission<BalanceOf<T>>,
+        pub is_extended: bool,
+    }
+
+    frame_support::generate_storage_alias!(
+        Auctions, Auctions<T: Config> => Map<
+            (Blake2_128Concat, NFTId),
+            AuctionData<T>
+        >
+    );
+}
+
+/// Records the current commission of their marketplace in every auction.
+pub fn migrate<T: Config>() -> Weight {
+    let mut reads: u64 = 0;
+    let mut writes: u64 = 0;
+
+    v3::Auctions::<T>::translate::<v2::AuctionData<T>, _>(|_, old| {
+        reads += 2;
+        writes += 1;
+
+        let commission_fee = T::MarketplaceHandler::get_marketplace(old.marketplace_id)
+            .map(|x| x.commission_fee)
+            .unwrap_or_default();
+
+        let auction = v3::AuctionData::<T> {
+            creator: old.creator,
+            start_block: old.start_block,
+            end_block: old.end_block,
+            start_price: old.start_price,
+            buy_it_price: old.buy_it_price,
+            bidders: old.bidders,
+            marketplace_id: old.marketplace_id,
+            commission_fee,
+            is_extended: old.is_extended,
+        };
+
+        Some(auction)
+    });
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
@@ -0,0 +1,41 @@
+use crate::migrations::v2::v2;
+use crate::migrations::v3::v3;
+use crate::types::AuctionData;
+use crate::{AuctionDataOf, Auctions, BalanceOf, Claims, Config};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use sp_std::vec::Vec;
+use ternoa_primitives::marketplace::PaymentAsset;
+
+/// Existing auctions and claims are paid with Caps.
+pub fn migrate<T: Config>() -> Weight {
+    let mut count: u64 = 0;
+
+    Auctions::<T>::translate::<v3::AuctionData<T>, _>(|_, old| {
+        count += 1;
+
+        let auction: AuctionDataOf<T> = AuctionData {
+            creator: old.creator,
+            start_block: old.start_block,
+            end_block: old.end_block,
+            start_price: old.start_price,
+            buy_it_price: old.buy_it_price,
+            asset: PaymentAsset::Native,
+            bidders: old.bidders,
+            marketplace_id: old.marketplace_id,
+            commission_fee: old.commission_fee,
+            is_extended: old.is_extended,
+        };
+
+        Some(auction)
+    });
+
+    // Both layouts share the same prefix, so the old claims are all taken out first.
+    let claims: Vec<(T::AccountId, BalanceOf<T>)> = v2::Claims::<T>::drain().collect();
+    for (account, amount) in claims {
+        count += 1;
+        Claims::<T>::insert(account, PaymentAsset::Native, amount);
+    }
+
+    T::DbWeight::get().reads_writes(count, count)
+}
@@ -0,0 +1,15 @@
+use crate::{Auctions, Config, MarketplaceAuctions};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+
+/// Index the existing auctions by marketplace.
+pub fn migrate<T: Config>() -> Weight {
+    let mut count: u64 = 0;
+
+    for (nft_id, auction) in Auctions::<T>::iter() {
+        count += 1;
+        MarketplaceAuctions::<T>::insert(auction.marketplace_id, nft_id, ());
+    }
+
+    T::DbWeight::get().reads_writes(count, count)
+}
@@ -2,13 +2,25 @@ use super::mock::AuctionState::{Before, Extended, InProgress};
 use super::mock::*;
 use crate::tests::mock;
 use crate::types::{AuctionData, BidderList, DeadlineList};
-use crate::{Auctions as AuctionsStorage, Claims, Deadlines, Error, Event as AuctionEvent};
+use crate::{
+    AuctionDataOf, Auctions as AuctionsStorage, Claims, Deadlines, Error, Event as AuctionEvent,
+    MarketplaceAuctions, WeightInfo,
+};
 use frame_support::error::BadOrigin;
+use frame_support::traits::Get;
+use frame_support::weights::constants::RocksDbWeight;
 use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
 use pallet_balances::Error as BalanceError;
+use sp_runtime::Permill;
+use std::convert::TryInto;
+use ternoa_common::helpers::marketplace_commission;
 use ternoa_common::traits::{MarketplaceTrait, NFTTrait};
 use ternoa_marketplace::Error as MarketError;
+use ternoa_nfts::Error as NFTError;
+use ternoa_nfts::WeightInfo as NFTsWeightInfo;
+use ternoa_primitives::marketplace::{MarketplaceCommission, MarketplaceRole, PaymentAsset};
+use ternoa_primitives::nfts::NFTLockReason;
 
 fn origin(account: u64) -> mock::Origin {
     RawOrigin::Signed(account).into()
@@ -18,6 +30,14 @@ fn root() -> mock::Origin {
     RawOrigin::Root.into()
 }
 
+fn set_royalty(nft_id: u32, creator: u64, royalty: Permill) {
+    ternoa_nfts::Data::<Test>::mutate(nft_id, |x| {
+        let x = x.as_mut().unwrap();
+        x.creator = creator;
+        x.royalty = royalty;
+    });
+}
+
 pub mod create_auction {
     pub use super::*;
 
@@ -27,18 +47,20 @@ pub mod create_auction {
             let (nft_id, market_id) = (ALICE_NFT_ID, ALICE_MARKET_ID);
 
             let start_block = 10;
-            let auction = AuctionData {
+            let auction: AuctionDataOf<Test> = AuctionData {
                 creator: ALICE,
                 start_block,
                 end_block: start_block + MIN_AUCTION_DURATION,
                 start_price: 300,
                 buy_it_price: Some(400),
+                asset: PaymentAsset::Native,
                 bidders: BidderList::new(BID_HISTORY_SIZE),
                 marketplace_id: market_id,
+                commission_fee: MARKETPLACE_COMMISSION_FEE,
                 is_extended: false,
             };
 
-            let deadline = DeadlineList(vec![(nft_id, auction.end_block)]);
+            let deadline = DeadlineList(vec![(nft_id, auction.end_block)].try_into().unwrap());
 
             let ok = Auctions::create_auction(
                 origin(ALICE),
@@ -48,16 +70,22 @@ pub mod create_auction {
                 auction.end_block,
                 auction.start_price,
                 auction.buy_it_price.clone(),
+                PaymentAsset::Native,
             );
             assert_ok!(ok);
 
             // Storage
-            assert_eq!(NFTs::is_listed_for_sale(nft_id), Some(true));
+            assert_eq!(
+                NFTs::is_locked(nft_id, NFTLockReason::ListedForSale),
+                Some(true)
+            );
             assert_eq!(AuctionsStorage::<Test>::iter().count(), 1);
             assert_eq!(Claims::<Test>::iter().count(), 0);
 
             assert_eq!(AuctionsStorage::<Test>::get(nft_id).unwrap(), auction);
             assert_eq!(Deadlines::<Test>::get(), deadline);
+            let indexed = MarketplaceAuctions::<Test>::contains_key(auction.marketplace_id, nft_id);
+            assert!(indexed);
 
             // Events
             let event = AuctionEvent::AuctionCreated {
@@ -66,6 +94,7 @@ pub mod create_auction {
                 start_block: auction.start_block,
                 end_block: auction.end_block,
                 buy_it_price: auction.buy_it_price,
+                asset: auction.asset,
                 marketplace_id: auction.marketplace_id,
                 start_price: auction.start_price,
             };
@@ -91,6 +120,7 @@ pub mod create_auction {
                 1000,
                 100,
                 Some(200),
+                PaymentAsset::Native,
             );
             assert_noop!(ok, Error::<Test>::AuctionCannotStartInThePast);
         })
@@ -112,6 +142,7 @@ pub mod create_auction {
                 end_block,
                 100,
                 Some(200),
+                PaymentAsset::Native,
             );
             assert_noop!(ok, Error::<Test>::AuctionCannotEndBeforeItHasStarted);
         })
@@ -133,6 +164,7 @@ pub mod create_auction {
                 end_block,
                 100,
                 Some(200),
+                PaymentAsset::Native,
             );
             assert_noop!(ok, Error::<Test>::AuctionDurationIsTooLong);
         })
@@ -154,6 +186,7 @@ pub mod create_auction {
                 end_block,
                 100,
                 Some(200),
+                PaymentAsset::Native,
             );
             assert_noop!(ok, Error::<Test>::AuctionDurationIsTooShort);
         })
@@ -175,6 +208,7 @@ pub mod create_auction {
                 end_block,
                 100,
                 Some(200),
+                PaymentAsset::Native,
             );
             assert_noop!(ok, Error::<Test>::AuctionStartIsTooFarAway);
         })
@@ -194,6 +228,7 @@ pub mod create_auction {
                 System::block_number() + MIN_AUCTION_DURATION,
                 start_price,
                 Some(start_price),
+                PaymentAsset::Native,
             );
             assert_noop!(
                 ok,
@@ -215,6 +250,7 @@ pub mod create_auction {
                 System::block_number() + MIN_AUCTION_DURATION,
                 100,
                 Some(101),
+                PaymentAsset::Native,
             );
             assert_noop!(ok, Error::<Test>::NFTDoesNotExist);
         })
@@ -232,6 +268,7 @@ pub mod create_auction {
                 System::block_number() + MIN_AUCTION_DURATION,
                 100,
                 Some(101),
+                PaymentAsset::Native,
             );
             assert_noop!(ok, Error::<Test>::CannotAuctionNotOwnedNFTs);
         })
@@ -241,7 +278,7 @@ pub mod create_auction {
     fn cannot_auction_nfts_listed_for_sale() {
         ExtBuilder::new_build(vec![], None).execute_with(|| {
             let (nft_id, market_id) = (ALICE_NFT_ID, ALICE_MARKET_ID);
-            assert_ok!(NFTs::set_listed_for_sale(nft_id, true));
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::ListedForSale));
 
             let ok = Auctions::create_auction(
                 origin(ALICE),
@@ -251,8 +288,9 @@ pub mod create_auction {
                 System::block_number() + MIN_AUCTION_DURATION,
                 100,
                 Some(101),
+                PaymentAsset::Native,
             );
-            assert_noop!(ok, Error::<Test>::CannotAuctionNFTsListedForSale);
+            assert_noop!(ok, NFTError::<Test>::NFTIsListedForSale);
         })
     }
 
@@ -260,7 +298,7 @@ pub mod create_auction {
     fn cannot_auction_nfts_in_transmission() {
         ExtBuilder::new_build(vec![], None).execute_with(|| {
             let (nft_id, market_id) = (ALICE_NFT_ID, ALICE_MARKET_ID);
-            assert_ok!(NFTs::set_in_transmission(nft_id, true));
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::InTransmission));
 
             let ok = Auctions::create_auction(
                 origin(ALICE),
@@ -270,8 +308,9 @@ pub mod create_auction {
                 System::block_number() + MIN_AUCTION_DURATION,
                 100,
                 Some(101),
+                PaymentAsset::Native,
             );
-            assert_noop!(ok, Error::<Test>::CannotAuctionNFTsInTransmission);
+            assert_noop!(ok, NFTError::<Test>::NFTIsInTransmission);
         })
     }
 
@@ -279,7 +318,7 @@ pub mod create_auction {
     fn cannot_auction_capsules() {
         ExtBuilder::new_build(vec![], None).execute_with(|| {
             let (nft_id, market_id) = (ALICE_NFT_ID, ALICE_MARKET_ID);
-            assert_ok!(NFTs::set_converted_to_capsule(ALICE_NFT_ID, true));
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::Capsule));
 
             let ok = Auctions::create_auction(
                 origin(ALICE),
@@ -289,8 +328,9 @@ pub mod create_auction {
                 System::block_number() + MIN_AUCTION_DURATION,
                 100,
                 Some(101),
+                PaymentAsset::Native,
             );
-            assert_noop!(ok, Error::<Test>::CannotAuctionCapsules);
+            assert_noop!(ok, NFTError::<Test>::NFTIsCapsule);
         })
     }
 
@@ -308,6 +348,7 @@ pub mod create_auction {
                 System::block_number() + MIN_AUCTION_DURATION,
                 100,
                 Some(101),
+                PaymentAsset::Native,
             );
             assert_noop!(ok, Error::<Test>::CannotAuctionNFTsInUncompletedSeries);
         })
@@ -330,16 +371,39 @@ pub mod create_auction {
                 System::block_number() + MIN_AUCTION_DURATION,
                 100,
                 Some(101),
+                PaymentAsset::Native,
             );
             assert_noop!(ok, MarketError::<Test>::NotAllowedToList);
         })
     }
 
     #[test]
-    fn cannot_auction_lent_nfts() {
+    fn cannot_auction_rented_nfts() {
+        ExtBuilder::new_build(vec![], None).execute_with(|| {
+            let (nft_id, market_id) = (ALICE_NFT_ID, ALICE_MARKET_ID);
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 0, 10, None));
+            assert_ok!(NFTs::rent(origin(BOB), nft_id));
+
+            let ok = Auctions::create_auction(
+                origin(ALICE),
+                nft_id,
+                market_id,
+                System::block_number(),
+                System::block_number() + MIN_AUCTION_DURATION,
+                100,
+                Some(101),
+                PaymentAsset::Native,
+            );
+            assert_noop!(ok, NFTError::<Test>::NFTIsRented);
+        })
+    }
+
+    #[test]
+    fn maximum_auctions_limit_reached() {
         ExtBuilder::new_build(vec![], None).execute_with(|| {
             let (nft_id, market_id) = (ALICE_NFT_ID, ALICE_MARKET_ID);
-            assert_ok!(NFTs::set_viewer(nft_id, Some(BOB)));
+            let full: Vec<(u32, u64)> = (0..100).map(|i| (INVALID_NFT_ID + i, 5000)).collect();
+            Deadlines::<Test>::put(DeadlineList(full.try_into().unwrap()));
 
             let ok = Auctions::create_auction(
                 origin(ALICE),
@@ -349,8 +413,26 @@ pub mod create_auction {
                 System::block_number() + MIN_AUCTION_DURATION,
                 100,
                 Some(101),
+                PaymentAsset::Native,
+            );
+            assert_noop!(ok, Error::<Test>::MaximumAuctionsLimitReached);
+        })
+    }
+
+    #[test]
+    fn asset_is_not_accepted() {
+        ExtBuilder::new_build(vec![], None).execute_with(|| {
+            let ok = Auctions::create_auction(
+                origin(ALICE),
+                ALICE_NFT_ID,
+                ALICE_MARKET_ID,
+                System::block_number(),
+                System::block_number() + MIN_AUCTION_DURATION,
+                100,
+                Some(200),
+                PaymentAsset::Asset(TIIME),
             );
-            assert_noop!(ok, Error::<Test>::CannotAuctionLentNFTs);
+            assert_noop!(ok, MarketError::<Test>::AssetNotAccepted);
         })
     }
 }
@@ -369,13 +451,16 @@ pub mod cancel_auction {
 
             // NFT
             let nft = NFTs::get_nft(nft_id).unwrap();
-            assert_eq!(nft.listed_for_sale, false);
+            assert_eq!(nft.lock, None);
             assert_eq!(nft.owner, ALICE);
 
             // Storage
             deadlines.remove(nft_id);
 
-            assert_eq!(NFTs::is_listed_for_sale(nft_id), Some(false));
+            assert_eq!(
+                NFTs::is_locked(nft_id, NFTLockReason::ListedForSale),
+                Some(false)
+            );
             assert_eq!(AuctionsStorage::<Test>::iter().count(), auction_count - 1);
             assert_eq!(Claims::<Test>::iter().count(), 0);
 
@@ -405,6 +490,22 @@ pub mod cancel_auction {
         })
     }
 
+    #[test]
+    fn marketplace_curator() {
+        ExtBuilder::new_build(vec![], Some(Before)).execute_with(|| {
+            let role = MarketplaceRole::Curator;
+            let ok = Marketplace::grant_role(origin(ALICE), ALICE_MARKET_ID, CHARLIE, role);
+            assert_ok!(ok);
+
+            assert_ok!(Auctions::cancel_auction(origin(CHARLIE), BOB_NFT_ID));
+            assert_eq!(AuctionsStorage::<Test>::get(BOB_NFT_ID), None);
+            assert_eq!(
+                NFTs::is_locked(BOB_NFT_ID, NFTLockReason::ListedForSale),
+                Some(false)
+            );
+        })
+    }
+
     #[test]
     fn cannot_cancel_auction_in_progress() {
         ExtBuilder::new_build(vec![], Some(Before)).execute_with(|| {
@@ -420,6 +521,156 @@ pub mod cancel_auction {
     }
 }
 
+pub mod force_burn {
+    pub use super::*;
+
+    #[test]
+    fn force_burn_refunds_bidders() {
+        ExtBuilder::new_build(vec![(BOB, 1000), (CHARLIE, 1000)], Some(InProgress)).execute_with(
+            || {
+                let nft_id = ALICE_NFT_ID;
+                let auction = AuctionsStorage::<Test>::get(nft_id).unwrap();
+                let mut deadlines = Deadlines::<Test>::get();
+                let bob_bid = auction.start_price + 10;
+                let charlie_bid = bob_bid + 10;
+                assert_ok!(Auctions::add_bid(origin(BOB), nft_id, bob_bid));
+                assert_ok!(Auctions::add_bid(origin(CHARLIE), nft_id, charlie_bid));
+                // Claims that are already there get topped up.
+                Claims::<Test>::insert(BOB, PaymentAsset::Native, 5);
+
+                let info = NFTs::force_burn(root(), nft_id).unwrap();
+
+                // The marketplace only looks for a sale of the NFT
+                let released = <() as WeightInfo>::release_nft(2) + RocksDbWeight::get().reads(2);
+                let expected = <() as NFTsWeightInfo>::force_burn() + released;
+                assert_eq!(info.actual_weight, Some(expected));
+
+                // Storage
+                deadlines.remove(nft_id);
+                assert_eq!(NFTs::get_nft(nft_id), None);
+                assert_eq!(AuctionsStorage::<Test>::get(nft_id), None);
+                assert_eq!(Deadlines::<Test>::get(), deadlines);
+                assert_eq!(
+                    Claims::<Test>::get(BOB, PaymentAsset::Native),
+                    Some(bob_bid + 5)
+                );
+                assert_eq!(
+                    Claims::<Test>::get(CHARLIE, PaymentAsset::Native),
+                    Some(charlie_bid)
+                );
+
+                // Check Events
+                let event = Event::Auctions(AuctionEvent::AuctionCancelled { nft_id });
+                assert!(System::events().iter().any(|x| x.event == event));
+
+                // Bidders get their funds back.
+                let charlie_balance = Balances::free_balance(CHARLIE);
+                assert_ok!(Auctions::claim(origin(CHARLIE), PaymentAsset::Native));
+                assert_eq!(
+                    Balances::free_balance(CHARLIE),
+                    charlie_balance + charlie_bid
+                );
+            },
+        )
+    }
+}
+
+pub mod accept_offer {
+    pub use super::*;
+
+    #[test]
+    fn cannot_accept_offer_on_auctioned_nft() {
+        ExtBuilder::new_build(vec![(ALICE, 1000), (BOB, 1000)], Some(InProgress)).execute_with(
+            || {
+                let nft_id = ALICE_NFT_ID;
+                let market_id = Some(ALICE_MARKET_ID);
+                assert_ok!(Marketplace::make_offer(
+                    origin(BOB),
+                    nft_id,
+                    50,
+                    market_id,
+                    10
+                ));
+                assert_ok!(Marketplace::make_series_offer(
+                    origin(BOB),
+                    vec![ALICE_SERIES_ID],
+                    50,
+                    market_id,
+                    10
+                ));
+
+                let ok = Marketplace::accept_offer(origin(ALICE), nft_id, BOB);
+                assert_noop!(ok, MarketError::<Test>::NftIsLocked);
+                let ok = Marketplace::accept_series_offer(origin(ALICE), nft_id, BOB);
+                assert_noop!(ok, MarketError::<Test>::NftIsLocked);
+
+                assert_eq!(NFTs::owner(nft_id), Some(ALICE));
+                assert!(AuctionsStorage::<Test>::get(nft_id).is_some());
+            },
+        )
+    }
+}
+
+pub mod close_marketplace {
+    pub use super::*;
+
+    #[test]
+    fn close_marketplace_settles_auctions() {
+        ExtBuilder::new_build(vec![(CHARLIE, 1000)], Some(InProgress)).execute_with(|| {
+            let auction = AuctionsStorage::<Test>::get(ALICE_NFT_ID).unwrap();
+            let bid = auction.start_price + 10;
+            assert_ok!(Auctions::add_bid(origin(CHARLIE), ALICE_NFT_ID, bid));
+
+            let ok = Marketplace::close_marketplace(root(), ALICE_MARKET_ID, 10);
+            assert_ok!(ok);
+
+            // The auction with a bid goes to the highest bidder
+            assert_eq!(NFTs::get_nft(ALICE_NFT_ID).unwrap().owner, CHARLIE);
+            let event = AuctionEvent::AuctionCompleted {
+                nft_id: ALICE_NFT_ID,
+                new_owner: Some(CHARLIE),
+                amount: Some(bid),
+            };
+            let event = Event::Auctions(event);
+            assert!(System::events().iter().any(|x| x.event == event));
+
+            // The other one is cancelled
+            let nft = NFTs::get_nft(BOB_NFT_ID).unwrap();
+            assert_eq!(nft.owner, BOB);
+            assert_eq!(nft.lock, None);
+            let event = Event::Auctions(AuctionEvent::AuctionCancelled { nft_id: BOB_NFT_ID });
+            assert!(System::events().iter().any(|x| x.event == event));
+
+            // Storage
+            assert_eq!(AuctionsStorage::<Test>::iter().count(), 0);
+            assert_eq!(MarketplaceAuctions::<Test>::iter().count(), 0);
+            assert_eq!(Deadlines::<Test>::get().0.len(), 0);
+            assert_eq!(Marketplace::marketplaces(ALICE_MARKET_ID), None);
+        })
+    }
+
+    #[test]
+    fn cannot_create_auction_on_closing_marketplace() {
+        ExtBuilder::new_build(vec![], None).execute_with(|| {
+            let ok = Marketplace::close_marketplace(root(), ALICE_MARKET_ID, 0);
+            assert_ok!(ok);
+
+            let start_block = 10;
+            let ok = Auctions::create_auction(
+                origin(ALICE),
+                ALICE_NFT_ID,
+                ALICE_MARKET_ID,
+                start_block,
+                start_block + MIN_AUCTION_DURATION,
+                100,
+                None,
+                PaymentAsset::Native,
+            );
+            assert_noop!(ok, MarketError::<Test>::MarketplaceClosing);
+        })
+    }
+}
+
 pub mod end_auction {
     pub use super::*;
 
@@ -438,7 +689,7 @@ pub mod end_auction {
                 let auction = AuctionsStorage::<Test>::get(nft_id).unwrap();
                 let market = Marketplace::get_marketplace(market_id).unwrap();
                 let market_fee = market.commission_fee;
-                assert!(market_fee > 0);
+                assert!(market_fee.rate > Permill::zero());
 
                 let charlie_bid = auction.start_price + 10;
                 let dave_bid = charlie_bid + 10;
@@ -457,7 +708,7 @@ pub mod end_auction {
                 let dave_new_balance = Balances::free_balance(DAVE);
                 let pallet_new_balance = Balances::free_balance(Auctions::account_id());
 
-                let market_owner_cut: u128 = dave_bid.saturating_mul(market_fee.into()) / 100u128;
+                let market_owner_cut: u128 = marketplace_commission(&market_fee, dave_bid);
                 let artist_cut: u128 = dave_bid.saturating_sub(market_owner_cut.into());
 
                 assert_ne!(market_owner_cut, artist_cut);
@@ -472,7 +723,7 @@ pub mod end_auction {
 
                 // NFT
                 let nft = NFTs::get_nft(nft_id).unwrap();
-                assert_eq!(nft.listed_for_sale, false);
+                assert_eq!(nft.lock, None);
                 assert_eq!(nft.owner, DAVE);
 
                 // Storage
@@ -483,7 +734,10 @@ pub mod end_auction {
 
                 assert_eq!(AuctionsStorage::<Test>::get(nft_id), None);
                 assert_eq!(Deadlines::<Test>::get(), deadlines);
-                assert_eq!(Claims::<Test>::get(CHARLIE), Some(charlie_bid));
+                assert_eq!(
+                    Claims::<Test>::get(CHARLIE, PaymentAsset::Native),
+                    Some(charlie_bid)
+                );
 
                 // Check Events
                 let event = AuctionEvent::AuctionCompleted {
@@ -497,6 +751,40 @@ pub mod end_auction {
         )
     }
 
+    #[test]
+    fn commission_is_taken_from_auction_creation() {
+        ExtBuilder::new_build(vec![(CHARLIE, 1000)], Some(Extended)).execute_with(|| {
+            let (nft_id, market_id) = (BOB_NFT_ID, ALICE_MARKET_ID);
+            let auction = AuctionsStorage::<Test>::get(nft_id).unwrap();
+            assert_eq!(auction.commission_fee, MARKETPLACE_COMMISSION_FEE);
+
+            // The marketplace owner raises the commission while the auction runs
+            let fee = MarketplaceCommission::new(Permill::one(), 0);
+            assert_ok!(Marketplace::set_commission_fee(
+                origin(ALICE),
+                market_id,
+                fee
+            ));
+
+            let bid = auction.start_price + 10;
+            assert_ok!(Auctions::add_bid(origin(CHARLIE), nft_id, bid));
+
+            let alice_balance = Balances::free_balance(ALICE);
+            let bob_balance = Balances::free_balance(BOB);
+            assert_ok!(Auctions::end_auction(origin(BOB), nft_id));
+
+            let market_owner_cut = marketplace_commission(&MARKETPLACE_COMMISSION_FEE, bid);
+            assert_eq!(
+                Balances::free_balance(ALICE),
+                alice_balance + market_owner_cut
+            );
+            assert_eq!(
+                Balances::free_balance(BOB),
+                bob_balance + bid - market_owner_cut
+            );
+        })
+    }
+
     #[test]
     fn auction_does_not_exist() {
         ExtBuilder::new_build(vec![], Some(Extended)).execute_with(|| {
@@ -602,7 +890,10 @@ pub mod add_bid {
             auction.bidders.list = accounts;
 
             assert_eq!(Claims::<Test>::iter().count(), 1);
-            assert_eq!(Claims::<Test>::get(BOB), Some(bob_bid));
+            assert_eq!(
+                Claims::<Test>::get(BOB, PaymentAsset::Native),
+                Some(bob_bid)
+            );
             assert_eq!(AuctionsStorage::<Test>::get(nft_id), Some(auction));
 
             // Check Events
@@ -867,7 +1158,7 @@ pub mod buy_it_now {
             let charlie_new_balance = Balances::free_balance(CHARLIE);
             let pallet_new_balance = Balances::free_balance(Auctions::account_id());
 
-            let market_owner_cut: u128 = price.saturating_mul(market_fee.into()) / 100u128;
+            let market_owner_cut: u128 = marketplace_commission(&market_fee, price);
             let artist_cut: u128 = price.saturating_sub(market_owner_cut.into());
 
             assert_eq!(alice_new_balance, alice_balance + market_owner_cut);
@@ -877,7 +1168,7 @@ pub mod buy_it_now {
 
             // NFT
             let nft = NFTs::get_nft(nft_id).unwrap();
-            assert_eq!(nft.listed_for_sale, false);
+            assert_eq!(nft.lock, None);
             assert_eq!(nft.owner, CHARLIE);
 
             // Storage
@@ -921,12 +1212,15 @@ pub mod buy_it_now {
 
                 // NFT
                 let nft = NFTs::get_nft(nft_id).unwrap();
-                assert_eq!(nft.listed_for_sale, false);
+                assert_eq!(nft.lock, None);
                 assert_eq!(nft.owner, CHARLIE);
 
                 // Storage
                 assert_eq!(Claims::<Test>::iter().count(), 1);
-                assert_eq!(Claims::<Test>::get(BOB), Some(bob_bid));
+                assert_eq!(
+                    Claims::<Test>::get(BOB, PaymentAsset::Native),
+                    Some(bob_bid)
+                );
                 assert_eq!(AuctionsStorage::<Test>::get(nft_id), None);
 
                 // Check Events
@@ -941,6 +1235,48 @@ pub mod buy_it_now {
         )
     }
 
+    #[test]
+    fn buy_it_now_with_royalty() {
+        ExtBuilder::new_build(vec![(CHARLIE, 1000)], Some(InProgress)).execute_with(|| {
+            let nft_id = BOB_NFT_ID;
+            set_royalty(nft_id, EVE, Permill::from_percent(20));
+
+            let alice_balance = Balances::free_balance(ALICE);
+            let bob_balance = Balances::free_balance(BOB);
+            let charlie_balance = Balances::free_balance(CHARLIE);
+            let eve_balance = Balances::free_balance(EVE);
+            let auction = AuctionsStorage::<Test>::get(nft_id).unwrap();
+            let market = Marketplace::get_marketplace(ALICE_MARKET_ID).unwrap();
+            let market_fee = market.commission_fee;
+
+            let price = auction.buy_it_price.clone().unwrap();
+            assert_ok!(Auctions::buy_it_now(origin(CHARLIE), nft_id));
+
+            // Balance
+            let creator_cut: u128 = price * 20 / 100;
+            let market_owner_cut: u128 = marketplace_commission(&market_fee, price);
+            let artist_cut: u128 = price - creator_cut - market_owner_cut;
+
+            assert_eq!(Balances::free_balance(EVE), eve_balance + creator_cut);
+            assert_eq!(
+                Balances::free_balance(ALICE),
+                alice_balance + market_owner_cut
+            );
+            assert_eq!(Balances::free_balance(BOB), bob_balance + artist_cut);
+            assert_eq!(Balances::free_balance(CHARLIE), charlie_balance - price);
+
+            // Check Events
+            let event = AuctionEvent::AuctionPaidOut {
+                nft_id,
+                seller_cut: artist_cut,
+                marketplace_cut: market_owner_cut,
+                royalty_cut: creator_cut,
+            };
+            let event = Event::Auctions(event);
+            assert!(System::events().iter().any(|x| x.event == event));
+        })
+    }
+
     #[test]
     fn auction_does_not_exist() {
         ExtBuilder::new_build(vec![], Some(InProgress)).execute_with(|| {
@@ -1002,7 +1338,7 @@ pub mod complete_auction {
 
             // NFT
             let nft = NFTs::get_nft(nft_id).unwrap();
-            assert_eq!(nft.listed_for_sale, false);
+            assert_eq!(nft.lock, None);
             assert_eq!(nft.owner, auction.creator);
 
             // Storage
@@ -1045,7 +1381,7 @@ pub mod complete_auction {
                 let charlie_new_balance = Balances::free_balance(CHARLIE);
                 let pallet_new_balance = Balances::free_balance(Auctions::account_id());
 
-                let market_owner_cut: u128 = bid.saturating_mul(market_fee.into()) / 100u128;
+                let market_owner_cut: u128 = marketplace_commission(&market_fee, bid);
                 let artist_cut: u128 = bid.saturating_sub(market_owner_cut.into());
 
                 assert_eq!(alice_new_balance, alice_balance + market_owner_cut);
@@ -1055,7 +1391,7 @@ pub mod complete_auction {
 
                 // NFT
                 let nft = NFTs::get_nft(nft_id).unwrap();
-                assert_eq!(nft.listed_for_sale, false);
+                assert_eq!(nft.lock, None);
                 assert_eq!(nft.owner, CHARLIE);
 
                 // Storage
@@ -1077,6 +1413,36 @@ pub mod complete_auction {
         )
     }
 
+    #[test]
+    fn complete_auction_with_royalty() {
+        ExtBuilder::new_build(vec![(CHARLIE, 1000)], Some(InProgress)).execute_with(|| {
+            let nft_id = BOB_NFT_ID;
+            set_royalty(nft_id, EVE, Permill::from_percent(50));
+
+            let alice_balance = Balances::free_balance(ALICE);
+            let bob_balance = Balances::free_balance(BOB);
+            let eve_balance = Balances::free_balance(EVE);
+            let auction = AuctionsStorage::<Test>::get(nft_id).unwrap();
+            let bid = auction.start_price + 10;
+            assert_ok!(Auctions::add_bid(origin(CHARLIE), nft_id, bid));
+            assert_ok!(Auctions::complete_auction(root(), nft_id));
+
+            // Balance
+            let creator_cut: u128 = bid / 2;
+            let market_owner_cut: u128 = bid * MARKETPLACE_COMMISSION_FEE as u128 / 100;
+            let artist_cut: u128 = bid - creator_cut - market_owner_cut;
+
+            assert_eq!(Balances::free_balance(EVE), eve_balance + creator_cut);
+            assert_eq!(
+                Balances::free_balance(ALICE),
+                alice_balance + market_owner_cut
+            );
+            assert_eq!(Balances::free_balance(BOB), bob_balance + artist_cut);
+            assert_eq!(Balances::free_balance(Auctions::account_id()), 0);
+            assert_eq!(NFTs::get_nft(nft_id).unwrap().owner, CHARLIE);
+        })
+    }
+
     #[test]
     fn complete_auction_with_two_bids() {
         ExtBuilder::new_build(vec![(BOB, 1000), (CHARLIE, 1000)], Some(InProgress)).execute_with(
@@ -1107,14 +1473,17 @@ pub mod complete_auction {
 
                 // NFT
                 let nft = NFTs::get_nft(nft_id).unwrap();
-                assert_eq!(nft.listed_for_sale, false);
+                assert_eq!(nft.lock, None);
                 assert_eq!(nft.owner, CHARLIE);
 
                 // Storage
                 deadlines.remove(nft_id);
 
                 assert_eq!(Claims::<Test>::iter().count(), 1);
-                assert_eq!(Claims::<Test>::get(BOB), Some(bob_bid));
+                assert_eq!(
+                    Claims::<Test>::get(BOB, PaymentAsset::Native),
+                    Some(bob_bid)
+                );
                 assert_eq!(AuctionsStorage::<Test>::get(nft_id), None);
                 assert_eq!(Deadlines::<Test>::get(), deadlines);
 
@@ -1130,6 +1499,42 @@ pub mod complete_auction {
         )
     }
 
+    #[test]
+    fn auction_that_cannot_be_completed_is_cancelled() {
+        ExtBuilder::new_build(vec![(BOB, 1000), (CHARLIE, 1000)], Some(InProgress)).execute_with(
+            || {
+                use frame_support::traits::Currency;
+
+                let nft_id = ALICE_NFT_ID;
+                let auction = AuctionsStorage::<Test>::get(nft_id).unwrap();
+                let bid = auction.start_price + 1;
+                assert_ok!(Auctions::add_bid(origin(CHARLIE), nft_id, bid));
+
+                // The bid is gone so the auction cannot be paid out
+                Balances::make_free_balance_be(&Auctions::account_id(), 0);
+                run_to_block(auction.end_block + 1);
+
+                // NFT
+                let nft = NFTs::get_nft(nft_id).unwrap();
+                assert_eq!(nft.lock, None);
+                assert_eq!(nft.owner, auction.creator);
+
+                // Storage
+                assert_eq!(AuctionsStorage::<Test>::get(nft_id), None);
+                assert!(!Deadlines::<Test>::get().remove(nft_id));
+                let marketplace_id = auction.marketplace_id;
+                let is_indexed = MarketplaceAuctions::<Test>::contains_key(marketplace_id, nft_id);
+                assert!(!is_indexed);
+                let claim = Claims::<Test>::get(CHARLIE, PaymentAsset::Native);
+                assert_eq!(claim, Some(bid));
+
+                // Event
+                let event = Event::Auctions(AuctionEvent::AuctionCancelled { nft_id });
+                assert!(System::events().iter().any(|x| x.event == event));
+            },
+        )
+    }
+
     #[test]
     fn bad_origin() {
         ExtBuilder::new_build(vec![], Some(InProgress)).execute_with(|| {
@@ -1165,8 +1570,8 @@ pub mod claim {
                 assert_ok!(Auctions::add_bid(origin(CHARLIE), nft_id, charlie_bid));
                 assert_ok!(Auctions::complete_auction(root(), nft_id));
 
-                let claim = Claims::<Test>::get(BOB).unwrap();
-                assert_ok!(Auctions::claim(origin(BOB)));
+                let claim = Claims::<Test>::get(BOB, PaymentAsset::Native).unwrap();
+                assert_ok!(Auctions::claim(origin(BOB), PaymentAsset::Native));
 
                 // Balance
                 let bob_new_balance = Balances::free_balance(BOB);
@@ -1177,10 +1582,11 @@ pub mod claim {
 
                 // Storage
                 assert_eq!(Claims::<Test>::iter().count(), 0);
-                assert_eq!(Claims::<Test>::get(BOB), None);
+                assert_eq!(Claims::<Test>::get(BOB, PaymentAsset::Native), None);
                 // Event
                 let event = AuctionEvent::BalanceClaimed {
                     account: BOB,
+                    asset: PaymentAsset::Native,
                     amount: claim,
                 };
                 let event = Event::Auctions(event);
@@ -1189,10 +1595,56 @@ pub mod claim {
         )
     }
 
+    #[test]
+    fn claim_asset() {
+        ExtBuilder::new_build(vec![(BOB, 1000), (CHARLIE, 1000)], None).execute_with(|| {
+            let nft_id = ALICE_NFT_ID;
+            let asset = PaymentAsset::Asset(TIIME);
+            let accepted = vec![TIIME];
+            let ok = Marketplace::set_accepted_assets(origin(ALICE), ALICE_MARKET_ID, accepted);
+            assert_ok!(ok);
+
+            let ok = Auctions::create_auction(
+                origin(ALICE),
+                nft_id,
+                ALICE_MARKET_ID,
+                System::block_number(),
+                System::block_number() + MIN_AUCTION_DURATION,
+                100,
+                Some(200),
+                asset,
+            );
+            assert_ok!(ok);
+
+            let bob_balance = Balances::free_balance(BOB);
+            assert_ok!(Auctions::add_bid(origin(BOB), nft_id, 101));
+            assert_ok!(Auctions::add_bid(origin(CHARLIE), nft_id, 102));
+            assert_eq!(Assets::balance(TIIME, Auctions::account_id()), 203);
+            assert_ok!(Auctions::complete_auction(root(), nft_id));
+
+            // Outbid Tiime can only be claimed as Tiime
+            assert_eq!(Claims::<Test>::get(BOB, PaymentAsset::Native), None);
+            assert_eq!(Claims::<Test>::get(BOB, asset), Some(101));
+            let ok = Auctions::claim(origin(BOB), PaymentAsset::Native);
+            assert_noop!(ok, Error::<Test>::ClaimDoesNotExist);
+            assert_ok!(Auctions::claim(origin(BOB), asset));
+
+            // Balances
+            assert_eq!(Assets::balance(TIIME, BOB), 1000);
+            assert_eq!(Assets::balance(TIIME, CHARLIE), 1000 - 102);
+            assert_eq!(Assets::balance(TIIME, Auctions::account_id()), 0);
+            assert_eq!(Balances::free_balance(BOB), bob_balance);
+            assert_eq!(NFTs::get_nft(nft_id).unwrap().owner, CHARLIE);
+
+            // Storage
+            assert_eq!(Claims::<Test>::iter().count(), 0);
+        })
+    }
+
     #[test]
     fn claim_does_not_exist() {
         ExtBuilder::new_build(vec![], Some(InProgress)).execute_with(|| {
-            let ok = Auctions::claim(origin(BOB));
+            let ok = Auctions::claim(origin(BOB), PaymentAsset::Native);
             assert_noop!(ok, Error::<Test>::ClaimDoesNotExist);
         })
     }
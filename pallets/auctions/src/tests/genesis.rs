@@ -1,7 +1,9 @@
 use super::mock::*;
 use crate::types::{AuctionData, BidderList, DeadlineList};
-use crate::GenesisConfig;
+use crate::{AuctionDataOf, GenesisConfig};
 use frame_support::traits::GenesisBuild;
+use std::convert::TryInto;
+use ternoa_primitives::marketplace::PaymentAsset;
 
 #[test]
 fn genesis() {
@@ -9,23 +11,34 @@ fn genesis() {
         .build_storage::<Test>()
         .unwrap();
 
-    let bid_history_size = 100;
-    let auction = AuctionData {
+    let bid_history_size = 10;
+    let auction: AuctionDataOf<Test> = AuctionData {
         creator: ALICE,
         start_block: 10,
         end_block: 20,
         start_price: 10,
         buy_it_price: Some(20),
+        asset: PaymentAsset::Native,
         bidders: BidderList::new(bid_history_size),
         marketplace_id: ALICE_MARKET_ID,
+        commission_fee: Default::default(),
         is_extended: false,
     };
 
-    let deadlines = DeadlineList(vec![(ALICE_NFT_ID, auction.end_block)]);
-    let auctions = vec![(ALICE_NFT_ID, auction)];
+    let deadlines = DeadlineList(vec![(ALICE_NFT_ID, auction.end_block)].try_into().unwrap());
+    let auctions = vec![(
+        ALICE_NFT_ID,
+        auction.creator,
+        auction.start_block,
+        auction.end_block,
+        auction.start_price,
+        auction.buy_it_price,
+        auction.marketplace_id,
+        auction.asset,
+    )];
 
     GenesisConfig::<Test> {
-        auctions: auctions.clone(),
+        auctions,
         bid_history_size,
     }
     .assimilate_storage(&mut t)
@@ -33,10 +46,23 @@ fn genesis() {
 
     let mut ext = sp_io::TestExternalities::new(t);
     ext.execute_with(|| {
-        for auction in auctions {
-            assert_eq!(Auctions::auctions(auction.0), Some(auction.1));
-        }
+        assert_eq!(Auctions::auctions(ALICE_NFT_ID), Some(auction));
         assert_eq!(Auctions::bid_history_size(), bid_history_size);
         assert_eq!(Auctions::deadlines(), deadlines);
     });
 }
+
+#[test]
+#[should_panic(expected = "Bid history size is bigger than MaxBidHistorySize")]
+fn genesis_bid_history_size_is_bounded() {
+    let mut t = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+
+    GenesisConfig::<Test> {
+        auctions: Default::default(),
+        bid_history_size: 11,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+}
@@ -1,9 +1,11 @@
 use super::mock::*;
 use crate::tests::mock;
 use crate::types::{AuctionData, BidderList, DeadlineList};
-use crate::{Auctions as AuctionsStorage, Deadlines};
+use crate::{AuctionDataOf, Auctions as AuctionsStorage, Deadlines};
 use frame_support::assert_ok;
 use frame_system::RawOrigin;
+use std::convert::TryInto;
+use ternoa_primitives::marketplace::PaymentAsset;
 
 fn origin(account: u64) -> mock::Origin {
     RawOrigin::Signed(account).into()
@@ -17,27 +19,31 @@ fn on_initialize() {
 
         let alice_start_block = 10;
         let alice_end_block = alice_start_block + MIN_AUCTION_DURATION;
-        let alice_auction = AuctionData {
+        let alice_auction: AuctionDataOf<Test> = AuctionData {
             creator: ALICE,
             start_block: alice_start_block,
             end_block: alice_end_block,
             start_price: 300,
             buy_it_price: Some(400),
+            asset: PaymentAsset::Native,
             bidders: BidderList::new(BID_HISTORY_SIZE),
             marketplace_id: market_id,
+            commission_fee: MARKETPLACE_COMMISSION_FEE,
             is_extended: false,
         };
 
         let bob_start_block = 10 + 5;
         let bob_end_block = bob_start_block + MIN_AUCTION_DURATION;
-        let bob_auction = AuctionData {
+        let bob_auction: AuctionDataOf<Test> = AuctionData {
             creator: BOB,
             start_block: bob_start_block,
             end_block: bob_end_block,
             start_price: 300,
             buy_it_price: Some(400),
+            asset: PaymentAsset::Native,
             bidders: BidderList::new(BID_HISTORY_SIZE),
             marketplace_id: market_id,
+            commission_fee: MARKETPLACE_COMMISSION_FEE,
             is_extended: false,
         };
 
@@ -49,6 +55,7 @@ fn on_initialize() {
             alice_auction.end_block,
             alice_auction.start_price,
             alice_auction.buy_it_price.clone(),
+            PaymentAsset::Native,
         );
         assert_ok!(ok);
 
@@ -60,14 +67,16 @@ fn on_initialize() {
             bob_auction.end_block,
             bob_auction.start_price,
             bob_auction.buy_it_price.clone(),
+            PaymentAsset::Native,
         );
         assert_ok!(ok);
 
         // At block one we should have two auctions and two entries in deadlines
-        let deadlines = DeadlineList(vec![
-            (ALICE_NFT_ID, alice_end_block),
-            (BOB_NFT_ID, bob_end_block),
-        ]);
+        let deadlines = DeadlineList(
+            vec![(ALICE_NFT_ID, alice_end_block), (BOB_NFT_ID, bob_end_block)]
+                .try_into()
+                .unwrap(),
+        );
 
         assert_eq!(Deadlines::<Test>::get(), deadlines);
         assert_eq!(AuctionsStorage::<Test>::iter().count(), 2);
@@ -77,7 +86,7 @@ fn on_initialize() {
         // At block alice_auction.end_block we should have 1 auction and 1 entry in deadlines
         run_to_block(alice_auction.end_block);
 
-        let deadlines = DeadlineList(vec![(BOB_NFT_ID, bob_end_block)]);
+        let deadlines = DeadlineList(vec![(BOB_NFT_ID, bob_end_block)].try_into().unwrap());
 
         assert_eq!(Deadlines::<Test>::get(), deadlines);
         assert_eq!(AuctionsStorage::<Test>::iter().count(), 1);
@@ -86,7 +95,7 @@ fn on_initialize() {
         // At block bob_auction.end_block we should have 0 auctions and 0 entries in deadlines
         run_to_block(bob_auction.end_block);
 
-        let deadlines = DeadlineList(vec![]);
+        let deadlines = DeadlineList(vec![].try_into().unwrap());
 
         assert_eq!(Deadlines::<Test>::get(), deadlines);
         assert_eq!(AuctionsStorage::<Test>::iter().count(), 0);
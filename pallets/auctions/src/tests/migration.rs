@@ -0,0 +1,200 @@
+use super::mock::*;
+use crate::migrations::v2::{v1, v2};
+use crate::migrations::v3::v3;
+use crate::types::{AuctionData, BidderList};
+use crate::{Auctions as AuctionsStorage, Claims, Deadlines, MarketplaceAuctions};
+use frame_support::assert_ok;
+use frame_support::storage::unhashed;
+use frame_support::traits::Get;
+use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+use ternoa_common::traits::NFTTrait;
+use ternoa_primitives::marketplace::PaymentAsset;
+use ternoa_primitives::nfts::NFTLockReason;
+
+mod version_2 {
+    use super::*;
+
+    fn insert_v1_auction(nft_id: u32, bids: Vec<(u64, u128)>) {
+        let auction = v1::AuctionData {
+            creator: ALICE,
+            start_block: 1,
+            end_block: 1 + MIN_AUCTION_DURATION,
+            start_price: 10,
+            buy_it_price: None,
+            bidders: v1::BidderList {
+                list: bids,
+                max_size: 20,
+            },
+            marketplace_id: ALICE_MARKET_ID,
+            is_extended: false,
+        };
+        unhashed::put(&AuctionsStorage::<Test>::hashed_key_for(nft_id), &auction);
+    }
+
+    #[test]
+    fn upgrade_from_v1_to_v2() {
+        ExtBuilder::new_build(vec![], None).execute_with(|| {
+            // MaxBidHistorySize is 10 so the two lowest bids do not fit
+            let bids: Vec<(u64, u128)> = (0..12).map(|i| (100 + i, 20 + i as u128)).collect();
+            insert_v1_auction(ALICE_NFT_ID, bids.clone());
+
+            let deadlines = v1::DeadlineList(vec![(ALICE_NFT_ID, 1 + MIN_AUCTION_DURATION)]);
+            unhashed::put(&Deadlines::<Test>::hashed_key(), &deadlines);
+
+            StorageVersion::put::<Auctions>(&StorageVersion::new(1));
+            let weight = <Auctions as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Auctions>(), StorageVersion::new(5));
+
+            let auction = Auctions::auctions(ALICE_NFT_ID).unwrap();
+            assert_eq!(auction.bidders.list, bids[2..].to_vec());
+            assert_eq!(auction.bidders.max_size, 20);
+            assert_eq!(auction.commission_fee, MARKETPLACE_COMMISSION_FEE);
+            assert_eq!(Claims::<Test>::get(100, PaymentAsset::Native), Some(20));
+            assert_eq!(Claims::<Test>::get(101, PaymentAsset::Native), Some(21));
+            assert_eq!(Claims::<Test>::get(102, PaymentAsset::Native), None);
+
+            assert_eq!(Auctions::deadlines().0, deadlines.0);
+        })
+    }
+
+    #[test]
+    fn upgrade_from_v1_to_v2_cancels_auctions_that_do_not_fit() {
+        ExtBuilder::new_build(vec![], None).execute_with(|| {
+            let bids = vec![(BOB, 20), (CHARLIE, 30)];
+            insert_v1_auction(ALICE_NFT_ID, bids);
+            assert_ok!(NFTs::lock(ALICE_NFT_ID, NFTLockReason::ListedForSale));
+
+            // MaxAuctions is 100 so the auction that ends last does not fit
+            let max = <Test as crate::Config>::MaxAuctions::get();
+            let mut list: Vec<(u32, u64)> = (0..max).map(|i| (1000 + i, 10)).collect();
+            list.push((ALICE_NFT_ID, 1 + MIN_AUCTION_DURATION));
+            unhashed::put(
+                &Deadlines::<Test>::hashed_key(),
+                &v1::DeadlineList(list.clone()),
+            );
+
+            StorageVersion::put::<Auctions>(&StorageVersion::new(1));
+            <Auctions as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+            assert_eq!(Auctions::deadlines().0, list[..max as usize].to_vec());
+            assert_eq!(Auctions::auctions(ALICE_NFT_ID), None);
+            assert_eq!(NFTs::data(ALICE_NFT_ID).unwrap().lock, None);
+            assert_eq!(Claims::<Test>::get(BOB, PaymentAsset::Native), Some(20));
+            assert_eq!(Claims::<Test>::get(CHARLIE, PaymentAsset::Native), Some(30));
+        })
+    }
+}
+
+mod version_3 {
+    use super::*;
+
+    fn insert_v2_auction(nft_id: u32, marketplace_id: u32) {
+        let auction = v2::AuctionData::<Test> {
+            creator: ALICE,
+            start_block: 1,
+            end_block: 1 + MIN_AUCTION_DURATION,
+            start_price: 10,
+            buy_it_price: Some(20),
+            bidders: BidderList::new(BID_HISTORY_SIZE),
+            marketplace_id,
+            is_extended: false,
+        };
+        unhashed::put(&AuctionsStorage::<Test>::hashed_key_for(nft_id), &auction);
+    }
+
+    #[test]
+    fn upgrade_from_v2_to_v3() {
+        ExtBuilder::new_build(vec![], None).execute_with(|| {
+            insert_v2_auction(ALICE_NFT_ID, ALICE_MARKET_ID);
+            insert_v2_auction(BOB_NFT_ID, 1001);
+
+            StorageVersion::put::<Auctions>(&StorageVersion::new(2));
+            let weight = <Auctions as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Auctions>(), StorageVersion::new(5));
+
+            let auction = Auctions::auctions(ALICE_NFT_ID).unwrap();
+            assert_eq!(auction.commission_fee, MARKETPLACE_COMMISSION_FEE);
+            assert_eq!(auction.buy_it_price, Some(20));
+            assert_eq!(auction.end_block, 1 + MIN_AUCTION_DURATION);
+
+            // Auctions on unknown marketplaces keep no commission
+            let auction = Auctions::auctions(BOB_NFT_ID).unwrap();
+            assert_eq!(auction.commission_fee, Default::default());
+        })
+    }
+}
+
+mod version_4 {
+    use super::*;
+
+    fn insert_v3_auction(nft_id: u32) {
+        let auction = v3::AuctionData::<Test> {
+            creator: ALICE,
+            start_block: 1,
+            end_block: 1 + MIN_AUCTION_DURATION,
+            start_price: 10,
+            buy_it_price: Some(20),
+            bidders: BidderList::new(BID_HISTORY_SIZE),
+            marketplace_id: ALICE_MARKET_ID,
+            commission_fee: MARKETPLACE_COMMISSION_FEE,
+            is_extended: false,
+        };
+        v3::Auctions::<Test>::insert(nft_id, auction);
+    }
+
+    #[test]
+    fn upgrade_from_v3_to_v4() {
+        ExtBuilder::new_build(vec![], None).execute_with(|| {
+            insert_v3_auction(ALICE_NFT_ID);
+            v2::Claims::<Test>::insert(BOB, 30);
+            v2::Claims::<Test>::insert(CHARLIE, 40);
+
+            StorageVersion::put::<Auctions>(&StorageVersion::new(3));
+            let weight = <Auctions as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Auctions>(), StorageVersion::new(5));
+
+            let auction = Auctions::auctions(ALICE_NFT_ID).unwrap();
+            assert_eq!(auction.asset, PaymentAsset::Native);
+            assert_eq!(auction.commission_fee, MARKETPLACE_COMMISSION_FEE);
+            assert_eq!(auction.buy_it_price, Some(20));
+
+            assert_eq!(Claims::<Test>::iter().count(), 2);
+            assert_eq!(Claims::<Test>::get(BOB, PaymentAsset::Native), Some(30));
+            assert_eq!(Claims::<Test>::get(CHARLIE, PaymentAsset::Native), Some(40));
+        })
+    }
+}
+
+mod version_5 {
+    use super::*;
+
+    #[test]
+    fn upgrade_from_v4_to_v5() {
+        ExtBuilder::new_build(vec![], None).execute_with(|| {
+            let auction = AuctionData {
+                creator: ALICE,
+                start_block: 1,
+                end_block: 1 + MIN_AUCTION_DURATION,
+                start_price: 10,
+                buy_it_price: None,
+                asset: PaymentAsset::Native,
+                bidders: BidderList::new(BID_HISTORY_SIZE),
+                marketplace_id: ALICE_MARKET_ID,
+                commission_fee: MARKETPLACE_COMMISSION_FEE,
+                is_extended: false,
+            };
+            AuctionsStorage::<Test>::insert(ALICE_NFT_ID, auction);
+
+            StorageVersion::put::<Auctions>(&StorageVersion::new(4));
+            let weight = <Auctions as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Auctions>(), StorageVersion::new(5));
+
+            let indexed = MarketplaceAuctions::<Test>::contains_key(ALICE_MARKET_ID, ALICE_NFT_ID);
+            assert!(indexed);
+        })
+    }
+}
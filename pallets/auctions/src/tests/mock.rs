@@ -1,12 +1,16 @@
-use crate::types::{AuctionData, BidderList};
 use crate::{self as ternoa_auctions, Config};
-use frame_support::traits::{ConstU32, Contains, GenesisBuild, OnFinalize, OnInitialize};
+use frame_support::traits::{
+    ConstU128, ConstU32, Contains, GenesisBuild, OnFinalize, OnInitialize,
+};
+use frame_support::weights::constants::RocksDbWeight;
 use frame_support::{parameter_types, PalletId};
+use frame_system::EnsureRoot;
 use sp_core::H256;
-use sp_runtime::testing::Header;
+use sp_runtime::testing::{Header, TestSignature, UintAuthorityId};
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
-use ternoa_primitives::marketplace::{MarketplaceInformation, MarketplaceType};
-use ternoa_primitives::nfts::{NFTData, NFTSeriesDetails};
+use sp_runtime::Permill;
+use ternoa_primitives::marketplace::{MarketplaceCommission, MarketplaceType, PaymentAsset};
+use ternoa_primitives::nfts::NFTSeriesDetails;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -31,8 +35,12 @@ pub const ALICE_MARKET_ID: u32 = 1;
 pub const BOB_NFT_ID: u32 = 10;
 pub const BOB_SERIES_ID: u8 = 10;
 pub const INVALID_NFT_ID: u32 = 404;
-pub const MARKETPLACE_COMMISSION_FEE: u8 = 10;
+pub const MARKETPLACE_COMMISSION_FEE: MarketplaceCommission<u128> = MarketplaceCommission {
+    rate: Permill::from_parts(100_000),
+    flat: 0,
+};
 pub const BID_HISTORY_SIZE: u16 = 3;
+pub const TIIME: u32 = 1;
 
 frame_support::construct_runtime!(
     pub enum Test where
@@ -42,6 +50,7 @@ frame_support::construct_runtime!(
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
         Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>, Config<T>},
         NFTs: ternoa_nfts::{Pallet, Call, Storage, Event<T>, Config<T>},
         Marketplace: ternoa_marketplace::{Pallet, Call, Event<T>},
         Auctions: ternoa_auctions::{Pallet, Call, Event<T>}
@@ -77,7 +86,7 @@ impl frame_system::Config for Test {
     type BaseCallFilter = TestBaseCallFilter;
     type BlockWeights = ();
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Origin = Origin;
     type Index = u64;
     type BlockNumber = BlockNumber;
@@ -123,12 +132,21 @@ parameter_types! {
     pub const MaxUriLen: u16 = 5;
     pub const MinIpfsLen: u16 = 1;
     pub const MaxIpfsLen: u16 = 5;
+    pub const MaxSeriesIdLen: u16 = 5;
     pub const MinDescriptionLen: u16 = 1;
     pub const MaxDescriptionLen: u16 = 500;
     pub const MinNameLen: u16 = 1;
     pub const MaxNameLen: u16 = 5;
 }
 
+parameter_types! {
+    pub const BatchMintDiscount: Permill = Permill::from_percent(0);
+    pub const UseStorageDeposits: bool = false;
+    pub const DepositBase: u128 = 0;
+    pub const DepositPerByte: u128 = 0;
+    pub const DepositFollowsOwner: bool = true;
+}
+
 impl ternoa_nfts::Config for Test {
     type Event = Event;
     type WeightInfo = ();
@@ -136,20 +154,71 @@ impl ternoa_nfts::Config for Test {
     type FeesCollector = ();
     type MinIpfsLen = MinIpfsLen;
     type MaxIpfsLen = MaxIpfsLen;
+    type IPFSReferenceValidator = ();
+    type MaxSeriesIdLen = MaxSeriesIdLen;
+    type MaxBatchSize = ConstU32<5>;
+    type BatchMintDiscount = BatchMintDiscount;
+    type UseStorageDeposits = UseStorageDeposits;
+    type DepositBase = DepositBase;
+    type DepositPerByte = DepositPerByte;
+    type DepositFollowsOwner = DepositFollowsOwner;
+    type ForceOrigin = EnsureRoot<u64>;
+    type NFTReleaseHandler = (Marketplace, Auctions);
+    type MaxRentalsPerBlock = ConstU32<5>;
+}
+
+impl pallet_assets::Config for Test {
+    type Event = Event;
+    type Balance = u128;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<u64>;
+    type AssetDeposit = ConstU128<0>;
+    type AssetAccountDeposit = ConstU128<0>;
+    type MetadataDepositBase = ConstU128<0>;
+    type MetadataDepositPerByte = ConstU128<0>;
+    type ApprovalDeposit = ConstU128<0>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
 }
 
 impl ternoa_marketplace::Config for Test {
     type Event = Event;
     type Currency = Balances;
     type NFTs = NFTs;
+    type MarketplaceCloseHandler = Auctions;
     type WeightInfo = ();
     type FeesCollector = ();
+    type AssetId = u32;
+    type Assets = Assets;
     type MinNameLen = MinNameLen;
     type MaxNameLen = MaxNameLen;
     type MinUriLen = MinUriLen;
     type MaxUriLen = MaxUriLen;
     type MinDescriptionLen = MinDescriptionLen;
     type MaxDescriptionLen = MaxDescriptionLen;
+    type MaxAccountsPerCall = ConstU32<10>;
+    type MaxListingsPerBlock = ConstU32<10>;
+    type MaxOffersPerBlock = ConstU32<10>;
+    type MaxAcceptedAssets = ConstU32<10>;
+    type MaxBundleSize = ConstU32<3>;
+    type OrderSignature = TestSignature;
+    type OrderSigner = UintAuthorityId;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = OrderSigning;
+}
+
+/// Signs orders in benchmarks with an account that no test uses.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct OrderSigning;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl ternoa_marketplace::BenchmarkHelper<u64, TestSignature> for OrderSigning {
+    fn sign(message: &[u8]) -> (u64, TestSignature) {
+        (100, TestSignature(100, message.to_vec()))
+    }
 }
 
 parameter_types! {
@@ -164,6 +233,8 @@ parameter_types! {
 impl Config for Test {
     type Event = Event;
     type Currency = Balances;
+    type AssetId = u32;
+    type Assets = Assets;
     type NFTHandler = NFTs;
     type MarketplaceHandler = Marketplace;
     type MaxAuctionDelay = MaxAuctionDelay;
@@ -172,6 +243,8 @@ impl Config for Test {
     type AuctionGracePeriod = AuctionGracePeriod;
     type AuctionEndingPeriod = AuctionEndingPeriod;
     type PalletId = AuctionsPalletId;
+    type MaxBidHistorySize = ConstU32<10>;
+    type MaxAuctions = ConstU32<100>;
     type WeightInfo = ();
 }
 
@@ -206,6 +279,15 @@ impl ExtBuilder {
             .build_storage::<Test>()
             .unwrap();
 
+        // Every account gets as much Tiime as Caps.
+        pallet_assets::GenesisConfig::<Test> {
+            assets: vec![(TIIME, ALICE, true, 1)],
+            metadata: vec![],
+            accounts: self.balances.iter().map(|x| (TIIME, x.0, x.1)).collect(),
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
         pallet_balances::GenesisConfig::<Test> {
             balances: self.balances,
         }
@@ -214,21 +296,46 @@ impl ExtBuilder {
 
         Self::build_nfts(&mut t);
         Self::build_market(&mut t);
-        Self::build_auction(&mut t, self.state);
+        let is_extended = Self::build_auction(&mut t, self.state);
 
         let mut ext = sp_io::TestExternalities::new(t);
-        ext.execute_with(|| System::set_block_number(1));
+        ext.execute_with(|| {
+            System::set_block_number(1);
+
+            // Genesis auctions always start as not extended
+            for nft_id in [ALICE_NFT_ID, BOB_NFT_ID] {
+                ternoa_auctions::Auctions::<Test>::mutate(nft_id, |x| {
+                    if let Some(auction) = x {
+                        auction.is_extended = is_extended;
+                    }
+                });
+            }
+        });
         ext
     }
 
     fn build_nfts(t: &mut sp_runtime::Storage) {
-        let alice_nft = NFTData::new_default(ALICE, vec![10], vec![ALICE_SERIES_ID]);
-        let bob_nft = NFTData::new_default(BOB, vec![10], vec![BOB_SERIES_ID]);
+        let alice_nft = (
+            ALICE_NFT_ID,
+            ALICE,
+            ALICE,
+            vec![10],
+            vec![ALICE_SERIES_ID],
+            Permill::zero(),
+        );
+        let bob_nft = (
+            BOB_NFT_ID,
+            BOB,
+            BOB,
+            vec![10],
+            vec![BOB_SERIES_ID],
+            Permill::zero(),
+        );
 
         let alice_series = NFTSeriesDetails::new(ALICE, false);
         let bob_series = NFTSeriesDetails::new(ALICE, false);
 
-        let nfts = vec![(ALICE_NFT_ID, alice_nft), (BOB_NFT_ID, bob_nft)];
+        let nfts = vec![alice_nft, bob_nft];
         let series = vec![
             (vec![ALICE_SERIES_ID], alice_series),
             (vec![BOB_SERIES_ID], bob_series),
@@ -244,18 +351,14 @@ impl ExtBuilder {
     }
 
     fn build_market(t: &mut sp_runtime::Storage) {
-        let alice_market = MarketplaceInformation::new(
+        let alice_market = (
+            ALICE_MARKET_ID,
+            ALICE,
             MarketplaceType::Public,
             MARKETPLACE_COMMISSION_FEE,
-            ALICE,
-            vec![],
-            vec![],
             vec![10],
-            None,
-            None,
-            None,
         );
-        let marketplaces = vec![(ALICE_MARKET_ID, alice_market)];
+        let marketplaces = vec![alice_market];
 
         ternoa_marketplace::GenesisConfig::<Test> {
             nfts_for_sale: vec![],
@@ -266,41 +369,43 @@ impl ExtBuilder {
         .unwrap();
     }
 
-    fn build_auction(t: &mut sp_runtime::Storage, state: Option<AuctionState>) {
+    fn build_auction(t: &mut sp_runtime::Storage, state: Option<AuctionState>) -> bool {
         pub const NFT_PRICE: u128 = 100;
         pub const NFT_BUY_PRICE: Option<u128> = Some(200);
 
         let mut auctions = vec![];
+        let mut is_extended = false;
         if let Some(state) = state {
             let (start, end, extended) = match state {
                 AuctionState::Before => (2, 2 + MAX_AUCTION_DURATION, false),
                 AuctionState::InProgress => (1, 1 + MAX_AUCTION_DURATION, false),
                 AuctionState::Extended => (1, 1 + MAX_AUCTION_DURATION, true),
             };
-
-            let alice_data = AuctionData {
-                creator: ALICE,
-                start_block: start,
-                end_block: end,
-                start_price: NFT_PRICE,
-                buy_it_price: NFT_BUY_PRICE.clone(),
-                bidders: BidderList::new(BID_HISTORY_SIZE),
-                marketplace_id: ALICE_MARKET_ID,
-                is_extended: extended,
-            };
-
-            let bob_data = AuctionData {
-                creator: BOB,
-                start_block: start,
-                end_block: end,
-                start_price: NFT_PRICE,
-                buy_it_price: NFT_BUY_PRICE.clone(),
-                bidders: BidderList::new(BID_HISTORY_SIZE),
-                marketplace_id: ALICE_MARKET_ID,
-                is_extended: extended,
-            };
-
-            auctions = vec![(ALICE_NFT_ID, alice_data), (BOB_NFT_ID, bob_data)];
+            is_extended = extended;
+
+            let alice_data = (
+                ALICE_NFT_ID,
+                ALICE,
+                start,
+                end,
+                NFT_PRICE,
+                NFT_BUY_PRICE.clone(),
+                ALICE_MARKET_ID,
+                PaymentAsset::Native,
+            );
+
+            let bob_data = (
+                BOB_NFT_ID,
+                BOB,
+                start,
+                end,
+                NFT_PRICE,
+                NFT_BUY_PRICE.clone(),
+                ALICE_MARKET_ID,
+                PaymentAsset::Native,
+            );
+
+            auctions = vec![alice_data, bob_data];
         }
         ternoa_auctions::GenesisConfig::<Test> {
             auctions,
@@ -308,6 +413,8 @@ impl ExtBuilder {
         }
         .assimilate_storage(t)
         .unwrap();
+
+        is_extended
     }
 }
 
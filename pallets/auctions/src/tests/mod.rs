@@ -1,5 +1,6 @@
 mod extrinsics;
 mod genesis;
 mod hooks;
+mod migration;
 pub mod mock;
 mod types;
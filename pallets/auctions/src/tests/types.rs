@@ -1,4 +1,5 @@
 use crate::{BidderList, DeadlineList};
+use frame_support::traits::ConstU32;
 
 mod bidder_list {
     use super::*;
@@ -10,7 +11,8 @@ mod bidder_list {
         // create a new list
         let max_size = 10;
 
-        let mut bidders_list: BidderList<MockAccount, MockBalance> = BidderList::new(max_size);
+        let mut bidders_list: BidderList<MockAccount, MockBalance, ConstU32<10>> =
+            BidderList::new(max_size);
         assert_eq!(bidders_list.max_size, max_size);
 
         // insert to list works
@@ -127,6 +129,17 @@ mod bidder_list {
         assert_eq!(bidders_list.remove_highest_bid(), Some((11, 12)));
         assert_eq!(bidders_list.remove_highest_bid(), Some((10, 11)));
     }
+
+    #[test]
+    fn bid_history_is_bounded() {
+        // The max size is bigger than the storage bound
+        let mut bidders_list: BidderList<u32, u32, ConstU32<2>> = BidderList::new(10);
+
+        assert_eq!(bidders_list.insert_new_bid(1, 2), None);
+        assert_eq!(bidders_list.insert_new_bid(2, 3), None);
+        assert_eq!(bidders_list.insert_new_bid(3, 4), Some((1, 2)));
+        assert_eq!(bidders_list.list, vec![(2, 3), (3, 4)]);
+    }
 }
 
 mod deadline_list {
@@ -134,7 +147,7 @@ mod deadline_list {
 
     #[test]
     fn insert_random_values() {
-        let mut deadlines = DeadlineList::<u32>(vec![]);
+        let mut deadlines = DeadlineList::<u32, ConstU32<10>>::default();
 
         // Insert 5 different values and after every insert check if the order is correct
 
@@ -147,14 +160,14 @@ mod deadline_list {
         ];
 
         for entry in entires {
-            deadlines.insert(entry.0, entry.1);
+            assert!(deadlines.insert(entry.0, entry.1));
             assert_eq!(deadlines.0, entry.2);
         }
     }
 
     #[test]
     fn remove_random_values() {
-        let mut deadlines = DeadlineList::<u32>(vec![]);
+        let mut deadlines = DeadlineList::<u32, ConstU32<10>>::default();
 
         // Insert 5 different values and after every insert check if the order is correct
 
@@ -167,7 +180,7 @@ mod deadline_list {
         ];
 
         for entry in entires.iter() {
-            deadlines.insert(entry.0, entry.1);
+            assert!(deadlines.insert(entry.0, entry.1));
         }
 
         for entry in entires.iter().rev() {
@@ -179,7 +192,7 @@ mod deadline_list {
 
     #[test]
     fn update_values() {
-        let mut deadlines = DeadlineList::<u32>(vec![]);
+        let mut deadlines = DeadlineList::<u32, ConstU32<10>>::default();
 
         // Insert 5 different values and after every insert check if the order is correct
 
@@ -191,7 +204,7 @@ mod deadline_list {
         ];
 
         for entry in entires {
-            deadlines.insert(entry.0, entry.1);
+            assert!(deadlines.insert(entry.0, entry.1));
         }
 
         for entry in new_entires {
@@ -203,13 +216,13 @@ mod deadline_list {
 
     #[test]
     fn get_next_ready_blocks() {
-        let mut deadlines = DeadlineList::<u32>(vec![]);
+        let mut deadlines = DeadlineList::<u32, ConstU32<10>>::default();
 
         // Insert 5 different values and after every insert check if the order is correct
 
         let entries = vec![(0, 100), (1, 50), (2, 150)];
         for entry in entries.iter() {
-            deadlines.insert(entry.0, entry.1);
+            assert!(deadlines.insert(entry.0, entry.1));
         }
 
         assert_eq!(deadlines.next(49), None);
@@ -226,4 +239,18 @@ mod deadline_list {
         }
         assert_eq!(nfts, vec![1, 0, 2]);
     }
+
+    #[test]
+    fn insert_in_full_list() {
+        let mut deadlines = DeadlineList::<u32, ConstU32<2>>::default();
+
+        assert!(deadlines.insert(0, 100));
+        assert!(deadlines.insert(1, 50));
+        assert!(!deadlines.insert(2, 150));
+        assert_eq!(deadlines.0, vec![(1, 50), (0, 100)]);
+
+        // Updating an entry still works when the list is full
+        assert!(deadlines.update(0, 25));
+        assert_eq!(deadlines.0, vec![(0, 25), (1, 50)]);
+    }
 }
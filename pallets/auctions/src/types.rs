@@ -1,20 +1,31 @@
-#[cfg(feature = "std")]
-use serde::{Deserialize, Serialize};
-
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use frame_support::{
+    BoundedVec, CloneNoBound, DefaultNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
-use sp_std::vec::Vec;
-use ternoa_primitives::marketplace::MarketplaceId;
+use sp_std::fmt::Debug;
+use ternoa_primitives::marketplace::{MarketplaceCommission, MarketplaceId, PaymentAsset};
 use ternoa_primitives::nfts::NFTId;
 
-#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(
+    CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(BidderListLengthLimit))]
+#[codec(mel_bound(
+    AccountId: MaxEncodedLen,
+    BlockNumber: MaxEncodedLen,
+    Balance: MaxEncodedLen,
+    AssetId: MaxEncodedLen
+))]
 /// Structure to store Auction data
-pub struct AuctionData<AccountId, BlockNumber, Balance>
+pub struct AuctionData<AccountId, BlockNumber, Balance, AssetId, BidderListLengthLimit>
 where
-    AccountId: Clone,
-    Balance: Clone + Default,
+    AccountId: Clone + PartialEq + Debug,
+    BlockNumber: Clone + PartialEq + Debug,
+    Balance: Clone + PartialEq + Debug,
+    AssetId: Clone + PartialEq + Debug,
+    BidderListLengthLimit: Get<u32>,
 {
     /// The owner of the nft that has listed the item on auction
     pub creator: AccountId,
@@ -26,32 +37,45 @@ where
     pub start_price: Balance,
     /// Optional price at which the auction is stopped and item can be bought
     pub buy_it_price: Option<Balance>,
+    /// Token the bids and the buy-it-now price are paid with
+    pub asset: PaymentAsset<AssetId>,
     /// List of bidders
-    pub bidders: BidderList<AccountId, Balance>,
+    pub bidders: BidderList<AccountId, Balance, BidderListLengthLimit>,
     /// The marketplace where the auction has been listed
     pub marketplace_id: MarketplaceId,
+    /// Commission of the marketplace at the time the auction was created
+    pub commission_fee: MarketplaceCommission<Balance>,
     /// Is the auction going beyond the original end_block
     pub is_extended: bool,
 }
 
-#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(
+    CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(Limit))]
+#[codec(mel_bound(AccountId: MaxEncodedLen, Balance: MaxEncodedLen))]
 /// wrapper type to store sorted list of all bids
 /// The wrapper exists to ensure a queue implementation of sorted bids
-pub struct BidderList<AccountId, Balance> {
-    pub list: Vec<(AccountId, Balance)>,
+pub struct BidderList<AccountId, Balance, Limit>
+where
+    AccountId: Clone + PartialEq + Debug,
+    Balance: Clone + PartialEq + Debug,
+    Limit: Get<u32>,
+{
+    pub list: BoundedVec<(AccountId, Balance), Limit>,
     pub max_size: u16,
 }
 
-impl<AccountId, Balance> BidderList<AccountId, Balance>
+impl<AccountId, Balance, Limit> BidderList<AccountId, Balance, Limit>
 where
-    AccountId: sp_std::cmp::Ord + Clone,
-    Balance: sp_std::cmp::PartialOrd,
+    AccountId: sp_std::cmp::Ord + Clone + Debug,
+    Balance: sp_std::cmp::PartialOrd + Clone + Debug,
+    Limit: Get<u32>,
 {
     /// Create a new empty bidders list
     pub fn new(max_size: u16) -> Self {
         Self {
-            list: Vec::new(),
+            list: BoundedVec::default(),
             max_size,
         }
     }
@@ -62,14 +86,17 @@ where
         account_id: AccountId,
         value: Balance,
     ) -> Option<(AccountId, Balance)> {
+        let capacity = (self.max_size as usize).min(Limit::get() as usize);
+
         // If list is at max capacity, remove lowest bid
-        if self.list.len() >= self.max_size as usize {
+        if self.list.len() >= capacity {
             let removed_bid = self.list.remove(0);
-            self.list.push((account_id, value));
+            // Cannot fail since a bid has just been removed
+            let _ = self.list.try_push((account_id, value));
             // return removed bid
             Some(removed_bid)
         } else {
-            self.list.push((account_id, value));
+            let _ = self.list.try_push((account_id, value));
             None
         }
     }
@@ -119,21 +146,36 @@ where
     }
 }
 
-#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo, Default)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(
+    CloneNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    DefaultNoBound,
+    Encode,
+    Decode,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(Limit))]
+#[codec(mel_bound(BlockNumber: MaxEncodedLen))]
 /// wrapper type to store sorted list of all bids
 /// The wrapper exists to ensure a queue implementation of sorted bids
-pub struct DeadlineList<BlockNumber>(pub Vec<(NFTId, BlockNumber)>);
+pub struct DeadlineList<BlockNumber, Limit>(pub BoundedVec<(NFTId, BlockNumber), Limit>)
+where
+    BlockNumber: Clone + PartialEq + Debug,
+    Limit: Get<u32>;
 
-impl<BlockNumber> DeadlineList<BlockNumber>
+impl<BlockNumber, Limit> DeadlineList<BlockNumber, Limit>
 where
-    BlockNumber: sp_std::cmp::PartialOrd,
+    BlockNumber: sp_std::cmp::PartialOrd + Clone + Debug,
+    Limit: Get<u32>,
 {
-    pub fn insert(&mut self, nft_id: NFTId, block_number: BlockNumber) {
+    /// Insert a deadline while keeping the list sorted. Returns false if the list is full.
+    pub fn insert(&mut self, nft_id: NFTId, block_number: BlockNumber) -> bool {
         let index = self.0.iter().position(|x| x.1 > block_number);
         let index = index.unwrap_or_else(|| self.0.len());
 
-        self.0.insert(index, (nft_id, block_number));
+        self.0.try_insert(index, (nft_id, block_number)).is_ok()
     }
 
     pub fn remove(&mut self, nft_id: NFTId) -> bool {
@@ -149,8 +191,8 @@ where
     pub fn update(&mut self, nft_id: NFTId, block_number: BlockNumber) -> bool {
         let removed = self.remove(nft_id);
         if removed {
-            self.insert(nft_id, block_number);
-            true
+            // Cannot fail since an entry has just been removed
+            self.insert(nft_id, block_number)
         } else {
             false
         }
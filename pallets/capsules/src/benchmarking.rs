@@ -6,6 +6,7 @@ use frame_benchmarking::{account as benchmark_account, benchmarks, impl_benchmar
 use frame_support::assert_ok;
 use frame_system::RawOrigin;
 use sp_runtime::traits::Bounded;
+use sp_std::convert::TryInto;
 use sp_std::prelude::*;
 use ternoa_common::traits::NFTTrait;
 
@@ -22,14 +23,19 @@ pub fn prepare_benchmarks<T: Config>() -> (NFTId, NFTId) {
     // Create default Capsule
     assert_ok!(TernoaCapsules::<T>::create(
         RawOrigin::Signed(alice.clone()).into(),
-        vec![1],
-        vec![2],
+        ipfs_reference::<T>(),
+        ipfs_reference::<T>(),
         None,
     ));
 
     // Create default NFT and series
     let series_id = vec![SERIES_ID];
-    let nft_id = T::NFTTrait::create_nft(alice.clone(), vec![1], Some(series_id.clone())).unwrap();
+    let nft_id = T::NFTTrait::create_nft(
+        alice.clone(),
+        ipfs_reference::<T>(),
+        Some(series_id.clone()),
+    )
+    .unwrap();
 
     // Lock series
     T::NFTTrait::benchmark_lock_series(series_id.clone());
@@ -37,6 +43,10 @@ pub fn prepare_benchmarks<T: Config>() -> (NFTId, NFTId) {
     (nft_id - 1, nft_id)
 }
 
+pub fn ipfs_reference<T: Config>() -> TextFormat {
+    T::IPFSReferenceValidator::benchmark_reference()
+}
+
 pub fn get_account<T: Config>(name: &'static str) -> T::AccountId {
     let account: T::AccountId = benchmark_account(name, 0, 0);
     account
@@ -51,10 +61,11 @@ benchmarks! {
         let (_, nft_id) = prepare_benchmarks::<T>();
 
         let alice: T::AccountId = get_account::<T>("ALICE");
-        let nft_reference = vec![50];
-        let capsule_reference = vec![51];
+        let nft_reference = ipfs_reference::<T>();
+        let capsule_reference = ipfs_reference::<T>();
         let nft_id = nft_id + 1;
-        let capsule = CapsuleData::new(alice.clone(), capsule_reference.clone());
+        let capsule: CapsuleDataOf<T> =
+            CapsuleData::new(alice.clone(), capsule_reference.clone().try_into().unwrap());
 
     }: _(RawOrigin::Signed(alice.clone()), nft_reference, capsule_reference, None)
     verify {
@@ -65,8 +76,9 @@ benchmarks! {
         let (_, nft_id) = prepare_benchmarks::<T>();
 
         let alice: T::AccountId = get_account::<T>("ALICE");
-        let capsule_reference = vec![51];
-        let capsule = CapsuleData::new(alice.clone(), capsule_reference.clone());
+        let capsule_reference = ipfs_reference::<T>();
+        let capsule: CapsuleDataOf<T> =
+            CapsuleData::new(alice.clone(), capsule_reference.clone().try_into().unwrap());
 
     }: _(RawOrigin::Signed(alice.clone()), nft_id, capsule_reference.clone())
     verify {
@@ -96,7 +108,7 @@ benchmarks! {
 
     set_ipfs_reference {
         let (nft_id, ..) = prepare_benchmarks::<T>();
-        let new_reference = vec![101];
+        let new_reference = ipfs_reference::<T>();
 
     }: _(get_origin::<T>("ALICE"), nft_id, new_reference.clone())
     verify {
@@ -7,23 +7,26 @@ mod benchmarking;
 mod tests;
 
 mod default_weights;
+mod migrations;
 mod types;
 
 pub use default_weights::WeightInfo;
-use frame_support::dispatch::DispatchResult;
+use frame_support::dispatch::{DispatchError, DispatchResult};
 pub use pallet::*;
 pub use types::*;
 
 use frame_support::traits::ExistenceRequirement::{AllowDeath, KeepAlive};
 use frame_support::traits::{Currency, Get, StorageVersion};
 use frame_support::traits::{ExistenceRequirement, WithdrawReasons};
-use frame_support::PalletId;
+use frame_support::weights::Weight;
+use frame_support::{ensure, PalletId};
 use sp_runtime::traits::AccountIdConversion;
-use sp_std::vec;
-use ternoa_primitives::nfts::{NFTId, NFTSeriesId};
+use ternoa_common::helpers::{bounded_text, U16ToU32};
+use ternoa_common::traits::{CapsuleTrait, NFTReleaseHandler, ReferenceValidator};
+use ternoa_primitives::nfts::{NFTId, NFTLockReason, NFTSeriesId};
 use ternoa_primitives::TextFormat;
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -34,7 +37,6 @@ pub mod pallet {
     use frame_system::pallet_prelude::*;
     use sp_runtime::traits::CheckedAdd;
     use sp_std::convert::TryInto;
-    use ternoa_common::helpers::check_bounds;
     use ternoa_common::traits::NFTTrait;
 
     #[pallet::config]
@@ -58,31 +60,43 @@ pub mod pallet {
         #[pallet::constant]
         type MaxIpfsLen: Get<u16>;
 
+        /// Check that IPFS references are well formed
+        type IPFSReferenceValidator: ReferenceValidator;
+
         /// The treasury's pallet id, used for deriving its sovereign account ID.
         #[pallet::constant]
         type PalletId: Get<PalletId>;
+
+        /// Max number of capsules an account can hold
+        #[pallet::constant]
+        type MaxCapsulesPerAccount: Get<u32>;
     }
 
     pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type IPFSReferenceOf<T> = BoundedVec<u8, U16ToU32<<T as Config>::MaxIpfsLen>>;
+    pub type CapsuleDataOf<T> =
+        CapsuleData<<T as frame_system::Config>::AccountId, U16ToU32<<T as Config>::MaxIpfsLen>>;
+    pub type CapsuleLedgerOf<T> = CapsuleLedger<BalanceOf<T>, <T as Config>::MaxCapsulesPerAccount>;
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_runtime_upgrade() -> frame_support::weights::Weight {
+            let mut weight = migrations::migrate::<T>();
+
             if !CapsuleMintFee::<T>::exists() {
                 let fee: BalanceOf<T> = 1000000000000000000000u128.try_into().ok().unwrap();
                 CapsuleMintFee::<T>::put(fee);
 
-                return 1;
+                weight = weight.saturating_add(1);
             }
 
-            0
+            weight
         }
     }
 
@@ -98,12 +112,7 @@ pub mod pallet {
             series_id: Option<NFTSeriesId>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-
-            check_bounds(
-                capsule_ipfs_reference.len(),
-                (T::MinIpfsLen::get(), Error::<T>::TooShortIpfsReference),
-                (T::MaxIpfsLen::get(), Error::<T>::TooLongIpfsReference),
-            )?;
+            let capsule_ipfs_reference = Self::bound_ipfs_reference(capsule_ipfs_reference)?;
 
             // Reserve funds
             let amount = CapsuleMintFee::<T>::get();
@@ -111,8 +120,8 @@ pub mod pallet {
 
             // Create NFT and capsule
             let nft_id = T::NFTTrait::create_nft(who.clone(), nft_ipfs_reference, series_id)?;
-            T::NFTTrait::set_converted_to_capsule(nft_id, true)?;
-            Self::new_capsule(&who, nft_id, capsule_ipfs_reference.clone(), amount);
+            T::NFTTrait::lock(nft_id, NFTLockReason::Capsule)?;
+            Self::new_capsule(&who, nft_id, capsule_ipfs_reference, amount)?;
 
             Self::deposit_event(Event::CapsuleDeposit { balance: amount });
             let event = Event::CapsuleCreated {
@@ -134,33 +143,22 @@ pub mod pallet {
             ipfs_reference: TextFormat,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-
-            check_bounds(
-                ipfs_reference.len(),
-                (T::MinIpfsLen::get(), Error::<T>::TooShortIpfsReference),
-                (T::MaxIpfsLen::get(), Error::<T>::TooLongIpfsReference),
-            )?;
+            let ipfs_reference = Self::bound_ipfs_reference(ipfs_reference)?;
 
             let nft = T::NFTTrait::get_nft(nft_id).ok_or(Error::<T>::UnknownNFT)?;
             ensure!(nft.owner == who, Error::<T>::NotOwner);
-            ensure!(!nft.listed_for_sale, Error::<T>::ListedForSale);
-            ensure!(!nft.in_transmission, Error::<T>::InTransmission);
-            ensure!(!nft.converted_to_capsule, Error::<T>::CapsuleAlreadyExists);
-            ensure!(
-                nft.viewer.is_none(),
-                Error::<T>::CannotCreateCapsuleFromLentNFTs
-            );
 
             let exists = Capsules::<T>::contains_key(nft_id);
             ensure!(!exists, Error::<T>::CapsuleAlreadyExists);
 
+            T::NFTTrait::lock(nft_id, NFTLockReason::Capsule)?;
+
             // Reserve funds
             let amount = CapsuleMintFee::<T>::get();
             Self::send_funds(&who, &Self::account_id(), amount, KeepAlive)?;
 
             // Create capsule
-            T::NFTTrait::set_converted_to_capsule(nft_id, true)?;
-            Self::new_capsule(&who, nft_id, ipfs_reference.clone(), amount);
+            Self::new_capsule(&who, nft_id, ipfs_reference, amount)?;
 
             Self::deposit_event(Event::CapsuleDeposit { balance: amount });
             let event = Event::CapsuleCreated {
@@ -178,32 +176,9 @@ pub mod pallet {
         #[transactional]
         pub fn remove(origin: OriginFor<T>, nft_id: NFTId) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            let mut unused_funds = Default::default();
-
-            Ledgers::<T>::mutate(&who, |x| -> DispatchResult {
-                let data = x.as_mut().ok_or(Error::<T>::NotOwner)?;
-
-                let error = Error::<T>::NotOwner;
-                let index = data.iter().position(|x| x.0 == nft_id).ok_or(error)?;
-
-                unused_funds = data[index].1;
-                Self::send_funds(&Self::account_id(), &who, data[index].1, AllowDeath)?;
-
-                data.swap_remove(index);
-                if data.is_empty() {
-                    *x = None;
-                }
 
-                Capsules::<T>::take(nft_id).ok_or(Error::<T>::InternalError)?;
-
-                Ok(())
-            })?;
-
-            let event = Event::CapsuleRemoved {
-                nft_id,
-                unfrozen_balance: unused_funds,
-            };
-            Self::deposit_event(event);
+            Self::remove_capsule(&who, nft_id)?;
+            T::NFTTrait::unlock(nft_id, NFTLockReason::Capsule)?;
 
             Ok(().into())
         }
@@ -248,18 +223,13 @@ pub mod pallet {
             ipfs_reference: TextFormat,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-
-            check_bounds(
-                ipfs_reference.len(),
-                (T::MinIpfsLen::get(), Error::<T>::TooShortIpfsReference),
-                (T::MaxIpfsLen::get(), Error::<T>::TooLongIpfsReference),
-            )?;
+            let bounded_ipfs_reference = Self::bound_ipfs_reference(ipfs_reference.clone())?;
 
             Capsules::<T>::mutate(nft_id, |x| -> DispatchResult {
                 let data = x.as_mut().ok_or(Error::<T>::UnknownNFT)?;
                 ensure!(data.owner == who, Error::<T>::NotOwner);
 
-                data.ipfs_reference = ipfs_reference.clone();
+                data.ipfs_reference = bounded_ipfs_reference;
                 Ok(())
             })?;
 
@@ -320,9 +290,6 @@ pub mod pallet {
 
     #[pallet::error]
     pub enum Error<T> {
-        /// TODO!
-        CannotCreateCapsuleFromLentNFTs,
-
         /// This should never happen.
         ArithmeticError,
         /// Callers is not the NFT owner.
@@ -331,18 +298,20 @@ pub mod pallet {
         TooShortIpfsReference,
         /// Ipfs reference is too long.
         TooLongIpfsReference,
+        /// Ipfs reference is not a valid CID.
+        InvalidIpfsReference,
         /// Capsule already exists.
         CapsuleAlreadyExists,
         /// This should never happen.
         InternalError,
-        /// NFT is locked.
-        ListedForSale,
         /// TODO!
         AlreadyACapsule,
         /// TODO!
         UnknownNFT,
-        /// TODO!
-        InTransmission,
+        /// The account cannot hold more capsules.
+        TooManyCapsules,
+        /// No capsule was found with that NFT id.
+        CapsuleNotFound,
     }
 
     /// Current capsule mint fee.
@@ -354,13 +323,13 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn capsules)]
     pub type Capsules<T: Config> =
-        StorageMap<_, Blake2_128Concat, NFTId, CapsuleData<T::AccountId>, OptionQuery>;
+        StorageMap<_, Blake2_128Concat, NFTId, CapsuleDataOf<T>, OptionQuery>;
 
     /// List of accounts that hold capsulized NFTs.
     #[pallet::storage]
     #[pallet::getter(fn ledgers)]
     pub type Ledgers<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::AccountId, CapsuleLedger<BalanceOf<T>>, OptionQuery>;
+        StorageMap<_, Blake2_128Concat, T::AccountId, CapsuleLedgerOf<T>, OptionQuery>;
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
@@ -387,6 +356,7 @@ pub mod pallet {
                 .clone()
                 .into_iter()
                 .for_each(|(nft_id, account, reference)| {
+                    let reference = reference.try_into().expect("IPFS reference is too long");
                     Capsules::<T>::insert(nft_id, CapsuleData::new(account, reference));
                 });
 
@@ -394,6 +364,7 @@ pub mod pallet {
                 .clone()
                 .into_iter()
                 .for_each(|(account, data)| {
+                    let data: CapsuleLedgerOf<T> = data.try_into().expect("Too many capsules");
                     Ledgers::<T>::insert(account, data);
                 });
 
@@ -406,19 +377,66 @@ impl<T: Config> Pallet<T> {
     fn new_capsule(
         owner: &T::AccountId,
         nft_id: NFTId,
-        ipfs_reference: TextFormat,
+        ipfs_reference: IPFSReferenceOf<T>,
         funds: BalanceOf<T>,
-    ) {
-        let data = CapsuleData::new(owner.clone(), ipfs_reference.clone());
+    ) -> DispatchResult {
+        Ledgers::<T>::try_mutate(&owner, |x| -> DispatchResult {
+            let data = x.get_or_insert_with(Default::default);
+            data.try_push((nft_id, funds))
+                .map_err(|_| Error::<T>::TooManyCapsules)?;
+            Ok(())
+        })?;
+
+        let data = CapsuleData::new(owner.clone(), ipfs_reference);
         Capsules::<T>::insert(nft_id, data);
 
-        Ledgers::<T>::mutate(&owner, |x| {
-            if let Some(data) = x {
-                data.push((nft_id, funds));
-            } else {
-                *x = Some(vec![(nft_id, funds)]);
+        Ok(())
+    }
+
+    /// Drop a capsule and give its funds back to the capsule owner.
+    fn remove_capsule(owner: &T::AccountId, nft_id: NFTId) -> DispatchResult {
+        let mut unused_funds = Default::default();
+
+        Ledgers::<T>::try_mutate(owner, |x| -> DispatchResult {
+            let data = x.as_mut().ok_or(Error::<T>::NotOwner)?;
+
+            let error = Error::<T>::NotOwner;
+            let index = data.iter().position(|x| x.0 == nft_id).ok_or(error)?;
+
+            unused_funds = data[index].1;
+            Self::send_funds(&Self::account_id(), owner, unused_funds, AllowDeath)?;
+
+            data.remove(index);
+            if data.is_empty() {
+                *x = None;
             }
-        });
+
+            Capsules::<T>::take(nft_id).ok_or(Error::<T>::InternalError)?;
+
+            Ok(())
+        })?;
+
+        let event = Event::CapsuleRemoved {
+            nft_id,
+            unfrozen_balance: unused_funds,
+        };
+        Self::deposit_event(event);
+
+        Ok(())
+    }
+
+    fn bound_ipfs_reference(ipfs_reference: TextFormat) -> Result<IPFSReferenceOf<T>, Error<T>> {
+        let ipfs_reference: IPFSReferenceOf<T> = bounded_text(
+            ipfs_reference,
+            (T::MinIpfsLen::get(), Error::<T>::TooShortIpfsReference),
+            (T::MaxIpfsLen::get(), Error::<T>::TooLongIpfsReference),
+        )?;
+        ensure!(
+            T::IPFSReferenceValidator::is_valid(&ipfs_reference),
+            Error::<T>::InvalidIpfsReference
+        );
+
+        Ok(ipfs_reference)
     }
 
     fn account_id() -> T::AccountId {
@@ -437,3 +455,56 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 }
+
+impl<T: Config> NFTReleaseHandler for Pallet<T> {
+    /// Turn a capsule back into an NFT, giving its funds back to its owner.
+    fn release_nft(nft_id: NFTId, reason: NFTLockReason) -> Result<Weight, DispatchError> {
+        if reason != NFTLockReason::Capsule {
+            return Ok(0);
+        }
+
+        match Capsules::<T>::get(nft_id) {
+            Some(capsule) => {
+                Self::remove_capsule(&capsule.owner, nft_id)?;
+                Ok(T::WeightInfo::remove())
+            }
+            None => Ok(T::DbWeight::get().reads(1)),
+        }
+    }
+
+    fn max_release_weight() -> Weight {
+        T::WeightInfo::remove()
+    }
+}
+
+impl<T: Config> CapsuleTrait<T::AccountId> for Pallet<T> {
+    fn set_capsule_owner(nft_id: NFTId, owner: &T::AccountId) -> DispatchResult {
+        let mut capsule = Capsules::<T>::get(nft_id).ok_or(Error::<T>::CapsuleNotFound)?;
+        let mut funds = Default::default();
+
+        Ledgers::<T>::try_mutate(&capsule.owner, |x| -> DispatchResult {
+            let data = x.as_mut().ok_or(Error::<T>::InternalError)?;
+
+            let error = Error::<T>::InternalError;
+            let index = data.iter().position(|x| x.0 == nft_id).ok_or(error)?;
+
+            funds = data.remove(index).1;
+            if data.is_empty() {
+                *x = None;
+            }
+
+            Ok(())
+        })?;
+
+        Ledgers::<T>::try_mutate(owner, |x| -> DispatchResult {
+            let data = x.get_or_insert_with(Default::default);
+            data.try_push((nft_id, funds))
+                .map_err(|_| Error::<T>::TooManyCapsules)?;
+            Ok(())
+        })?;
+
+        capsule.owner = owner.clone();
+        Capsules::<T>::insert(nft_id, capsule);
+        T::NFTTrait::set_owner(nft_id, owner)
+    }
+}
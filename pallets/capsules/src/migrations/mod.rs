@@ -0,0 +1,29 @@
+pub mod v2;
+
+use crate::{Config, Pallet};
+use frame_support::traits::StorageVersion;
+use frame_support::weights::Weight;
+
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get::<Pallet<T>>() == 1 {
+        log::info!(target: "runtime::capsules", "Capsules pallet: migrating to StorageVersion V2");
+
+        match v2::migrate::<T>() {
+            Ok(used) => {
+                weight = weight.saturating_add(used);
+                StorageVersion::new(2).put::<Pallet<T>>();
+
+                log::info!(target: "runtime::capsules", "Capsules pallet: migration to StorageVersion V2 done");
+            }
+            Err(used) => {
+                weight = weight.saturating_add(used);
+
+                log::error!(target: "runtime::capsules", "Capsules pallet: migration to StorageVersion V2 aborted");
+            }
+        }
+    }
+
+    weight
+}
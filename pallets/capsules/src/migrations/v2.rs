@@ -0,0 +1,85 @@
+use crate::{BalanceOf, CapsuleData, Capsules, Config, Ledgers, Pallet};
+use frame_support::storage::migration::storage_key_iter;
+use frame_support::traits::ExistenceRequirement::AllowDeath;
+use frame_support::traits::{Get, PalletInfoAccess};
+use frame_support::weights::Weight;
+use frame_support::Blake2_128Concat;
+use ternoa_common::helpers::{fits_bound, truncate_to_bound, U16ToU32};
+use ternoa_common::traits::NFTTrait;
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
+
+pub mod v1 {
+    use codec::{Decode, Encode};
+    use sp_runtime::RuntimeDebug;
+    use ternoa_primitives::TextFormat;
+
+    // CapsuleData as it was stored before the ipfs reference was bounded
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct CapsuleData<AccountId> {
+        pub owner: AccountId,
+        pub ipfs_reference: TextFormat,
+    }
+}
+
+/// Bounds the capsule ipfs references and the capsule ledgers. Capsules that do
+/// not fit in the ledger of their owner are removed and their funds are refunded.
+/// Nothing is changed if an ipfs reference does not fit: every offending capsule
+/// is logged and an error is returned so that the storage version stays the same.
+pub fn migrate<T: Config>() -> Result<Weight, Weight> {
+    let mut reads: u64 = 0;
+    let mut writes: u64 = 0;
+    let mut fits = true;
+
+    for (nft_id, old) in storage_key_iter::<NFTId, v1::CapsuleData<T::AccountId>, Blake2_128Concat>(
+        Pallet::<T>::name().as_bytes(),
+        b"Capsules",
+    ) {
+        reads += 1;
+
+        if !fits_bound::<_, U16ToU32<T::MaxIpfsLen>>(&old.ipfs_reference) {
+            log::error!(target: "runtime::capsules", "Capsule {} does not fit", nft_id);
+            fits = false;
+        }
+    }
+
+    if !fits {
+        return Err(T::DbWeight::get().reads(reads));
+    }
+
+    // Every ipfs reference has been checked above, so none of them gets truncated.
+    Capsules::<T>::translate::<v1::CapsuleData<T::AccountId>, _>(|_, old| {
+        reads += 1;
+        writes += 1;
+
+        Some(CapsuleData::new(
+            old.owner,
+            truncate_to_bound(old.ipfs_reference),
+        ))
+    });
+
+    let limit = T::MaxCapsulesPerAccount::get() as usize;
+    Ledgers::<T>::translate::<sp_std::vec::Vec<(NFTId, BalanceOf<T>)>, _>(|owner, mut old| {
+        reads += 1;
+        writes += 1;
+
+        if old.len() > limit {
+            log::warn!(target: "runtime::capsules", "Some capsules have been removed");
+
+            for (nft_id, funds) in old.drain(limit..) {
+                let ok =
+                    Pallet::<T>::send_funds(&Pallet::<T>::account_id(), &owner, funds, AllowDeath);
+                debug_assert!(ok.is_ok());
+                let ok = T::NFTTrait::unlock(nft_id, NFTLockReason::Capsule);
+                debug_assert!(ok.is_ok());
+                Capsules::<T>::remove(nft_id);
+
+                reads += 2;
+                writes += 3;
+            }
+        }
+
+        Some(truncate_to_bound(old))
+    });
+
+    Ok(T::DbWeight::get().reads_writes(reads, writes))
+}
@@ -6,6 +6,8 @@ use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
 use pallet_balances::Error as BalanceError;
 use ternoa_common::traits::NFTTrait;
+use ternoa_nfts::Error as NFTError;
+use ternoa_primitives::nfts::NFTLockReason;
 
 #[test]
 fn create_happy() {
@@ -17,7 +19,7 @@ fn create_happy() {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
             let ipfs_reference = vec![60];
             let nft_id = 0;
-            let data = CapsuleData::new(ALICE, ipfs_reference.clone());
+            let data = CapsuleData::new(ALICE, bounded(ipfs_reference.clone()));
             let ledger = vec![(nft_id, TernoaCapsules::capsule_mint_fee())];
             assert_eq!(TernoaCapsules::capsules(&nft_id), None);
             assert_eq!(TernoaCapsules::ledgers(&ALICE), None);
@@ -26,7 +28,7 @@ fn create_happy() {
             let ok = TernoaCapsules::create(alice.clone(), vec![50], ipfs_reference, None);
             assert_ok!(ok);
             assert_eq!(TernoaCapsules::capsules(&nft_id), Some(data));
-            assert_eq!(TernoaCapsules::ledgers(&ALICE), Some(ledger));
+            assert_eq!(TernoaCapsules::ledgers(&ALICE), Some(bounded(ledger)));
         })
 }
 
@@ -48,6 +50,11 @@ fn create_unhappy() {
             let ok = TernoaCapsules::create(bob.clone(), vec![], long, None);
             assert_noop!(ok, Error::<Test>::TooLongIpfsReference);
 
+            // Unhappy malformed ipfs reference
+            let bad = INVALID_REFERENCE.to_vec();
+            let ok = TernoaCapsules::create(bob.clone(), vec![], bad, None);
+            assert_noop!(ok, Error::<Test>::InvalidIpfsReference);
+
             // Unhappy not enough caps to reserve a capsule
             let ok = TernoaCapsules::create(bob.clone(), vec![], vec![1], None);
             assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
@@ -55,6 +62,12 @@ fn create_unhappy() {
             // Unhappy nft creation failed
             let ok = TernoaCapsules::create(alice.clone(), vec![], vec![1], None);
             assert_noop!(ok, ternoa_nfts::Error::<Test>::IPFSReferenceIsTooShort);
+
+            // Unhappy too many capsules
+            help::create_capsule_fast(alice.clone());
+            help::create_capsule_fast(alice.clone());
+            let ok = TernoaCapsules::create(alice.clone(), vec![50], vec![1], None);
+            assert_noop!(ok, Error::<Test>::TooManyCapsules);
         })
 }
 
@@ -124,13 +137,13 @@ fn create_from_nft_happy() {
             assert_eq!(TernoaCapsules::ledgers(&ALICE), None);
 
             // Happy path
-            let data = CapsuleData::new(ALICE, ipfs_reference.clone());
+            let data = CapsuleData::new(ALICE, bounded(ipfs_reference.clone()));
             let ledger = vec![(nft_id, TernoaCapsules::capsule_mint_fee())];
 
             let ok = TernoaCapsules::create_from_nft(alice.clone(), nft_id, ipfs_reference);
             assert_ok!(ok);
             assert_eq!(TernoaCapsules::capsules(&nft_id), Some(data));
-            assert_eq!(TernoaCapsules::ledgers(&ALICE), Some(ledger));
+            assert_eq!(TernoaCapsules::ledgers(&ALICE), Some(bounded(ledger)));
         })
 }
 
@@ -153,6 +166,11 @@ fn create_from_nft_unhappy() {
             let ok = TernoaCapsules::create_from_nft(alice.clone(), nft_id, long);
             assert_noop!(ok, Error::<Test>::TooLongIpfsReference);
 
+            // Unhappy malformed ipfs reference
+            let bad = INVALID_REFERENCE.to_vec();
+            let ok = TernoaCapsules::create_from_nft(alice.clone(), nft_id, bad);
+            assert_noop!(ok, Error::<Test>::InvalidIpfsReference);
+
             // Unhappy not nft owner
             let nft_id = help::create_nft_fast(bob.clone());
             let ok = TernoaCapsules::create_from_nft(alice.clone(), nft_id, vec![25]);
@@ -160,15 +178,22 @@ fn create_from_nft_unhappy() {
 
             // Unhappy nft is listed for sale
             let nft_id = help::create_nft_fast(alice.clone());
-            <TernoaNFTs as NFTTrait>::set_listed_for_sale(nft_id, true).unwrap();
+            <TernoaNFTs as NFTTrait>::lock(nft_id, NFTLockReason::ListedForSale).unwrap();
             let ok = TernoaCapsules::create_from_nft(alice.clone(), nft_id, vec![25]);
-            assert_noop!(ok, Error::<Test>::ListedForSale);
+            assert_noop!(ok, NFTError::<Test>::NFTIsListedForSale);
 
             // Unhappy nft is in transmission
             let nft_id = help::create_nft_fast(alice.clone());
-            <TernoaNFTs as NFTTrait>::set_in_transmission(nft_id, true).unwrap();
+            <TernoaNFTs as NFTTrait>::lock(nft_id, NFTLockReason::InTransmission).unwrap();
             let ok = TernoaCapsules::create_from_nft(alice.clone(), nft_id, vec![25]);
-            assert_noop!(ok, Error::<Test>::InTransmission);
+            assert_noop!(ok, NFTError::<Test>::NFTIsInTransmission);
+
+            // Unhappy nft is rented
+            let nft_id = help::create_nft_fast(alice.clone());
+            assert_ok!(TernoaNFTs::offer_rental(alice.clone(), nft_id, 0, 10, None));
+            assert_ok!(TernoaNFTs::rent(RawOrigin::Signed(BOB).into(), nft_id));
+            let ok = TernoaCapsules::create_from_nft(alice.clone(), nft_id, vec![25]);
+            assert_noop!(ok, NFTError::<Test>::NFTIsRented);
 
             // Unhappy nft is already a capsule
             let nft_id = help::create_nft_fast(alice.clone());
@@ -221,7 +246,9 @@ fn remove_happy() {
             // Happy path delete one nft id associated with that owner
             assert_ok!(TernoaCapsules::remove(alice.clone(), nft_id_1));
             assert_eq!(TernoaCapsules::capsules(&nft_id_1), None);
-            assert_eq!(TernoaCapsules::ledgers(&ALICE), Some(ledger));
+            assert_eq!(TernoaCapsules::ledgers(&ALICE), Some(bounded(ledger)));
+            let is_capsule = TernoaNFTs::is_locked(nft_id_1, NFTLockReason::Capsule);
+            assert_eq!(is_capsule, Some(false));
 
             // Happy path delete last nft id associated with that owner
             assert_ok!(TernoaCapsules::remove(alice.clone(), nft_id_2));
@@ -278,6 +305,30 @@ fn remove_caps_transfer() {
         })
 }
 
+#[test]
+fn force_burn_refunds_capsule() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 10001)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let nft_id = help::create_capsule_fast(alice.clone());
+            let fee = TernoaCapsules::ledgers(ALICE).unwrap()[0].1;
+            let pallet_id = TernoaCapsules::account_id();
+
+            let pallet_balance = Balances::free_balance(pallet_id);
+            let alice_balance = Balances::free_balance(ALICE);
+
+            assert_ok!(TernoaNFTs::force_burn(RawOrigin::Root.into(), nft_id));
+            assert_eq!(TernoaNFTs::get_nft(nft_id), None);
+            assert_eq!(TernoaCapsules::capsules(&nft_id), None);
+            assert_eq!(TernoaCapsules::ledgers(&ALICE), None);
+            assert_eq!(Balances::free_balance(ALICE), alice_balance + fee);
+            assert_eq!(Balances::free_balance(pallet_id), pallet_balance - fee);
+        })
+}
+
 #[test]
 fn add_funds_happy() {
     ExtBuilder::default()
@@ -289,13 +340,13 @@ fn add_funds_happy() {
             let nft_id = help::create_capsule_fast(alice.clone());
             let fee = TernoaCapsules::capsule_mint_fee();
             let ledger = vec![(nft_id, fee)];
-            assert_eq!(TernoaCapsules::ledgers(&ALICE), Some(ledger));
+            assert_eq!(TernoaCapsules::ledgers(&ALICE), Some(bounded(ledger)));
 
             // Happy path
             let add = 55;
             let ledger = vec![(nft_id, fee + add)];
             assert_ok!(TernoaCapsules::add_funds(alice.clone(), nft_id, add));
-            assert_eq!(TernoaCapsules::ledgers(&ALICE), Some(ledger));
+            assert_eq!(TernoaCapsules::ledgers(&ALICE), Some(bounded(ledger)));
         })
 }
 
@@ -389,6 +440,11 @@ fn set_ipfs_reference_unhappy() {
             let ok = TernoaCapsules::set_ipfs_reference(alice.clone(), nft_id, long);
             assert_noop!(ok, Error::<Test>::TooLongIpfsReference);
 
+            // Unhappy malformed ipfs reference
+            let bad = INVALID_REFERENCE.to_vec();
+            let ok = TernoaCapsules::set_ipfs_reference(alice.clone(), nft_id, bad);
+            assert_noop!(ok, Error::<Test>::InvalidIpfsReference);
+
             // Unhappy not nft owner
             let bob_nft_id = help::create_capsule_fast(bob.clone());
             let ok = TernoaCapsules::set_ipfs_reference(alice.clone(), bob_nft_id, vec![1]);
@@ -14,7 +14,7 @@ fn register_capsules() {
     let owner = ALICE;
     let reference = vec![20];
 
-    let data = CapsuleData::new(owner, reference.clone());
+    let data = CapsuleData::new(owner, bounded(reference.clone()));
     let ledger = vec![(nft_id, mint_fee)];
 
     GenesisConfig::<Test> {
@@ -27,7 +27,7 @@ fn register_capsules() {
 
     let mut ext = sp_io::TestExternalities::new(t);
     ext.execute_with(|| {
-        assert_eq!(TernoaCapsules::ledgers(owner), Some(ledger));
+        assert_eq!(TernoaCapsules::ledgers(owner), Some(bounded(ledger)));
         assert_eq!(TernoaCapsules::capsules(nft_id), Some(data));
         assert_eq!(TernoaCapsules::capsule_mint_fee(), mint_fee);
     });
@@ -1,5 +1,12 @@
 use super::mock::*;
-use frame_support::traits::OnRuntimeUpgrade;
+use crate::migrations::v2::v1;
+use crate::{Capsules, Ledgers};
+use frame_support::assert_ok;
+use frame_support::storage::unhashed;
+use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+use frame_system::RawOrigin;
+use ternoa_common::traits::NFTTrait;
+use ternoa_primitives::nfts::NFTLockReason;
 
 mod version_1 {
     use super::*;
@@ -23,6 +30,95 @@ mod version_1 {
     }
 }
 
+mod version_2 {
+    use super::*;
+
+    fn insert_v1_capsule(nft_id: u32, ipfs_reference: Vec<u8>) {
+        let data = v1::CapsuleData {
+            owner: ALICE,
+            ipfs_reference,
+        };
+        unhashed::put(&Capsules::<Test>::hashed_key_for(nft_id), &data);
+    }
+
+    #[test]
+    fn upgrade_from_v1_to_v2() {
+        ExtBuilder::default()
+            .caps(vec![(ALICE, 10000)])
+            .build()
+            .execute_with(|| {
+                let alice: Origin = RawOrigin::Signed(ALICE).into();
+                let fee = TernoaCapsules::capsule_mint_fee();
+
+                // MaxCapsulesPerAccount is 2 so the third capsule does not fit
+                let nft_1 = help::create_capsule_fast(alice.clone());
+                let nft_2 = help::create_capsule_fast(alice.clone());
+                let nft_3 = help::create_nft_fast(alice.clone());
+                assert_ok!(TernoaNFTs::lock(nft_3, NFTLockReason::Capsule));
+                let pallet_id = TernoaCapsules::account_id();
+                assert_ok!(Balances::transfer(alice, pallet_id, fee));
+
+                insert_v1_capsule(nft_1, vec![60; 5]);
+                insert_v1_capsule(nft_3, vec![60]);
+                let ledger = vec![(nft_1, fee), (nft_2, fee), (nft_3, fee)];
+                unhashed::put(&Ledgers::<Test>::hashed_key_for(ALICE), &ledger);
+                let balance = Balances::free_balance(ALICE);
+
+                StorageVersion::put::<TernoaCapsules>(&StorageVersion::new(1));
+                let weight = <TernoaCapsules as OnRuntimeUpgrade>::on_runtime_upgrade();
+                assert_ne!(weight, 0);
+                assert_eq!(
+                    StorageVersion::get::<TernoaCapsules>(),
+                    StorageVersion::new(2)
+                );
+
+                let reference = TernoaCapsules::capsules(nft_1).unwrap().ipfs_reference;
+                assert_eq!(reference, vec![60; 5]);
+
+                let ledger = vec![(nft_1, fee), (nft_2, fee)];
+                assert_eq!(TernoaCapsules::ledgers(ALICE), Some(bounded(ledger)));
+                assert_eq!(TernoaCapsules::capsules(nft_3), None);
+                let is_capsule = TernoaNFTs::is_locked(nft_3, NFTLockReason::Capsule);
+                assert_eq!(is_capsule, Some(false));
+                assert_eq!(Balances::free_balance(ALICE), balance + fee);
+            })
+    }
+
+    #[test]
+    fn upgrade_from_v1_to_v2_is_aborted_when_a_reference_does_not_fit() {
+        ExtBuilder::default()
+            .caps(vec![(ALICE, 10000)])
+            .build()
+            .execute_with(|| {
+                let alice: Origin = RawOrigin::Signed(ALICE).into();
+                let fee = TernoaCapsules::capsule_mint_fee();
+
+                // MaxIpfsLen is 5
+                let nft_1 = help::create_capsule_fast(alice.clone());
+                let nft_2 = help::create_capsule_fast(alice.clone());
+                insert_v1_capsule(nft_1, vec![60]);
+                insert_v1_capsule(nft_2, vec![60; 7]);
+                let ledger = vec![(nft_1, fee), (nft_2, fee)];
+                unhashed::put(&Ledgers::<Test>::hashed_key_for(ALICE), &ledger);
+
+                StorageVersion::put::<TernoaCapsules>(&StorageVersion::new(1));
+                let weight = <TernoaCapsules as OnRuntimeUpgrade>::on_runtime_upgrade();
+                assert_ne!(weight, 0);
+                assert_eq!(
+                    StorageVersion::get::<TernoaCapsules>(),
+                    StorageVersion::new(1)
+                );
+
+                let key = Capsules::<Test>::hashed_key_for(nft_2);
+                let old: v1::CapsuleData<u64> = unhashed::get(&key).unwrap();
+                assert_eq!(old.ipfs_reference, vec![60; 7]);
+                let key = Ledgers::<Test>::hashed_key_for(ALICE);
+                let old: Vec<(u32, u128)> = unhashed::get(&key).unwrap();
+                assert_eq!(old, ledger);
+            })
+    }
+}
+
 #[test]
 fn upgrade_from_latest_to_latest() {
     ExtBuilder::default().build().execute_with(|| {
@@ -1,9 +1,14 @@
 use crate::{self as ternoa_capsules, Config};
-use frame_support::traits::{ConstU32, Contains, GenesisBuild};
-use frame_support::{parameter_types, PalletId};
+use frame_support::traits::{ConstU32, Contains, GenesisBuild, Get};
+use frame_support::weights::constants::RocksDbWeight;
+use frame_support::{parameter_types, BoundedVec, PalletId};
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use sp_runtime::Permill;
+use std::convert::TryInto;
+use ternoa_common::traits::ReferenceValidator;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -44,7 +49,7 @@ impl frame_system::Config for Test {
     type BaseCallFilter = TestBaseCallFilter;
     type BlockWeights = ();
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Origin = Origin;
     type Index = u64;
     type BlockNumber = u64;
@@ -88,9 +93,18 @@ impl pallet_balances::Config for Test {
 parameter_types! {
     pub const MinIpfsLen: u16 = 1;
     pub const MaxIpfsLen: u16 = 5;
+    pub const MaxSeriesIdLen: u16 = 5;
     pub const CapsulePalletId: PalletId = PalletId(*b"mockcaps");
 }
 
+parameter_types! {
+    pub const BatchMintDiscount: Permill = Permill::from_percent(0);
+    pub const UseStorageDeposits: bool = false;
+    pub const DepositBase: u128 = 0;
+    pub const DepositPerByte: u128 = 0;
+    pub const DepositFollowsOwner: bool = true;
+}
+
 impl ternoa_nfts::Config for Test {
     type Event = Event;
     type WeightInfo = ();
@@ -98,6 +112,17 @@ impl ternoa_nfts::Config for Test {
     type FeesCollector = ();
     type MinIpfsLen = MinIpfsLen;
     type MaxIpfsLen = MaxIpfsLen;
+    type IPFSReferenceValidator = ();
+    type MaxSeriesIdLen = MaxSeriesIdLen;
+    type MaxBatchSize = ConstU32<5>;
+    type BatchMintDiscount = BatchMintDiscount;
+    type UseStorageDeposits = UseStorageDeposits;
+    type DepositBase = DepositBase;
+    type DepositPerByte = DepositPerByte;
+    type DepositFollowsOwner = DepositFollowsOwner;
+    type ForceOrigin = EnsureRoot<u64>;
+    type NFTReleaseHandler = TernoaCapsules;
+    type MaxRentalsPerBlock = ConstU32<5>;
 }
 
 impl Config for Test {
@@ -108,6 +133,22 @@ impl Config for Test {
     type PalletId = CapsulePalletId;
     type MinIpfsLen = MinIpfsLen;
     type MaxIpfsLen = MaxIpfsLen;
+    type IPFSReferenceValidator = MockReferenceValidator;
+    type MaxCapsulesPerAccount = ConstU32<2>;
+}
+
+/// Reference that `MockReferenceValidator` refuses.
+pub const INVALID_REFERENCE: &[u8] = b"bad";
+
+pub struct MockReferenceValidator;
+impl ReferenceValidator for MockReferenceValidator {
+    fn is_valid(reference: &[u8]) -> bool {
+        reference != INVALID_REFERENCE
+    }
+
+    fn benchmark_reference() -> Vec<u8> {
+        vec![1]
+    }
 }
 
 // Do not use the `0` account id since this would be the default value
@@ -188,11 +229,20 @@ pub mod help {
         ipfs_reference: TextFormat,
         series_id: Option<NFTSeriesId>,
     ) -> NFTId {
-        assert_ok!(TernoaNFTs::create(owner, ipfs_reference, series_id));
+        assert_ok!(TernoaNFTs::create(
+            owner,
+            ipfs_reference,
+            series_id,
+            Permill::zero()
+        ));
         TernoaNFTs::nft_id_generator() - 1
     }
 }
 
+pub fn bounded<T, S: Get<u32>>(value: Vec<T>) -> BoundedVec<T, S> {
+    value.try_into().unwrap()
+}
+
 #[allow(dead_code)]
 pub fn new_test_ext() -> sp_io::TestExternalities {
     let t = frame_system::GenesisConfig::default()
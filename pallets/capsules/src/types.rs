@@ -1,28 +1,38 @@
-#[cfg(feature = "std")]
-use serde::{Deserialize, Serialize};
 use ternoa_primitives::nfts::NFTId;
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use frame_support::{BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
-use sp_std::vec::Vec;
-use ternoa_primitives::TextFormat;
+use sp_std::fmt::Debug;
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct CapsuleData<AccountId>
+#[derive(
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    RuntimeDebugNoBound,
+    Encode,
+    Decode,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(IPFSLengthLimit))]
+#[codec(mel_bound(AccountId: MaxEncodedLen))]
+pub struct CapsuleData<AccountId, IPFSLengthLimit>
 where
-    AccountId: Clone,
+    AccountId: Clone + PartialEq + Debug,
+    IPFSLengthLimit: Get<u32>,
 {
     pub owner: AccountId,
-    pub ipfs_reference: TextFormat,
+    pub ipfs_reference: BoundedVec<u8, IPFSLengthLimit>,
 }
 
-impl<AccountId> CapsuleData<AccountId>
+impl<AccountId, IPFSLengthLimit> CapsuleData<AccountId, IPFSLengthLimit>
 where
-    AccountId: Clone,
+    AccountId: Clone + PartialEq + Debug,
+    IPFSLengthLimit: Get<u32>,
 {
-    pub fn new(owner: AccountId, ipfs_reference: TextFormat) -> CapsuleData<AccountId> {
+    pub fn new(owner: AccountId, ipfs_reference: BoundedVec<u8, IPFSLengthLimit>) -> Self {
         Self {
             owner,
             ipfs_reference,
@@ -30,4 +40,4 @@ where
     }
 }
 
-pub type CapsuleLedger<Balance> = Vec<(NFTId, Balance)>;
+pub type CapsuleLedger<Balance, Limit> = BoundedVec<(NFTId, Balance), Limit>;
@@ -0,0 +1,84 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as Fractionalization;
+use frame_benchmarking::{account as benchmark_account, benchmarks};
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use sp_runtime::traits::StaticLookup;
+use ternoa_common::traits::NFTTrait;
+
+const SHARES: Shares = 1_000_000;
+
+pub fn prepare_benchmarks<T: Config>() -> NFTId {
+    let alice: T::AccountId = get_account::<T>("ALICE");
+    let nft_id = T::NFTs::benchmark_create_nft(&alice);
+    let series_id = T::NFTs::get_nft(nft_id).unwrap().series_id.to_vec();
+    T::NFTs::benchmark_lock_series(series_id);
+
+    assert_ok!(Fractionalization::<T>::fractionalize(
+        get_origin::<T>("ALICE").into(),
+        nft_id,
+        SHARES,
+    ));
+
+    nft_id
+}
+
+pub fn get_account<T: Config>(name: &'static str) -> T::AccountId {
+    let account: T::AccountId = benchmark_account(name, 0, 0);
+    account
+}
+
+pub fn get_origin<T: Config>(name: &'static str) -> RawOrigin<T::AccountId> {
+    RawOrigin::Signed(get_account::<T>(name))
+}
+
+benchmarks! {
+    fractionalize {
+        let alice: T::AccountId = get_account::<T>("ALICE");
+        let nft_id = T::NFTs::benchmark_create_nft(&alice);
+        let series_id = T::NFTs::get_nft(nft_id).unwrap().series_id.to_vec();
+        T::NFTs::benchmark_lock_series(series_id);
+
+    }: _(get_origin::<T>("ALICE"), nft_id, SHARES)
+    verify {
+        assert_eq!(Fractionalization::<T>::shares(nft_id, &alice), SHARES);
+    }
+
+    transfer_shares {
+        let nft_id = prepare_benchmarks::<T>();
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
+
+    }: _(get_origin::<T>("ALICE"), nft_id, bob_lookup, SHARES / 2)
+    verify {
+        assert_eq!(Fractionalization::<T>::shares(nft_id, &bob), SHARES / 2);
+    }
+
+    redeem {
+        let nft_id = prepare_benchmarks::<T>();
+        let alice: T::AccountId = get_account::<T>("ALICE");
+
+    }: _(get_origin::<T>("ALICE"), nft_id)
+    verify {
+        assert_eq!(T::NFTs::owner(nft_id), Some(alice));
+        assert_eq!(Fractionalization::<T>::fractions(nft_id), None);
+    }
+
+    release_nft {
+        let s in 1 .. T::MaxShareholders::get();
+        let nft_id = prepare_benchmarks::<T>();
+        for i in 1 .. s {
+            let holder: T::AccountId = benchmark_account("HOLDER", i, 0);
+            let holder_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(holder);
+            assert_ok!(Fractionalization::<T>::transfer_shares(get_origin::<T>("ALICE").into(), nft_id, holder_lookup, 1));
+        }
+
+    }: {
+        assert_ok!(<Fractionalization<T> as NFTReleaseHandler>::release_nft(nft_id, NFTLockReason::Fractionalized));
+    }
+    verify {
+        assert_eq!(Fractionalization::<T>::fractions(nft_id), None);
+    }
+}
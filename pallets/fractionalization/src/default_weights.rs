@@ -0,0 +1,46 @@
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+pub trait WeightInfo {
+    fn fractionalize() -> Weight;
+    fn transfer_shares() -> Weight;
+    fn redeem() -> Weight;
+    fn release_nft(s: u32) -> Weight;
+}
+
+impl WeightInfo for () {
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Fractionalization Fractions (r:1 w:1)
+    // Storage: Fractionalization Shares (r:0 w:1)
+    fn fractionalize() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Fractionalization Fractions (r:1 w:1)
+    // Storage: Fractionalization Shares (r:2 w:2)
+    fn transfer_shares() -> Weight {
+        (42_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Fractionalization Fractions (r:1 w:1)
+    // Storage: Fractionalization Shares (r:1 w:1)
+    // Storage: Nfts Data (r:2 w:1)
+    // Storage: Nfts NftsByOwner (r:0 w:2)
+    fn redeem() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    // Storage: Fractionalization Fractions (r:1 w:1)
+    // Storage: Fractionalization Shares (r:1 w:1)
+    fn release_nft(s: u32) -> Weight {
+        (20_000_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((4_000_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+    }
+}
@@ -0,0 +1,267 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod tests;
+
+mod default_weights;
+mod types;
+
+pub use default_weights::WeightInfo;
+pub use pallet::*;
+pub use types::*;
+
+use frame_support::dispatch::DispatchError;
+use frame_support::traits::{Get, StorageVersion};
+use frame_support::weights::Weight;
+use ternoa_common::traits::NFTReleaseHandler;
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::transactional;
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::StaticLookup;
+    use ternoa_common::traits::NFTTrait;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Because this pallet emits events, it depends on the runtime's definition of an event.
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// Weight values for this pallet
+        type WeightInfo: WeightInfo;
+
+        /// Pallet managing the NFTs that get fractionalized
+        type NFTs: NFTTrait<AccountId = Self::AccountId>;
+
+        /// Maximum number of accounts that can hold shares of the same NFT
+        #[pallet::constant]
+        type MaxShareholders: Get<u32>;
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Lock an NFT and mint `shares` fungible shares for it. All the shares
+        /// go to the NFT owner. The NFT must be part of a completed series.
+        #[pallet::weight(T::WeightInfo::fractionalize())]
+        #[transactional]
+        pub fn fractionalize(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+            shares: Shares,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let nft = T::NFTs::get_nft(nft_id).ok_or(Error::<T>::UnknownNFT)?;
+            ensure!(nft.owner == who, Error::<T>::NotTheNFTOwner);
+            ensure!(shares > 0, Error::<T>::ZeroShares);
+
+            let is_nft_in_completed_series =
+                T::NFTs::is_nft_in_completed_series(nft_id) == Some(true);
+            ensure!(is_nft_in_completed_series, Error::<T>::SeriesNotCompleted);
+
+            T::NFTs::lock(nft_id, NFTLockReason::Fractionalized)?;
+
+            Fractions::<T>::insert(nft_id, FractionalizedNFT::new(who.clone(), shares));
+            SharesOf::<T>::insert(nft_id, &who, shares);
+
+            let event = Event::NFTFractionalized {
+                nft_id,
+                owner: who,
+                shares,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Transfer some shares of a fractionalized NFT to another account.
+        #[pallet::weight(T::WeightInfo::transfer_shares())]
+        #[transactional]
+        pub fn transfer_shares(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+            to: <T::Lookup as StaticLookup>::Source,
+            amount: Shares,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let to = T::Lookup::lookup(to)?;
+
+            let mut data = Fractions::<T>::get(nft_id).ok_or(Error::<T>::NFTIsNotFractionalized)?;
+            ensure!(amount > 0, Error::<T>::ZeroShares);
+
+            let balance = SharesOf::<T>::get(nft_id, &who);
+            let left = balance
+                .checked_sub(amount)
+                .ok_or(Error::<T>::InsufficientShares)?;
+            Self::set_shares(nft_id, &who, left);
+            if left == 0 {
+                data.holders = data.holders.saturating_sub(1);
+            }
+
+            let balance = SharesOf::<T>::get(nft_id, &to);
+            if balance == 0 {
+                data.holders = data.holders.saturating_add(1);
+                ensure!(
+                    data.holders <= T::MaxShareholders::get(),
+                    Error::<T>::TooManyShareholders
+                );
+            }
+            let balance = balance
+                .checked_add(amount)
+                .ok_or(Error::<T>::ArithmeticError)?;
+            Self::set_shares(nft_id, &to, balance);
+            Fractions::<T>::insert(nft_id, data);
+
+            let event = Event::SharesTransferred {
+                nft_id,
+                from: who,
+                to,
+                amount,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Burn all the shares of a fractionalized NFT and get the NFT back.
+        /// The caller needs to hold every share.
+        #[pallet::weight(T::WeightInfo::redeem())]
+        #[transactional]
+        pub fn redeem(origin: OriginFor<T>, nft_id: NFTId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let data = Fractions::<T>::get(nft_id).ok_or(Error::<T>::NFTIsNotFractionalized)?;
+            let balance = SharesOf::<T>::get(nft_id, &who);
+            ensure!(balance == data.total_shares, Error::<T>::NotAllSharesOwned);
+
+            let is_nft_in_completed_series =
+                T::NFTs::is_nft_in_completed_series(nft_id) == Some(true);
+            ensure!(is_nft_in_completed_series, Error::<T>::SeriesNotCompleted);
+
+            SharesOf::<T>::remove(nft_id, &who);
+            Fractions::<T>::remove(nft_id);
+
+            T::NFTs::unlock(nft_id, NFTLockReason::Fractionalized)?;
+            T::NFTs::set_owner(nft_id, &who)?;
+
+            let event = Event::NFTRedeemed { nft_id, owner: who };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// An NFT was fractionalized.
+        NFTFractionalized {
+            nft_id: NFTId,
+            owner: T::AccountId,
+            shares: Shares,
+        },
+        /// Shares of a fractionalized NFT were transferred.
+        SharesTransferred {
+            nft_id: NFTId,
+            from: T::AccountId,
+            to: T::AccountId,
+            amount: Shares,
+        },
+        /// A fractionalized NFT was redeemed by the holder of all its shares.
+        NFTRedeemed { nft_id: NFTId, owner: T::AccountId },
+        /// The shares of a fractionalized NFT were cancelled by governance.
+        SharesCancelled { nft_id: NFTId },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// This should never happen.
+        ArithmeticError,
+        /// No NFT was found with that NFT id.
+        UnknownNFT,
+        /// Operation is not permitted because the caller is not the NFT owner.
+        NotTheNFTOwner,
+        /// An NFT cannot be fractionalized into zero shares.
+        ZeroShares,
+        /// The NFT is not fractionalized.
+        NFTIsNotFractionalized,
+        /// The caller does not hold enough shares.
+        InsufficientShares,
+        /// The caller does not hold every share of the NFT.
+        NotAllSharesOwned,
+        /// The shares of the NFT cannot be spread over more accounts.
+        TooManyShareholders,
+        /// NFTs from a draft series cannot change hands.
+        SeriesNotCompleted,
+    }
+
+    /// List of NFTs that are fractionalized.
+    #[pallet::storage]
+    #[pallet::getter(fn fractions)]
+    pub type Fractions<T: Config> =
+        StorageMap<_, Blake2_128Concat, NFTId, FractionalizedNFT<T::AccountId>, OptionQuery>;
+
+    /// Shares held by each account, per fractionalized NFT.
+    #[pallet::storage]
+    #[pallet::getter(fn shares)]
+    pub type SharesOf<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        NFTId,
+        Blake2_128Concat,
+        T::AccountId,
+        Shares,
+        ValueQuery,
+    >;
+}
+
+impl<T: Config> Pallet<T> {
+    /// Update the shares of an account, dropping the entry once it is empty.
+    fn set_shares(nft_id: NFTId, account: &T::AccountId, amount: Shares) {
+        if amount == 0 {
+            SharesOf::<T>::remove(nft_id, account);
+        } else {
+            SharesOf::<T>::insert(nft_id, account, amount);
+        }
+    }
+}
+
+impl<T: Config> NFTReleaseHandler for Pallet<T> {
+    /// Cancel the shares of a fractionalized NFT. Their holders are not
+    /// compensated.
+    fn release_nft(nft_id: NFTId, reason: NFTLockReason) -> Result<Weight, DispatchError> {
+        if reason != NFTLockReason::Fractionalized {
+            return Ok(0);
+        }
+
+        let data = match Fractions::<T>::take(nft_id) {
+            Some(data) => data,
+            None => return Ok(T::DbWeight::get().reads(1)),
+        };
+
+        SharesOf::<T>::drain_prefix(nft_id).for_each(drop);
+        Self::deposit_event(Event::SharesCancelled { nft_id });
+
+        Ok(T::WeightInfo::release_nft(data.holders))
+    }
+
+    fn max_release_weight() -> Weight {
+        T::WeightInfo::release_nft(T::MaxShareholders::get())
+    }
+}
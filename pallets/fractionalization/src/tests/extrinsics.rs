@@ -0,0 +1,259 @@
+use super::mock::*;
+use crate::tests::mock;
+use crate::{Error, Event as FractionalizationEvent, FractionalizedNFT, WeightInfo};
+use frame_support::error::BadOrigin;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::Permill;
+use ternoa_common::traits::NFTTrait;
+use ternoa_nfts::Error as NFTError;
+use ternoa_nfts::WeightInfo as NFTsWeightInfo;
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
+
+fn origin(account: u64) -> mock::Origin {
+    RawOrigin::Signed(account).into()
+}
+
+fn create_nft(owner: u64) -> NFTId {
+    let nft_id = create_draft_nft(owner);
+    let series_id = NFTs::data(nft_id).unwrap().series_id.to_vec();
+    assert_ok!(NFTs::finish_series(origin(owner), series_id));
+    nft_id
+}
+
+fn create_draft_nft(owner: u64) -> NFTId {
+    assert_ok!(NFTs::create(origin(owner), vec![1], None, Permill::zero()));
+    NFTs::nft_id_generator() - 1
+}
+
+mod fractionalize {
+    use super::*;
+
+    #[test]
+    fn fractionalize() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+
+            assert_ok!(Fractionalization::fractionalize(origin(ALICE), nft_id, 100));
+
+            // Storage
+            let data = FractionalizedNFT::new(ALICE, 100);
+            assert_eq!(Fractionalization::fractions(nft_id), Some(data));
+            assert_eq!(Fractionalization::shares(nft_id, ALICE), 100);
+            let is_locked = NFTs::is_locked(nft_id, NFTLockReason::Fractionalized);
+            assert_eq!(is_locked, Some(true));
+
+            // Events
+            let event = FractionalizationEvent::NFTFractionalized {
+                nft_id,
+                owner: ALICE,
+                shares: 100,
+            };
+            let event = Event::Fractionalization(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+    }
+
+    #[test]
+    fn fractionalized_nft_is_locked() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            assert_ok!(Fractionalization::fractionalize(origin(ALICE), nft_id, 100));
+
+            let ok = NFTs::transfer(origin(ALICE), nft_id, BOB);
+            assert_noop!(ok, NFTError::<Test>::NFTIsFractionalized);
+
+            let ok = NFTs::lock(nft_id, NFTLockReason::ListedForSale);
+            assert_noop!(ok, NFTError::<Test>::NFTIsFractionalized);
+
+            let ok = NFTs::lock(nft_id, NFTLockReason::Capsule);
+            assert_noop!(ok, NFTError::<Test>::NFTIsFractionalized);
+        })
+    }
+
+    #[test]
+    fn fractionalize_unhappy() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+
+            // Unhappy bad origin
+            let ok = Fractionalization::fractionalize(RawOrigin::None.into(), nft_id, 100);
+            assert_noop!(ok, BadOrigin);
+
+            // Unhappy unknown NFT
+            let ok = Fractionalization::fractionalize(origin(ALICE), 1001, 100);
+            assert_noop!(ok, Error::<Test>::UnknownNFT);
+
+            // Unhappy not the NFT owner
+            let ok = Fractionalization::fractionalize(origin(BOB), nft_id, 100);
+            assert_noop!(ok, Error::<Test>::NotTheNFTOwner);
+
+            // Unhappy zero shares
+            let ok = Fractionalization::fractionalize(origin(ALICE), nft_id, 0);
+            assert_noop!(ok, Error::<Test>::ZeroShares);
+
+            // Unhappy series not completed
+            let draft_nft_id = create_draft_nft(ALICE);
+            let ok = Fractionalization::fractionalize(origin(ALICE), draft_nft_id, 100);
+            assert_noop!(ok, Error::<Test>::SeriesNotCompleted);
+
+            // Unhappy already fractionalized
+            assert_ok!(Fractionalization::fractionalize(origin(ALICE), nft_id, 100));
+            let ok = Fractionalization::fractionalize(origin(ALICE), nft_id, 100);
+            assert_noop!(ok, NFTError::<Test>::NFTIsFractionalized);
+        })
+    }
+}
+
+mod transfer_shares {
+    use super::*;
+
+    #[test]
+    fn transfer_shares() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            assert_ok!(Fractionalization::fractionalize(origin(ALICE), nft_id, 100));
+
+            let ok = Fractionalization::transfer_shares(origin(ALICE), nft_id, BOB, 30);
+            assert_ok!(ok);
+
+            // Storage
+            assert_eq!(Fractionalization::shares(nft_id, ALICE), 70);
+            assert_eq!(Fractionalization::shares(nft_id, BOB), 30);
+            assert_eq!(Fractionalization::fractions(nft_id).unwrap().holders, 2);
+
+            // Events
+            let event = FractionalizationEvent::SharesTransferred {
+                nft_id,
+                from: ALICE,
+                to: BOB,
+                amount: 30,
+            };
+            let event = Event::Fractionalization(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+
+            // Sending every share drops the entry
+            let ok = Fractionalization::transfer_shares(origin(BOB), nft_id, CHARLIE, 30);
+            assert_ok!(ok);
+            let exists = crate::SharesOf::<Test>::contains_key(nft_id, BOB);
+            assert!(!exists);
+            assert_eq!(Fractionalization::shares(nft_id, CHARLIE), 30);
+            assert_eq!(Fractionalization::fractions(nft_id).unwrap().holders, 2);
+        })
+    }
+
+    #[test]
+    fn transfer_shares_unhappy() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+
+            // Unhappy NFT is not fractionalized
+            let ok = Fractionalization::transfer_shares(origin(ALICE), nft_id, BOB, 10);
+            assert_noop!(ok, Error::<Test>::NFTIsNotFractionalized);
+
+            assert_ok!(Fractionalization::fractionalize(origin(ALICE), nft_id, 100));
+
+            // Unhappy zero shares
+            let ok = Fractionalization::transfer_shares(origin(ALICE), nft_id, BOB, 0);
+            assert_noop!(ok, Error::<Test>::ZeroShares);
+
+            // Unhappy not enough shares
+            let ok = Fractionalization::transfer_shares(origin(ALICE), nft_id, BOB, 101);
+            assert_noop!(ok, Error::<Test>::InsufficientShares);
+            let ok = Fractionalization::transfer_shares(origin(BOB), nft_id, ALICE, 1);
+            assert_noop!(ok, Error::<Test>::InsufficientShares);
+
+            // Unhappy MaxShareholders is 2
+            let ok = Fractionalization::transfer_shares(origin(ALICE), nft_id, BOB, 10);
+            assert_ok!(ok);
+            let ok = Fractionalization::transfer_shares(origin(ALICE), nft_id, CHARLIE, 10);
+            assert_noop!(ok, Error::<Test>::TooManyShareholders);
+        })
+    }
+}
+
+mod redeem {
+    use super::*;
+
+    #[test]
+    fn redeem() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            assert_ok!(Fractionalization::fractionalize(origin(ALICE), nft_id, 100));
+            let ok = Fractionalization::transfer_shares(origin(ALICE), nft_id, BOB, 100);
+            assert_ok!(ok);
+
+            assert_ok!(Fractionalization::redeem(origin(BOB), nft_id));
+
+            // Storage
+            assert_eq!(Fractionalization::fractions(nft_id), None);
+            assert_eq!(Fractionalization::shares(nft_id, BOB), 0);
+            assert_eq!(NFTs::owner(nft_id), Some(BOB));
+            let is_locked = NFTs::is_locked(nft_id, NFTLockReason::Fractionalized);
+            assert_eq!(is_locked, Some(false));
+
+            // Events
+            let event = FractionalizationEvent::NFTRedeemed { nft_id, owner: BOB };
+            let event = Event::Fractionalization(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+    }
+
+    #[test]
+    fn redeem_unhappy() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+
+            // Unhappy NFT is not fractionalized
+            let ok = Fractionalization::redeem(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::NFTIsNotFractionalized);
+
+            // Unhappy not every share is owned
+            assert_ok!(Fractionalization::fractionalize(origin(ALICE), nft_id, 100));
+            let ok = Fractionalization::transfer_shares(origin(ALICE), nft_id, BOB, 1);
+            assert_ok!(ok);
+            let ok = Fractionalization::redeem(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::NotAllSharesOwned);
+
+            // Unhappy series not completed
+            let ok = Fractionalization::transfer_shares(origin(BOB), nft_id, ALICE, 1);
+            assert_ok!(ok);
+            let series_id = NFTs::data(nft_id).unwrap().series_id.to_vec();
+            assert_ok!(NFTs::set_series_completion(&series_id, false));
+            let ok = Fractionalization::redeem(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::SeriesNotCompleted);
+        })
+    }
+}
+
+mod force_transfer {
+    use super::*;
+
+    #[test]
+    fn force_transfer_cancels_shares() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            assert_ok!(Fractionalization::fractionalize(origin(ALICE), nft_id, 100));
+            let ok = Fractionalization::transfer_shares(origin(ALICE), nft_id, BOB, 40);
+            assert_ok!(ok);
+
+            let info = NFTs::force_transfer(RawOrigin::Root.into(), nft_id, BOB).unwrap();
+            let released = <() as WeightInfo>::release_nft(2);
+            let expected = <() as NFTsWeightInfo>::force_transfer() + released;
+            assert_eq!(info.actual_weight, Some(expected));
+
+            // Storage
+            assert_eq!(Fractionalization::fractions(nft_id), None);
+            assert_eq!(Fractionalization::shares(nft_id, ALICE), 0);
+            assert_eq!(Fractionalization::shares(nft_id, BOB), 0);
+            assert_eq!(NFTs::owner(nft_id), Some(BOB));
+            let is_locked = NFTs::is_locked(nft_id, NFTLockReason::Fractionalized);
+            assert_eq!(is_locked, Some(false));
+
+            // Events
+            let event = FractionalizationEvent::SharesCancelled { nft_id };
+            let event = Event::Fractionalization(event);
+            assert!(System::events().iter().any(|x| x.event == event));
+        })
+    }
+}
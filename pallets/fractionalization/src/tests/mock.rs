@@ -0,0 +1,168 @@
+use crate::{self as ternoa_fractionalization, Config};
+use frame_support::parameter_types;
+use frame_support::traits::{ConstU32, Contains, GenesisBuild};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::testing::Header;
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use sp_runtime::Permill;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+        NFTs: ternoa_nfts::{Pallet, Call, Storage, Event<T>, Config<T>},
+        Fractionalization: ternoa_fractionalization::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+pub struct TestBaseCallFilter;
+impl Contains<Call> for TestBaseCallFilter {
+    fn contains(c: &Call) -> bool {
+        match *c {
+            // For benchmarking, this acts as a noop call
+            Call::System(frame_system::Call::remark { .. }) => true,
+            // For tests
+            _ => false,
+        }
+    }
+}
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub BlockWeights: frame_system::limits::BlockWeights =
+        frame_system::limits::BlockWeights::simple_max(1024);
+}
+impl frame_system::Config for Test {
+    type BaseCallFilter = TestBaseCallFilter;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Call = Call;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = MaxLocks;
+    type MaxReserves = MaxReserves;
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type DustRemoval = ();
+    type Event = Event;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MinIpfsLen: u16 = 1;
+    pub const MaxIpfsLen: u16 = 5;
+    pub const MaxSeriesIdLen: u16 = 5;
+    pub const BatchMintDiscount: Permill = Permill::from_percent(0);
+    pub const UseStorageDeposits: bool = false;
+    pub const DepositBase: u64 = 0;
+    pub const DepositPerByte: u64 = 0;
+    pub const DepositFollowsOwner: bool = true;
+}
+
+impl ternoa_nfts::Config for Test {
+    type Event = Event;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type FeesCollector = ();
+    type MinIpfsLen = MinIpfsLen;
+    type MaxIpfsLen = MaxIpfsLen;
+    type IPFSReferenceValidator = ();
+    type MaxSeriesIdLen = MaxSeriesIdLen;
+    type MaxBatchSize = ConstU32<5>;
+    type BatchMintDiscount = BatchMintDiscount;
+    type UseStorageDeposits = UseStorageDeposits;
+    type DepositBase = DepositBase;
+    type DepositPerByte = DepositPerByte;
+    type DepositFollowsOwner = DepositFollowsOwner;
+    type ForceOrigin = EnsureRoot<u64>;
+    type NFTReleaseHandler = Fractionalization;
+    type MaxRentalsPerBlock = ConstU32<5>;
+}
+
+impl Config for Test {
+    type Event = Event;
+    type WeightInfo = ();
+    type NFTs = NFTs;
+    type MaxShareholders = ConstU32<2>;
+}
+
+// Do not use the `0` account id since this would be the default value
+// for our account id. This would mess with some tests.
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+
+pub struct ExtBuilder {
+    endowed_accounts: Vec<(u64, u64)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        ExtBuilder {
+            endowed_accounts: vec![(ALICE, 1000), (BOB, 1000)],
+        }
+    }
+}
+
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        pallet_balances::GenesisConfig::<Test> {
+            balances: self.endowed_accounts,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        ternoa_nfts::GenesisConfig::<Test> {
+            nfts: Default::default(),
+            series: Default::default(),
+            nft_mint_fee: 10,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        let mut ext = sp_io::TestExternalities::new(t);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+}
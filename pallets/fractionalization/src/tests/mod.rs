@@ -0,0 +1,3 @@
+mod extrinsics;
+
+pub mod mock;
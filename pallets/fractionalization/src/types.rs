@@ -0,0 +1,27 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// How share amounts are encoded.
+pub type Shares = u128;
+
+/// Data related to a fractionalized NFT.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct FractionalizedNFT<AccountId> {
+    // Account that fractionalized the NFT
+    pub owner: AccountId,
+    // Number of shares that were minted
+    pub total_shares: Shares,
+    // Number of accounts that hold some shares
+    pub holders: u32,
+}
+
+impl<AccountId> FractionalizedNFT<AccountId> {
+    pub fn new(owner: AccountId, total_shares: Shares) -> Self {
+        Self {
+            owner,
+            total_shares,
+            holders: 1,
+        }
+    }
+}
@@ -3,10 +3,12 @@
 use super::*;
 use crate::Pallet as Marketplace;
 use frame_benchmarking::{account as benchmark_account, benchmarks, impl_benchmark_test_suite};
-use frame_support::assert_ok;
-use frame_support::traits::Currency;
+use frame_support::traits::{Currency, Hooks};
+use frame_support::{assert_ok, BoundedVec};
 use frame_system::RawOrigin;
 use sp_runtime::traits::{Bounded, StaticLookup};
+use sp_runtime::Permill;
+use sp_std::convert::TryFrom;
 use sp_std::prelude::*;
 use ternoa_common::traits::NFTTrait;
 
@@ -31,7 +33,7 @@ pub fn prepare_benchmarks<T: Config>() -> (MarketplaceId, MarketplaceId, NFTId)
     assert_ok!(Marketplace::<T>::create(
         get_origin::<T>("ALICE").into(),
         MarketplaceType::Public,
-        0,
+        Default::default(),
         vec![50],
         None,
         None,
@@ -43,7 +45,7 @@ pub fn prepare_benchmarks<T: Config>() -> (MarketplaceId, MarketplaceId, NFTId)
     assert_ok!(Marketplace::<T>::create(
         get_origin::<T>("ALICE").into(),
         MarketplaceType::Private,
-        0,
+        Default::default(),
         vec![51],
         None,
         None,
@@ -64,13 +66,16 @@ pub fn get_origin<T: Config>(name: &'static str) -> RawOrigin<T::AccountId> {
 }
 
 benchmarks! {
+    where_clause { where T::AssetId: From<u32> }
+
     list {
         let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
 
         let alice: T::AccountId = get_account::<T>("ALICE");
         let price: BalanceOf<T> = 100u32.into();
+        let buyers = (0..MaxBuyers::get()).map(|i| T::Lookup::unlookup(benchmark_account("BOB", i, 0))).collect();
 
-    }: _(RawOrigin::Signed(alice.clone()), nft_id, price, Some(mkp_id))
+    }: _(RawOrigin::Signed(alice.clone()), nft_id, price, PaymentAsset::Native, Some(mkp_id), None, None, buyers)
     verify {
         assert_eq!(T::NFTs::owner(nft_id), Some(alice));
         assert_eq!(NFTsForSale::<T>::contains_key(nft_id), true);
@@ -81,7 +86,7 @@ benchmarks! {
 
         let alice = get_origin::<T>("ALICE");
         let price: BalanceOf<T> = 100u32.into();
-        drop(Marketplace::<T>::list(alice.clone().into(), nft_id, price, Some(mkp_id)));
+        drop(Marketplace::<T>::list(alice.clone().into(), nft_id, price, PaymentAsset::Native, Some(mkp_id), None, None, vec![]));
 
     }: _(alice.clone(), nft_id)
     verify {
@@ -94,19 +99,46 @@ benchmarks! {
         let bob: T::AccountId = get_account::<T>("BOB");
         let price: BalanceOf<T> = 0u32.into();
 
-        drop(Marketplace::<T>::list(get_origin::<T>("ALICE").into(), nft_id, price, Some(mkp_id)));
+        drop(Marketplace::<T>::list(get_origin::<T>("ALICE").into(), nft_id, price, PaymentAsset::Native, Some(mkp_id), None, None, vec![]));
     }: _(RawOrigin::Signed(bob.clone().into()), nft_id)
     verify {
         assert_eq!(T::NFTs::owner(nft_id), Some(bob));
         assert_eq!(NFTsForSale::<T>::contains_key(nft_id), false);
     }
 
+    set_price {
+        let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
+
+        let alice = get_origin::<T>("ALICE");
+        let price: BalanceOf<T> = 100u32.into();
+        let new_price: BalanceOf<T> = 200u32.into();
+        assert_ok!(Marketplace::<T>::list(alice.clone().into(), nft_id, price, PaymentAsset::Native, Some(mkp_id), None, None, vec![]));
+
+    }: _(alice.clone(), nft_id, new_price)
+    verify {
+        assert_eq!(NFTsForSale::<T>::get(nft_id).unwrap().price, new_price);
+    }
+
+    move_listing {
+        let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
+
+        let alice = get_origin::<T>("ALICE");
+        let price: BalanceOf<T> = 100u32.into();
+        assert_ok!(Marketplace::<T>::list(alice.clone().into(), nft_id, price, PaymentAsset::Native, Some(mkp_id), None, None, vec![]));
+        assert_ok!(Marketplace::<T>::create(alice.clone().into(), MarketplaceType::Public, Default::default(), vec![52], None, None, None));
+        let new_mkp_id = Marketplace::<T>::marketplace_id_generator();
+
+    }: _(alice.clone(), nft_id, new_mkp_id)
+    verify {
+        assert_eq!(NFTsForSale::<T>::get(nft_id).unwrap().marketplace_id, new_mkp_id);
+    }
+
     create {
         prepare_benchmarks::<T>();
 
         let alice: T::AccountId = get_account::<T>("ALICE");
         let mkp_id = Marketplace::<T>::marketplace_id_generator() + 1;
-    }: _(RawOrigin::Signed(alice.clone().into()), MarketplaceType::Public, 0, "Hop".into(), None, None, None)
+    }: _(RawOrigin::Signed(alice.clone().into()), MarketplaceType::Public, Default::default(), "Hop".into(), None, None, None)
     verify {
         assert_eq!(Marketplaces::<T>::contains_key(mkp_id), true);
         assert_eq!(Marketplaces::<T>::get(mkp_id).unwrap().owner, alice);
@@ -121,7 +153,7 @@ benchmarks! {
 
     }: _(get_origin::<T>("ALICE"), mkp_id, bob_lookup.into())
     verify {
-        assert_eq!(Marketplaces::<T>::get(mkp_id).unwrap().allow_list, vec![bob]);
+        assert!(AllowList::<T>::contains_key(mkp_id, bob));
     }
 
     remove_account_from_allow_list {
@@ -134,7 +166,7 @@ benchmarks! {
 
     }: _(alice.clone(), mkp_id, bob_lookup)
     verify {
-        assert_eq!(Marketplaces::<T>::get(mkp_id).unwrap().allow_list, vec![]);
+        assert!(!AllowList::<T>::contains_key(mkp_id, bob));
     }
 
     set_owner {
@@ -180,19 +212,28 @@ benchmarks! {
     set_commission_fee {
         let (mkp_id, ..) = prepare_benchmarks::<T>();
 
-        let commission_fee = 67;
+        let commission_fee = MarketplaceCommission::new(Permill::from_percent(67), 10u32.into());
     }: _(get_origin::<T>("ALICE"), mkp_id, commission_fee)
     verify {
         assert_eq!(Marketplaces::<T>::get(mkp_id).unwrap().commission_fee, commission_fee);
     }
 
+    set_accepted_assets {
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+
+        let assets: Vec<T::AssetId> = (0..T::MaxAcceptedAssets::get()).map(|x| x.into()).collect();
+    }: _(get_origin::<T>("ALICE"), mkp_id, assets.clone())
+    verify {
+        assert_eq!(AcceptedAssets::<T>::get(mkp_id).into_inner(), assets);
+    }
+
     set_uri {
         let (mkp_id, ..) = prepare_benchmarks::<T>();
 
         let uri: TextFormat = "test".as_bytes().to_vec();
     }: _(get_origin::<T>("ALICE"), mkp_id, uri.clone())
     verify {
-        assert_eq!(Marketplaces::<T>::get(mkp_id).unwrap().uri, Some(uri));
+        assert_eq!(Marketplaces::<T>::get(mkp_id).unwrap().uri.map(|x| x.into_inner()), Some(uri));
     }
 
     set_logo_uri {
@@ -201,7 +242,7 @@ benchmarks! {
         let uri: TextFormat = "test".as_bytes().to_vec();
     }: _(get_origin::<T>("ALICE"), mkp_id, uri.clone())
     verify {
-        assert_eq!(Marketplaces::<T>::get(mkp_id).unwrap().logo_uri, Some(uri));
+        assert_eq!(Marketplaces::<T>::get(mkp_id).unwrap().logo_uri.map(|x| x.into_inner()), Some(uri));
     }
 
     add_account_to_disallow_list {
@@ -212,7 +253,7 @@ benchmarks! {
 
     }: _(get_origin::<T>("ALICE"), mkp_id, bob_lookup.into())
     verify {
-        assert_eq!(Marketplaces::<T>::get(mkp_id).unwrap().disallow_list, vec![bob]);
+        assert!(DisallowList::<T>::contains_key(mkp_id, bob));
     }
 
     remove_account_from_disallow_list {
@@ -226,7 +267,358 @@ benchmarks! {
 
     }: _(alice.clone(), 1, bob_lookup.into())
     verify {
-        assert_eq!(Marketplaces::<T>::get(mkp_id).unwrap().disallow_list, vec![]);
+        assert!(!DisallowList::<T>::contains_key(mkp_id, bob));
+    }
+
+    add_accounts_to_allow_list {
+        let s in 1 .. T::MaxAccountsPerCall::get();
+
+        let (_, mkp_id, _) = prepare_benchmarks::<T>();
+        let accounts: Vec<T::AccountId> = (0..s).map(|i| benchmark_account("BOB", i, 0)).collect();
+        let lookups = accounts.iter().cloned().map(T::Lookup::unlookup).collect();
+
+    }: _(get_origin::<T>("ALICE"), mkp_id, lookups)
+    verify {
+        assert_eq!(Marketplace::<T>::allow_list(mkp_id, None, s).len(), s as usize);
+    }
+
+    remove_accounts_from_allow_list {
+        let s in 1 .. T::MaxAccountsPerCall::get();
+
+        let (_, mkp_id, _) = prepare_benchmarks::<T>();
+        let accounts: Vec<T::AccountId> = (0..s).map(|i| benchmark_account("BOB", i, 0)).collect();
+        let lookups: Vec<_> = accounts.iter().cloned().map(T::Lookup::unlookup).collect();
+        let alice = get_origin::<T>("ALICE");
+        assert_ok!(Marketplace::<T>::add_accounts_to_allow_list(alice.clone().into(), mkp_id, lookups.clone()));
+
+    }: _(alice, mkp_id, lookups)
+    verify {
+        assert!(Marketplace::<T>::allow_list(mkp_id, None, s).is_empty());
+    }
+
+    add_accounts_to_disallow_list {
+        let s in 1 .. T::MaxAccountsPerCall::get();
+
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+        let accounts: Vec<T::AccountId> = (0..s).map(|i| benchmark_account("BOB", i, 0)).collect();
+        let lookups = accounts.iter().cloned().map(T::Lookup::unlookup).collect();
+
+    }: _(get_origin::<T>("ALICE"), mkp_id, lookups)
+    verify {
+        assert_eq!(Marketplace::<T>::disallow_list(mkp_id, None, s).len(), s as usize);
+    }
+
+    remove_accounts_from_disallow_list {
+        let s in 1 .. T::MaxAccountsPerCall::get();
+
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+        let accounts: Vec<T::AccountId> = (0..s).map(|i| benchmark_account("BOB", i, 0)).collect();
+        let lookups: Vec<_> = accounts.iter().cloned().map(T::Lookup::unlookup).collect();
+        let alice = get_origin::<T>("ALICE");
+        assert_ok!(Marketplace::<T>::add_accounts_to_disallow_list(alice.clone().into(), mkp_id, lookups.clone()));
+
+    }: _(alice, mkp_id, lookups)
+    verify {
+        assert!(Marketplace::<T>::disallow_list(mkp_id, None, s).is_empty());
+    }
+
+    make_offer {
+        let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let price: BalanceOf<T> = 100u32.into();
+        let expiration: T::BlockNumber = 10u32.into();
+        let expiring: Vec<_> = (1..T::MaxOffersPerBlock::get()).map(|i| (OfferTarget::NFT(NFTId::MAX - i), bob.clone())).collect();
+        OfferExpirations::<T>::insert(expiration, BoundedVec::try_from(expiring).unwrap());
+
+    }: _(RawOrigin::Signed(bob.clone()), nft_id, price, Some(mkp_id), expiration)
+    verify {
+        assert!(Offers::<T>::contains_key(OfferTarget::NFT(nft_id), bob));
+    }
+
+    withdraw_offer {
+        let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let price: BalanceOf<T> = 100u32.into();
+        let expiration: T::BlockNumber = 10u32.into();
+        assert_ok!(Marketplace::<T>::make_offer(get_origin::<T>("BOB").into(), nft_id, price, Some(mkp_id), expiration));
+
+    }: _(RawOrigin::Signed(bob.clone()), nft_id)
+    verify {
+        assert!(!Offers::<T>::contains_key(OfferTarget::NFT(nft_id), bob));
+    }
+
+    accept_offer {
+        let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
+        let price: BalanceOf<T> = 100u32.into();
+        let expiration: T::BlockNumber = 10u32.into();
+        assert_ok!(Marketplace::<T>::list(get_origin::<T>("ALICE").into(), nft_id, price, PaymentAsset::Native, Some(mkp_id), None, None, vec![]));
+        assert_ok!(Marketplace::<T>::make_offer(get_origin::<T>("BOB").into(), nft_id, price, Some(mkp_id), expiration));
+
+    }: _(get_origin::<T>("ALICE"), nft_id, bob_lookup)
+    verify {
+        assert_eq!(T::NFTs::owner(nft_id), Some(bob));
+        assert_eq!(NFTsForSale::<T>::contains_key(nft_id), false);
+    }
+
+    expire_offer {
+        let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let price: BalanceOf<T> = 100u32.into();
+        let expiration: T::BlockNumber = 10u32.into();
+        assert_ok!(Marketplace::<T>::make_offer(get_origin::<T>("BOB").into(), nft_id, price, Some(mkp_id), expiration));
+
+    }: { Marketplace::<T>::on_initialize(expiration); }
+    verify {
+        assert!(!Offers::<T>::contains_key(OfferTarget::NFT(nft_id), bob));
+    }
+
+    expire_listing {
+        let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
+
+        let price: BalanceOf<T> = 100u32.into();
+        let end_block: T::BlockNumber = 10u32.into();
+        assert_ok!(Marketplace::<T>::list(get_origin::<T>("ALICE").into(), nft_id, price, PaymentAsset::Native, Some(mkp_id), None, Some(end_block), vec![]));
+
+    }: { Marketplace::<T>::on_initialize(end_block); }
+    verify {
+        assert_eq!(NFTsForSale::<T>::contains_key(nft_id), false);
+        assert_eq!(T::NFTs::is_locked(nft_id, NFTLockReason::ListedForSale), Some(false));
+    }
+
+    grant_role {
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
+
+    }: _(get_origin::<T>("ALICE"), mkp_id, bob_lookup, MarketplaceRole::Admin)
+    verify {
+        assert_eq!(MarketplaceRoles::<T>::get(mkp_id, bob), Some(MarketplaceRole::Admin));
+    }
+
+    revoke_role {
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
+        let alice = get_origin::<T>("ALICE");
+        assert_ok!(Marketplace::<T>::grant_role(alice.clone().into(), mkp_id, bob_lookup.clone(), MarketplaceRole::Admin));
+
+    }: _(alice, mkp_id, bob_lookup)
+    verify {
+        assert_eq!(MarketplaceRoles::<T>::get(mkp_id, bob), None);
+    }
+
+    take_down_listing {
+        let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
+        let price: BalanceOf<T> = 100u32.into();
+        let alice = get_origin::<T>("ALICE");
+        assert_ok!(Marketplace::<T>::grant_role(alice.clone().into(), mkp_id, bob_lookup, MarketplaceRole::Curator));
+        assert_ok!(Marketplace::<T>::list(alice.into(), nft_id, price, PaymentAsset::Native, Some(mkp_id), None, None, vec![]));
+
+    }: _(RawOrigin::Signed(bob), nft_id)
+    verify {
+        assert_eq!(NFTsForSale::<T>::contains_key(nft_id), false);
+        assert_eq!(T::NFTs::is_locked(nft_id, NFTLockReason::ListedForSale), Some(false));
+    }
+
+    close_marketplace {
+        let s in 1 .. 100;
+
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+
+        let alice = get_origin::<T>("ALICE");
+        let price: BalanceOf<T> = 100u32.into();
+        let series_id = vec![SERIES_ID + 1];
+        let nft_ids: Vec<NFTId> = (0..s)
+            .map(|_| T::NFTs::create_nft(get_account::<T>("ALICE"), vec![1], Some(series_id.clone())).unwrap())
+            .collect();
+        T::NFTs::benchmark_lock_series(series_id);
+        for nft_id in &nft_ids {
+            assert_ok!(Marketplace::<T>::list(alice.clone().into(), *nft_id, price, PaymentAsset::Native, Some(mkp_id), None, None, vec![]));
+        }
+
+    }: _(RawOrigin::Root, mkp_id, s + 1)
+    verify {
+        assert_eq!(Marketplaces::<T>::contains_key(mkp_id), false);
+        assert!(nft_ids.iter().all(|x| !NFTsForSale::<T>::contains_key(x)));
+    }
+
+    list_bundle {
+        let s in 1 .. T::MaxBundleSize::get();
+
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+
+        let price: BalanceOf<T> = 100u32.into();
+        let series_id = vec![SERIES_ID + 1];
+        let nft_ids: Vec<NFTId> = (0..s)
+            .map(|_| T::NFTs::create_nft(get_account::<T>("ALICE"), vec![1], Some(series_id.clone())).unwrap())
+            .collect();
+        T::NFTs::benchmark_lock_series(series_id);
+
+    }: _(get_origin::<T>("ALICE"), nft_ids.clone(), price, PaymentAsset::Native, Some(mkp_id))
+    verify {
+        let bundle_id = Marketplace::<T>::bundle_id_generator();
+        assert_eq!(Bundles::<T>::contains_key(bundle_id), true);
+        assert!(nft_ids.iter().all(|x| NFTBundles::<T>::get(x) == Some(bundle_id)));
+    }
+
+    unlist_bundle {
+        let s in 1 .. T::MaxBundleSize::get();
+
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+
+        let price: BalanceOf<T> = 100u32.into();
+        let series_id = vec![SERIES_ID + 1];
+        let nft_ids: Vec<NFTId> = (0..s)
+            .map(|_| T::NFTs::create_nft(get_account::<T>("ALICE"), vec![1], Some(series_id.clone())).unwrap())
+            .collect();
+        T::NFTs::benchmark_lock_series(series_id);
+        assert_ok!(Marketplace::<T>::list_bundle(get_origin::<T>("ALICE").into(), nft_ids.clone(), price, PaymentAsset::Native, Some(mkp_id)));
+        let bundle_id = Marketplace::<T>::bundle_id_generator();
+
+    }: _(get_origin::<T>("ALICE"), bundle_id)
+    verify {
+        assert_eq!(Bundles::<T>::contains_key(bundle_id), false);
+        assert!(nft_ids.iter().all(|x| !NFTBundles::<T>::contains_key(x)));
+    }
+
+    buy_bundle {
+        let s in 1 .. T::MaxBundleSize::get();
+
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let price: BalanceOf<T> = 100u32.into();
+        let series_id = vec![SERIES_ID + 1];
+        let nft_ids: Vec<NFTId> = (0..s)
+            .map(|_| T::NFTs::create_nft(get_account::<T>("ALICE"), vec![1], Some(series_id.clone())).unwrap())
+            .collect();
+        T::NFTs::benchmark_lock_series(series_id);
+        assert_ok!(Marketplace::<T>::list_bundle(get_origin::<T>("ALICE").into(), nft_ids.clone(), price, PaymentAsset::Native, Some(mkp_id)));
+        let bundle_id = Marketplace::<T>::bundle_id_generator();
+
+    }: _(RawOrigin::Signed(bob.clone().into()), bundle_id)
+    verify {
+        assert_eq!(Bundles::<T>::contains_key(bundle_id), false);
+        assert!(nft_ids.iter().all(|x| T::NFTs::owner(*x) == Some(bob.clone())));
+    }
+
+    set_bundle_price {
+        let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
+
+        let alice = get_origin::<T>("ALICE");
+        let price: BalanceOf<T> = 100u32.into();
+        let new_price: BalanceOf<T> = 200u32.into();
+        assert_ok!(Marketplace::<T>::list_bundle(alice.clone().into(), vec![nft_id], price, PaymentAsset::Native, Some(mkp_id)));
+        let bundle_id = Marketplace::<T>::bundle_id_generator();
+
+    }: _(alice.clone(), bundle_id, new_price)
+    verify {
+        assert_eq!(Bundles::<T>::get(bundle_id).unwrap().price, new_price);
+    }
+
+    move_bundle {
+        let (mkp_id, _, nft_id) = prepare_benchmarks::<T>();
+
+        let alice = get_origin::<T>("ALICE");
+        let price: BalanceOf<T> = 100u32.into();
+        assert_ok!(Marketplace::<T>::list_bundle(alice.clone().into(), vec![nft_id], price, PaymentAsset::Native, Some(mkp_id)));
+        let bundle_id = Marketplace::<T>::bundle_id_generator();
+        assert_ok!(Marketplace::<T>::create(alice.clone().into(), MarketplaceType::Public, Default::default(), vec![52], None, None, None));
+        let new_mkp_id = Marketplace::<T>::marketplace_id_generator();
+
+    }: _(alice.clone(), bundle_id, new_mkp_id)
+    verify {
+        assert_eq!(Bundles::<T>::get(bundle_id).unwrap().marketplace_id, new_mkp_id);
+    }
+
+    take_down_bundle {
+        let s in 1 .. T::MaxBundleSize::get();
+
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
+        let alice = get_origin::<T>("ALICE");
+        let price: BalanceOf<T> = 100u32.into();
+        let series_id = vec![SERIES_ID + 1];
+        let nft_ids: Vec<NFTId> = (0..s)
+            .map(|_| T::NFTs::create_nft(get_account::<T>("ALICE"), vec![1], Some(series_id.clone())).unwrap())
+            .collect();
+        T::NFTs::benchmark_lock_series(series_id);
+        assert_ok!(Marketplace::<T>::grant_role(alice.clone().into(), mkp_id, bob_lookup, MarketplaceRole::Curator));
+        assert_ok!(Marketplace::<T>::list_bundle(alice.into(), nft_ids.clone(), price, PaymentAsset::Native, Some(mkp_id)));
+        let bundle_id = Marketplace::<T>::bundle_id_generator();
+
+    }: _(RawOrigin::Signed(bob), bundle_id)
+    verify {
+        assert_eq!(Bundles::<T>::contains_key(bundle_id), false);
+        assert!(nft_ids.iter().all(|x| T::NFTs::is_locked(*x, NFTLockReason::ListedForSale) == Some(false)));
+    }
+
+    fill_order {
+        let (mkp_id, ..) = prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let series_id = vec![SERIES_ID + 1];
+        let nft_id = T::NFTs::create_nft(get_account::<T>("ALICE"), vec![1], Some(series_id.clone())).unwrap();
+        T::NFTs::benchmark_lock_series(series_id);
+
+        let order = Order { nft_id, price: 100u32.into(), marketplace_id: mkp_id, max_commission: Marketplace::<T>::marketplaces(mkp_id).unwrap().commission_fee, expiry: 100u32.into(), nonce: 0 };
+        let (seller, signature) = T::BenchmarkHelper::sign(&order.signing_payload(&frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero())));
+        T::NFTs::set_owner(nft_id, &seller).unwrap();
+        T::Currency::make_free_balance_be(&seller, BalanceOf::<T>::max_value() / 2u32.into());
+        let seller_lookup = T::Lookup::unlookup(seller.clone());
+
+    }: _(RawOrigin::Signed(bob.clone().into()), seller_lookup, order, signature)
+    verify {
+        assert_eq!(T::NFTs::owner(nft_id), Some(bob));
+        assert_eq!(UsedOrderNonces::<T>::contains_key(&seller, 0), true);
+    }
+
+    cancel_order {
+        let alice: T::AccountId = get_account::<T>("ALICE");
+    }: _(RawOrigin::Signed(alice.clone()), 0)
+    verify {
+        assert_eq!(UsedOrderNonces::<T>::contains_key(&alice, 0), true);
+    }
+
+    cancel_orders {
+        let alice: T::AccountId = get_account::<T>("ALICE");
+    }: _(RawOrigin::Signed(alice.clone()), 10)
+    verify {
+        assert_eq!(OrderNonces::<T>::get(&alice), 10);
+    }
+
+    redeem_voucher {
+        prepare_benchmarks::<T>();
+
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let voucher = MintVoucher {
+            ipfs_reference: BoundedVec::try_from(vec![1]).unwrap(),
+            series_id: None,
+            price: 100u32.into(),
+            royalty: Permill::from_percent(10),
+            expiry: 100u32.into(),
+            nonce: 0,
+        };
+        let (creator, signature) = T::BenchmarkHelper::sign(&voucher.signing_payload(&frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero())));
+        T::Currency::make_free_balance_be(&creator, BalanceOf::<T>::max_value() / 2u32.into());
+        let creator_lookup = T::Lookup::unlookup(creator.clone());
+
+    }: _(RawOrigin::Signed(bob.clone().into()), creator_lookup, voucher, signature)
+    verify {
+        assert_eq!(UsedOrderNonces::<T>::contains_key(&creator, 0), true);
     }
 }
 
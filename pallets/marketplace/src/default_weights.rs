@@ -4,6 +4,8 @@ pub trait WeightInfo {
     fn list() -> Weight;
     fn unlist() -> Weight;
     fn buy() -> Weight;
+    fn set_price() -> Weight;
+    fn move_listing() -> Weight;
     fn create() -> Weight;
     fn add_account_to_allow_list() -> Weight;
     fn remove_account_from_allow_list() -> Weight;
@@ -12,10 +14,34 @@ pub trait WeightInfo {
     fn set_name() -> Weight;
     fn set_marketplace_mint_fee() -> Weight;
     fn set_commission_fee() -> Weight;
+    fn set_accepted_assets() -> Weight;
     fn set_uri() -> Weight;
     fn set_logo_uri() -> Weight;
     fn add_account_to_disallow_list() -> Weight;
     fn remove_account_from_disallow_list() -> Weight;
+    fn add_accounts_to_allow_list(s: u32) -> Weight;
+    fn remove_accounts_from_allow_list(s: u32) -> Weight;
+    fn add_accounts_to_disallow_list(s: u32) -> Weight;
+    fn remove_accounts_from_disallow_list(s: u32) -> Weight;
+    fn make_offer() -> Weight;
+    fn withdraw_offer() -> Weight;
+    fn accept_offer() -> Weight;
+    fn expire_offer() -> Weight;
+    fn expire_listing() -> Weight;
+    fn grant_role() -> Weight;
+    fn revoke_role() -> Weight;
+    fn take_down_listing() -> Weight;
+    fn close_marketplace(s: u32) -> Weight;
+    fn list_bundle(s: u32) -> Weight;
+    fn unlist_bundle(s: u32) -> Weight;
+    fn buy_bundle(s: u32) -> Weight;
+    fn set_bundle_price() -> Weight;
+    fn move_bundle() -> Weight;
+    fn take_down_bundle(s: u32) -> Weight;
+    fn fill_order() -> Weight;
+    fn cancel_order() -> Weight;
+    fn cancel_orders() -> Weight;
+    fn redeem_voucher() -> Weight;
 }
 
 impl WeightInfo for () {
@@ -23,10 +49,12 @@ impl WeightInfo for () {
     // Storage: Nfts Series (r:1 w:0)
     // Storage: Capsules Capsules (r:1 w:0)
     // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace DisallowList (r:1 w:0)
+    // Storage: Marketplace AcceptedAssets (r:1 w:0)
     // Storage: Marketplace NFTsForSale (r:0 w:1)
     fn list() -> Weight {
-        (51_580_000 as Weight)
-            .saturating_add(DbWeight::get().reads(4 as Weight))
+        (53_120_000 as Weight)
+            .saturating_add(DbWeight::get().reads(6 as Weight))
             .saturating_add(DbWeight::get().writes(2 as Weight))
     }
     // Storage: Nfts Data (r:1 w:1)
@@ -44,6 +72,20 @@ impl WeightInfo for () {
             .saturating_add(DbWeight::get().reads(3 as Weight))
             .saturating_add(DbWeight::get().writes(2 as Weight))
     }
+    // Storage: Marketplace NFTsForSale (r:1 w:1)
+    fn set_price() -> Weight {
+        (22_410_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Marketplace NFTsForSale (r:1 w:1)
+    // Storage: Marketplace Marketplaces (r:2 w:0)
+    // Storage: Marketplace DisallowList (r:1 w:0)
+    fn move_listing() -> Weight {
+        (33_400_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
     // Storage: Marketplace MarketplaceMintFee (r:1 w:0)
     // Storage: System Account (r:1 w:1)
     // Storage: Marketplace MarketplaceIdGenerator (r:1 w:1)
@@ -53,16 +95,20 @@ impl WeightInfo for () {
             .saturating_add(DbWeight::get().reads(3 as Weight))
             .saturating_add(DbWeight::get().writes(3 as Weight))
     }
-    // Storage: Marketplace Marketplaces (r:1 w:1)
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
+    // Storage: Marketplace AllowList (r:0 w:1)
     fn add_account_to_allow_list() -> Weight {
         (27_150_000 as Weight)
-            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
-    // Storage: Marketplace Marketplaces (r:1 w:1)
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
+    // Storage: Marketplace AllowList (r:1 w:1)
     fn remove_account_from_allow_list() -> Weight {
         (25_770_000 as Weight)
-            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads(3 as Weight))
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
     // Storage: Marketplace Marketplaces (r:1 w:1)
@@ -78,9 +124,10 @@ impl WeightInfo for () {
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
     // Storage: Marketplace Marketplaces (r:1 w:1)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
     fn set_name() -> Weight {
         (26_481_000 as Weight)
-            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
     // Storage: Marketplace MarketplaceMintFee (r:0 w:1)
@@ -93,28 +140,282 @@ impl WeightInfo for () {
             .saturating_add(DbWeight::get().reads(1 as Weight))
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace AcceptedAssets (r:0 w:1)
+    fn set_accepted_assets() -> Weight {
+        (24_130_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
     // Storage: Marketplace Marketplaces (r:1 w:1)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
     fn set_uri() -> Weight {
         (26_270_000 as Weight)
-            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
     // Storage: Marketplace Marketplaces (r:1 w:1)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
     fn set_logo_uri() -> Weight {
         (26_501_000 as Weight)
-            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
-    // Storage: Marketplace Marketplaces (r:1 w:1)
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
+    // Storage: Marketplace DisallowList (r:0 w:1)
     fn add_account_to_disallow_list() -> Weight {
         (26_810_000 as Weight)
-            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
-    // Storage: Marketplace Marketplaces (r:1 w:1)
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
+    // Storage: Marketplace DisallowList (r:1 w:1)
     fn remove_account_from_disallow_list() -> Weight {
         (25_470_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nfts Data (r:1 w:0)
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace Offers (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Marketplace OfferExpirations (r:1 w:1)
+    fn make_offer() -> Weight {
+        (42_310_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Marketplace Offers (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Marketplace OfferExpirations (r:1 w:1)
+    fn withdraw_offer() -> Weight {
+        (35_120_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Nfts Series (r:1 w:0)
+    // Storage: Marketplace Offers (r:1 w:1)
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace NFTsForSale (r:0 w:1)
+    // Storage: Marketplace OfferExpirations (r:1 w:1)
+    // Storage: System Account (r:3 w:3)
+    // Storage: Nfts NftsByOwner (r:0 w:2)
+    fn accept_offer() -> Weight {
+        (78_430_000 as Weight)
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+    // Storage: Marketplace Offers (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    fn expire_offer() -> Weight {
+        (24_950_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Marketplace NFTsForSale (r:1 w:1)
+    // Storage: Nfts Data (r:1 w:1)
+    fn expire_listing() -> Weight {
+        (26_310_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
+    // Storage: Marketplace AllowList (r:0 w:1)
+    fn add_accounts_to_allow_list(s: u32) -> Weight {
+        (18_240_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((2_310_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+    }
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
+    // Storage: Marketplace AllowList (r:0 w:1)
+    fn remove_accounts_from_allow_list(s: u32) -> Weight {
+        (18_010_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((2_170_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+    }
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
+    // Storage: Marketplace DisallowList (r:0 w:1)
+    fn add_accounts_to_disallow_list(s: u32) -> Weight {
+        (18_190_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((2_300_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+    }
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
+    // Storage: Marketplace DisallowList (r:0 w:1)
+    fn remove_accounts_from_disallow_list(s: u32) -> Weight {
+        (17_960_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((2_160_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+    }
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:0 w:1)
+    fn grant_role() -> Weight {
+        (24_870_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:1)
+    fn revoke_role() -> Weight {
+        (25_640_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Marketplace NFTsForSale (r:1 w:1)
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
+    // Storage: Nfts Data (r:1 w:1)
+    fn take_down_listing() -> Weight {
+        (30_420_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Marketplace Marketplaces (r:1 w:1)
+    // Storage: Marketplace ClosingMarketplaces (r:1 w:1)
+    // Storage: Marketplace NFTsForSale (r:1 w:1)
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Marketplace AcceptedAssets (r:0 w:1)
+    fn close_marketplace(s: u32) -> Weight {
+        (31_250_000 as Weight)
+            // Standard Error: 4_000
+            .saturating_add((21_730_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+    }
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace ClosingMarketplaces (r:1 w:0)
+    // Storage: Marketplace DisallowList (r:1 w:0)
+    // Storage: Marketplace AcceptedAssets (r:1 w:0)
+    // Storage: Marketplace BundleIdGenerator (r:1 w:1)
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Nfts Series (r:1 w:0)
+    // Storage: Marketplace NFTBundles (r:0 w:1)
+    // Storage: Marketplace Bundles (r:0 w:1)
+    fn list_bundle(s: u32) -> Weight {
+        (30_840_000 as Weight)
+            // Standard Error: 3_000
+            .saturating_add((9_120_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+    }
+    // Storage: Marketplace Bundles (r:1 w:1)
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Marketplace NFTBundles (r:0 w:1)
+    fn unlist_bundle(s: u32) -> Weight {
+        (21_470_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((6_340_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+    }
+    // Storage: Marketplace Bundles (r:1 w:1)
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Marketplace NFTBundles (r:0 w:1)
+    // Storage: Nfts NftsByOwner (r:0 w:2)
+    fn buy_bundle(s: u32) -> Weight {
+        (52_610_000 as Weight)
+            // Standard Error: 5_000
+            .saturating_add((14_980_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+            .saturating_add(DbWeight::get().writes((4 as Weight).saturating_mul(s as Weight)))
+    }
+    // Storage: Marketplace Bundles (r:1 w:1)
+    fn set_bundle_price() -> Weight {
+        (22_830_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Marketplace Bundles (r:1 w:1)
+    // Storage: Marketplace Marketplaces (r:2 w:0)
+    // Storage: Marketplace DisallowList (r:1 w:0)
+    // Storage: Marketplace AcceptedAssets (r:1 w:0)
+    // Storage: Marketplace MarketplaceBundles (r:0 w:2)
+    fn move_bundle() -> Weight {
+        (35_120_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Marketplace Bundles (r:1 w:1)
+    // Storage: Marketplace MarketplaceRoles (r:1 w:0)
+    // Storage: Marketplace MarketplaceBundles (r:0 w:1)
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Marketplace NFTBundles (r:0 w:1)
+    fn take_down_bundle(s: u32) -> Weight {
+        (27_940_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((6_340_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+    }
+    // Storage: System BlockHash (r:1 w:0)
+    // Storage: Marketplace OrderNonces (r:1 w:0)
+    // Storage: Marketplace UsedOrderNonces (r:1 w:1)
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Nfts Series (r:1 w:0)
+    // Storage: Marketplace Marketplaces (r:1 w:0)
+    // Storage: Marketplace ClosingMarketplaces (r:1 w:0)
+    // Storage: Marketplace DisallowList (r:1 w:0)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Nfts NftsByOwner (r:0 w:2)
+    fn fill_order() -> Weight {
+        (96_270_000 as Weight)
+            .saturating_add(DbWeight::get().reads(10 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+    }
+    // Storage: Marketplace OrderNonces (r:1 w:0)
+    // Storage: Marketplace UsedOrderNonces (r:1 w:1)
+    fn cancel_order() -> Weight {
+        (17_840_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Marketplace OrderNonces (r:1 w:1)
+    fn cancel_orders() -> Weight {
+        (15_310_000 as Weight)
             .saturating_add(DbWeight::get().reads(1 as Weight))
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
+    // Storage: System BlockHash (r:1 w:0)
+    // Storage: Marketplace OrderNonces (r:1 w:0)
+    // Storage: Marketplace UsedOrderNonces (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
+    // Storage: Nfts NftMintFee (r:1 w:0)
+    // Storage: Nfts SeriesIdGenerator (r:1 w:1)
+    // Storage: Nfts NftIdGenerator (r:1 w:1)
+    // Storage: Nfts Series (r:2 w:2)
+    // Storage: Nfts Data (r:2 w:1)
+    // Storage: Nfts NftsByOwner (r:0 w:2)
+    // Storage: Nfts NftsBySeries (r:0 w:1)
+    fn redeem_voucher() -> Weight {
+        (109_310_000 as Weight)
+            .saturating_add(DbWeight::get().reads(12 as Weight))
+            .saturating_add(DbWeight::get().writes(12 as Weight))
+    }
 }
@@ -10,27 +10,39 @@ mod default_weights;
 mod migrations;
 mod types;
 
-use frame_support::dispatch::{DispatchErrorWithPostInfo, DispatchResult};
+use frame_support::dispatch::{DispatchError, DispatchErrorWithPostInfo, DispatchResult};
 pub use pallet::*;
 pub use types::*;
 
 use default_weights::WeightInfo;
 use frame_support::ensure;
 use frame_support::pallet_prelude::DispatchResultWithPostInfo;
+use frame_support::traits::tokens::fungibles;
 use frame_support::traits::{
-    Currency, ExistenceRequirement::KeepAlive, Get, OnUnbalanced, StorageVersion, WithdrawReasons,
+    Currency, ExistenceRequirement::KeepAlive, Get, OnUnbalanced, ReservableCurrency,
+    StorageVersion, WithdrawReasons,
 };
-// use frame_support::weights::Weight;
+use frame_support::weights::Weight;
 use frame_system::Origin;
+use sp_runtime::traits::{CheckedSub, IdentifyAccount, Saturating, StaticLookup, Verify, Zero};
+use sp_runtime::PerThing;
+use sp_std::convert::TryInto;
 use sp_std::vec::Vec;
-use ternoa_common::helpers::check_bounds;
-use ternoa_common::traits::MarketplaceTrait;
-use ternoa_primitives::marketplace::{MarketplaceId, MarketplaceInformation, MarketplaceType};
-use ternoa_primitives::nfts::NFTId;
+use ternoa_common::helpers::{
+    bounded_text, can_receive_payment, marketplace_commission, transfer_payment, U16ToU32,
+};
+use ternoa_common::traits::{
+    MarketplaceCloseHandler, MarketplaceTrait, NFTReleaseHandler, NFTTrait,
+};
+use ternoa_primitives::marketplace::{
+    MarketplaceCommission, MarketplaceId, MarketplaceInformation, MarketplaceRole, MarketplaceType,
+    MintVoucher, Order, OrderNonce, PaymentAsset,
+};
+use ternoa_primitives::nfts::{NFTId, NFTLockReason, NFTSeriesId};
 use ternoa_primitives::TextFormat;
 
 /// The current storage version.
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(9);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -38,14 +50,46 @@ pub mod pallet {
     use frame_support::pallet_prelude::*;
     use frame_support::transactional;
     use frame_system::pallet_prelude::*;
-    use sp_runtime::traits::{CheckedDiv, CheckedSub, StaticLookup};
-    use ternoa_common::traits::NFTTrait;
+    use sp_runtime::traits::MaybeSerializeDeserialize;
 
     pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
     pub(crate) type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
         <T as frame_system::Config>::AccountId,
     >>::NegativeImbalance;
+    pub type MarketplaceInformationOf<T> = MarketplaceInformation<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        U16ToU32<<T as Config>::MaxNameLen>,
+        U16ToU32<<T as Config>::MaxUriLen>,
+        U16ToU32<<T as Config>::MaxDescriptionLen>,
+    >;
+    pub type MarketplaceCommissionOf<T> = MarketplaceCommission<BalanceOf<T>>;
+    pub type SeriesIdOf<T> = BoundedVec<u8, <<T as Config>::NFTs as NFTTrait>::SeriesIdLengthLimit>;
+    pub type OfferTargetOf<T> = OfferTarget<SeriesIdOf<T>>;
+    pub type OfferOf<T> = Offer<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+    pub type SaleInformationOf<T> = SaleInformation<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+        <T as Config>::AssetId,
+    >;
+    pub type PaymentAssetOf<T> = PaymentAsset<<T as Config>::AssetId>;
+    pub type IndexKey = [u8; sp_std::mem::size_of::<NFTId>()];
+    pub type OrderOf<T> = Order<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+    pub type MintVoucherOf<T> = MintVoucher<
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+        <<T as Config>::NFTs as NFTTrait>::IPFSLengthLimit,
+    >;
+    pub type BundleInformationOf<T> = BundleInformation<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        <T as Config>::AssetId,
+        <T as Config>::MaxBundleSize,
+    >;
+    pub type AcceptedAssetsOf<T> =
+        BoundedVec<<T as Config>::AssetId, <T as Config>::MaxAcceptedAssets>;
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -53,17 +97,30 @@ pub mod pallet {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
         /// Pallet managing nfts.
-        type NFTs: NFTTrait<AccountId = Self::AccountId>;
+        type NFTs: NFTTrait<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
+
+        /// Pallets that settle what they host on a marketplace when it closes.
+        type MarketplaceCloseHandler: MarketplaceCloseHandler;
 
         /// Weight values for this pallet
         type WeightInfo: WeightInfo;
 
         /// Caps Currency
-        type Currency: Currency<Self::AccountId>;
+        type Currency: ReservableCurrency<Self::AccountId>;
 
         /// Place where the marketplace fees go.
         type FeesCollector: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+        /// Id of the assets that sales can be paid with besides Caps.
+        type AssetId: Member + Parameter + Copy + MaxEncodedLen + MaybeSerializeDeserialize;
+
+        /// Pallet managing the assets that sales can be paid with besides Caps.
+        type Assets: fungibles::Transfer<
+            Self::AccountId,
+            AssetId = Self::AssetId,
+            Balance = BalanceOf<Self>,
+        >;
+
         /// Min name length.
         #[pallet::constant]
         type MinNameLen: Get<u16>;
@@ -87,63 +144,160 @@ pub mod pallet {
         /// Max uri length.
         #[pallet::constant]
         type MaxUriLen: Get<u16>;
+
+        /// Max number of accounts that can be added to or removed from an allow list or a
+        /// disallow list at once.
+        #[pallet::constant]
+        type MaxAccountsPerCall: Get<u32>;
+
+        /// Max number of listings that end at the same block.
+        #[pallet::constant]
+        type MaxListingsPerBlock: Get<u32>;
+
+        /// Max number of offers that expire at the same block.
+        #[pallet::constant]
+        type MaxOffersPerBlock: Get<u32>;
+
+        /// Max number of assets besides Caps that a marketplace accepts.
+        #[pallet::constant]
+        type MaxAcceptedAssets: Get<u32>;
+
+        /// Max number of NFTs in a bundle.
+        #[pallet::constant]
+        type MaxBundleSize: Get<u32>;
+
+        /// Signature of the orders that sellers sign off-chain.
+        type OrderSignature: Verify<Signer = Self::OrderSigner> + Parameter;
+
+        /// Key that signs orders, identifying the account of the seller.
+        type OrderSigner: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// Signs orders in benchmarks.
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::OrderSignature>;
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        /*         fn on_runtime_upgrade() -> frame_support::weights::Weight {
+        fn on_runtime_upgrade() -> frame_support::weights::Weight {
             migrations::migrate::<T>()
-        } */
+        }
+
+        /// Give the funds of the offers that expire at this block back to the buyers
+        /// and unlist the NFTs whose listing has ended.
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let expired = OfferExpirations::<T>::take(now);
+
+            for (target, buyer) in expired.iter() {
+                if let Some(offer) = Offers::<T>::take(target, buyer) {
+                    T::Currency::unreserve(buyer, offer.price);
+                    let event = Event::OfferExpired {
+                        target: target.clone(),
+                        buyer: buyer.clone(),
+                    };
+                    Self::deposit_event(event);
+                }
+            }
+
+            let ended = ListingDeadlines::<T>::take(now);
+
+            for nft_id in ended.iter().copied() {
+                if let Some(sale) = NFTsForSale::<T>::take(nft_id) {
+                    MarketplaceSales::<T>::remove(sale.marketplace_id, index_key(nft_id));
+                    let ok = T::NFTs::unlock(nft_id, NFTLockReason::ListedForSale);
+                    debug_assert_eq!(ok, Ok(()));
+                    Self::deposit_event(Event::NftUnlisted { nft_id });
+                }
+            }
+
+            let count = expired.len() as Weight;
+            let unlisted = ended.len() as Weight;
+            T::DbWeight::get()
+                .reads_writes(2, 2)
+                .saturating_add(T::WeightInfo::expire_offer().saturating_mul(count))
+                .saturating_add(T::WeightInfo::expire_listing().saturating_mul(unlisted))
+        }
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Deposit a nft and list it on the marketplace
+        /// Deposit a nft and list it on the marketplace for `price` paid in `asset`.
+        /// The NFT can only be bought from `start_block` on and is unlisted
+        /// automatically at `end_block`. If `buyers` is not empty, only those
+        /// accounts can buy it.
         #[pallet::weight(T::WeightInfo::list())]
+        #[transactional]
         pub fn list(
             origin: OriginFor<T>,
             nft_id: NFTId,
             price: BalanceOf<T>,
+            asset: PaymentAssetOf<T>,
             marketplace_id: Option<MarketplaceId>,
+            start_block: Option<T::BlockNumber>,
+            end_block: Option<T::BlockNumber>,
+            buyers: Vec<<T::Lookup as StaticLookup>::Source>,
         ) -> DispatchResultWithPostInfo {
             let account_id = ensure_signed(origin)?;
             let mkp_id = marketplace_id.unwrap_or(0);
 
+            let buyers = buyers.into_iter().map(T::Lookup::lookup);
+            let buyers = buyers.collect::<Result<Vec<_>, _>>()?;
+            let buyers: BoundedVec<T::AccountId, MaxBuyers> =
+                buyers.try_into().map_err(|_| Error::<T>::TooManyBuyers)?;
+
+            if let Some(end) = end_block {
+                let now = frame_system::Pallet::<T>::block_number();
+                ensure!(end > now, Error::<T>::ListingEndIsInThePast);
+                if let Some(start) = start_block {
+                    ensure!(start < end, Error::<T>::ListingCannotEndBeforeItStarts);
+                }
+            }
+
             let nft = T::NFTs::get_nft(nft_id).ok_or(Error::<T>::UnknownNFT)?;
             ensure!(nft.owner == account_id, Error::<T>::NotNftOwner);
-            ensure!(!nft.converted_to_capsule, Error::<T>::CannotListCapsules);
-            ensure!(!nft.listed_for_sale, Error::<T>::AlreadyListedForSale);
-            ensure!(nft.viewer.is_none(), Error::<T>::CannotListLentNFTs);
 
             let is_nft_in_completed_series =
                 T::NFTs::is_nft_in_completed_series(nft_id) == Some(true);
             ensure!(is_nft_in_completed_series, Error::<T>::SeriesNotCompleted);
 
             let market = Marketplaces::<T>::get(mkp_id).ok_or(Error::<T>::UnknownMarketplace)?;
+            Self::is_allowed_to_list(mkp_id, account_id.clone())?;
+            Self::is_asset_accepted(mkp_id, &asset)?;
 
-            if market.kind == MarketplaceType::Private {
-                let is_on_list = market.allow_list.contains(&account_id);
-                ensure!(is_on_list, Error::<T>::NotAllowedToList);
-            } else {
-                let is_on_list = market.disallow_list.contains(&account_id);
-                ensure!(!is_on_list, Error::<T>::NotAllowedToList);
-            }
+            T::NFTs::lock(nft_id, NFTLockReason::ListedForSale)?;
 
-            T::NFTs::set_listed_for_sale(nft_id, true)?;
+            if let Some(end) = end_block {
+                ListingDeadlines::<T>::try_mutate(end, |x| {
+                    x.try_push(nft_id)
+                        .map_err(|_| Error::<T>::TooManyListingsAtBlock)
+                })?;
+            }
 
-            let sale_info = SaleInformation::new(account_id, price.clone(), mkp_id);
+            let sale_info = SaleInformation::new(
+                account_id,
+                price.clone(),
+                asset,
+                mkp_id,
+                market.commission_fee,
+                start_block,
+                end_block,
+                buyers,
+            );
+            MarketplaceSales::<T>::insert(mkp_id, index_key(nft_id), sale_info.is_private());
             NFTsForSale::<T>::insert(nft_id, sale_info);
 
             Self::deposit_event(Event::NftListed {
                 nft_id,
                 price,
+                asset,
                 marketplace_id: mkp_id,
+                start_block,
+                end_block,
             });
 
             Ok(().into())
@@ -160,8 +314,8 @@ pub mod pallet {
                 Error::<T>::NftNotForSale
             );
 
-            T::NFTs::set_listed_for_sale(nft_id, false)?;
-            NFTsForSale::<T>::remove(nft_id);
+            T::NFTs::unlock(nft_id, NFTLockReason::ListedForSale)?;
+            Self::remove_sale(nft_id);
 
             Self::deposit_event(Event::NftUnlisted { nft_id });
 
@@ -176,53 +330,278 @@ pub mod pallet {
 
             let sale = NFTsForSale::<T>::get(nft_id).ok_or(Error::<T>::NftNotForSale)?;
             ensure!(sale.account_id != caller, Error::<T>::NftAlreadyOwned);
+            ensure!(sale.can_buy(&caller), Error::<T>::NotAllowedToBuy);
 
-            // Check if there is any commission fee.
-            let market = Marketplaces::<T>::get(sale.marketplace_id)
-                .ok_or(Error::<T>::UnknownMarketplace)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            if let Some(start) = sale.start_block {
+                ensure!(start <= now, Error::<T>::ListingNotStarted);
+            }
+            if let Some(end) = sale.end_block {
+                ensure!(now < end, Error::<T>::ListingExpired);
+            }
+
+            let (price, marketplace_cut, royalty_cut) = Self::pay_out(
+                &[nft_id],
+                &caller,
+                &sale.account_id,
+                sale.price,
+                &sale.asset,
+                sale.marketplace_id,
+                &sale.commission_fee,
+            )?;
+
+            T::NFTs::unlock(nft_id, NFTLockReason::ListedForSale)?;
+            T::NFTs::set_owner(nft_id, &caller)?;
+
+            Self::remove_sale(nft_id);
+
+            let event = Event::NftSold {
+                nft_id,
+                owner: caller,
+                seller_cut: price,
+                marketplace_cut,
+                royalty_cut,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Deposit several nfts and list them together on the marketplace for
+        /// `price` paid in `asset`. Each nft must be allowed to be listed on its own.
+        #[pallet::weight(T::WeightInfo::list_bundle(nft_ids.len() as u32))]
+        #[transactional]
+        pub fn list_bundle(
+            origin: OriginFor<T>,
+            nft_ids: Vec<NFTId>,
+            price: BalanceOf<T>,
+            asset: PaymentAssetOf<T>,
+            marketplace_id: Option<MarketplaceId>,
+        ) -> DispatchResultWithPostInfo {
+            let account_id = ensure_signed(origin)?;
+            let mkp_id = marketplace_id.unwrap_or(0);
+
+            ensure!(!nft_ids.is_empty(), Error::<T>::EmptyBundle);
+            let nft_ids: BoundedVec<NFTId, T::MaxBundleSize> = nft_ids
+                .try_into()
+                .map_err(|_| Error::<T>::BundleIsTooLarge)?;
 
-            let commission_fee = market.commission_fee;
-            let mut price = sale.price;
+            let market = Marketplaces::<T>::get(mkp_id).ok_or(Error::<T>::UnknownMarketplace)?;
+            Self::is_allowed_to_list(mkp_id, account_id.clone())?;
+            Self::is_asset_accepted(mkp_id, &asset)?;
 
-            // KeepAlive because they need to be able to use the NFT later on
-            if commission_fee != 0 {
-                let tmp = 100u8
-                    .checked_div(commission_fee)
-                    .ok_or(Error::<T>::InternalMathError)?;
+            let bundle_id = BundleIdGenerator::<T>::get();
+            let bundle_id = bundle_id
+                .checked_add(1)
+                .ok_or(Error::<T>::BundleIdOverflow)?;
 
-                let fee = price
-                    .checked_div(&(tmp.into()))
-                    .ok_or(Error::<T>::InternalMathError)?;
+            for nft_id in nft_ids.iter() {
+                let nft = T::NFTs::get_nft(*nft_id).ok_or(Error::<T>::UnknownNFT)?;
+                ensure!(nft.owner == account_id, Error::<T>::NotNftOwner);
 
-                price = price
-                    .checked_sub(&fee)
-                    .ok_or(Error::<T>::InternalMathError)?;
+                let is_nft_in_completed_series =
+                    T::NFTs::is_nft_in_completed_series(*nft_id) == Some(true);
+                ensure!(is_nft_in_completed_series, Error::<T>::SeriesNotCompleted);
 
-                T::Currency::transfer(&caller, &market.owner, fee, KeepAlive)?;
+                // Fails for nfts that are already locked, including duplicates
+                T::NFTs::lock(*nft_id, NFTLockReason::ListedForSale)?;
+                NFTBundles::<T>::insert(nft_id, bundle_id);
             }
 
-            T::Currency::transfer(&caller, &sale.account_id, price, KeepAlive)?;
+            let bundle = BundleInformation {
+                account_id,
+                nft_ids: nft_ids.clone(),
+                price,
+                asset,
+                marketplace_id: mkp_id,
+                commission_fee: market.commission_fee,
+            };
+            Bundles::<T>::insert(bundle_id, bundle);
+            MarketplaceBundles::<T>::insert(mkp_id, bundle_id, ());
+            BundleIdGenerator::<T>::set(bundle_id);
 
-            T::NFTs::set_listed_for_sale(nft_id, false)?;
-            T::NFTs::set_owner(nft_id, &caller)?;
+            Self::deposit_event(Event::BundleListed {
+                bundle_id,
+                nft_ids: nft_ids.into_inner(),
+                price,
+                asset,
+                marketplace_id: mkp_id,
+            });
 
-            NFTsForSale::<T>::remove(nft_id);
+            Ok(().into())
+        }
 
-            let event = Event::NftSold {
-                nft_id,
+        /// Take a bundle off the marketplace and give its nfts back to the seller.
+        #[pallet::weight(T::WeightInfo::unlist_bundle(T::MaxBundleSize::get()))]
+        pub fn unlist_bundle(
+            origin: OriginFor<T>,
+            bundle_id: BundleId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let bundle = Bundles::<T>::get(bundle_id).ok_or(Error::<T>::UnknownBundle)?;
+            ensure!(bundle.account_id == who, Error::<T>::NotNftOwner);
+
+            Self::remove_bundle(bundle_id)?;
+
+            Self::deposit_event(Event::BundleUnlisted { bundle_id });
+
+            Ok(().into())
+        }
+
+        /// Buy every nft of a bundle at once. The commission of the marketplace
+        /// applies once, on the price of the bundle.
+        #[pallet::weight(T::WeightInfo::buy_bundle(T::MaxBundleSize::get()))]
+        #[transactional]
+        pub fn buy_bundle(origin: OriginFor<T>, bundle_id: BundleId) -> DispatchResultWithPostInfo {
+            let caller = ensure_signed(origin)?;
+
+            let bundle = Bundles::<T>::get(bundle_id).ok_or(Error::<T>::UnknownBundle)?;
+            ensure!(bundle.account_id != caller, Error::<T>::NftAlreadyOwned);
+
+            let (price, marketplace_cut, royalty_cut) = Self::pay_out(
+                &bundle.nft_ids,
+                &caller,
+                &bundle.account_id,
+                bundle.price,
+                &bundle.asset,
+                bundle.marketplace_id,
+                &bundle.commission_fee,
+            )?;
+
+            Self::remove_bundle(bundle_id)?;
+            for nft_id in bundle.nft_ids.iter() {
+                T::NFTs::set_owner(*nft_id, &caller)?;
+            }
+
+            let event = Event::BundleSold {
+                bundle_id,
                 owner: caller,
+                seller_cut: price,
+                marketplace_cut,
+                royalty_cut,
             };
             Self::deposit_event(event);
 
             Ok(().into())
         }
 
+        /// Change the price of a listed nft.
+        #[pallet::weight(T::WeightInfo::set_price())]
+        pub fn set_price(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+            price: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            NFTsForSale::<T>::try_mutate(nft_id, |x| -> DispatchResult {
+                let sale = x.as_mut().ok_or(Error::<T>::NftNotForSale)?;
+                ensure!(sale.account_id == who, Error::<T>::NotNftOwner);
+                sale.price = price;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::NftPriceChanged { nft_id, price });
+
+            Ok(().into())
+        }
+
+        /// Move a listed nft to another marketplace. The listing takes the commission
+        /// of the new marketplace and keeps its price, its asset and its start and
+        /// end blocks. The new marketplace must accept the asset of the listing.
+        #[pallet::weight(T::WeightInfo::move_listing())]
+        pub fn move_listing(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+            marketplace_id: MarketplaceId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut sale = NFTsForSale::<T>::get(nft_id).ok_or(Error::<T>::NftNotForSale)?;
+            ensure!(sale.account_id == who, Error::<T>::NotNftOwner);
+
+            Self::is_allowed_to_list(marketplace_id, who)?;
+            Self::is_asset_accepted(marketplace_id, &sale.asset)?;
+            let market =
+                Marketplaces::<T>::get(marketplace_id).ok_or(Error::<T>::UnknownMarketplace)?;
+
+            MarketplaceSales::<T>::remove(sale.marketplace_id, index_key(nft_id));
+            MarketplaceSales::<T>::insert(marketplace_id, index_key(nft_id), sale.is_private());
+
+            sale.marketplace_id = marketplace_id;
+            sale.commission_fee = market.commission_fee;
+            NFTsForSale::<T>::insert(nft_id, sale);
+
+            Self::deposit_event(Event::NftListingMoved {
+                nft_id,
+                marketplace_id,
+            });
+
+            Ok(().into())
+        }
+
+        /// Change the price of a listed bundle.
+        #[pallet::weight(T::WeightInfo::set_bundle_price())]
+        pub fn set_bundle_price(
+            origin: OriginFor<T>,
+            bundle_id: BundleId,
+            price: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Bundles::<T>::try_mutate(bundle_id, |x| -> DispatchResult {
+                let bundle = x.as_mut().ok_or(Error::<T>::UnknownBundle)?;
+                ensure!(bundle.account_id == who, Error::<T>::NotNftOwner);
+                bundle.price = price;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::BundlePriceChanged { bundle_id, price });
+
+            Ok(().into())
+        }
+
+        /// Move a listed bundle to another marketplace. The bundle takes the
+        /// commission of the new marketplace and keeps its price and its asset.
+        /// The new marketplace must accept the asset of the bundle.
+        #[pallet::weight(T::WeightInfo::move_bundle())]
+        pub fn move_bundle(
+            origin: OriginFor<T>,
+            bundle_id: BundleId,
+            marketplace_id: MarketplaceId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut bundle = Bundles::<T>::get(bundle_id).ok_or(Error::<T>::UnknownBundle)?;
+            ensure!(bundle.account_id == who, Error::<T>::NotNftOwner);
+
+            Self::is_allowed_to_list(marketplace_id, who)?;
+            Self::is_asset_accepted(marketplace_id, &bundle.asset)?;
+            let market =
+                Marketplaces::<T>::get(marketplace_id).ok_or(Error::<T>::UnknownMarketplace)?;
+
+            MarketplaceBundles::<T>::remove(bundle.marketplace_id, bundle_id);
+            MarketplaceBundles::<T>::insert(marketplace_id, bundle_id, ());
+
+            bundle.marketplace_id = marketplace_id;
+            bundle.commission_fee = market.commission_fee;
+            Bundles::<T>::insert(bundle_id, bundle);
+
+            Self::deposit_event(Event::BundleMoved {
+                bundle_id,
+                marketplace_id,
+            });
+
+            Ok(().into())
+        }
+
         #[pallet::weight(T::WeightInfo::create())]
         #[transactional]
         pub fn create(
             origin: OriginFor<T>,
             kind: MarketplaceType,
-            commission_fee: u8,
+            commission_fee: MarketplaceCommissionOf<T>,
             name: TextFormat,
             uri: Option<TextFormat>,
             logo_uri: Option<TextFormat>,
@@ -230,36 +609,41 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let caller_id = ensure_signed(origin)?;
 
-            ensure!(commission_fee <= 100, Error::<T>::InvalidCommissionFeeValue);
-            check_bounds(
-                name.len(),
+            let name = bounded_text(
+                name,
                 (T::MinNameLen::get(), Error::<T>::TooShortMarketplaceName),
                 (T::MaxNameLen::get(), Error::<T>::TooLongMarketplaceName),
             )?;
 
-            if let Some(text) = uri.as_ref() {
-                check_bounds(
-                    text.len(),
-                    (T::MinUriLen::get(), Error::<T>::TooShortUri),
-                    (T::MaxUriLen::get(), Error::<T>::TooLongUri),
-                )?;
-            }
-
-            if let Some(text) = logo_uri.as_ref() {
-                check_bounds(
-                    text.len(),
-                    (T::MinUriLen::get(), Error::<T>::TooShortLogoUri),
-                    (T::MaxUriLen::get(), Error::<T>::TooLongLogoUri),
-                )?;
-            }
-
-            if let Some(text) = description.as_ref() {
-                check_bounds(
-                    text.len(),
-                    (T::MinDescriptionLen::get(), Error::<T>::TooShortDescription),
-                    (T::MaxDescriptionLen::get(), Error::<T>::TooLongDescription),
-                )?;
-            }
+            let uri = uri
+                .map(|text| {
+                    bounded_text(
+                        text,
+                        (T::MinUriLen::get(), Error::<T>::TooShortUri),
+                        (T::MaxUriLen::get(), Error::<T>::TooLongUri),
+                    )
+                })
+                .transpose()?;
+
+            let logo_uri = logo_uri
+                .map(|text| {
+                    bounded_text(
+                        text,
+                        (T::MinUriLen::get(), Error::<T>::TooShortLogoUri),
+                        (T::MaxUriLen::get(), Error::<T>::TooLongLogoUri),
+                    )
+                })
+                .transpose()?;
+
+            let description = description
+                .map(|text| {
+                    bounded_text(
+                        text,
+                        (T::MinDescriptionLen::get(), Error::<T>::TooShortDescription),
+                        (T::MaxDescriptionLen::get(), Error::<T>::TooLongDescription),
+                    )
+                })
+                .transpose()?;
 
             // Needs to have enough money
             let imbalance = T::Currency::withdraw(
@@ -274,8 +658,6 @@ pub mod pallet {
                 kind,
                 commission_fee,
                 caller_id.clone(),
-                Vec::default(),
-                Vec::default(),
                 name,
                 uri,
                 logo_uri,
@@ -303,21 +685,9 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let caller_id = ensure_signed(origin)?;
             let account_id = T::Lookup::lookup(account_id)?;
+            Self::ensure_list_admin(marketplace_id, &caller_id, MarketplaceType::Private)?;
 
-            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
-                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
-                ensure!(
-                    market_info.owner == caller_id,
-                    Error::<T>::NotMarketplaceOwner
-                );
-                ensure!(
-                    market_info.kind == MarketplaceType::Private,
-                    Error::<T>::UnsupportedMarketplace
-                );
-
-                market_info.allow_list.push(account_id.clone());
-                Ok(())
-            })?;
+            AllowList::<T>::insert(marketplace_id, &account_id, ());
 
             let event = Event::AccountAddedToAllowList {
                 marketplace_id,
@@ -336,23 +706,11 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let caller_id = ensure_signed(origin)?;
             let account_id = T::Lookup::lookup(account_id)?;
+            Self::ensure_list_admin(marketplace_id, &caller_id, MarketplaceType::Private)?;
 
-            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
-                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
-                ensure!(
-                    market_info.owner == caller_id,
-                    Error::<T>::NotMarketplaceOwner
-                );
-                ensure!(
-                    market_info.kind == MarketplaceType::Private,
-                    Error::<T>::UnsupportedMarketplace
-                );
-
-                let index = market_info.allow_list.iter().position(|x| *x == account_id);
-                let index = index.ok_or(Error::<T>::AccountNotFound)?;
-                market_info.allow_list.swap_remove(index);
-                Ok(())
-            })?;
+            let is_on_list = AllowList::<T>::contains_key(marketplace_id, &account_id);
+            ensure!(is_on_list, Error::<T>::AccountNotFound);
+            AllowList::<T>::remove(marketplace_id, &account_id);
 
             let event = Event::AccountRemovedFromAllowList {
                 marketplace_id,
@@ -371,21 +729,9 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let caller_id = ensure_signed(origin)?;
             let account_id = T::Lookup::lookup(account_id)?;
+            Self::ensure_list_admin(marketplace_id, &caller_id, MarketplaceType::Public)?;
 
-            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
-                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
-                ensure!(
-                    market_info.owner == caller_id,
-                    Error::<T>::NotMarketplaceOwner
-                );
-                ensure!(
-                    market_info.kind == MarketplaceType::Public,
-                    Error::<T>::UnsupportedMarketplace
-                );
-
-                market_info.disallow_list.push(account_id.clone());
-                Ok(())
-            })?;
+            DisallowList::<T>::insert(marketplace_id, &account_id, ());
 
             let event = Event::AccountAddedToDisallowList {
                 marketplace_id,
@@ -404,26 +750,11 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let caller_id = ensure_signed(origin)?;
             let account_id = T::Lookup::lookup(account_id)?;
+            Self::ensure_list_admin(marketplace_id, &caller_id, MarketplaceType::Public)?;
 
-            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
-                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
-                ensure!(
-                    market_info.owner == caller_id,
-                    Error::<T>::NotMarketplaceOwner
-                );
-                ensure!(
-                    market_info.kind == MarketplaceType::Public,
-                    Error::<T>::UnsupportedMarketplace
-                );
-
-                let index = market_info
-                    .disallow_list
-                    .iter()
-                    .position(|x| *x == account_id);
-                let index = index.ok_or(Error::<T>::AccountNotFound)?;
-                market_info.disallow_list.swap_remove(index);
-                Ok(())
-            })?;
+            let is_on_list = DisallowList::<T>::contains_key(marketplace_id, &account_id);
+            ensure!(is_on_list, Error::<T>::AccountNotFound);
+            DisallowList::<T>::remove(marketplace_id, &account_id);
 
             let event = Event::AccountRemovedFromDisallowList {
                 marketplace_id,
@@ -434,96 +765,197 @@ pub mod pallet {
             Ok(().into())
         }
 
-        #[pallet::weight(T::WeightInfo::set_owner())]
-        pub fn set_owner(
+        /// Add several accounts to the allow list of a private marketplace.
+        #[pallet::weight(T::WeightInfo::add_accounts_to_allow_list(accounts.len() as u32))]
+        pub fn add_accounts_to_allow_list(
             origin: OriginFor<T>,
             marketplace_id: MarketplaceId,
-            account_id: <T::Lookup as StaticLookup>::Source,
+            accounts: Vec<<T::Lookup as StaticLookup>::Source>,
         ) -> DispatchResultWithPostInfo {
             let caller_id = ensure_signed(origin)?;
-            let account_id = T::Lookup::lookup(account_id)?;
+            let accounts = Self::lookup_accounts(accounts)?;
+            Self::ensure_list_admin(marketplace_id, &caller_id, MarketplaceType::Private)?;
 
-            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
-                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
-                ensure!(
-                    market_info.owner == caller_id,
-                    Error::<T>::NotMarketplaceOwner
-                );
-                market_info.owner = account_id.clone();
-                Ok(())
-            })?;
+            for account_id in &accounts {
+                AllowList::<T>::insert(marketplace_id, account_id, ());
+            }
 
-            let event = Event::MarketplaceChangedOwner {
+            let event = Event::AccountsAddedToAllowList {
                 marketplace_id,
-                owner: account_id,
+                accounts,
             };
             Self::deposit_event(event);
 
             Ok(().into())
         }
 
-        #[pallet::weight(T::WeightInfo::set_market_type())]
-        pub fn set_market_type(
+        /// Remove several accounts from the allow list of a private marketplace.
+        /// Accounts that are not on the list are ignored.
+        #[pallet::weight(T::WeightInfo::remove_accounts_from_allow_list(accounts.len() as u32))]
+        pub fn remove_accounts_from_allow_list(
             origin: OriginFor<T>,
             marketplace_id: MarketplaceId,
-            kind: MarketplaceType,
+            accounts: Vec<<T::Lookup as StaticLookup>::Source>,
         ) -> DispatchResultWithPostInfo {
             let caller_id = ensure_signed(origin)?;
+            let accounts = Self::lookup_accounts(accounts)?;
+            Self::ensure_list_admin(marketplace_id, &caller_id, MarketplaceType::Private)?;
 
-            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
-                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
-                ensure!(
-                    market_info.owner == caller_id,
-                    Error::<T>::NotMarketplaceOwner
-                );
-
-                market_info.kind = kind;
-                Ok(())
-            })?;
+            for account_id in &accounts {
+                AllowList::<T>::remove(marketplace_id, account_id);
+            }
 
-            let event = Event::MarketplaceTypeChanged {
+            let event = Event::AccountsRemovedFromAllowList {
                 marketplace_id,
-                kind,
+                accounts,
             };
             Self::deposit_event(event);
 
             Ok(().into())
         }
 
-        #[pallet::weight(T::WeightInfo::set_name())]
-        pub fn set_name(
+        /// Add several accounts to the disallow list of a public marketplace.
+        #[pallet::weight(T::WeightInfo::add_accounts_to_disallow_list(accounts.len() as u32))]
+        pub fn add_accounts_to_disallow_list(
             origin: OriginFor<T>,
             marketplace_id: MarketplaceId,
-            name: TextFormat,
+            accounts: Vec<<T::Lookup as StaticLookup>::Source>,
         ) -> DispatchResultWithPostInfo {
             let caller_id = ensure_signed(origin)?;
+            let accounts = Self::lookup_accounts(accounts)?;
+            Self::ensure_list_admin(marketplace_id, &caller_id, MarketplaceType::Public)?;
 
-            check_bounds(
-                name.len(),
-                (T::MinNameLen::get(), Error::<T>::TooShortMarketplaceName),
-                (T::MaxNameLen::get(), Error::<T>::TooLongMarketplaceName),
-            )?;
-
-            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
-                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
-                ensure!(
-                    market_info.owner == caller_id,
-                    Error::<T>::NotMarketplaceOwner
-                );
-                market_info.name = name.clone();
-                Ok(())
-            })?;
+            for account_id in &accounts {
+                DisallowList::<T>::insert(marketplace_id, account_id, ());
+            }
 
-            let event = Event::MarketplaceNameChanged {
+            let event = Event::AccountsAddedToDisallowList {
                 marketplace_id,
-                name,
+                accounts,
             };
             Self::deposit_event(event);
 
             Ok(().into())
         }
 
-        #[pallet::weight(T::WeightInfo::set_marketplace_mint_fee())]
+        /// Remove several accounts from the disallow list of a public marketplace.
+        /// Accounts that are not on the list are ignored.
+        #[pallet::weight(T::WeightInfo::remove_accounts_from_disallow_list(accounts.len() as u32))]
+        pub fn remove_accounts_from_disallow_list(
+            origin: OriginFor<T>,
+            marketplace_id: MarketplaceId,
+            accounts: Vec<<T::Lookup as StaticLookup>::Source>,
+        ) -> DispatchResultWithPostInfo {
+            let caller_id = ensure_signed(origin)?;
+            let accounts = Self::lookup_accounts(accounts)?;
+            Self::ensure_list_admin(marketplace_id, &caller_id, MarketplaceType::Public)?;
+
+            for account_id in &accounts {
+                DisallowList::<T>::remove(marketplace_id, account_id);
+            }
+
+            let event = Event::AccountsRemovedFromDisallowList {
+                marketplace_id,
+                accounts,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        #[pallet::weight(T::WeightInfo::set_owner())]
+        pub fn set_owner(
+            origin: OriginFor<T>,
+            marketplace_id: MarketplaceId,
+            account_id: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResultWithPostInfo {
+            let caller_id = ensure_signed(origin)?;
+            let account_id = T::Lookup::lookup(account_id)?;
+
+            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
+                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
+                ensure!(
+                    market_info.owner == caller_id,
+                    Error::<T>::NotMarketplaceOwner
+                );
+                market_info.owner = account_id.clone();
+                Ok(())
+            })?;
+
+            let event = Event::MarketplaceChangedOwner {
+                marketplace_id,
+                owner: account_id,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        #[pallet::weight(T::WeightInfo::set_market_type())]
+        pub fn set_market_type(
+            origin: OriginFor<T>,
+            marketplace_id: MarketplaceId,
+            kind: MarketplaceType,
+        ) -> DispatchResultWithPostInfo {
+            let caller_id = ensure_signed(origin)?;
+
+            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
+                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
+                ensure!(
+                    market_info.owner == caller_id,
+                    Error::<T>::NotMarketplaceOwner
+                );
+
+                market_info.kind = kind;
+                Ok(())
+            })?;
+
+            let event = Event::MarketplaceTypeChanged {
+                marketplace_id,
+                kind,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        #[pallet::weight(T::WeightInfo::set_name())]
+        pub fn set_name(
+            origin: OriginFor<T>,
+            marketplace_id: MarketplaceId,
+            name: TextFormat,
+        ) -> DispatchResultWithPostInfo {
+            let caller_id = ensure_signed(origin)?;
+
+            let bounded_name = bounded_text(
+                name.clone(),
+                (T::MinNameLen::get(), Error::<T>::TooShortMarketplaceName),
+                (T::MaxNameLen::get(), Error::<T>::TooLongMarketplaceName),
+            )?;
+
+            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
+                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
+                let is_admin = Self::has_role_in(
+                    market_info,
+                    marketplace_id,
+                    &caller_id,
+                    MarketplaceRole::Admin,
+                );
+                ensure!(is_admin, Error::<T>::NotMarketplaceAdmin);
+                market_info.name = bounded_name;
+                Ok(())
+            })?;
+
+            let event = Event::MarketplaceNameChanged {
+                marketplace_id,
+                name,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        #[pallet::weight(T::WeightInfo::set_marketplace_mint_fee())]
         pub fn set_marketplace_mint_fee(
             origin: OriginFor<T>,
             mint_fee: BalanceOf<T>,
@@ -537,14 +969,15 @@ pub mod pallet {
             Ok(().into())
         }
 
+        /// Change the commission of a marketplace. It only applies to NFTs listed
+        /// from now on.
         #[pallet::weight(T::WeightInfo::set_commission_fee())]
         pub fn set_commission_fee(
             origin: OriginFor<T>,
             marketplace_id: MarketplaceId,
-            commission_fee: u8,
+            commission_fee: MarketplaceCommissionOf<T>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            ensure!(commission_fee <= 100, Error::<T>::InvalidCommissionFeeValue);
 
             Marketplaces::<T>::mutate(marketplace_id, |x| -> DispatchResult {
                 let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
@@ -562,6 +995,34 @@ pub mod pallet {
             Ok(().into())
         }
 
+        /// Set the assets a marketplace accepts besides Caps. NFTs that are already
+        /// listed keep the asset they were listed with.
+        #[pallet::weight(T::WeightInfo::set_accepted_assets())]
+        pub fn set_accepted_assets(
+            origin: OriginFor<T>,
+            marketplace_id: MarketplaceId,
+            assets: Vec<T::AssetId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let market =
+                Marketplaces::<T>::get(marketplace_id).ok_or(Error::<T>::UnknownMarketplace)?;
+            ensure!(market.owner == who, Error::<T>::NotMarketplaceOwner);
+
+            let assets: AcceptedAssetsOf<T> = assets
+                .try_into()
+                .map_err(|_| Error::<T>::TooManyAcceptedAssets)?;
+            AcceptedAssets::<T>::insert(marketplace_id, assets.clone());
+
+            let event = Event::MarketplaceAcceptedAssetsChanged {
+                marketplace_id,
+                assets,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
         #[pallet::weight(T::WeightInfo::set_uri())]
         pub fn set_uri(
             origin: OriginFor<T>,
@@ -570,16 +1031,18 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
-            check_bounds(
-                uri.len(),
+            let bounded_uri = bounded_text(
+                uri.clone(),
                 (T::MinUriLen::get(), Error::<T>::TooShortUri),
                 (T::MaxUriLen::get(), Error::<T>::TooLongUri),
             )?;
 
             Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
                 let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
-                ensure!(market_info.owner == who, Error::<T>::NotMarketplaceOwner);
-                market_info.uri = Some(uri.clone());
+                let is_admin =
+                    Self::has_role_in(market_info, marketplace_id, &who, MarketplaceRole::Admin);
+                ensure!(is_admin, Error::<T>::NotMarketplaceAdmin);
+                market_info.uri = Some(bounded_uri);
                 Ok(())
             })?;
 
@@ -599,16 +1062,18 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
-            check_bounds(
-                logo_uri.len(),
+            let bounded_logo_uri = bounded_text(
+                logo_uri.clone(),
                 (T::MinUriLen::get(), Error::<T>::TooShortLogoUri),
                 (T::MaxUriLen::get(), Error::<T>::TooLongLogoUri),
             )?;
 
             Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
                 let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
-                ensure!(market_info.owner == who, Error::<T>::NotMarketplaceOwner);
-                market_info.logo_uri = Some(logo_uri.clone());
+                let is_admin =
+                    Self::has_role_in(market_info, marketplace_id, &who, MarketplaceRole::Admin);
+                ensure!(is_admin, Error::<T>::NotMarketplaceAdmin);
+                market_info.logo_uri = Some(bounded_logo_uri);
                 Ok(())
             })?;
 
@@ -626,26 +1091,503 @@ pub mod pallet {
             marketplace_id: MarketplaceId,
             description: TextFormat,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
+
+            let bounded_description = bounded_text(
+                description.clone(),
+                (T::MinDescriptionLen::get(), Error::<T>::TooShortDescription),
+                (T::MaxDescriptionLen::get(), Error::<T>::TooLongDescription),
+            )?;
+
+            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
+                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
+                let is_admin =
+                    Self::has_role_in(market_info, marketplace_id, &who, MarketplaceRole::Admin);
+                ensure!(is_admin, Error::<T>::NotMarketplaceAdmin);
+                market_info.description = Some(bounded_description);
+                Ok(())
+            })?;
+
+            let event = Event::MarketplaceDescriptionUpdated {
+                marketplace_id,
+                description,
+            };
+            Self::deposit_event(event);
+            Ok(().into())
+        }
+
+        /// Give a role on a marketplace to an account, replacing the role it had.
+        #[pallet::weight(T::WeightInfo::grant_role())]
+        pub fn grant_role(
+            origin: OriginFor<T>,
+            marketplace_id: MarketplaceId,
+            account_id: <T::Lookup as StaticLookup>::Source,
+            role: MarketplaceRole,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account_id = T::Lookup::lookup(account_id)?;
+
+            let market =
+                Marketplaces::<T>::get(marketplace_id).ok_or(Error::<T>::UnknownMarketplace)?;
+            ensure!(market.owner == who, Error::<T>::NotMarketplaceOwner);
+
+            MarketplaceRoles::<T>::insert(marketplace_id, &account_id, role);
+
+            let event = Event::MarketplaceRoleGranted {
+                marketplace_id,
+                account_id,
+                role,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Take away the role of an account on a marketplace. Accounts can also
+        /// give up their own role.
+        #[pallet::weight(T::WeightInfo::revoke_role())]
+        pub fn revoke_role(
+            origin: OriginFor<T>,
+            marketplace_id: MarketplaceId,
+            account_id: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account_id = T::Lookup::lookup(account_id)?;
+
+            let market =
+                Marketplaces::<T>::get(marketplace_id).ok_or(Error::<T>::UnknownMarketplace)?;
+            ensure!(
+                market.owner == who || account_id == who,
+                Error::<T>::NotMarketplaceOwner
+            );
+            let role = MarketplaceRoles::<T>::take(marketplace_id, &account_id);
+            ensure!(role.is_some(), Error::<T>::RoleNotFound);
+
+            let event = Event::MarketplaceRoleRevoked {
+                marketplace_id,
+                account_id,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Take an infringing NFT off a marketplace. Reserved to the curators of
+        /// the marketplace the NFT is listed on.
+        #[pallet::weight(T::WeightInfo::take_down_listing())]
+        pub fn take_down_listing(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let sale = NFTsForSale::<T>::get(nft_id).ok_or(Error::<T>::NftNotForSale)?;
+            let is_curator = Self::has_role(sale.marketplace_id, &who, MarketplaceRole::Curator);
+            ensure!(is_curator, Error::<T>::NotMarketplaceCurator);
+
+            T::NFTs::unlock(nft_id, NFTLockReason::ListedForSale)?;
+            Self::remove_sale(nft_id);
+
+            let event = Event::NftTakenDown {
+                nft_id,
+                marketplace_id: sale.marketplace_id,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Take an infringing bundle off a marketplace. Reserved to the curators
+        /// of the marketplace the bundle is listed on.
+        #[pallet::weight(T::WeightInfo::take_down_bundle(T::MaxBundleSize::get()))]
+        #[transactional]
+        pub fn take_down_bundle(
+            origin: OriginFor<T>,
+            bundle_id: BundleId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let bundle = Bundles::<T>::get(bundle_id).ok_or(Error::<T>::UnknownBundle)?;
+            let is_curator = Self::has_role(bundle.marketplace_id, &who, MarketplaceRole::Curator);
+            ensure!(is_curator, Error::<T>::NotMarketplaceCurator);
+
+            Self::remove_bundle(bundle_id)?;
+
+            let event = Event::BundleTakenDown {
+                bundle_id,
+                marketplace_id: bundle.marketplace_id,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Close a marketplace. Nothing can be listed on it anymore. Its sales are
+        /// taken down, what other pallets host on it is settled or cancelled and its
+        /// lists are cleared, `limit` items per call. Call it again until the
+        /// marketplace is removed, which emits `MarketplaceClosed`. Reserved to the
+        /// owner of the marketplace and to root.
+        #[pallet::weight(
+            T::WeightInfo::close_marketplace(*limit)
+                .saturating_add(T::MarketplaceCloseHandler::close_marketplace_weight(*limit))
+        )]
+        #[transactional]
+        pub fn close_marketplace(
+            origin: OriginFor<T>,
+            marketplace_id: MarketplaceId,
+            limit: u32,
+        ) -> DispatchResultWithPostInfo {
+            let who = match ensure_root(origin.clone()) {
+                Ok(()) => None,
+                Err(_) => Some(ensure_signed(origin)?),
+            };
+
+            let market =
+                Marketplaces::<T>::get(marketplace_id).ok_or(Error::<T>::UnknownMarketplace)?;
+            if let Some(who) = who {
+                ensure!(market.owner == who, Error::<T>::NotMarketplaceOwner);
+            }
+
+            if !ClosingMarketplaces::<T>::contains_key(marketplace_id) {
+                ClosingMarketplaces::<T>::insert(marketplace_id, ());
+                Self::deposit_event(Event::MarketplaceClosing { marketplace_id });
+            }
+
+            let mut remaining = limit;
+
+            let sales: Vec<NFTId> = MarketplaceSales::<T>::iter_key_prefix(marketplace_id)
+                .take(remaining as usize)
+                .map(NFTId::from_be_bytes)
+                .collect();
+            for nft_id in &sales {
+                T::NFTs::unlock(*nft_id, NFTLockReason::ListedForSale)?;
+                Self::remove_sale(*nft_id);
+                Self::deposit_event(Event::NftUnlisted { nft_id: *nft_id });
+            }
+            remaining = remaining.saturating_sub(sales.len() as u32);
+
+            let bundles: Vec<BundleId> = MarketplaceBundles::<T>::iter_key_prefix(marketplace_id)
+                .take(remaining as usize)
+                .collect();
+            for bundle_id in &bundles {
+                Self::remove_bundle(*bundle_id)?;
+                Self::deposit_event(Event::BundleUnlisted {
+                    bundle_id: *bundle_id,
+                });
+            }
+            remaining = remaining.saturating_sub(bundles.len() as u32);
+
+            let handled = T::MarketplaceCloseHandler::close_marketplace(marketplace_id, remaining)?;
+            remaining = remaining.saturating_sub(handled);
+
+            let allowed: Vec<T::AccountId> = AllowList::<T>::iter_key_prefix(marketplace_id)
+                .take(remaining as usize)
+                .collect();
+            for account_id in &allowed {
+                AllowList::<T>::remove(marketplace_id, account_id);
+            }
+            remaining = remaining.saturating_sub(allowed.len() as u32);
+
+            let disallowed: Vec<T::AccountId> = DisallowList::<T>::iter_key_prefix(marketplace_id)
+                .take(remaining as usize)
+                .collect();
+            for account_id in &disallowed {
+                DisallowList::<T>::remove(marketplace_id, account_id);
+            }
+            remaining = remaining.saturating_sub(disallowed.len() as u32);
+
+            let roles: Vec<T::AccountId> = MarketplaceRoles::<T>::iter_key_prefix(marketplace_id)
+                .take(remaining as usize)
+                .collect();
+            for account_id in &roles {
+                MarketplaceRoles::<T>::remove(marketplace_id, account_id);
+            }
+            remaining = remaining.saturating_sub(roles.len() as u32);
+
+            // Every step handled less than it was allowed to, so nothing is left
+            if remaining != 0 {
+                Marketplaces::<T>::remove(marketplace_id);
+                AcceptedAssets::<T>::remove(marketplace_id);
+                ClosingMarketplaces::<T>::remove(marketplace_id);
+                Self::deposit_event(Event::MarketplaceClosed { marketplace_id });
+            }
+
+            Ok(().into())
+        }
+
+        /// Offer to buy an NFT, listed or not. The price is reserved until the
+        /// offer is accepted, withdrawn or expires at the `expiration` block.
+        #[pallet::weight(T::WeightInfo::make_offer())]
+        #[transactional]
+        pub fn make_offer(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+            price: BalanceOf<T>,
+            marketplace_id: Option<MarketplaceId>,
+            expiration: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let owner = T::NFTs::owner(nft_id).ok_or(Error::<T>::UnknownNFT)?;
+            ensure!(owner != who, Error::<T>::NftAlreadyOwned);
+
+            Self::add_offer(
+                who,
+                OfferTarget::NFT(nft_id),
+                price,
+                marketplace_id,
+                expiration,
+            )?;
+
+            Ok(().into())
+        }
+
+        /// Offer to buy any NFT of a series. Whoever holds one of them can
+        /// accept the offer.
+        #[pallet::weight(T::WeightInfo::make_offer())]
+        #[transactional]
+        pub fn make_series_offer(
+            origin: OriginFor<T>,
+            series_id: NFTSeriesId,
+            price: BalanceOf<T>,
+            marketplace_id: Option<MarketplaceId>,
+            expiration: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let series_id: SeriesIdOf<T> = series_id
+                .try_into()
+                .map_err(|_| Error::<T>::TooLongSeriesId)?;
+            let target = OfferTarget::Series(series_id);
+
+            Self::add_offer(who, target, price, marketplace_id, expiration)?;
+
+            Ok(().into())
+        }
+
+        /// Withdraw an offer made on an NFT and get the funds back.
+        #[pallet::weight(T::WeightInfo::withdraw_offer())]
+        pub fn withdraw_offer(origin: OriginFor<T>, nft_id: NFTId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::remove_offer(who, OfferTarget::NFT(nft_id))?;
+
+            Ok(().into())
+        }
+
+        /// Withdraw an offer made on a series and get the funds back.
+        #[pallet::weight(T::WeightInfo::withdraw_offer())]
+        pub fn withdraw_series_offer(
+            origin: OriginFor<T>,
+            series_id: NFTSeriesId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let series_id: SeriesIdOf<T> = series_id
+                .try_into()
+                .map_err(|_| Error::<T>::OfferNotFound)?;
+            Self::remove_offer(who, OfferTarget::Series(series_id))?;
+
+            Ok(().into())
+        }
+
+        /// Accept the offer of `buyer` on an NFT. The NFT goes to the buyer and
+        /// the price is split like in `buy`. A listed NFT gets unlisted.
+        #[pallet::weight(T::WeightInfo::accept_offer())]
+        #[transactional]
+        pub fn accept_offer(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+            buyer: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let buyer = T::Lookup::lookup(buyer)?;
+
+            Self::sell_to_offer(who, nft_id, buyer, OfferTarget::NFT(nft_id))?;
+
+            Ok(().into())
+        }
+
+        /// Accept the offer of `buyer` on the series of an NFT with that NFT.
+        #[pallet::weight(T::WeightInfo::accept_offer())]
+        #[transactional]
+        pub fn accept_series_offer(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+            buyer: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let buyer = T::Lookup::lookup(buyer)?;
+
+            let nft = T::NFTs::get_nft(nft_id).ok_or(Error::<T>::UnknownNFT)?;
+            let target = OfferTarget::Series(nft.series_id);
+
+            Self::sell_to_offer(who, nft_id, buyer, target)?;
+
+            Ok(().into())
+        }
+
+        /// Buy an nft with an order that its owner signed off-chain. The nft does
+        /// not need to be listed and the price is split like in `buy`. Fails if
+        /// the marketplace raised its commission above the one of the order.
+        #[pallet::weight(T::WeightInfo::fill_order())]
+        #[transactional]
+        pub fn fill_order(
+            origin: OriginFor<T>,
+            seller: <T::Lookup as StaticLookup>::Source,
+            order: OrderOf<T>,
+            signature: T::OrderSignature,
+        ) -> DispatchResultWithPostInfo {
+            let caller = ensure_signed(origin)?;
+            let seller = T::Lookup::lookup(seller)?;
+            ensure!(seller != caller, Error::<T>::NftAlreadyOwned);
+
+            let payload = order.signing_payload(&Self::genesis_hash());
+            let is_signed = signature.verify(&payload[..], &seller);
+            ensure!(is_signed, Error::<T>::InvalidOrderSignature);
+
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(now <= order.expiry, Error::<T>::OrderExpired);
+            ensure!(
+                Self::is_order_open(&seller, order.nonce),
+                Error::<T>::InvalidOrderNonce
+            );
+
+            let nft = T::NFTs::get_nft(order.nft_id).ok_or(Error::<T>::UnknownNFT)?;
+            ensure!(nft.owner == seller, Error::<T>::NotNftOwner);
+            ensure!(nft.lock.is_none(), Error::<T>::NftIsLocked);
+
+            let is_nft_in_completed_series =
+                T::NFTs::is_nft_in_completed_series(order.nft_id) == Some(true);
+            ensure!(is_nft_in_completed_series, Error::<T>::SeriesNotCompleted);
+
+            let market = Marketplaces::<T>::get(order.marketplace_id)
+                .ok_or(Error::<T>::UnknownMarketplace)?;
+            Self::is_allowed_to_list(order.marketplace_id, seller.clone())?;
+            let fee = &market.commission_fee;
+            let max = &order.max_commission;
+            ensure!(
+                fee.rate <= max.rate && fee.flat <= max.flat,
+                Error::<T>::CommissionAboveOrder
+            );
+
+            let (price, marketplace_cut, royalty_cut) = Self::pay_out(
+                &[order.nft_id],
+                &caller,
+                &seller,
+                order.price,
+                &PaymentAsset::Native,
+                order.marketplace_id,
+                &market.commission_fee,
+            )?;
+
+            UsedOrderNonces::<T>::insert(&seller, order.nonce, ());
+            T::NFTs::set_owner(order.nft_id, &caller)?;
+
+            let event = Event::OrderFilled {
+                seller,
+                nonce: order.nonce,
+                nft_id: order.nft_id,
+                owner: caller,
+                seller_cut: price,
+                marketplace_cut,
+                royalty_cut,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Cancel the order or the voucher of the caller with this nonce.
+        #[pallet::weight(T::WeightInfo::cancel_order())]
+        pub fn cancel_order(origin: OriginFor<T>, nonce: OrderNonce) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                Self::is_order_open(&who, nonce),
+                Error::<T>::InvalidOrderNonce
+            );
+            UsedOrderNonces::<T>::insert(&who, nonce, ());
+
+            Self::deposit_event(Event::OrderCancelled { seller: who, nonce });
+
+            Ok(().into())
+        }
+
+        /// Cancel every order and voucher of the caller with a nonce lower than `nonce`.
+        #[pallet::weight(T::WeightInfo::cancel_orders())]
+        pub fn cancel_orders(
+            origin: OriginFor<T>,
+            nonce: OrderNonce,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            OrderNonces::<T>::try_mutate(&who, |x| -> DispatchResult {
+                ensure!(nonce > *x, Error::<T>::InvalidOrderNonce);
+                *x = nonce;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::OrdersCancelled { seller: who, nonce });
+
+            Ok(().into())
+        }
+
+        /// Buy an nft that does not exist yet with a voucher that its creator signed
+        /// off-chain. The nft is minted by the creator, who pays the mint fee out of
+        /// the price, and goes straight to the caller. Without a series id, the nft
+        /// gets a series of its own. The series of the nft is always completed so
+        /// that the caller can sell it again.
+        #[pallet::weight(T::WeightInfo::redeem_voucher())]
+        #[transactional]
+        pub fn redeem_voucher(
+            origin: OriginFor<T>,
+            creator: <T::Lookup as StaticLookup>::Source,
+            voucher: MintVoucherOf<T>,
+            signature: T::OrderSignature,
+        ) -> DispatchResultWithPostInfo {
+            let caller = ensure_signed(origin)?;
+            let creator = T::Lookup::lookup(creator)?;
+            ensure!(creator != caller, Error::<T>::NftAlreadyOwned);
 
-            check_bounds(
-                description.len(),
-                (T::MinDescriptionLen::get(), Error::<T>::TooShortDescription),
-                (T::MaxDescriptionLen::get(), Error::<T>::TooLongDescription),
-            )?;
+            let payload = voucher.signing_payload(&Self::genesis_hash());
+            let is_signed = signature.verify(&payload[..], &creator);
+            ensure!(is_signed, Error::<T>::InvalidOrderSignature);
 
-            Marketplaces::<T>::try_mutate(marketplace_id, |x| -> DispatchResult {
-                let market_info = x.as_mut().ok_or(Error::<T>::UnknownMarketplace)?;
-                ensure!(market_info.owner == who, Error::<T>::NotMarketplaceOwner);
-                market_info.description = Some(description.clone());
-                Ok(())
-            })?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(now <= voucher.expiry, Error::<T>::OrderExpired);
+            ensure!(
+                Self::is_order_open(&creator, voucher.nonce),
+                Error::<T>::InvalidOrderNonce
+            );
+            ensure!(
+                voucher.price >= T::NFTs::mint_fee(),
+                Error::<T>::VoucherPriceBelowMintFee
+            );
+            UsedOrderNonces::<T>::insert(&creator, voucher.nonce, ());
 
-            let event = Event::MarketplaceDescriptionUpdated {
-                marketplace_id,
-                description,
+            T::Currency::transfer(&caller, &creator, voucher.price, KeepAlive)?;
+
+            let ipfs_reference = voucher.ipfs_reference.into_inner();
+            let nft_id = T::NFTs::create_nft(creator.clone(), ipfs_reference, voucher.series_id)
+                .map_err(|x| x.error)?;
+            T::NFTs::set_royalty(nft_id, voucher.royalty)?;
+            T::NFTs::set_owner(nft_id, &caller)?;
+
+            // Minting never goes into a completed series, so whether the voucher
+            // created the series or named a draft one, it is completed here.
+            let nft = T::NFTs::get_nft(nft_id).ok_or(Error::<T>::UnknownNFT)?;
+            T::NFTs::set_series_completion(&nft.series_id.into_inner(), true)?;
+
+            let event = Event::VoucherRedeemed {
+                creator,
+                nonce: voucher.nonce,
+                nft_id,
+                owner: caller,
+                price: voucher.price,
             };
             Self::deposit_event(event);
+
             Ok(().into())
         }
     }
@@ -657,12 +1599,29 @@ pub mod pallet {
         NftListed {
             nft_id: NFTId,
             price: BalanceOf<T>,
+            asset: PaymentAssetOf<T>,
             marketplace_id: MarketplaceId,
+            start_block: Option<T::BlockNumber>,
+            end_block: Option<T::BlockNumber>,
         },
-        /// A nft is removed from the marketplace by its owner.
+        /// A nft is removed from the marketplace by its owner or because its listing ended.
         NftUnlisted { nft_id: NFTId },
-        /// A nft has been sold.
-        NftSold { nft_id: NFTId, owner: T::AccountId },
+        /// The price of a listed nft has changed.
+        NftPriceChanged { nft_id: NFTId, price: BalanceOf<T> },
+        /// A listed nft has been moved to another marketplace.
+        NftListingMoved {
+            nft_id: NFTId,
+            marketplace_id: MarketplaceId,
+        },
+        /// A nft has been sold. The price was split between the seller,
+        /// the marketplace owner and the creator of the nft.
+        NftSold {
+            nft_id: NFTId,
+            owner: T::AccountId,
+            seller_cut: BalanceOf<T>,
+            marketplace_cut: BalanceOf<T>,
+            royalty_cut: BalanceOf<T>,
+        },
         /// A marketplace has been created.
         MarketplaceCreated {
             marketplace_id: MarketplaceId,
@@ -695,10 +1654,16 @@ pub mod pallet {
         },
         /// Marketplace mint fee changed.
         MarketplaceMintFeeChanged { fee: BalanceOf<T> },
-        /// Marketplace mint fee changed.
+        /// Marketplace commission fee changed. NFTs that are already listed keep
+        /// the commission they were listed with.
         MarketplaceCommissionFeeChanged {
             marketplace_id: MarketplaceId,
-            fee: u8,
+            fee: MarketplaceCommissionOf<T>,
+        },
+        /// Assets accepted by a marketplace besides Caps changed.
+        MarketplaceAcceptedAssetsChanged {
+            marketplace_id: MarketplaceId,
+            assets: AcceptedAssetsOf<T>,
         },
         /// Marketplace TextFormat updated.
         MarketplaceUriUpdated {
@@ -720,18 +1685,143 @@ pub mod pallet {
             marketplace_id: MarketplaceId,
             account_id: T::AccountId,
         },
+        /// An account was given a role on a marketplace.
+        MarketplaceRoleGranted {
+            marketplace_id: MarketplaceId,
+            account_id: T::AccountId,
+            role: MarketplaceRole,
+        },
+        /// An account lost its role on a marketplace.
+        MarketplaceRoleRevoked {
+            marketplace_id: MarketplaceId,
+            account_id: T::AccountId,
+        },
+        /// Several nfts are listed together.
+        BundleListed {
+            bundle_id: BundleId,
+            nft_ids: Vec<NFTId>,
+            price: BalanceOf<T>,
+            asset: PaymentAssetOf<T>,
+            marketplace_id: MarketplaceId,
+        },
+        /// A bundle is removed from the marketplace and its nfts are unlocked.
+        BundleUnlisted { bundle_id: BundleId },
+        /// A bundle was bought.
+        BundleSold {
+            bundle_id: BundleId,
+            owner: T::AccountId,
+            seller_cut: BalanceOf<T>,
+            marketplace_cut: BalanceOf<T>,
+            royalty_cut: BalanceOf<T>,
+        },
+        /// The price of a listed bundle changed.
+        BundlePriceChanged {
+            bundle_id: BundleId,
+            price: BalanceOf<T>,
+        },
+        /// A listed bundle has been moved to another marketplace.
+        BundleMoved {
+            bundle_id: BundleId,
+            marketplace_id: MarketplaceId,
+        },
+        /// A marketplace is being closed. Nothing can be listed on it anymore.
+        MarketplaceClosing { marketplace_id: MarketplaceId },
+        /// A marketplace was closed and removed.
+        MarketplaceClosed { marketplace_id: MarketplaceId },
+        /// A curator took an NFT off a marketplace.
+        NftTakenDown {
+            nft_id: NFTId,
+            marketplace_id: MarketplaceId,
+        },
+        /// A curator took a bundle off a marketplace.
+        BundleTakenDown {
+            bundle_id: BundleId,
+            marketplace_id: MarketplaceId,
+        },
+        /// Accounts added to the allow list of a marketplace.
+        AccountsAddedToAllowList {
+            marketplace_id: MarketplaceId,
+            accounts: Vec<T::AccountId>,
+        },
+        /// Accounts removed from the allow list of a marketplace.
+        AccountsRemovedFromAllowList {
+            marketplace_id: MarketplaceId,
+            accounts: Vec<T::AccountId>,
+        },
+        /// Accounts added to the disallow list of a marketplace.
+        AccountsAddedToDisallowList {
+            marketplace_id: MarketplaceId,
+            accounts: Vec<T::AccountId>,
+        },
+        /// Accounts removed from the disallow list of a marketplace.
+        AccountsRemovedFromDisallowList {
+            marketplace_id: MarketplaceId,
+            accounts: Vec<T::AccountId>,
+        },
         /// Marketplace description updated.
         MarketplaceDescriptionUpdated {
             marketplace_id: MarketplaceId,
             description: TextFormat,
         },
+        /// An offer was made on an NFT or on a series.
+        OfferMade {
+            target: OfferTargetOf<T>,
+            buyer: T::AccountId,
+            price: BalanceOf<T>,
+            marketplace_id: MarketplaceId,
+            expiration: T::BlockNumber,
+        },
+        /// An offer was withdrawn by its buyer.
+        OfferWithdrawn {
+            target: OfferTargetOf<T>,
+            buyer: T::AccountId,
+        },
+        /// An offer expired and its funds went back to the buyer.
+        OfferExpired {
+            target: OfferTargetOf<T>,
+            buyer: T::AccountId,
+        },
+        /// An offer was accepted. The price was split like for a sale.
+        OfferAccepted {
+            target: OfferTargetOf<T>,
+            nft_id: NFTId,
+            buyer: T::AccountId,
+            seller_cut: BalanceOf<T>,
+            marketplace_cut: BalanceOf<T>,
+            royalty_cut: BalanceOf<T>,
+        },
+        /// An order signed off-chain was filled.
+        OrderFilled {
+            seller: T::AccountId,
+            nonce: OrderNonce,
+            nft_id: NFTId,
+            owner: T::AccountId,
+            seller_cut: BalanceOf<T>,
+            marketplace_cut: BalanceOf<T>,
+            royalty_cut: BalanceOf<T>,
+        },
+        /// An order of a seller was cancelled.
+        OrderCancelled {
+            seller: T::AccountId,
+            nonce: OrderNonce,
+        },
+        /// Every order of a seller with a nonce lower than `nonce` was cancelled.
+        OrdersCancelled {
+            seller: T::AccountId,
+            nonce: OrderNonce,
+        },
+        /// An nft was minted from a voucher and sold to its first owner.
+        VoucherRedeemed {
+            creator: T::AccountId,
+            nonce: OrderNonce,
+            nft_id: NFTId,
+            owner: T::AccountId,
+            price: BalanceOf<T>,
+        },
     }
 
     #[pallet::error]
     pub enum Error<T> {
-        /// TODO!
-        CannotListLentNFTs,
-
         /// This function is reserved to the owner of a nft.
         NotNftOwner,
         /// Nft is not present on the marketplace.
@@ -744,10 +1834,16 @@ pub mod pallet {
         MarketplaceIdOverflow,
         /// No marketplace found with that Id.
         UnknownMarketplace,
-        /// Commission fee cannot be more then 100.
-        InvalidCommissionFeeValue,
         /// This function is reserved to the owner of a marketplace.
         NotMarketplaceOwner,
+        /// This function is reserved to the owner and the admins of a marketplace.
+        NotMarketplaceAdmin,
+        /// This function is reserved to the owner, the admins and the curators of a marketplace.
+        NotMarketplaceCurator,
+        /// The account has no role on this marketplace.
+        RoleNotFound,
+        /// The marketplace is being closed.
+        MarketplaceClosing,
         /// This marketplace does not allow for this operation to be executed.
         UnsupportedMarketplace,
         /// Account not found.
@@ -770,40 +1866,214 @@ pub mod pallet {
         TooLongLogoUri,
         // Marketplace logo uri is too short.
         TooShortLogoUri,
-        /// Nft is capsulized.
-        CannotListCapsules,
         /// Marketplace description in too short.
         TooShortDescription,
         /// Marketplace description in too long.
         TooLongDescription,
         /// TODO!
-        AlreadyListedForSale,
-        /// TODO!
         UnknownNFT,
+        /// Too many accounts were given at once.
+        TooManyAccounts,
+        /// The expiration block of an offer has already passed.
+        OfferExpirationIsInThePast,
+        /// The buyer already made an offer on this NFT or series.
+        OfferAlreadyExists,
+        /// Too many offers already expire at that block.
+        TooManyOffersAtBlock,
+        /// No offer was found from this buyer.
+        OfferNotFound,
+        /// Series id is too long.
+        TooLongSeriesId,
+        /// The NFT is locked for another reason than being listed.
+        NftIsLocked,
+        /// The end block of a listing has already passed.
+        ListingEndIsInThePast,
+        /// The end block of a listing must come after its start block.
+        ListingCannotEndBeforeItStarts,
+        /// Too many listings already end at that block.
+        TooManyListingsAtBlock,
+        /// The listing has not started yet.
+        ListingNotStarted,
+        /// The listing has ended.
+        ListingExpired,
+        /// The marketplace does not accept payments with this asset.
+        AssetNotAccepted,
+        /// Too many accepted assets.
+        TooManyAcceptedAssets,
+        /// A listing cannot be reserved to that many buyers.
+        TooManyBuyers,
+        /// The listing is reserved to other buyers.
+        NotAllowedToBuy,
+        /// A bundle needs at least one nft.
+        EmptyBundle,
+        /// Too many nfts in a bundle.
+        BundleIsTooLarge,
+        /// No bundle with this id.
+        UnknownBundle,
+        /// No more bundle ids are available.
+        BundleIdOverflow,
+        /// The order or the voucher was not signed by the seller.
+        InvalidOrderSignature,
+        /// The order or the voucher has expired.
+        OrderExpired,
+        /// The order or the voucher was already used or cancelled.
+        InvalidOrderNonce,
+        /// The marketplace takes more commission than the order allows.
+        CommissionAboveOrder,
+        /// The price of the voucher does not cover the mint fee.
+        VoucherPriceBelowMintFee,
     }
 
     /// Nfts listed on the marketplace
     #[pallet::storage]
     #[pallet::getter(fn nft_for_sale)]
-    pub type NFTsForSale<T: Config> = StorageMap<
+    pub type NFTsForSale<T: Config> =
+        StorageMap<_, Blake2_128Concat, NFTId, SaleInformationOf<T>, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn bundle_id_generator)]
+    pub type BundleIdGenerator<T: Config> = StorageValue<_, BundleId, ValueQuery>;
+
+    /// Nfts listed together on the marketplace.
+    #[pallet::storage]
+    #[pallet::getter(fn bundles)]
+    pub type Bundles<T: Config> =
+        StorageMap<_, Blake2_128Concat, BundleId, BundleInformationOf<T>, OptionQuery>;
+
+    /// Bundle that each bundled nft belongs to.
+    #[pallet::storage]
+    #[pallet::getter(fn nft_bundle)]
+    pub type NFTBundles<T: Config> = StorageMap<_, Blake2_128Concat, NFTId, BundleId, OptionQuery>;
+
+    /// Nfts listed on each marketplace, in ascending order. The value tells
+    /// whether the listing is reserved to some buyers.
+    #[pallet::storage]
+    pub type MarketplaceSales<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, MarketplaceId, Identity, IndexKey, bool, OptionQuery>;
+
+    /// Bundles listed on each marketplace.
+    #[pallet::storage]
+    pub type MarketplaceBundles<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        MarketplaceId,
+        Blake2_128Concat,
+        BundleId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Listings indexed by the block at which they end.
+    #[pallet::storage]
+    #[pallet::getter(fn listing_deadlines)]
+    pub type ListingDeadlines<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::BlockNumber,
+        BoundedVec<NFTId, T::MaxListingsPerBlock>,
+        ValueQuery,
+    >;
+
+    /// Offers made on NFTs and series, by buyer.
+    #[pallet::storage]
+    #[pallet::getter(fn offers)]
+    pub type Offers<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        NFTId,
-        SaleInformation<T::AccountId, BalanceOf<T>>,
+        OfferTargetOf<T>,
+        Blake2_128Concat,
+        T::AccountId,
+        OfferOf<T>,
         OptionQuery,
     >;
 
+    /// Offers indexed by the block at which they expire.
+    #[pallet::storage]
+    #[pallet::getter(fn offer_expirations)]
+    pub type OfferExpirations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::BlockNumber,
+        BoundedVec<(OfferTargetOf<T>, T::AccountId), T::MaxOffersPerBlock>,
+        ValueQuery,
+    >;
+
+    /// Assets a marketplace accepts besides Caps.
+    #[pallet::storage]
+    #[pallet::getter(fn accepted_assets)]
+    pub type AcceptedAssets<T: Config> =
+        StorageMap<_, Blake2_128Concat, MarketplaceId, AcceptedAssetsOf<T>, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn marketplace_id_generator)]
     pub type MarketplaceIdGenerator<T: Config> = StorageValue<_, MarketplaceId, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn marketplaces)]
-    pub type Marketplaces<T: Config> = StorageMap<
+    pub type Marketplaces<T: Config> =
+        StorageMap<_, Blake2_128Concat, MarketplaceId, MarketplaceInformationOf<T>, OptionQuery>;
+
+    /// Roles given by the owner of a marketplace to other accounts.
+    #[pallet::storage]
+    #[pallet::getter(fn marketplace_role)]
+    pub type MarketplaceRoles<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        MarketplaceId,
+        Blake2_128Concat,
+        T::AccountId,
+        MarketplaceRole,
+        OptionQuery,
+    >;
+
+    /// Marketplaces that are being closed. Nothing can be listed on them anymore.
+    #[pallet::storage]
+    #[pallet::getter(fn closing_marketplaces)]
+    pub type ClosingMarketplaces<T: Config> =
+        StorageMap<_, Blake2_128Concat, MarketplaceId, (), OptionQuery>;
+
+    /// Accounts that can list on a private marketplace. Accounts are stored
+    /// unhashed so that they can be read back page by page.
+    #[pallet::storage]
+    pub type AllowList<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        MarketplaceId,
+        Identity,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Accounts that cannot list on a public marketplace. Accounts are stored
+    /// unhashed so that they can be read back page by page.
+    #[pallet::storage]
+    pub type DisallowList<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
         MarketplaceId,
-        MarketplaceInformation<T::AccountId>,
+        Identity,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Orders and vouchers of a seller with a lower nonce are cancelled.
+    #[pallet::storage]
+    #[pallet::getter(fn order_nonce)]
+    pub type OrderNonces<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, OrderNonce, ValueQuery>;
+
+    /// Orders and vouchers that were used or cancelled one by one, by seller and
+    /// nonce.
+    #[pallet::storage]
+    pub type UsedOrderNonces<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        OrderNonce,
+        (),
         OptionQuery,
     >;
 
@@ -814,8 +2084,14 @@ pub mod pallet {
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
-        pub nfts_for_sale: Vec<(NFTId, SaleInformation<T::AccountId, BalanceOf<T>>)>,
-        pub marketplaces: Vec<(MarketplaceId, MarketplaceInformation<T::AccountId>)>,
+        pub nfts_for_sale: Vec<(NFTId, SaleInformationOf<T>)>,
+        pub marketplaces: Vec<(
+            MarketplaceId,
+            T::AccountId,
+            MarketplaceType,
+            MarketplaceCommissionOf<T>,
+            TextFormat,
+        )>,
         pub marketplace_mint_fee: BalanceOf<T>,
     }
 
@@ -837,21 +2113,394 @@ pub mod pallet {
                 .clone()
                 .into_iter()
                 .for_each(|(nft_id, sale_information)| {
+                    if let Some(end) = sale_information.end_block {
+                        let ok = ListingDeadlines::<T>::mutate(end, |x| x.try_push(nft_id).is_ok());
+                        assert!(ok, "Too many listings end at the same block");
+                    }
+                    MarketplaceSales::<T>::insert(
+                        sale_information.marketplace_id,
+                        index_key(nft_id),
+                        sale_information.is_private(),
+                    );
                     NFTsForSale::<T>::insert(nft_id, sale_information);
                 });
 
-            self.marketplaces
-                .clone()
-                .into_iter()
-                .for_each(|(market_id, market_info)| {
+            self.marketplaces.clone().into_iter().for_each(
+                |(market_id, owner, kind, commission_fee, name)| {
+                    let name = name.try_into().expect("Marketplace name is too long");
+                    let market_info = MarketplaceInformation::new(
+                        kind,
+                        commission_fee,
+                        owner,
+                        name,
+                        None,
+                        None,
+                        None,
+                    );
                     Marketplaces::<T>::insert(market_id, market_info);
-                });
+                },
+            );
             MarketplaceMintFee::<T>::put(self.marketplace_mint_fee);
         }
     }
 }
 
-impl<T: Config> MarketplaceTrait<T::AccountId> for Pallet<T> {
+impl<T: Config> Pallet<T> {
+    /// Pay the price of an NFT from `buyer` to `seller`. The creator gets
+    /// royalties on secondary sales and the marketplace gets `commission_fee`.
+    /// Return the seller, marketplace and royalty cuts.
+    fn pay_out(
+        nft_ids: &[NFTId],
+        buyer: &T::AccountId,
+        seller: &T::AccountId,
+        price: BalanceOf<T>,
+        asset: &PaymentAssetOf<T>,
+        marketplace_id: MarketplaceId,
+        commission_fee: &MarketplaceCommissionOf<T>,
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        let transfer = |dest: &T::AccountId, amount: BalanceOf<T>| {
+            transfer_payment::<_, T::Currency, T::Assets>(asset, buyer, dest, amount, KeepAlive)
+        };
+
+        let market =
+            Marketplaces::<T>::get(marketplace_id).ok_or(Error::<T>::UnknownMarketplace)?;
+
+        let full_price = price;
+        let mut price = price;
+
+        // The creators are paid first, on the share of the price of their nft.
+        // Royalties are only due on secondary sales. A royalty that is too
+        // small to be received by its creator is left to the seller.
+        let share = full_price / BalanceOf::<T>::from(nft_ids.len() as u32);
+        let mut royalty_cut: BalanceOf<T> = Zero::zero();
+        for nft_id in nft_ids {
+            let nft = T::NFTs::get_nft(*nft_id).ok_or(Error::<T>::UnknownNFT)?;
+            if nft.creator == *seller {
+                continue;
+            }
+
+            let royalty = nft.royalty.mul_floor(share);
+            let creator = &nft.creator;
+            if !can_receive_payment::<_, T::Currency, T::Assets>(asset, creator, royalty) {
+                continue;
+            }
+
+            price = price
+                .checked_sub(&royalty)
+                .ok_or(Error::<T>::InternalMathError)?;
+            royalty_cut = royalty_cut.saturating_add(royalty);
+
+            transfer(creator, royalty)?;
+        }
+
+        // KeepAlive because they need to be able to use the NFT later on
+        let mut marketplace_cut: BalanceOf<T> = Zero::zero();
+        let fee = marketplace_commission(commission_fee, full_price);
+        if !fee.is_zero() {
+            // The marketplace cannot take more than what is left after royalties.
+            marketplace_cut = fee.min(price);
+            price = price
+                .checked_sub(&marketplace_cut)
+                .ok_or(Error::<T>::InternalMathError)?;
+
+            transfer(&market.owner, marketplace_cut)?;
+        }
+
+        transfer(seller, price)?;
+
+        Ok((price, marketplace_cut, royalty_cut))
+    }
+
+    /// Reserve the funds of a new offer and record it.
+    fn add_offer(
+        buyer: T::AccountId,
+        target: OfferTargetOf<T>,
+        price: BalanceOf<T>,
+        marketplace_id: Option<MarketplaceId>,
+        expiration: T::BlockNumber,
+    ) -> DispatchResult {
+        let mkp_id = marketplace_id.unwrap_or(0);
+        ensure!(
+            Marketplaces::<T>::contains_key(mkp_id),
+            Error::<T>::UnknownMarketplace
+        );
+
+        let now = frame_system::Pallet::<T>::block_number();
+        ensure!(expiration > now, Error::<T>::OfferExpirationIsInThePast);
+        ensure!(
+            !Offers::<T>::contains_key(&target, &buyer),
+            Error::<T>::OfferAlreadyExists
+        );
+
+        OfferExpirations::<T>::try_mutate(expiration, |x| {
+            x.try_push((target.clone(), buyer.clone()))
+                .map_err(|_| Error::<T>::TooManyOffersAtBlock)
+        })?;
+        T::Currency::reserve(&buyer, price)?;
+
+        let offer = Offer::new(price, mkp_id, expiration);
+        Offers::<T>::insert(&target, &buyer, offer);
+
+        let event = Event::OfferMade {
+            target,
+            buyer,
+            price,
+            marketplace_id: mkp_id,
+            expiration,
+        };
+        Self::deposit_event(event);
+
+        Ok(())
+    }
+
+    /// Drop an offer and give its funds back to the buyer.
+    fn remove_offer(buyer: T::AccountId, target: OfferTargetOf<T>) -> DispatchResult {
+        let offer = Offers::<T>::take(&target, &buyer).ok_or(Error::<T>::OfferNotFound)?;
+        Self::remove_offer_expiration(offer.expiration, &target, &buyer);
+        T::Currency::unreserve(&buyer, offer.price);
+
+        Self::deposit_event(Event::OfferWithdrawn { target, buyer });
+
+        Ok(())
+    }
+
+    /// Unschedule the expiration of an offer.
+    fn remove_offer_expiration(
+        expiration: T::BlockNumber,
+        target: &OfferTargetOf<T>,
+        buyer: &T::AccountId,
+    ) {
+        OfferExpirations::<T>::mutate_exists(expiration, |x| {
+            if let Some(expiring) = x {
+                expiring.retain(|(t, b)| t != target || b != buyer);
+                if expiring.is_empty() {
+                    *x = None;
+                }
+            }
+        });
+    }
+
+    /// Take an NFT off the marketplace along with its listing deadline and its
+    /// entry in the marketplace index.
+    fn remove_sale(nft_id: NFTId) -> Option<SaleInformationOf<T>> {
+        let sale = NFTsForSale::<T>::take(nft_id)?;
+        MarketplaceSales::<T>::remove(sale.marketplace_id, index_key(nft_id));
+        if let Some(end) = sale.end_block {
+            ListingDeadlines::<T>::mutate_exists(end, |x| {
+                if let Some(ending) = x {
+                    ending.retain(|id| *id != nft_id);
+                    if ending.is_empty() {
+                        *x = None;
+                    }
+                }
+            });
+        }
+
+        Some(sale)
+    }
+
+    /// Return whether the order of `seller` with this nonce can still be filled.
+    fn is_order_open(seller: &T::AccountId, nonce: OrderNonce) -> bool {
+        nonce >= OrderNonces::<T>::get(seller) && !UsedOrderNonces::<T>::contains_key(seller, nonce)
+    }
+
+    /// Take a bundle off the marketplace and unlock its NFTs.
+    fn remove_bundle(bundle_id: BundleId) -> Result<BundleInformationOf<T>, DispatchError> {
+        let bundle = Bundles::<T>::take(bundle_id).ok_or(Error::<T>::UnknownBundle)?;
+        MarketplaceBundles::<T>::remove(bundle.marketplace_id, bundle_id);
+        for nft_id in bundle.nft_ids.iter() {
+            NFTBundles::<T>::remove(nft_id);
+            T::NFTs::unlock(*nft_id, NFTLockReason::ListedForSale)?;
+        }
+
+        Ok(bundle)
+    }
+
+    /// Sell an NFT of `seller` to the buyer of an offer.
+    fn sell_to_offer(
+        seller: T::AccountId,
+        nft_id: NFTId,
+        buyer: T::AccountId,
+        target: OfferTargetOf<T>,
+    ) -> DispatchResult {
+        let nft = T::NFTs::get_nft(nft_id).ok_or(Error::<T>::UnknownNFT)?;
+        ensure!(nft.owner == seller, Error::<T>::NotNftOwner);
+        ensure!(seller != buyer, Error::<T>::NftAlreadyOwned);
+
+        let is_nft_in_completed_series = T::NFTs::is_nft_in_completed_series(nft_id) == Some(true);
+        ensure!(is_nft_in_completed_series, Error::<T>::SeriesNotCompleted);
+
+        let offer = Offers::<T>::take(&target, &buyer).ok_or(Error::<T>::OfferNotFound)?;
+        Self::remove_offer_expiration(offer.expiration, &target, &buyer);
+        Self::is_allowed_to_list(offer.marketplace_id, seller.clone())?;
+        let market =
+            Marketplaces::<T>::get(offer.marketplace_id).ok_or(Error::<T>::UnknownMarketplace)?;
+
+        // An NFT listed on this pallet is taken off the marketplace, any other lock,
+        // auctions included, prevents the sale.
+        if nft.lock.is_some() {
+            if let Some(bundle_id) = NFTBundles::<T>::get(nft_id) {
+                Self::remove_bundle(bundle_id)?;
+                Self::deposit_event(Event::BundleUnlisted { bundle_id });
+            } else if NFTsForSale::<T>::contains_key(nft_id) {
+                T::NFTs::unlock(nft_id, NFTLockReason::ListedForSale)?;
+                Self::remove_sale(nft_id);
+                Self::deposit_event(Event::NftUnlisted { nft_id });
+            } else {
+                return Err(Error::<T>::NftIsLocked.into());
+            }
+        }
+
+        // Offers are always made in Caps.
+        T::Currency::unreserve(&buyer, offer.price);
+        // The seller agrees to the current terms of the marketplace by accepting.
+        let (seller_cut, marketplace_cut, royalty_cut) = Self::pay_out(
+            &[nft_id],
+            &buyer,
+            &seller,
+            offer.price,
+            &PaymentAsset::Native,
+            offer.marketplace_id,
+            &market.commission_fee,
+        )?;
+
+        T::NFTs::set_owner(nft_id, &buyer)?;
+
+        let event = Event::OfferAccepted {
+            target,
+            nft_id,
+            buyer,
+            seller_cut,
+            marketplace_cut,
+            royalty_cut,
+        };
+        Self::deposit_event(event);
+
+        Ok(())
+    }
+
+    /// Check that `who` administrates a marketplace of the `kind` whose list is being changed.
+    fn ensure_list_admin(
+        marketplace_id: MarketplaceId,
+        who: &T::AccountId,
+        kind: MarketplaceType,
+    ) -> DispatchResult {
+        let market =
+            Marketplaces::<T>::get(marketplace_id).ok_or(Error::<T>::UnknownMarketplace)?;
+        let is_admin = Self::has_role_in(&market, marketplace_id, who, MarketplaceRole::Admin);
+        ensure!(is_admin, Error::<T>::NotMarketplaceAdmin);
+        ensure!(market.kind == kind, Error::<T>::UnsupportedMarketplace);
+
+        Ok(())
+    }
+
+    /// Return whether `who` is the owner of `market` or was granted at least `role` on it.
+    fn has_role_in(
+        market: &MarketplaceInformationOf<T>,
+        marketplace_id: MarketplaceId,
+        who: &T::AccountId,
+        role: MarketplaceRole,
+    ) -> bool {
+        if market.owner == *who {
+            return true;
+        }
+
+        MarketplaceRoles::<T>::get(marketplace_id, who).map_or(false, |x| x.includes(role))
+    }
+
+    fn lookup_accounts(
+        accounts: Vec<<T::Lookup as StaticLookup>::Source>,
+    ) -> Result<Vec<T::AccountId>, DispatchError> {
+        let limit = T::MaxAccountsPerCall::get() as usize;
+        ensure!(accounts.len() <= limit, Error::<T>::TooManyAccounts);
+
+        let accounts = accounts.into_iter().map(T::Lookup::lookup);
+        let accounts = accounts.collect::<Result<Vec<_>, _>>()?;
+
+        Ok(accounts)
+    }
+
+    /// Return up to `limit` accounts of the allow list of a marketplace, in
+    /// the order of their encoding, starting after `start_after`.
+    pub fn allow_list(
+        marketplace_id: MarketplaceId,
+        start_after: Option<T::AccountId>,
+        limit: u32,
+    ) -> Vec<T::AccountId> {
+        let accounts = match start_after {
+            Some(account) => {
+                let start = AllowList::<T>::hashed_key_for(marketplace_id, account);
+                AllowList::<T>::iter_key_prefix_from(marketplace_id, start)
+            }
+            None => AllowList::<T>::iter_key_prefix(marketplace_id),
+        };
+
+        accounts.take(limit as usize).collect()
+    }
+
+    /// Return up to `limit` accounts of the disallow list of a marketplace, in
+    /// the order of their encoding, starting after `start_after`.
+    pub fn disallow_list(
+        marketplace_id: MarketplaceId,
+        start_after: Option<T::AccountId>,
+        limit: u32,
+    ) -> Vec<T::AccountId> {
+        let accounts = match start_after {
+            Some(account) => {
+                let start = DisallowList::<T>::hashed_key_for(marketplace_id, account);
+                DisallowList::<T>::iter_key_prefix_from(marketplace_id, start)
+            }
+            None => DisallowList::<T>::iter_key_prefix(marketplace_id),
+        };
+
+        accounts.take(limit as usize).collect()
+    }
+
+    /// Return up to `limit` NFTs listed on a marketplace, in ascending order,
+    /// starting after `start_after`. Listings reserved to some buyers are only
+    /// returned to those buyers and to the seller, so only those listings are
+    /// read to check the caller.
+    pub fn listings(
+        marketplace_id: MarketplaceId,
+        caller: Option<T::AccountId>,
+        start_after: Option<NFTId>,
+        limit: u32,
+    ) -> Vec<NFTId> {
+        let can_see = |nft_id: &NFTId| match &caller {
+            Some(caller) => NFTsForSale::<T>::get(nft_id).map_or(false, |sale| {
+                sale.account_id == *caller || sale.can_buy(caller)
+            }),
+            None => false,
+        };
+        let entries = match start_after {
+            Some(id) => {
+                let start = MarketplaceSales::<T>::hashed_key_for(marketplace_id, index_key(id));
+                MarketplaceSales::<T>::iter_prefix_from(marketplace_id, start)
+            }
+            None => MarketplaceSales::<T>::iter_prefix(marketplace_id),
+        };
+
+        entries
+            .map(|(key, is_reserved)| (NFTId::from_be_bytes(key), is_reserved))
+            .filter(|(nft_id, is_reserved)| !is_reserved || can_see(nft_id))
+            .map(|(nft_id, _)| nft_id)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Hash of the genesis block, which orders and vouchers are signed with so
+    /// that their signatures are only valid on this chain.
+    fn genesis_hash() -> T::Hash {
+        frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero())
+    }
+}
+
+impl<T: Config> MarketplaceTrait<T::AccountId, BalanceOf<T>> for Pallet<T> {
+    type NameLengthLimit = U16ToU32<T::MaxNameLen>;
+    type URILengthLimit = U16ToU32<T::MaxUriLen>;
+    type DescriptionLengthLimit = U16ToU32<T::MaxDescriptionLen>;
+    type AssetId = T::AssetId;
+
     // Return if an account is permitted to list on given marketplace
     fn is_allowed_to_list(
         marketplace_id: MarketplaceId,
@@ -859,22 +2508,47 @@ impl<T: Config> MarketplaceTrait<T::AccountId> for Pallet<T> {
     ) -> DispatchResult {
         let market =
             Marketplaces::<T>::get(marketplace_id).ok_or(Error::<T>::UnknownMarketplace)?;
+        let is_closing = ClosingMarketplaces::<T>::contains_key(marketplace_id);
+        ensure!(!is_closing, Error::<T>::MarketplaceClosing);
 
         if market.kind == MarketplaceType::Private {
-            let is_on_list = market.allow_list.contains(&account_id);
+            let is_on_list = AllowList::<T>::contains_key(marketplace_id, &account_id);
             ensure!(is_on_list, Error::<T>::NotAllowedToList);
             Ok(())
         } else {
-            let is_on_list = market.disallow_list.contains(&account_id);
+            let is_on_list = DisallowList::<T>::contains_key(marketplace_id, &account_id);
             ensure!(!is_on_list, Error::<T>::NotAllowedToList);
             Ok(())
         }
     }
 
-    // Return the owner account and commision for marketplace with `marketplace_id`
-    fn get_marketplace(
+    // The owner has every role, admins also have the rights of curators
+    fn has_role(
+        marketplace_id: MarketplaceId,
+        account_id: &T::AccountId,
+        role: MarketplaceRole,
+    ) -> bool {
+        match Marketplaces::<T>::get(marketplace_id) {
+            Some(market) => Self::has_role_in(&market, marketplace_id, account_id, role),
+            None => false,
+        }
+    }
+
+    // Caps are accepted everywhere, other assets only if the marketplace owner allowed them
+    fn is_asset_accepted(
         marketplace_id: MarketplaceId,
-    ) -> Option<MarketplaceInformation<T::AccountId>> {
+        asset: &PaymentAssetOf<T>,
+    ) -> DispatchResult {
+        if let PaymentAsset::Asset(id) = asset {
+            let accepted = AcceptedAssets::<T>::get(marketplace_id).contains(id);
+            ensure!(accepted, Error::<T>::AssetNotAccepted);
+        }
+
+        Ok(())
+    }
+
+    // Return the owner account and commision for marketplace with `marketplace_id`
+    fn get_marketplace(marketplace_id: MarketplaceId) -> Option<MarketplaceInformationOf<T>> {
         match Marketplaces::<T>::get(marketplace_id) {
             Some(marketplace) => Some(marketplace),
             None => None,
@@ -885,7 +2559,7 @@ impl<T: Config> MarketplaceTrait<T::AccountId> for Pallet<T> {
     fn create(
         caller_id: <T as frame_system::Config>::AccountId,
         kind: MarketplaceType,
-        commission_fee: u8,
+        commission_fee: MarketplaceCommissionOf<T>,
         name: TextFormat,
         uri: Option<TextFormat>,
         logo_uri: Option<TextFormat>,
@@ -904,3 +2578,34 @@ impl<T: Config> MarketplaceTrait<T::AccountId> for Pallet<T> {
         Ok(MarketplaceIdGenerator::<T>::get())
     }
 }
+
+impl<T: Config> NFTReleaseHandler for Pallet<T> {
+    /// Take down the sale of an NFT, or the whole bundle it is part of.
+    fn release_nft(nft_id: NFTId, reason: NFTLockReason) -> Result<Weight, DispatchError> {
+        if reason != NFTLockReason::ListedForSale {
+            return Ok(0);
+        }
+
+        if let Some(bundle_id) = NFTBundles::<T>::get(nft_id) {
+            let bundle = Self::remove_bundle(bundle_id)?;
+            Self::deposit_event(Event::BundleUnlisted { bundle_id });
+            Ok(T::WeightInfo::unlist_bundle(bundle.nft_ids.len() as u32))
+        } else if Self::remove_sale(nft_id).is_some() {
+            Self::deposit_event(Event::NftUnlisted { nft_id });
+            Ok(T::WeightInfo::unlist())
+        } else {
+            Ok(T::DbWeight::get().reads(2))
+        }
+    }
+
+    fn max_release_weight() -> Weight {
+        let bundle = T::WeightInfo::unlist_bundle(T::MaxBundleSize::get());
+        bundle.max(T::WeightInfo::unlist())
+    }
+}
+
+/// Key of an NFT in the marketplace index. Big-endian keys keep the listings
+/// of a marketplace in ascending order.
+pub(crate) fn index_key(id: NFTId) -> IndexKey {
+    id.to_be_bytes()
+}
@@ -1,27 +1,99 @@
-/* pub mod v6;
+pub mod v2;
+pub mod v3;
+pub mod v4;
+pub mod v5;
+pub mod v6;
 pub mod v7;
+pub mod v8;
+pub mod v9;
 
-use crate::{Config, Pallet, Weight};
+use crate::{Config, Pallet};
 use frame_support::traits::StorageVersion;
+use frame_support::weights::Weight;
 
 pub fn migrate<T: Config>() -> Weight {
-    let weight: Weight = 0;
-    let storage_version = StorageVersion::get::<Pallet<T>>();
+    let mut weight: Weight = 0;
 
-    if storage_version == 7 {
-        log::info!(target: "runtime::marketplace", "Marketplace pallet: no migration was run.");
-        return weight;
+    if StorageVersion::get::<Pallet<T>>() == 1 {
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migrating to StorageVersion V2");
+
+        match v2::migrate::<T>() {
+            Ok(used) => {
+                weight = weight.saturating_add(used);
+                StorageVersion::new(2).put::<Pallet<T>>();
+
+                log::info!(target: "runtime::marketplace", "Marketplace pallet: migration to StorageVersion V2 done");
+            }
+            Err(used) => {
+                weight = weight.saturating_add(used);
+
+                log::error!(target: "runtime::marketplace", "Marketplace pallet: migration to StorageVersion V2 aborted");
+            }
+        }
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 2 {
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migrating to StorageVersion V3");
+
+        weight = weight.saturating_add(v3::migrate::<T>());
+        StorageVersion::new(3).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migration to StorageVersion V3 done");
     }
 
-    if storage_version == 6 {
+    if StorageVersion::get::<Pallet<T>>() == 3 {
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migrating to StorageVersion V4");
+
+        weight = weight.saturating_add(v4::migrate::<T>());
+        StorageVersion::new(4).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migration to StorageVersion V4 done");
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 4 {
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migrating to StorageVersion V5");
+
+        weight = weight.saturating_add(v5::migrate::<T>());
+        StorageVersion::new(5).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migration to StorageVersion V5 done");
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 5 {
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migrating to StorageVersion V6");
+
+        weight = weight.saturating_add(v6::migrate::<T>());
+        StorageVersion::new(6).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migration to StorageVersion V6 done");
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 6 {
         log::info!(target: "runtime::marketplace", "Marketplace pallet: migrating to StorageVersion V7");
 
-        weight = v7::migrate::<T>();
+        weight = weight.saturating_add(v7::migrate::<T>());
         StorageVersion::new(7).put::<Pallet<T>>();
 
         log::info!(target: "runtime::marketplace", "Marketplace pallet: migration to StorageVersion V7 done");
     }
 
+    if StorageVersion::get::<Pallet<T>>() == 7 {
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migrating to StorageVersion V8");
+
+        weight = weight.saturating_add(v8::migrate::<T>());
+        StorageVersion::new(8).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migration to StorageVersion V8 done");
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 8 {
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migrating to StorageVersion V9");
+
+        weight = weight.saturating_add(v9::migrate::<T>());
+        StorageVersion::new(9).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::marketplace", "Marketplace pallet: migration to StorageVersion V9 done");
+    }
+
     weight
 }
- */
@@ -0,0 +1,122 @@
+use crate::Config;
+use frame_support::storage::migration::storage_key_iter;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use frame_support::Blake2_128Concat;
+use ternoa_common::helpers::{fits_bound, truncate_to_bound, U16ToU32};
+use ternoa_primitives::marketplace::MarketplaceId;
+
+pub mod v1 {
+    use codec::{Decode, Encode};
+    use sp_runtime::RuntimeDebug;
+    use sp_std::vec::Vec;
+    use ternoa_primitives::marketplace::MarketplaceType;
+    use ternoa_primitives::TextFormat;
+
+    // MarketplaceInformation as it was stored before its fields were bounded
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct MarketplaceInformation<AccountId> {
+        pub kind: MarketplaceType,
+        pub commission_fee: u8,
+        pub owner: AccountId,
+        pub allow_list: Vec<AccountId>,
+        pub disallow_list: Vec<AccountId>,
+        pub name: TextFormat,
+        pub uri: Option<TextFormat>,
+        pub logo_uri: Option<TextFormat>,
+        pub description: Option<TextFormat>,
+    }
+}
+
+pub mod v2 {
+    use crate::Config;
+    use codec::{Decode, Encode};
+    use frame_support::traits::ConstU32;
+    use frame_support::{Blake2_128Concat, BoundedVec};
+    use ternoa_common::helpers::U16ToU32;
+    use ternoa_primitives::marketplace::{MarketplaceId, MarketplaceType};
+
+    // Bound the account lists had until they were moved to their own storage
+    pub type MaxAccountListSize = ConstU32<10_000>;
+
+    // MarketplaceInformation as it was stored while the commission was a percentage
+    #[derive(Encode, Decode)]
+    pub struct MarketplaceInformation<T: Config> {
+        pub kind: MarketplaceType,
+        pub commission_fee: u8,
+        pub owner: T::AccountId,
+        pub allow_list: BoundedVec<T::AccountId, MaxAccountListSize>,
+        pub disallow_list: BoundedVec<T::AccountId, MaxAccountListSize>,
+        pub name: BoundedVec<u8, U16ToU32<T::MaxNameLen>>,
+        pub uri: Option<BoundedVec<u8, U16ToU32<T::MaxUriLen>>>,
+        pub logo_uri: Option<BoundedVec<u8, U16ToU32<T::MaxUriLen>>>,
+        pub description: Option<BoundedVec<u8, U16ToU32<T::MaxDescriptionLen>>>,
+    }
+
+    frame_support::generate_storage_alias!(
+        Marketplace, Marketplaces<T: Config> => Map<
+            (Blake2_128Concat, MarketplaceId),
+            MarketplaceInformation<T>
+        >
+    );
+}
+
+/// Bounds the account lists and the texts of every marketplace. Nothing is
+/// changed if one of them does not fit: every offending marketplace is logged
+/// and an error is returned so that the storage version stays the same.
+pub fn migrate<T: Config>() -> Result<Weight, Weight> {
+    let mut count: u64 = 0;
+    let mut fits = true;
+
+    for (id, old) in storage_key_iter::<
+        MarketplaceId,
+        v1::MarketplaceInformation<T::AccountId>,
+        Blake2_128Concat,
+    >(b"Marketplace", b"Marketplaces")
+    {
+        count += 1;
+
+        let texts_fit = fits_bound::<_, U16ToU32<T::MaxNameLen>>(&old.name)
+            && old
+                .uri
+                .as_ref()
+                .map_or(true, |x| fits_bound::<_, U16ToU32<T::MaxUriLen>>(x))
+            && old
+                .logo_uri
+                .as_ref()
+                .map_or(true, |x| fits_bound::<_, U16ToU32<T::MaxUriLen>>(x))
+            && old
+                .description
+                .as_ref()
+                .map_or(true, |x| fits_bound::<_, U16ToU32<T::MaxDescriptionLen>>(x));
+
+        if !texts_fit
+            || !fits_bound::<_, v2::MaxAccountListSize>(&old.allow_list)
+            || !fits_bound::<_, v2::MaxAccountListSize>(&old.disallow_list)
+        {
+            log::error!(target: "runtime::marketplace", "Marketplace {} does not fit", id);
+            fits = false;
+        }
+    }
+
+    if !fits {
+        return Err(T::DbWeight::get().reads(count));
+    }
+
+    // Everything has been checked above, so nothing gets truncated from here on.
+    v2::Marketplaces::<T>::translate::<v1::MarketplaceInformation<T::AccountId>, _>(|_, old| {
+        Some(v2::MarketplaceInformation::<T> {
+            kind: old.kind,
+            commission_fee: old.commission_fee,
+            owner: old.owner,
+            allow_list: truncate_to_bound(old.allow_list),
+            disallow_list: truncate_to_bound(old.disallow_list),
+            name: truncate_to_bound(old.name),
+            uri: old.uri.map(truncate_to_bound),
+            logo_uri: old.logo_uri.map(truncate_to_bound),
+            description: old.description.map(truncate_to_bound),
+        })
+    });
+
+    Ok(T::DbWeight::get().reads_writes(count.saturating_mul(2), count))
+}
@@ -0,0 +1,35 @@
+use crate::migrations::v2::v2;
+use crate::migrations::v7::v6;
+use crate::Config;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use sp_runtime::traits::Zero;
+use sp_runtime::Permill;
+use ternoa_primitives::marketplace::MarketplaceCommission;
+
+/// Turns the percentage commission of every marketplace into an exact rate
+/// without any flat fee.
+pub fn migrate<T: Config>() -> Weight {
+    let mut count: u64 = 0;
+
+    v6::Marketplaces::<T>::translate::<v2::MarketplaceInformation<T>, _>(|_, old| {
+        count += 1;
+
+        let rate = Permill::from_percent(old.commission_fee.min(100).into());
+        let info = v6::MarketplaceInformation::<T> {
+            kind: old.kind,
+            commission_fee: MarketplaceCommission::new(rate, Zero::zero()),
+            owner: old.owner,
+            allow_list: old.allow_list,
+            disallow_list: old.disallow_list,
+            name: old.name,
+            uri: old.uri,
+            logo_uri: old.logo_uri,
+            description: old.description,
+        };
+
+        Some(info)
+    });
+
+    T::DbWeight::get().reads_writes(count, count)
+}
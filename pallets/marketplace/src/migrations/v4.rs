@@ -0,0 +1,69 @@
+use crate::migrations::v7::v6;
+use crate::Config;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+
+pub mod v3 {
+    use codec::{Decode, Encode};
+    use sp_runtime::RuntimeDebug;
+    use ternoa_primitives::marketplace::MarketplaceId;
+
+    // SaleInformation as it was stored before the commission was recorded
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct SaleInformation<AccountId, Balance> {
+        pub account_id: AccountId,
+        pub price: Balance,
+        pub marketplace_id: MarketplaceId,
+    }
+}
+
+pub mod v4 {
+    use crate::{BalanceOf, Config};
+    use codec::{Decode, Encode};
+    use frame_support::Blake2_128Concat;
+    use ternoa_primitives::marketplace::{MarketplaceCommission, MarketplaceId};
+    use ternoa_primitives::nfts::NFTId;
+
+    // SaleInformation as it was stored before listings had a start and an end block
+    #[derive(Encode, Decode)]
+    pub struct SaleInformation<T: Config> {
+        pub account_id: T::AccountId,
+        pub price: BalanceOf<T>,
+        pub marketplace_id: MarketplaceId,
+        pub commission_fee: MarketplaceCommission<BalanceOf<T>>,
+    }
+
+    frame_support::generate_storage_alias!(
+        Marketplace, NFTsForSale<T: Config> => Map<
+            (Blake2_128Concat, NFTId),
+            SaleInformation<T>
+        >
+    );
+}
+
+/// Records the current commission of their marketplace in every sale.
+pub fn migrate<T: Config>() -> Weight {
+    let mut reads: u64 = 0;
+    let mut writes: u64 = 0;
+
+    type OldSaleInformation<T> =
+        v3::SaleInformation<<T as frame_system::Config>::AccountId, crate::BalanceOf<T>>;
+
+    v4::NFTsForSale::<T>::translate::<OldSaleInformation<T>, _>(|_, old| {
+        reads += 2;
+        writes += 1;
+
+        let commission_fee = v6::Marketplaces::<T>::get(old.marketplace_id)
+            .map(|x| x.commission_fee)
+            .unwrap_or_default();
+
+        Some(v4::SaleInformation {
+            account_id: old.account_id,
+            price: old.price,
+            marketplace_id: old.marketplace_id,
+            commission_fee,
+        })
+    });
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
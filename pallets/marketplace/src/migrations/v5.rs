@@ -0,0 +1,50 @@
+use crate::migrations::v4::v4;
+use crate::Config;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+
+pub mod v5 {
+    use crate::{BalanceOf, Config};
+    use codec::{Decode, Encode};
+    use frame_support::Blake2_128Concat;
+    use ternoa_primitives::marketplace::{MarketplaceCommission, MarketplaceId};
+    use ternoa_primitives::nfts::NFTId;
+
+    // SaleInformation as it was stored before listings could be paid with assets
+    #[derive(Encode, Decode)]
+    pub struct SaleInformation<T: Config> {
+        pub account_id: T::AccountId,
+        pub price: BalanceOf<T>,
+        pub marketplace_id: MarketplaceId,
+        pub commission_fee: MarketplaceCommission<BalanceOf<T>>,
+        pub start_block: Option<T::BlockNumber>,
+        pub end_block: Option<T::BlockNumber>,
+    }
+
+    frame_support::generate_storage_alias!(
+        Marketplace, NFTsForSale<T: Config> => Map<
+            (Blake2_128Concat, NFTId),
+            SaleInformation<T>
+        >
+    );
+}
+
+/// Existing listings have no start block and never end.
+pub fn migrate<T: Config>() -> Weight {
+    let mut count: u64 = 0;
+
+    v5::NFTsForSale::<T>::translate::<v4::SaleInformation<T>, _>(|_, old| {
+        count += 1;
+
+        Some(v5::SaleInformation {
+            account_id: old.account_id,
+            price: old.price,
+            marketplace_id: old.marketplace_id,
+            commission_fee: old.commission_fee,
+            start_block: None,
+            end_block: None,
+        })
+    });
+
+    T::DbWeight::get().reads_writes(count, count)
+}
@@ -1,57 +1,27 @@
-/* pub mod v6 {
-    use crate::Config;
-    use codec::{Decode, Encode};
-    use frame_support::Blake2_128Concat;
-    #[cfg(feature = "std")]
-    use serde::{Deserialize, Serialize};
-    use sp_runtime::RuntimeDebug;
-    use sp_std::vec::Vec;
+use crate::migrations::v5::v5;
+use crate::migrations::v8::v7;
+use crate::Config;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use ternoa_primitives::marketplace::PaymentAsset;
 
-    #[allow(dead_code)]
-    pub type MarketplaceId = u32;
+/// Existing listings are paid with Caps.
+pub fn migrate<T: Config>() -> Weight {
+    let mut count: u64 = 0;
 
-    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
-    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-    pub enum MarketplaceType {
-        Public,
-        Private,
-    }
+    v7::NFTsForSale::<T>::translate::<v5::SaleInformation<T>, _>(|_, old| {
+        count += 1;
 
-    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-    pub struct MarketplaceInformation<T: Config> {
-        pub kind: MarketplaceType,
-        pub commission_fee: u8,
-        pub owner: T::AccountId,
-        pub allow_list: Vec<T::AccountId>,
-        pub name: Vec<u8>,
-    }
+        Some(v7::SaleInformation {
+            account_id: old.account_id,
+            price: old.price,
+            asset: PaymentAsset::Native,
+            marketplace_id: old.marketplace_id,
+            commission_fee: old.commission_fee,
+            start_block: old.start_block,
+            end_block: old.end_block,
+        })
+    });
 
-    frame_support::generate_storage_alias!(
-        Marketplace, Marketplaces<T: Config> => Map<
-            (Blake2_128Concat, MarketplaceId),
-            MarketplaceInformation<T>
-        >
-    );
-
-    #[allow(dead_code)]
-    pub fn insert_marketplace<T: Config>(
-        id: MarketplaceId,
-        owner: T::AccountId,
-        kind: MarketplaceType,
-        commission_fee: u8,
-        allow_list: Vec<T::AccountId>,
-        name: Vec<u8>,
-    ) {
-        let data = MarketplaceInformation {
-            kind,
-            commission_fee,
-            owner,
-            allow_list,
-            name,
-        };
-
-        Marketplaces::<T>::insert(id, data);
-    }
+    T::DbWeight::get().reads_writes(count, count)
 }
- */
@@ -0,0 +1,52 @@
+use crate::{Config, NFTsForSale, SaleInformation};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+
+pub mod v7 {
+    use crate::{BalanceOf, Config};
+    use codec::{Decode, Encode};
+    use frame_support::Blake2_128Concat;
+    use ternoa_primitives::marketplace::{MarketplaceCommission, MarketplaceId, PaymentAsset};
+    use ternoa_primitives::nfts::NFTId;
+
+    // SaleInformation as it was stored before listings could be reserved to some buyers
+    #[derive(Encode, Decode)]
+    pub struct SaleInformation<T: Config> {
+        pub account_id: T::AccountId,
+        pub price: BalanceOf<T>,
+        pub asset: PaymentAsset<T::AssetId>,
+        pub marketplace_id: MarketplaceId,
+        pub commission_fee: MarketplaceCommission<BalanceOf<T>>,
+        pub start_block: Option<T::BlockNumber>,
+        pub end_block: Option<T::BlockNumber>,
+    }
+
+    frame_support::generate_storage_alias!(
+        Marketplace, NFTsForSale<T: Config> => Map<
+            (Blake2_128Concat, NFTId),
+            SaleInformation<T>
+        >
+    );
+}
+
+/// Existing listings can be bought by anyone.
+pub fn migrate<T: Config>() -> Weight {
+    let mut count: u64 = 0;
+
+    NFTsForSale::<T>::translate::<v7::SaleInformation<T>, _>(|_, old| {
+        count += 1;
+
+        Some(SaleInformation::new(
+            old.account_id,
+            old.price,
+            old.asset,
+            old.marketplace_id,
+            old.commission_fee,
+            old.start_block,
+            old.end_block,
+            Default::default(),
+        ))
+    });
+
+    T::DbWeight::get().reads_writes(count, count)
+}
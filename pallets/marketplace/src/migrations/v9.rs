@@ -0,0 +1,20 @@
+use crate::{index_key, Bundles, Config, MarketplaceBundles, MarketplaceSales, NFTsForSale};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+
+/// Index the existing listings and bundles by marketplace.
+pub fn migrate<T: Config>() -> Weight {
+    let mut count: u64 = 0;
+
+    for (nft_id, sale) in NFTsForSale::<T>::iter() {
+        count += 1;
+        MarketplaceSales::<T>::insert(sale.marketplace_id, index_key(nft_id), sale.is_private());
+    }
+
+    for (bundle_id, bundle) in Bundles::<T>::iter() {
+        count += 1;
+        MarketplaceBundles::<T>::insert(bundle.marketplace_id, bundle_id, ());
+    }
+
+    T::DbWeight::get().reads_writes(count, count)
+}
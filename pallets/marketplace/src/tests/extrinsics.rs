@@ -1,15 +1,30 @@
 use super::mock::*;
 use crate::tests::mock;
-use crate::{Error, MarketplaceInformation, SaleInformation};
+use crate::{
+    BundleInformation, Error, Event as MarketplaceEvent, MarketplaceBundles,
+    MarketplaceInformation, MarketplaceSales, MaxBuyers, Offer, OfferTarget, SaleInformation,
+    UsedOrderNonces,
+};
+use codec::Encode;
 use frame_support::error::BadOrigin;
+use frame_support::traits::{Get, OnInitialize};
 use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
 use pallet_balances::Error as BalanceError;
-use ternoa_common::traits::NFTTrait;
-use ternoa_primitives::marketplace::MarketplaceType;
+use sp_core::H256;
+use sp_runtime::testing::TestSignature;
+use sp_runtime::Permill;
+use std::convert::TryInto;
+use ternoa_common::traits::{MarketplaceTrait, NFTTrait};
+use ternoa_nfts::Error as NFTError;
+use ternoa_primitives::marketplace::{
+    MarketplaceCommission, MarketplaceRole, MarketplaceType, MintVoucher, Order, PaymentAsset,
+};
+use ternoa_primitives::nfts::NFTLockReason;
 use ternoa_primitives::TextFormat;
 
 type MPT = MarketplaceType;
+type MPR = MarketplaceRole;
 
 #[test]
 fn list_happy() {
@@ -25,25 +40,67 @@ fn list_happy() {
             let series_id = vec![50];
             let nft_id =
                 <NFTs as NFTTrait>::create_nft(ALICE, vec![50], Some(series_id.clone())).unwrap();
-            let sale_info = SaleInformation::new(ALICE, price.clone(), 0);
+            let sale_info = SaleInformation::new(
+                ALICE,
+                price.clone(),
+                PaymentAsset::Native,
+                0,
+                commission(0, 0),
+                None,
+                None,
+                Default::default(),
+            );
 
             help::finish_series(alice.clone(), series_id);
-            assert_ok!(Marketplace::list(alice.clone(), nft_id, price, Some(0)));
+            assert_ok!(Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(0),
+                None,
+                None,
+                vec![]
+            ));
             assert_eq!(Marketplace::nft_for_sale(nft_id), Some(sale_info));
-            assert_eq!(<NFTs as NFTTrait>::is_listed_for_sale(nft_id), Some(true));
+            assert_eq!(
+                <NFTs as NFTTrait>::is_locked(nft_id, NFTLockReason::ListedForSale),
+                Some(true)
+            );
 
             // Happy path Private marketplace
             let series_id = vec![51];
-            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 0, vec![1], vec![ALICE]);
-            let sale_info = SaleInformation::new(ALICE, price.clone(), mkp_id);
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 10, vec![1], vec![ALICE]);
+            let sale_info = SaleInformation::new(
+                ALICE,
+                price.clone(),
+                PaymentAsset::Native,
+                mkp_id,
+                commission(10, 0),
+                None,
+                None,
+                Default::default(),
+            );
             let nft_id =
                 <NFTs as NFTTrait>::create_nft(ALICE, vec![50], Some(series_id.clone())).unwrap();
 
             help::finish_series(alice.clone(), series_id);
-            let ok = Marketplace::list(alice.clone(), nft_id, price, Some(mkp_id));
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(mkp_id),
+                None,
+                None,
+                vec![],
+            );
             assert_ok!(ok);
             assert_eq!(Marketplace::nft_for_sale(nft_id), Some(sale_info));
-            assert_eq!(<NFTs as NFTTrait>::is_listed_for_sale(nft_id), Some(true));
+            assert_eq!(
+                <NFTs as NFTTrait>::is_locked(nft_id, NFTLockReason::ListedForSale),
+                Some(true)
+            );
         })
 }
 
@@ -58,46 +115,143 @@ fn list_unhappy() {
             let price = 50;
 
             // Unhappy unknown NFT
-            let ok = Marketplace::list(alice.clone(), 10001, price, Some(0));
+            let ok = Marketplace::list(
+                alice.clone(),
+                10001,
+                price,
+                PaymentAsset::Native,
+                Some(0),
+                None,
+                None,
+                vec![],
+            );
             assert_noop!(ok, Error::<Test>::UnknownNFT);
 
             // Unhappy not the NFT owner
             let nft_id = <NFTs as NFTTrait>::create_nft(BOB, vec![50], None).unwrap();
-            let ok = Marketplace::list(alice.clone(), nft_id, price, Some(0));
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(0),
+                None,
+                None,
+                vec![],
+            );
             assert_noop!(ok, Error::<Test>::NotNftOwner);
 
             // Unhappy series not completed
             let series_id = vec![50];
             let nft_id =
                 <NFTs as NFTTrait>::create_nft(ALICE, vec![50], Some(series_id.clone())).unwrap();
-            let ok = Marketplace::list(alice.clone(), nft_id, price, Some(0));
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(0),
+                None,
+                None,
+                vec![],
+            );
             assert_noop!(ok, Error::<Test>::SeriesNotCompleted);
 
             // Unhappy nft is capsulized
             help::finish_series(alice.clone(), series_id);
-            <NFTs as NFTTrait>::set_converted_to_capsule(nft_id, true).unwrap();
-            let ok = Marketplace::list(alice.clone(), nft_id, price, Some(0));
-            assert_noop!(ok, Error::<Test>::CannotListCapsules);
-            <NFTs as NFTTrait>::set_converted_to_capsule(nft_id, false).unwrap();
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::Capsule).unwrap();
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(0),
+                None,
+                None,
+                vec![],
+            );
+            assert_noop!(ok, NFTError::<Test>::NFTIsCapsule);
+            <NFTs as NFTTrait>::unlock(nft_id, NFTLockReason::Capsule).unwrap();
+
+            // Unhappy nft is rented
+            assert_ok!(NFTs::offer_rental(alice.clone(), nft_id, 0, 10, None));
+            assert_ok!(NFTs::rent(bob.clone(), nft_id));
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(0),
+                None,
+                None,
+                vec![],
+            );
+            assert_noop!(ok, NFTError::<Test>::NFTIsRented);
+            assert_ok!(NFTs::end_rental(bob.clone(), nft_id));
 
             // Unhappy unknown marketplace
-            let ok = Marketplace::list(alice.clone(), nft_id, price, Some(10001));
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(10001),
+                None,
+                None,
+                vec![],
+            );
             assert_noop!(ok, Error::<Test>::UnknownMarketplace);
 
             // Unhappy not on the private list
             let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 0, vec![1], vec![]);
-            let ok = Marketplace::list(alice.clone(), nft_id, price, Some(mkp_id));
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(mkp_id),
+                None,
+                None,
+                vec![],
+            );
             assert_noop!(ok, Error::<Test>::NotAllowedToList);
 
             // Unhappy on the disallow list
             let mkp_id = help::create_mkp(bob.clone(), MPT::Public, 0, vec![1], vec![ALICE]);
-            let ok = Marketplace::list(alice.clone(), nft_id, price, Some(mkp_id));
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(mkp_id),
+                None,
+                None,
+                vec![],
+            );
             assert_noop!(ok, Error::<Test>::NotAllowedToList);
 
             // Unhappy already listed for sale
-            <NFTs as NFTTrait>::set_listed_for_sale(nft_id, true).unwrap();
-            let ok = Marketplace::list(alice.clone(), nft_id, price, None);
-            assert_noop!(ok, Error::<Test>::AlreadyListedForSale);
+            assert_ok!(Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![]
+            ));
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![],
+            );
+            assert_noop!(ok, NFTError::<Test>::NFTIsListedForSale);
         })
 }
 
@@ -116,10 +270,22 @@ fn unlist_happy() {
 
             // Happy path
             help::finish_series(alice.clone(), series_id);
-            assert_ok!(Marketplace::list(alice.clone(), nft_id, price, Some(0)));
+            assert_ok!(Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(0),
+                None,
+                None,
+                vec![]
+            ));
             assert_ok!(Marketplace::unlist(alice.clone(), nft_id));
             assert_eq!(Marketplace::nft_for_sale(nft_id), None);
-            assert_eq!(<NFTs as NFTTrait>::is_listed_for_sale(nft_id), Some(false));
+            assert_eq!(
+                <NFTs as NFTTrait>::is_locked(nft_id, NFTLockReason::ListedForSale),
+                Some(false)
+            );
         })
 }
 
@@ -142,6 +308,41 @@ fn unlist_unhappy() {
         })
 }
 
+#[test]
+fn force_transfer_unlists_nft() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let series_id = vec![50];
+            let nft_id =
+                <NFTs as NFTTrait>::create_nft(ALICE, vec![50], Some(series_id.clone())).unwrap();
+            help::finish_series(alice.clone(), series_id);
+            assert_ok!(Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                Some(0),
+                None,
+                None,
+                vec![]
+            ));
+
+            assert_ok!(NFTs::force_transfer(RawOrigin::Root.into(), nft_id, BOB));
+            assert_eq!(Marketplace::nft_for_sale(nft_id), None);
+            assert_eq!(NFTs::data(nft_id).unwrap().lock, None);
+            let event = Event::Marketplace(MarketplaceEvent::NftUnlisted { nft_id });
+            assert!(System::events().iter().any(|x| x.event == event));
+
+            let ok = Marketplace::buy(bob.clone(), nft_id);
+            assert_noop!(ok, Error::<Test>::NftNotForSale);
+        })
+}
+
 #[test]
 fn buy_happy() {
     ExtBuilder::default()
@@ -158,9 +359,27 @@ fn buy_happy() {
             let mkt_id = help::create_mkp(dave.clone(), MPT::Private, 10, vec![0], vec![ALICE]);
 
             let price = 50;
-            assert_ok!(Marketplace::list(alice.clone(), nft_id_1, price, None));
+            assert_ok!(Marketplace::list(
+                alice.clone(),
+                nft_id_1,
+                price,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![]
+            ));
 
-            let ok = Marketplace::list(alice.clone(), nft_id_2, price, Some(mkt_id));
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id_2,
+                price,
+                PaymentAsset::Native,
+                Some(mkt_id),
+                None,
+                None,
+                vec![],
+            );
             assert_ok!(ok);
 
             // Happy path CAPS
@@ -169,7 +388,7 @@ fn buy_happy() {
 
             assert_ok!(Marketplace::buy(bob.clone(), nft_id_1));
             assert_eq!(
-                <NFTs as NFTTrait>::is_listed_for_sale(nft_id_1),
+                <NFTs as NFTTrait>::is_locked(nft_id_1, NFTLockReason::ListedForSale),
                 Some(false)
             );
             assert_eq!(<NFTs as NFTTrait>::owner(nft_id_1), Some(BOB));
@@ -185,7 +404,7 @@ fn buy_happy() {
 
             assert_ok!(Marketplace::buy(bob.clone(), nft_id_2));
             assert_eq!(
-                <NFTs as NFTTrait>::is_listed_for_sale(nft_id_2),
+                <NFTs as NFTTrait>::is_locked(nft_id_2, NFTLockReason::ListedForSale),
                 Some(false)
             );
             assert_eq!(<NFTs as NFTTrait>::owner(nft_id_2), Some(BOB));
@@ -198,621 +417,3492 @@ fn buy_happy() {
 }
 
 #[test]
-fn buy_unhappy() {
+fn buy_with_royalty() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 100), (BOB, 100)])
-        .tiime(vec![(ALICE, 100), (BOB, 100)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
             let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
 
-            let price = 5000;
-            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
-            assert_ok!(Marketplace::list(alice.clone(), nft_id, price, None));
+            let series_id = vec![50];
+            let royalty = Permill::from_percent(10);
+            let ok = NFTs::create(alice.clone(), vec![50], Some(series_id.clone()), royalty);
+            assert_ok!(ok);
+            let nft_id = NFTs::nft_id_generator() - 1;
+            help::finish_series(alice.clone(), series_id);
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Public, 10, vec![0], vec![]);
 
-            // Unhappy nft not on sale
-            let ok = Marketplace::buy(bob.clone(), 1001);
-            assert_noop!(ok, Error::<Test>::NftNotForSale);
+            // Primary sale: the creator is the seller so no royalty is paid
+            let price = 100;
+            assert_ok!(Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![]
+            ));
 
-            // Unhappy not enough caps
-            let ok = Marketplace::buy(bob.clone(), nft_id);
-            assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
+            let alice_before = Balances::free_balance(ALICE);
+            assert_ok!(Marketplace::buy(bob.clone(), nft_id));
+            assert_eq!(Balances::free_balance(ALICE), alice_before + price);
+
+            // Secondary sale: creator first, then marketplace, then seller
+            assert_ok!(Marketplace::list(
+                bob.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(mkt_id),
+                None,
+                None,
+                vec![]
+            ));
+
+            let alice_before = Balances::free_balance(ALICE);
+            let bob_before = Balances::free_balance(BOB);
+            let dave_before = Balances::free_balance(DAVE);
+
+            assert_ok!(Marketplace::buy(dave.clone(), nft_id));
+            assert_eq!(<NFTs as NFTTrait>::owner(nft_id), Some(DAVE));
+
+            let royalty_cut = 10;
+            let marketplace_cut = 10;
+            let seller_cut = price - royalty_cut - marketplace_cut;
+            assert_eq!(Balances::free_balance(ALICE), alice_before + royalty_cut);
+            assert_eq!(Balances::free_balance(BOB), bob_before + seller_cut);
+            assert_eq!(
+                Balances::free_balance(DAVE),
+                dave_before - price + marketplace_cut
+            );
+
+            let event = MarketplaceEvent::NftSold {
+                nft_id,
+                owner: DAVE,
+                seller_cut,
+                marketplace_cut,
+                royalty_cut,
+            };
+            let event = Event::Marketplace(event);
+            assert_eq!(System::events().last().unwrap().event, event);
         })
 }
 
 #[test]
-fn create_happy() {
+fn buy_with_full_royalty() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 10000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
 
-            assert_eq!(Marketplace::marketplace_id_generator(), 0);
-            assert_eq!(Marketplace::marketplaces(1), None);
-            let balance = Balances::free_balance(ALICE);
-            let fee = 25;
-            let name = vec![50];
-            let kind = MPT::Public;
-            let uri = Some(vec![65]);
-            let logo_uri = Some(vec![66]);
-            let info = MarketplaceInformation::new(
-                kind,
-                fee,
-                ALICE,
-                vec![],
-                vec![],
-                name.clone(),
-                uri.clone(),
-                logo_uri.clone(),
+            let series_id = vec![50];
+            let royalty = Permill::from_percent(100);
+            let ok = NFTs::create(alice.clone(), vec![50], Some(series_id.clone()), royalty);
+            assert_ok!(ok);
+            let nft_id = NFTs::nft_id_generator() - 1;
+            help::finish_series(alice.clone(), series_id);
+            assert_ok!(NFTs::transfer(alice.clone(), nft_id, BOB));
+
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Public, 10, vec![0], vec![]);
+            let price = 100;
+            assert_ok!(Marketplace::list(
+                bob.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(mkt_id),
                 None,
-            );
-
-            // Happy path
-            assert_ok!(Marketplace::create(
-                alice.clone(),
-                kind,
-                fee,
-                name,
-                uri.clone(),
-                logo_uri.clone(),
                 None,
+                vec![]
             ));
-            assert_eq!(Marketplace::marketplace_id_generator(), 1);
-            assert_eq!(Marketplace::marketplaces(1), Some(info));
-            assert_eq!(
-                Balances::free_balance(ALICE),
-                balance - Marketplace::marketplace_mint_fee()
-            );
+
+            let alice_before = Balances::free_balance(ALICE);
+            let bob_before = Balances::free_balance(BOB);
+            let dave_before = Balances::free_balance(DAVE);
+
+            // The marketplace cut is capped to what is left after royalties
+            assert_ok!(Marketplace::buy(dave.clone(), nft_id));
+            assert_eq!(Balances::free_balance(ALICE), alice_before + price);
+            assert_eq!(Balances::free_balance(BOB), bob_before);
+            assert_eq!(Balances::free_balance(DAVE), dave_before - price);
         })
 }
 
 #[test]
-fn create_unhappy() {
+fn buy_skips_royalty_below_minimum_balance() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 5)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
-            let normal_uri: Option<TextFormat> = Some(vec![66]);
-            let too_short_uri: Option<TextFormat> = Some(vec![]);
-            let too_long_uri: Option<TextFormat> = Some([0; 1001].to_vec());
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
 
-            // Unhappy invalid commission fee
-            let ok = Marketplace::create(
-                alice.clone(),
-                MPT::Public,
-                101,
-                vec![50],
-                normal_uri.clone(),
-                normal_uri.clone(),
-                None,
-            );
-            assert_noop!(ok, Error::<Test>::InvalidCommissionFeeValue);
+            // Nobody but DAVE holds this asset and its minimum balance is 10
+            let asset_id = TIIME + 1;
+            let root: mock::Origin = RawOrigin::Root.into();
+            assert_ok!(Assets::force_create(root, asset_id, ALICE, true, 10));
+            assert_ok!(Assets::mint(alice.clone(), asset_id, DAVE, 1000));
 
-            // Unhappy too short name
-            let ok = Marketplace::create(
-                alice.clone(),
-                MPT::Public,
-                0,
-                vec![],
-                normal_uri.clone(),
-                normal_uri.clone(),
+            let series_id = vec![50];
+            let royalty = Permill::from_percent(5);
+            let ok = NFTs::create(alice.clone(), vec![50], Some(series_id.clone()), royalty);
+            assert_ok!(ok);
+            let nft_id = NFTs::nft_id_generator() - 1;
+            help::finish_series(alice.clone(), series_id);
+            assert_ok!(NFTs::transfer(alice.clone(), nft_id, BOB));
+
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Public, 0, vec![0], vec![]);
+            let ok = Marketplace::set_accepted_assets(dave.clone(), mkt_id, vec![asset_id]);
+            assert_ok!(ok);
+            let price = 100;
+            assert_ok!(Marketplace::list(
+                bob.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Asset(asset_id),
+                Some(mkt_id),
                 None,
-            );
-            assert_noop!(ok, Error::<Test>::TooShortMarketplaceName);
+                None,
+                vec![]
+            ));
 
-            // Unhappy too long name
-            let ok = Marketplace::create(
+            // A royalty of 5 cannot open the account of the creator
+            assert_ok!(Marketplace::buy(dave.clone(), nft_id));
+            assert_eq!(NFTs::data(nft_id).unwrap().owner, DAVE);
+            assert_eq!(Assets::balance(asset_id, ALICE), 0);
+            assert_eq!(Assets::balance(asset_id, BOB), price);
+            assert_eq!(Assets::balance(asset_id, DAVE), 1000 - price);
+
+            let event = MarketplaceEvent::NftSold {
+                nft_id,
+                owner: DAVE,
+                seller_cut: price,
+                marketplace_cut: 0,
+                royalty_cut: 0,
+            };
+            let event = Event::Marketplace(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+}
+
+#[test]
+fn buy_with_rate_and_flat_commission() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Public, 0, vec![0], vec![]);
+            let ok = Marketplace::set_commission_fee(dave.clone(), mkt_id, commission(30, 2));
+            assert_ok!(ok);
+
+            let price = 100;
+            assert_ok!(Marketplace::list(
                 alice.clone(),
-                MPT::Public,
-                0,
-                vec![1, 2, 3, 4, 5, 6],
-                normal_uri.clone(),
-                normal_uri.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                Some(mkt_id),
                 None,
-            );
-            assert_noop!(ok, Error::<Test>::TooLongMarketplaceName);
+                None,
+                vec![]
+            ));
 
-            // Unhappy not enough funds
-            let ok = Marketplace::create(
-                alice.clone(),
-                MPT::Public,
-                5,
-                vec![50],
-                normal_uri.clone(),
-                normal_uri.clone(),
+            let alice_before = Balances::free_balance(ALICE);
+            let dave_before = Balances::free_balance(DAVE);
+
+            // 30% of the price plus the flat fee go to the marketplace
+            assert_ok!(Marketplace::buy(bob.clone(), nft_id));
+            assert_eq!(Balances::free_balance(ALICE), alice_before + 68);
+            assert_eq!(Balances::free_balance(DAVE), dave_before + 32);
+        })
+}
+
+#[test]
+fn buy_with_commission_from_listing_time() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Public, 10, vec![0], vec![]);
+            assert_ok!(Marketplace::list(
+                alice.clone(),
+                nft_id,
+                100,
+                PaymentAsset::Native,
+                Some(mkt_id),
                 None,
-            );
+                None,
+                vec![]
+            ));
+
+            // The new commission only applies to new listings
+            let ok = Marketplace::set_commission_fee(dave.clone(), mkt_id, commission(100, 0));
+            assert_ok!(ok);
+            let sale = Marketplace::nft_for_sale(nft_id).unwrap();
+            assert_eq!(sale.commission_fee, commission(10, 0));
+
+            let alice_before = Balances::free_balance(ALICE);
+            let dave_before = Balances::free_balance(DAVE);
+
+            assert_ok!(Marketplace::buy(bob.clone(), nft_id));
+            assert_eq!(Balances::free_balance(ALICE), alice_before + 90);
+            assert_eq!(Balances::free_balance(DAVE), dave_before + 10);
+
+            // Listing again picks up the new commission
+            assert_ok!(Marketplace::list(
+                bob.clone(),
+                nft_id,
+                100,
+                PaymentAsset::Native,
+                Some(mkt_id),
+                None,
+                None,
+                vec![]
+            ));
+            let sale = Marketplace::nft_for_sale(nft_id).unwrap();
+            assert_eq!(sale.commission_fee, commission(100, 0));
+        })
+}
+
+#[test]
+fn buy_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 100), (BOB, 100)])
+        .tiime(vec![(ALICE, 100), (BOB, 100)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let price = 5000;
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            assert_ok!(Marketplace::list(
+                alice.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![]
+            ));
+
+            // Unhappy nft not on sale
+            let ok = Marketplace::buy(bob.clone(), 1001);
+            assert_noop!(ok, Error::<Test>::NftNotForSale);
+
+            // Unhappy not enough caps
+            let ok = Marketplace::buy(bob.clone(), nft_id);
             assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
+        })
+}
 
-            // Unhappy too short uri
-            let ok = Marketplace::create(
+#[test]
+fn private_sale() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
+
+            let price = 50;
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list(
                 alice.clone(),
-                MPT::Public,
-                0,
-                vec![50],
-                too_short_uri.clone(),
-                normal_uri.clone(),
+                nft_id,
+                price,
+                PaymentAsset::Native,
                 None,
+                None,
+                None,
+                vec![BOB],
             );
-            assert_noop!(ok, Error::<Test>::TooShortUri);
+            assert_ok!(ok);
+            let sale = Marketplace::nft_for_sale(nft_id).unwrap();
+            assert_eq!(sale.buyers.to_vec(), vec![BOB]);
 
-            // Unhappy too long uri
-            let ok = Marketplace::create(
+            // Unhappy not the named buyer
+            let ok = Marketplace::buy(dave, nft_id);
+            assert_noop!(ok, Error::<Test>::NotAllowedToBuy);
+
+            // Happy path
+            assert_ok!(Marketplace::buy(bob, nft_id));
+            assert_eq!(<NFTs as NFTTrait>::owner(nft_id), Some(BOB));
+        })
+}
+
+#[test]
+fn private_sale_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            // Unhappy too many buyers
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let buyers = (100..100 + MaxBuyers::get() as u64 + 1).collect();
+            let ok = Marketplace::list(
                 alice.clone(),
-                MPT::Public,
-                0,
-                vec![50],
-                too_long_uri.clone(),
-                normal_uri.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
                 None,
+                None,
+                None,
+                buyers,
             );
-            assert_noop!(ok, Error::<Test>::TooLongUri);
+            assert_noop!(ok, Error::<Test>::TooManyBuyers);
+        })
+}
 
-            // Unhappy too short logo uri
-            let ok = Marketplace::create(
+#[test]
+fn listings_hide_private_sales() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let series_id = vec![50];
+            let public_id = help::create_nft(alice.clone(), vec![50], Some(series_id.clone()));
+            let private_id = help::create_nft(alice.clone(), vec![50], Some(series_id.clone()));
+            help::finish_series(alice.clone(), series_id);
+            for (nft_id, buyers) in [(public_id, vec![]), (private_id, vec![BOB])] {
+                let ok = Marketplace::list(
+                    alice.clone(),
+                    nft_id,
+                    50,
+                    PaymentAsset::Native,
+                    None,
+                    None,
+                    None,
+                    buyers,
+                );
+                assert_ok!(ok);
+            }
+
+            let all = vec![public_id, private_id];
+            assert_eq!(Marketplace::listings(0, None, None, 10), vec![public_id]);
+            assert_eq!(
+                Marketplace::listings(0, Some(DAVE), None, 10),
+                vec![public_id]
+            );
+            assert_eq!(Marketplace::listings(0, Some(BOB), None, 10), all);
+            assert_eq!(Marketplace::listings(0, Some(ALICE), None, 10), all);
+            let page = Marketplace::listings(0, Some(BOB), Some(public_id), 10);
+            assert_eq!(page, vec![private_id]);
+            assert_eq!(Marketplace::listings(1001, Some(BOB), None, 10).len(), 0);
+        })
+}
+
+#[test]
+fn list_bundle_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
+
+            let nft_id_1 = help::create_nft(alice.clone(), vec![50], Some(vec![50]));
+            let nft_id_2 = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Public, 10, vec![0], vec![]);
+
+            // Happy path
+            let nft_ids = vec![nft_id_1, nft_id_2];
+            let ok = Marketplace::list_bundle(
                 alice.clone(),
-                MPT::Public,
-                0,
-                vec![50],
-                normal_uri.clone(),
-                too_short_uri,
-                None,
+                nft_ids.clone(),
+                100,
+                PaymentAsset::Native,
+                Some(mkt_id),
             );
-            assert_noop!(ok, Error::<Test>::TooShortLogoUri);
+            assert_ok!(ok);
 
-            // Unhappy too long logo uri
-            let ok = Marketplace::create(
+            let bundle_id = Marketplace::bundle_id_generator();
+            let bundle = BundleInformation {
+                account_id: ALICE,
+                nft_ids: nft_ids.clone().try_into().unwrap(),
+                price: 100,
+                asset: PaymentAsset::Native,
+                marketplace_id: mkt_id,
+                commission_fee: commission(10, 0),
+            };
+            assert_eq!(Marketplace::bundles(bundle_id), Some(bundle));
+            for nft_id in &nft_ids {
+                assert_eq!(Marketplace::nft_bundle(nft_id), Some(bundle_id));
+                assert_eq!(Marketplace::nft_for_sale(nft_id), None);
+                assert_eq!(
+                    <NFTs as NFTTrait>::is_locked(*nft_id, NFTLockReason::ListedForSale),
+                    Some(true)
+                );
+            }
+
+            let event = MarketplaceEvent::BundleListed {
+                bundle_id,
+                nft_ids,
+                price: 100,
+                asset: PaymentAsset::Native,
+                marketplace_id: mkt_id,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+        })
+}
+
+#[test]
+fn list_bundle_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let nft_id_1 = help::create_nft(alice.clone(), vec![50], Some(vec![50]));
+            let nft_id_2 = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let bob_nft_id = help::create_nft_and_lock_series(bob.clone(), vec![50], vec![51]);
+            let draft_nft_id = help::create_nft(alice.clone(), vec![50], Some(vec![52]));
+
+            // Unhappy empty bundle
+            let ok =
+                Marketplace::list_bundle(alice.clone(), vec![], 100, PaymentAsset::Native, None);
+            assert_noop!(ok, Error::<Test>::EmptyBundle);
+
+            // Unhappy too many nfts
+            let max: u32 = <Test as crate::Config>::MaxBundleSize::get();
+            let nft_ids = vec![nft_id_1; max as usize + 1];
+            let ok =
+                Marketplace::list_bundle(alice.clone(), nft_ids, 100, PaymentAsset::Native, None);
+            assert_noop!(ok, Error::<Test>::BundleIsTooLarge);
+
+            // Unhappy unknown marketplace
+            let nft_ids = vec![nft_id_1, nft_id_2];
+            let ok = Marketplace::list_bundle(
                 alice.clone(),
-                MPT::Public,
-                0,
-                vec![50],
-                normal_uri,
-                too_long_uri,
+                nft_ids.clone(),
+                100,
+                PaymentAsset::Native,
+                Some(10001),
+            );
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy asset not accepted
+            let ok = Marketplace::list_bundle(
+                alice.clone(),
+                nft_ids.clone(),
+                100,
+                PaymentAsset::Asset(TIIME),
                 None,
             );
-            assert_noop!(ok, Error::<Test>::TooLongLogoUri);
+            assert_noop!(ok, Error::<Test>::AssetNotAccepted);
+
+            // Unhappy one nft is not owned by the seller
+            let nft_ids = vec![nft_id_1, bob_nft_id];
+            let ok =
+                Marketplace::list_bundle(alice.clone(), nft_ids, 100, PaymentAsset::Native, None);
+            assert_noop!(ok, Error::<Test>::NotNftOwner);
+
+            // Unhappy one nft is in a series that is not completed
+            let nft_ids = vec![nft_id_1, draft_nft_id];
+            let ok =
+                Marketplace::list_bundle(alice.clone(), nft_ids, 100, PaymentAsset::Native, None);
+            assert_noop!(ok, Error::<Test>::SeriesNotCompleted);
+
+            // Unhappy one nft is capsulized
+            <NFTs as NFTTrait>::lock(nft_id_2, NFTLockReason::Capsule).unwrap();
+            let nft_ids = vec![nft_id_1, nft_id_2];
+            let ok =
+                Marketplace::list_bundle(alice.clone(), nft_ids, 100, PaymentAsset::Native, None);
+            assert_noop!(ok, NFTError::<Test>::NFTIsCapsule);
+            <NFTs as NFTTrait>::unlock(nft_id_2, NFTLockReason::Capsule).unwrap();
+
+            // Unhappy one nft is rented
+            assert_ok!(NFTs::offer_rental(alice.clone(), nft_id_2, 0, 10, None));
+            assert_ok!(NFTs::rent(bob.clone(), nft_id_2));
+            let nft_ids = vec![nft_id_1, nft_id_2];
+            let ok =
+                Marketplace::list_bundle(alice.clone(), nft_ids, 100, PaymentAsset::Native, None);
+            assert_noop!(ok, NFTError::<Test>::NFTIsRented);
+            assert_ok!(NFTs::end_rental(bob.clone(), nft_id_2));
+
+            // Unhappy the same nft is given twice
+            let nft_ids = vec![nft_id_1, nft_id_1];
+            let ok =
+                Marketplace::list_bundle(alice.clone(), nft_ids, 100, PaymentAsset::Native, None);
+            assert_noop!(ok, NFTError::<Test>::NFTIsListedForSale);
+
+            // Unhappy one nft is already listed
+            assert_ok!(Marketplace::list(
+                alice.clone(),
+                nft_id_2,
+                100,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![]
+            ));
+            let nft_ids = vec![nft_id_1, nft_id_2];
+            let ok =
+                Marketplace::list_bundle(alice.clone(), nft_ids, 100, PaymentAsset::Native, None);
+            assert_noop!(ok, NFTError::<Test>::NFTIsListedForSale);
         })
 }
 
 #[test]
-fn add_account_to_allow_list_happy() {
+fn unlist_bundle_happy() {
     ExtBuilder::default()
         .caps(vec![(ALICE, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
 
+            let nft_id_1 = help::create_nft(alice.clone(), vec![50], Some(vec![50]));
+            let nft_id_2 = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let nft_ids = vec![nft_id_1, nft_id_2];
+            assert_ok!(Marketplace::list_bundle(
+                alice.clone(),
+                nft_ids.clone(),
+                100,
+                PaymentAsset::Native,
+                None
+            ));
+            let bundle_id = Marketplace::bundle_id_generator();
+
             // Happy path
-            let list = vec![];
-            let mkp_1 = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], list.clone());
-            assert_eq!(Marketplace::marketplaces(mkp_1).unwrap().allow_list, list);
+            assert_ok!(Marketplace::unlist_bundle(alice.clone(), bundle_id));
+            assert_eq!(Marketplace::bundles(bundle_id), None);
+            for nft_id in &nft_ids {
+                assert_eq!(Marketplace::nft_bundle(nft_id), None);
+                assert_eq!(
+                    <NFTs as NFTTrait>::is_locked(*nft_id, NFTLockReason::ListedForSale),
+                    Some(false)
+                );
+            }
+
+            let event = MarketplaceEvent::BundleUnlisted { bundle_id };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+        })
+}
 
-            let ok = Marketplace::add_account_to_allow_list(alice.clone(), mkp_1, BOB);
-            assert_ok!(ok);
-            let list = vec![BOB];
-            assert_eq!(Marketplace::marketplaces(mkp_1).unwrap().allow_list, list);
+#[test]
+fn unlist_bundle_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            assert_ok!(Marketplace::list_bundle(
+                alice.clone(),
+                vec![nft_id],
+                100,
+                PaymentAsset::Native,
+                None
+            ));
+            let bundle_id = Marketplace::bundle_id_generator();
+
+            // Unhappy unknown bundle
+            let ok = Marketplace::unlist_bundle(alice.clone(), bundle_id + 1);
+            assert_noop!(ok, Error::<Test>::UnknownBundle);
+
+            // Unhappy not the seller
+            let ok = Marketplace::unlist_bundle(bob.clone(), bundle_id);
+            assert_noop!(ok, Error::<Test>::NotNftOwner);
+
+            // Unhappy single nfts of a bundle cannot be unlisted
+            let ok = Marketplace::unlist(alice.clone(), nft_id);
+            assert_noop!(ok, Error::<Test>::NftNotForSale);
         })
 }
 
 #[test]
-fn add_account_to_allow_list_unhappy() {
+fn buy_bundle_happy() {
     ExtBuilder::default()
-        .caps(vec![(BOB, 1000), (DAVE, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
         .build()
         .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
             let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
 
-            // Unhappy unknown marketplace
-            let ok = Marketplace::add_account_to_allow_list(bob.clone(), 1001, DAVE);
-            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+            // Alice creates the nfts with a royalty and Bob resells them
+            let series_id = vec![50];
+            let royalty = Permill::from_percent(10);
+            assert_ok!(NFTs::create(
+                alice.clone(),
+                vec![50],
+                Some(series_id.clone()),
+                royalty
+            ));
+            assert_ok!(NFTs::create(
+                alice.clone(),
+                vec![50],
+                Some(series_id.clone()),
+                royalty
+            ));
+            let nft_id_2 = NFTs::nft_id_generator() - 1;
+            let nft_id_1 = nft_id_2 - 1;
+            help::finish_series(alice.clone(), series_id);
+            assert_ok!(NFTs::transfer(alice.clone(), nft_id_1, BOB));
+            assert_ok!(NFTs::transfer(alice.clone(), nft_id_2, BOB));
+
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Public, 10, vec![0], vec![]);
+            let price = 100;
+            let nft_ids = vec![nft_id_1, nft_id_2];
+            assert_ok!(Marketplace::list_bundle(
+                bob.clone(),
+                nft_ids.clone(),
+                price,
+                PaymentAsset::Native,
+                Some(mkt_id)
+            ));
+            let bundle_id = Marketplace::bundle_id_generator();
 
-            // Unhappy not marketplace owner
-            let ok = Marketplace::add_account_to_allow_list(bob.clone(), 0, DAVE);
-            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+            let alice_before = Balances::free_balance(ALICE);
+            let bob_before = Balances::free_balance(BOB);
+            let dave_before = Balances::free_balance(DAVE);
 
-            // Unhappy unsupported marketplace type
-            let mkp_id = help::create_mkp(bob.clone(), MPT::Public, 0, vec![50], vec![]);
-            let ok = Marketplace::add_account_to_allow_list(bob.clone(), mkp_id, DAVE);
-            assert_noop!(ok, Error::<Test>::UnsupportedMarketplace);
+            // Happy path
+            assert_ok!(Marketplace::buy_bundle(dave.clone(), bundle_id));
+            assert_eq!(Marketplace::bundles(bundle_id), None);
+            for nft_id in &nft_ids {
+                assert_eq!(<NFTs as NFTTrait>::owner(*nft_id), Some(DAVE));
+                assert_eq!(Marketplace::nft_bundle(nft_id), None);
+                assert_eq!(
+                    <NFTs as NFTTrait>::is_locked(*nft_id, NFTLockReason::ListedForSale),
+                    Some(false)
+                );
+            }
+
+            // Each creator is paid on the share of its nft, the commission once
+            let royalty_cut = 10;
+            let marketplace_cut = 10;
+            let seller_cut = price - royalty_cut - marketplace_cut;
+            assert_eq!(Balances::free_balance(ALICE), alice_before + royalty_cut);
+            assert_eq!(Balances::free_balance(BOB), bob_before + seller_cut);
+            assert_eq!(
+                Balances::free_balance(DAVE),
+                dave_before - price + marketplace_cut
+            );
+
+            let event = MarketplaceEvent::BundleSold {
+                bundle_id,
+                owner: DAVE,
+                seller_cut,
+                marketplace_cut,
+                royalty_cut,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+        })
+}
+
+#[test]
+fn buy_bundle_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 10)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let nft_id_1 = help::create_nft(alice.clone(), vec![50], Some(vec![50]));
+            let nft_id_2 = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            assert_ok!(Marketplace::list_bundle(
+                alice.clone(),
+                vec![nft_id_1, nft_id_2],
+                100,
+                PaymentAsset::Native,
+                None
+            ));
+            let bundle_id = Marketplace::bundle_id_generator();
+
+            // Unhappy unknown bundle
+            let ok = Marketplace::buy_bundle(bob.clone(), bundle_id + 1);
+            assert_noop!(ok, Error::<Test>::UnknownBundle);
+
+            // Unhappy the seller cannot buy its own bundle
+            let ok = Marketplace::buy_bundle(alice.clone(), bundle_id);
+            assert_noop!(ok, Error::<Test>::NftAlreadyOwned);
+
+            // Unhappy not enough caps
+            let ok = Marketplace::buy_bundle(bob.clone(), bundle_id);
+            assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
+
+            // Unhappy single nfts of a bundle cannot be bought
+            let ok = Marketplace::buy(bob.clone(), nft_id_1);
+            assert_noop!(ok, Error::<Test>::NftNotForSale);
+        })
+}
+
+#[test]
+fn accept_offer_on_bundled_nft() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let nft_id_1 = help::create_nft(alice.clone(), vec![50], Some(vec![50]));
+            let nft_id_2 = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            assert_ok!(Marketplace::list_bundle(
+                alice.clone(),
+                vec![nft_id_1, nft_id_2],
+                100,
+                PaymentAsset::Native,
+                None
+            ));
+            let bundle_id = Marketplace::bundle_id_generator();
+            assert_ok!(Marketplace::make_offer(bob.clone(), nft_id_1, 50, None, 10));
+
+            // Selling one nft of the bundle takes the whole bundle down
+            assert_ok!(Marketplace::accept_offer(alice.clone(), nft_id_1, BOB));
+            assert_eq!(<NFTs as NFTTrait>::owner(nft_id_1), Some(BOB));
+            assert_eq!(<NFTs as NFTTrait>::owner(nft_id_2), Some(ALICE));
+            assert_eq!(Marketplace::bundles(bundle_id), None);
+            assert_eq!(Marketplace::nft_bundle(nft_id_2), None);
+            assert_eq!(
+                <NFTs as NFTTrait>::is_locked(nft_id_2, NFTLockReason::ListedForSale),
+                Some(false)
+            );
+            let event = Event::Marketplace(MarketplaceEvent::BundleUnlisted { bundle_id });
+            assert!(System::events().iter().any(|x| x.event == event));
+        })
+}
+
+#[test]
+fn force_transfer_unlists_bundle() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let nft_id_1 = help::create_nft(alice.clone(), vec![50], Some(vec![50]));
+            let nft_id_2 = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            assert_ok!(Marketplace::list_bundle(
+                alice.clone(),
+                vec![nft_id_1, nft_id_2],
+                100,
+                PaymentAsset::Native,
+                None
+            ));
+            let bundle_id = Marketplace::bundle_id_generator();
+
+            assert_ok!(NFTs::force_transfer(RawOrigin::Root.into(), nft_id_1, BOB));
+            assert_eq!(Marketplace::bundles(bundle_id), None);
+            assert_eq!(NFTs::data(nft_id_1).unwrap().lock, None);
+            assert_eq!(NFTs::data(nft_id_2).unwrap().lock, None);
+            let event = Event::Marketplace(MarketplaceEvent::BundleUnlisted { bundle_id });
+            assert!(System::events().iter().any(|x| x.event == event));
+        })
+}
+
+#[test]
+fn set_price_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![],
+            );
+            assert_ok!(ok);
+
+            // Happy path
+            assert_ok!(Marketplace::set_price(alice.clone(), nft_id, 70));
+            assert_eq!(Marketplace::nft_for_sale(nft_id).unwrap().price, 70);
+
+            let event = MarketplaceEvent::NftPriceChanged { nft_id, price: 70 };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+        })
+}
+
+#[test]
+fn set_price_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy not listed NFT
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::set_price(alice.clone(), nft_id, 70);
+            assert_noop!(ok, Error::<Test>::NftNotForSale);
+
+            // Unhappy not the NFT owner
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![],
+            );
+            assert_ok!(ok);
+            let ok = Marketplace::set_price(bob.clone(), nft_id, 70);
+            assert_noop!(ok, Error::<Test>::NotNftOwner);
+        })
+}
+
+#[test]
+fn move_listing_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                Some(10),
+                vec![],
+            );
+            assert_ok!(ok);
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 10, vec![1], vec![ALICE]);
+
+            // Happy path
+            assert_ok!(Marketplace::move_listing(alice.clone(), nft_id, mkp_id));
+            let expected = SaleInformation::new(
+                ALICE,
+                50,
+                PaymentAsset::Native,
+                mkp_id,
+                commission(10, 0),
+                None,
+                Some(10),
+                Default::default(),
+            );
+            assert_eq!(Marketplace::nft_for_sale(nft_id), Some(expected));
+            assert_eq!(
+                <NFTs as NFTTrait>::is_locked(nft_id, NFTLockReason::ListedForSale),
+                Some(true)
+            );
+            assert_eq!(Marketplace::listings(0, None, None, 10), vec![]);
+            assert_eq!(Marketplace::listings(mkp_id, None, None, 10), vec![nft_id]);
+
+            let event = MarketplaceEvent::NftListingMoved {
+                nft_id,
+                marketplace_id: mkp_id,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+        })
+}
+
+#[test]
+fn move_listing_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy not listed NFT
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::move_listing(alice.clone(), nft_id, 0);
+            assert_noop!(ok, Error::<Test>::NftNotForSale);
+
+            // Unhappy not the NFT owner
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![],
+            );
+            assert_ok!(ok);
+            let ok = Marketplace::move_listing(bob.clone(), nft_id, 0);
+            assert_noop!(ok, Error::<Test>::NotNftOwner);
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::move_listing(alice.clone(), nft_id, 10001);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not on the allow list
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 0, vec![1], vec![]);
+            let ok = Marketplace::move_listing(alice.clone(), nft_id, mkp_id);
+            assert_noop!(ok, Error::<Test>::NotAllowedToList);
+
+            // Unhappy on the disallow list
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Public, 0, vec![2], vec![ALICE]);
+            let ok = Marketplace::move_listing(alice.clone(), nft_id, mkp_id);
+            assert_noop!(ok, Error::<Test>::NotAllowedToList);
+        })
+}
+
+#[test]
+fn buy_with_asset() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
+        .tiime(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Public, 10, vec![0], vec![]);
+            let asset = PaymentAsset::Asset(TIIME);
+
+            // Unhappy the marketplace does not accept the asset
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                100,
+                asset,
+                Some(mkt_id),
+                None,
+                None,
+                vec![],
+            );
+            assert_noop!(ok, Error::<Test>::AssetNotAccepted);
+
+            // Happy path
+            let ok = Marketplace::set_accepted_assets(dave.clone(), mkt_id, vec![TIIME]);
+            assert_ok!(ok);
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                100,
+                asset,
+                Some(mkt_id),
+                None,
+                None,
+                vec![],
+            );
+            assert_ok!(ok);
+
+            let caps_before = Balances::free_balance(BOB);
+            assert_ok!(Marketplace::buy(bob.clone(), nft_id));
+            assert_eq!(NFTs::data(nft_id).unwrap().owner, BOB);
+            assert_eq!(Balances::free_balance(BOB), caps_before);
+            assert_eq!(Assets::balance(TIIME, BOB), 900);
+            assert_eq!(Assets::balance(TIIME, ALICE), 1090);
+            assert_eq!(Assets::balance(TIIME, DAVE), 1010);
+        })
+}
+
+#[test]
+fn move_listing_checks_asset() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let mkt_1 = help::create_mkp(dave.clone(), MPT::Public, 0, vec![0], vec![]);
+            let mkt_2 = help::create_mkp(dave.clone(), MPT::Public, 0, vec![1], vec![]);
+            let asset = PaymentAsset::Asset(TIIME);
+
+            let ok = Marketplace::set_accepted_assets(dave.clone(), mkt_1, vec![TIIME]);
+            assert_ok!(ok);
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                100,
+                asset,
+                Some(mkt_1),
+                None,
+                None,
+                vec![],
+            );
+            assert_ok!(ok);
+
+            // Unhappy the new marketplace does not accept the asset
+            let ok = Marketplace::move_listing(alice.clone(), nft_id, mkt_2);
+            assert_noop!(ok, Error::<Test>::AssetNotAccepted);
+
+            // Happy path
+            let ok = Marketplace::set_accepted_assets(dave.clone(), mkt_2, vec![TIIME]);
+            assert_ok!(ok);
+            assert_ok!(Marketplace::move_listing(alice.clone(), nft_id, mkt_2));
+        })
+}
+
+#[test]
+fn set_bundle_price_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list_bundle(
+                alice.clone(),
+                vec![nft_id],
+                100,
+                PaymentAsset::Native,
+                None,
+            );
+            assert_ok!(ok);
+            let bundle_id = Marketplace::bundle_id_generator();
+
+            // Happy path
+            assert_ok!(Marketplace::set_bundle_price(alice.clone(), bundle_id, 200));
+            assert_eq!(Marketplace::bundles(bundle_id).unwrap().price, 200);
+
+            let event = MarketplaceEvent::BundlePriceChanged {
+                bundle_id,
+                price: 200,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+        })
+}
+
+#[test]
+fn set_bundle_price_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list_bundle(
+                alice.clone(),
+                vec![nft_id],
+                100,
+                PaymentAsset::Native,
+                None,
+            );
+            assert_ok!(ok);
+            let bundle_id = Marketplace::bundle_id_generator();
+
+            // Unhappy unknown bundle
+            let ok = Marketplace::set_bundle_price(alice.clone(), bundle_id + 1, 200);
+            assert_noop!(ok, Error::<Test>::UnknownBundle);
+
+            // Unhappy not the seller
+            let ok = Marketplace::set_bundle_price(bob.clone(), bundle_id, 200);
+            assert_noop!(ok, Error::<Test>::NotNftOwner);
+        })
+}
+
+#[test]
+fn move_bundle_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list_bundle(
+                alice.clone(),
+                vec![nft_id],
+                100,
+                PaymentAsset::Native,
+                None,
+            );
+            assert_ok!(ok);
+            let bundle_id = Marketplace::bundle_id_generator();
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 10, vec![1], vec![ALICE]);
+
+            // Happy path
+            assert_ok!(Marketplace::move_bundle(alice.clone(), bundle_id, mkp_id));
+            let bundle = Marketplace::bundles(bundle_id).unwrap();
+            assert_eq!(bundle.marketplace_id, mkp_id);
+            assert_eq!(bundle.commission_fee, commission(10, 0));
+            assert_eq!(bundle.price, 100);
+            assert!(!MarketplaceBundles::<Test>::contains_key(0, bundle_id));
+            assert!(MarketplaceBundles::<Test>::contains_key(mkp_id, bundle_id));
+
+            let event = MarketplaceEvent::BundleMoved {
+                bundle_id,
+                marketplace_id: mkp_id,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+        })
+}
+
+#[test]
+fn move_bundle_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .tiime(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list_bundle(
+                alice.clone(),
+                vec![nft_id],
+                100,
+                PaymentAsset::Native,
+                None,
+            );
+            assert_ok!(ok);
+            let bundle_id = Marketplace::bundle_id_generator();
+
+            // Unhappy unknown bundle
+            let ok = Marketplace::move_bundle(alice.clone(), bundle_id + 1, 0);
+            assert_noop!(ok, Error::<Test>::UnknownBundle);
+
+            // Unhappy not the seller
+            let ok = Marketplace::move_bundle(bob.clone(), bundle_id, 0);
+            assert_noop!(ok, Error::<Test>::NotNftOwner);
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::move_bundle(alice.clone(), bundle_id, 10001);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not on the allow list
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 0, vec![1], vec![]);
+            let ok = Marketplace::move_bundle(alice.clone(), bundle_id, mkp_id);
+            assert_noop!(ok, Error::<Test>::NotAllowedToList);
+
+            // Unhappy on the disallow list
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Public, 0, vec![2], vec![ALICE]);
+            let ok = Marketplace::move_bundle(alice.clone(), bundle_id, mkp_id);
+            assert_noop!(ok, Error::<Test>::NotAllowedToList);
+
+            // Unhappy the new marketplace does not accept the asset
+            assert_ok!(Marketplace::unlist_bundle(alice.clone(), bundle_id));
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Public, 0, vec![3], vec![]);
+            let ok = Marketplace::set_accepted_assets(bob.clone(), mkp_id, vec![TIIME]);
+            assert_ok!(ok);
+            let ok = Marketplace::list_bundle(
+                alice.clone(),
+                vec![nft_id],
+                100,
+                PaymentAsset::Asset(TIIME),
+                Some(mkp_id),
+            );
+            assert_ok!(ok);
+            let bundle_id = Marketplace::bundle_id_generator();
+            let ok = Marketplace::move_bundle(alice.clone(), bundle_id, 0);
+            assert_noop!(ok, Error::<Test>::AssetNotAccepted);
+        })
+}
+
+#[test]
+fn list_with_start_and_end_blocks() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                Some(5),
+                Some(10),
+                vec![],
+            );
+            assert_ok!(ok);
+
+            let sale_info = SaleInformation::new(
+                ALICE,
+                50,
+                PaymentAsset::Native,
+                0,
+                commission(0, 0),
+                Some(5),
+                Some(10),
+                Default::default(),
+            );
+            assert_eq!(Marketplace::nft_for_sale(nft_id), Some(sale_info));
+            assert_eq!(Marketplace::listing_deadlines(10).to_vec(), vec![nft_id]);
+
+            // Unhappy the listing has not started yet
+            let ok = Marketplace::buy(bob.clone(), nft_id);
+            assert_noop!(ok, Error::<Test>::ListingNotStarted);
+
+            // Happy the listing is open
+            System::set_block_number(5);
+            assert_ok!(Marketplace::buy(bob.clone(), nft_id));
+            assert_eq!(NFTs::data(nft_id).unwrap().owner, BOB);
+            assert!(Marketplace::listing_deadlines(10).is_empty());
+        })
+}
+
+#[test]
+fn list_with_start_and_end_blocks_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            System::set_block_number(10);
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+
+            // Unhappy end block has already passed
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                Some(10),
+                vec![],
+            );
+            assert_noop!(ok, Error::<Test>::ListingEndIsInThePast);
+
+            // Unhappy end block before start block
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                Some(20),
+                Some(20),
+                vec![],
+            );
+            assert_noop!(ok, Error::<Test>::ListingCannotEndBeforeItStarts);
+
+            // Unhappy too many listings ending at the same block
+            let mut timed_nfts = Vec::new();
+            for id in 0..3u8 {
+                let series_id = vec![60 + id];
+                let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], series_id);
+                let ok = Marketplace::list(
+                    alice.clone(),
+                    nft_id,
+                    50,
+                    PaymentAsset::Native,
+                    None,
+                    None,
+                    Some(20),
+                    vec![],
+                );
+                assert_ok!(ok);
+                timed_nfts.push(nft_id);
+            }
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                Some(20),
+                vec![],
+            );
+            assert_noop!(ok, Error::<Test>::TooManyListingsAtBlock);
+
+            // Happy other blocks still have room
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                Some(21),
+                vec![],
+            );
+            assert_ok!(ok);
+
+            // Unhappy the listing has ended
+            System::set_block_number(20);
+            let ok = Marketplace::buy(bob.clone(), timed_nfts[0]);
+            assert_noop!(ok, Error::<Test>::ListingExpired);
+        })
+}
+
+#[test]
+fn unlist_removes_listing_deadline() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                Some(10),
+                vec![],
+            );
+            assert_ok!(ok);
+            assert_ok!(Marketplace::unlist(alice.clone(), nft_id));
+            assert!(Marketplace::listing_deadlines(10).is_empty());
+        })
+}
+
+#[test]
+fn listings_expire() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let nft_1 = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let nft_2 = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![51]);
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_1,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                Some(10),
+                vec![],
+            );
+            assert_ok!(ok);
+            let ok = Marketplace::list(
+                alice.clone(),
+                nft_2,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                Some(20),
+                vec![],
+            );
+            assert_ok!(ok);
+
+            Marketplace::on_initialize(10);
+            assert_eq!(Marketplace::nft_for_sale(nft_1), None);
+            assert_eq!(NFTs::data(nft_1).unwrap().lock, None);
+            assert!(Marketplace::listing_deadlines(10).is_empty());
+            assert_eq!(Marketplace::listing_deadlines(20).to_vec(), vec![nft_2]);
+
+            let event = MarketplaceEvent::NftUnlisted { nft_id: nft_1 };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+
+            Marketplace::on_initialize(20);
+            assert_eq!(Marketplace::nft_for_sale(nft_2), None);
+            assert_eq!(NFTs::data(nft_2).unwrap().lock, None);
+            assert!(Marketplace::listing_deadlines(20).is_empty());
+        })
+}
+
+#[test]
+fn create_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 10000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            assert_eq!(Marketplace::marketplace_id_generator(), 0);
+            assert_eq!(Marketplace::marketplaces(1), None);
+            let balance = Balances::free_balance(ALICE);
+            let fee = commission(25, 0);
+            let name = vec![50];
+            let kind = MPT::Public;
+            let uri = Some(vec![65]);
+            let logo_uri = Some(vec![66]);
+            let info = MarketplaceInformation::new(
+                kind,
+                fee,
+                ALICE,
+                bounded(name.clone()),
+                uri.clone().map(bounded),
+                logo_uri.clone().map(bounded),
+                None,
+            );
+
+            // Happy path
+            assert_ok!(Marketplace::create(
+                alice.clone(),
+                kind,
+                fee,
+                name,
+                uri.clone(),
+                logo_uri.clone(),
+                None,
+            ));
+            assert_eq!(Marketplace::marketplace_id_generator(), 1);
+            assert_eq!(Marketplace::marketplaces(1), Some(info));
+            assert_eq!(
+                Balances::free_balance(ALICE),
+                balance - Marketplace::marketplace_mint_fee()
+            );
+        })
+}
+
+#[test]
+fn create_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 5)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let normal_uri: Option<TextFormat> = Some(vec![66]);
+            let too_short_uri: Option<TextFormat> = Some(vec![]);
+            let too_long_uri: Option<TextFormat> = Some([0; 1001].to_vec());
+
+            // Unhappy too short name
+            let ok = Marketplace::create(
+                alice.clone(),
+                MPT::Public,
+                commission(0, 0),
+                vec![],
+                normal_uri.clone(),
+                normal_uri.clone(),
+                None,
+            );
+            assert_noop!(ok, Error::<Test>::TooShortMarketplaceName);
+
+            // Unhappy too long name
+            let ok = Marketplace::create(
+                alice.clone(),
+                MPT::Public,
+                commission(0, 0),
+                vec![1, 2, 3, 4, 5, 6],
+                normal_uri.clone(),
+                normal_uri.clone(),
+                None,
+            );
+            assert_noop!(ok, Error::<Test>::TooLongMarketplaceName);
+
+            // Unhappy not enough funds
+            let ok = Marketplace::create(
+                alice.clone(),
+                MPT::Public,
+                commission(5, 0),
+                vec![50],
+                normal_uri.clone(),
+                normal_uri.clone(),
+                None,
+            );
+            assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
+
+            // Unhappy too short uri
+            let ok = Marketplace::create(
+                alice.clone(),
+                MPT::Public,
+                commission(0, 0),
+                vec![50],
+                too_short_uri.clone(),
+                normal_uri.clone(),
+                None,
+            );
+            assert_noop!(ok, Error::<Test>::TooShortUri);
+
+            // Unhappy too long uri
+            let ok = Marketplace::create(
+                alice.clone(),
+                MPT::Public,
+                commission(0, 0),
+                vec![50],
+                too_long_uri.clone(),
+                normal_uri.clone(),
+                None,
+            );
+            assert_noop!(ok, Error::<Test>::TooLongUri);
+
+            // Unhappy too short logo uri
+            let ok = Marketplace::create(
+                alice.clone(),
+                MPT::Public,
+                commission(0, 0),
+                vec![50],
+                normal_uri.clone(),
+                too_short_uri,
+                None,
+            );
+            assert_noop!(ok, Error::<Test>::TooShortLogoUri);
+
+            // Unhappy too long logo uri
+            let ok = Marketplace::create(
+                alice.clone(),
+                MPT::Public,
+                commission(0, 0),
+                vec![50],
+                normal_uri,
+                too_long_uri,
+                None,
+            );
+            assert_noop!(ok, Error::<Test>::TooLongLogoUri);
+        })
+}
+
+#[test]
+fn add_account_to_allow_list_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            // Happy path
+            let list = vec![];
+            let mkp_1 = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], list.clone());
+            assert_eq!(Marketplace::allow_list(mkp_1, None, 10), list);
+
+            let ok = Marketplace::add_account_to_allow_list(alice.clone(), mkp_1, BOB);
+            assert_ok!(ok);
+            let list = vec![BOB];
+            assert_eq!(Marketplace::allow_list(mkp_1, None, 10), list);
+        })
+}
+
+#[test]
+fn add_account_to_allow_list_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(BOB, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::add_account_to_allow_list(bob.clone(), 1001, DAVE);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace admin
+            let ok = Marketplace::add_account_to_allow_list(bob.clone(), 0, DAVE);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceAdmin);
+
+            // Unhappy unsupported marketplace type
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Public, 0, vec![50], vec![]);
+            let ok = Marketplace::add_account_to_allow_list(bob.clone(), mkp_id, DAVE);
+            assert_noop!(ok, Error::<Test>::UnsupportedMarketplace);
+        })
+}
+
+#[test]
+fn remove_account_from_allow_list_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            // Happy path
+            let list = vec![BOB];
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], list.clone());
+            assert_eq!(Marketplace::allow_list(mkp_id, None, 10), list);
+
+            let ok = Marketplace::remove_account_from_allow_list(alice.clone(), mkp_id, BOB);
+            assert_ok!(ok);
+            let list: Vec<u64> = vec![];
+            assert_eq!(Marketplace::allow_list(mkp_id, None, 10), list);
+        })
+}
+
+#[test]
+fn remove_account_from_allow_list_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(BOB, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::remove_account_from_allow_list(bob.clone(), 1001, DAVE);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace admin
+            let ok = Marketplace::remove_account_from_allow_list(bob.clone(), 0, DAVE);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceAdmin);
+
+            // Unhappy unsupported marketplace type
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Public, 0, vec![50], vec![]);
+            let ok = Marketplace::remove_account_from_allow_list(bob.clone(), mkp_id, DAVE);
+            assert_noop!(ok, Error::<Test>::UnsupportedMarketplace);
+        })
+}
+
+#[test]
+fn add_accounts_to_allow_list_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], vec![BOB]);
+
+            // Accounts that are already on the list stay on it once
+            let ok =
+                Marketplace::add_accounts_to_allow_list(alice.clone(), mkp_id, vec![DAVE, BOB]);
+            assert_ok!(ok);
+            assert_eq!(Marketplace::allow_list(mkp_id, None, 10), vec![BOB, DAVE]);
+
+            let event = MarketplaceEvent::AccountsAddedToAllowList {
+                marketplace_id: mkp_id,
+                accounts: vec![DAVE, BOB],
+            };
+            let event = Event::Marketplace(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+}
+
+#[test]
+fn add_accounts_to_allow_list_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(BOB, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::add_accounts_to_allow_list(bob.clone(), 1001, vec![DAVE]);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace admin
+            let ok = Marketplace::add_accounts_to_allow_list(bob.clone(), 0, vec![DAVE]);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceAdmin);
+
+            // Unhappy unsupported marketplace type
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Public, 0, vec![50], vec![]);
+            let ok = Marketplace::add_accounts_to_allow_list(bob.clone(), mkp_id, vec![DAVE]);
+            assert_noop!(ok, Error::<Test>::UnsupportedMarketplace);
+
+            // Unhappy too many accounts
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 0, vec![50], vec![]);
+            let accounts = vec![ALICE, BOB, DAVE, 4];
+            let ok = Marketplace::add_accounts_to_allow_list(bob.clone(), mkp_id, accounts);
+            assert_noop!(ok, Error::<Test>::TooManyAccounts);
+        })
+}
+
+#[test]
+fn remove_accounts_from_allow_list_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let list = vec![BOB, DAVE];
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], list);
+
+            // Accounts that are not on the list are ignored
+            let accounts = vec![BOB, 4];
+            let ok = Marketplace::remove_accounts_from_allow_list(alice.clone(), mkp_id, accounts);
+            assert_ok!(ok);
+            assert_eq!(Marketplace::allow_list(mkp_id, None, 10), vec![DAVE]);
+
+            // Removed accounts cannot list anymore
+            let ok = Marketplace::is_allowed_to_list(mkp_id, BOB);
+            assert_noop!(ok, Error::<Test>::NotAllowedToList);
+            assert_ok!(Marketplace::is_allowed_to_list(mkp_id, DAVE));
+        })
+}
+
+#[test]
+fn allow_list_pagination() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], vec![]);
+            let ok = Marketplace::add_accounts_to_allow_list(alice.clone(), mkp_id, vec![7, 5, 6]);
+            assert_ok!(ok);
+            let ok = Marketplace::add_accounts_to_allow_list(alice.clone(), mkp_id, vec![9, 8]);
+            assert_ok!(ok);
+
+            assert_eq!(Marketplace::allow_list(mkp_id, None, 2), vec![5, 6]);
+            assert_eq!(Marketplace::allow_list(mkp_id, Some(6), 2), vec![7, 8]);
+            assert_eq!(Marketplace::allow_list(mkp_id, Some(8), 2), vec![9]);
+            assert!(Marketplace::allow_list(mkp_id, Some(9), 2).is_empty());
+            assert!(Marketplace::allow_list(0, None, 2).is_empty());
+        })
+}
+
+#[test]
+fn disallow_list_pagination() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![]);
+            let ok =
+                Marketplace::add_accounts_to_disallow_list(alice.clone(), mkp_id, vec![8, 6, 7]);
+            assert_ok!(ok);
+
+            assert_eq!(Marketplace::disallow_list(mkp_id, None, 2), vec![6, 7]);
+            assert_eq!(Marketplace::disallow_list(mkp_id, Some(7), 2), vec![8]);
+            assert!(Marketplace::disallow_list(mkp_id, Some(8), 2).is_empty());
+        })
+}
+
+#[test]
+fn set_owner_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            // Happy path
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], vec![]);
+            assert_ok!(Marketplace::set_owner(alice.clone(), mkp_id, BOB));
+            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().owner, BOB);
+        })
+}
+
+#[test]
+fn set_owner_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::set_owner(bob.clone(), 1001, DAVE);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace owner
+            let ok = Marketplace::set_owner(bob.clone(), 0, DAVE);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+        })
+}
+
+#[test]
+fn set_market_type_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let kind = MPT::Public;
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![]);
+            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().kind, kind);
+
+            // Happy path Public to Private
+            let kind = MPT::Private;
+            assert_ok!(Marketplace::set_market_type(alice.clone(), mkp_id, kind));
+            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().kind, kind);
+
+            // Happy path Private to Public
+            let kind = MPT::Public;
+            assert_ok!(Marketplace::set_market_type(alice.clone(), mkp_id, kind));
+            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().kind, kind);
+        })
+}
+
+#[test]
+fn set_market_type_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            let kind = MPT::Public;
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::set_market_type(bob.clone(), 1001, kind);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace owner
+            let ok = Marketplace::set_market_type(bob.clone(), 0, kind);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+        })
+}
+
+#[test]
+fn set_name_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            // Happy path
+            let name = vec![50];
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, name.clone(), vec![]);
+            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().name, name);
+
+            let name = vec![51];
+            assert_ok!(Marketplace::set_name(alice.clone(), mkp_id, name.clone()));
+            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().name, name);
+        })
+}
+
+#[test]
+fn set_name_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy too short name
+            let ok = Marketplace::set_name(bob.clone(), 0, vec![]);
+            assert_noop!(ok, Error::<Test>::TooShortMarketplaceName);
+
+            // Unhappy too long name
+            let ok = Marketplace::set_name(bob.clone(), 0, vec![1, 2, 3, 4, 5, 6]);
+            assert_noop!(ok, Error::<Test>::TooLongMarketplaceName);
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::set_name(bob.clone(), 1001, vec![51]);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace admin
+            let ok = Marketplace::set_name(bob.clone(), 0, vec![51]);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceAdmin);
+        })
+}
+
+#[test]
+fn set_marketplace_mint_fee_happy() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Happy path
+        let old_mint_fee = Marketplace::marketplace_mint_fee();
+        let new_mint_fee = 654u128;
+        assert_eq!(Marketplace::marketplace_mint_fee(), old_mint_fee);
+
+        let ok = Marketplace::set_marketplace_mint_fee(mock::Origin::root(), new_mint_fee);
+        assert_ok!(ok);
+        assert_eq!(Marketplace::marketplace_mint_fee(), new_mint_fee);
+    })
+}
+
+#[test]
+fn set_marketplace_mint_fee_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 10000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            // Unhappy non root user tries to modify the mint fee
+            let ok = Marketplace::set_marketplace_mint_fee(alice.clone(), 654);
+            assert_noop!(ok, BadOrigin);
+        })
+}
+
+#[test]
+fn set_commission_fee_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let id = help::create_mkp(alice.clone(), MPT::Public, 10, vec![50], vec![]);
+            let fee = Marketplace::marketplaces(id).unwrap().commission_fee;
+            assert_eq!(fee, commission(10, 0));
+
+            // Happy path
+            let fee = MarketplaceCommission::new(Permill::from_parts(152_500), 7);
+            assert_ok!(Marketplace::set_commission_fee(alice.clone(), id, fee));
+            assert_eq!(Marketplace::marketplaces(id).unwrap().commission_fee, fee);
+
+            let event = MarketplaceEvent::MarketplaceCommissionFeeChanged {
+                marketplace_id: id,
+                fee,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+        })
+}
+
+#[test]
+fn set_commission_fee_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::set_commission_fee(bob.clone(), 1001, commission(15, 0));
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace owner
+            let ok = Marketplace::set_commission_fee(bob.clone(), 0, commission(15, 0));
+            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+        })
+}
+
+#[test]
+fn set_accepted_assets_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            // Happy path
+            assert_ok!(Marketplace::set_accepted_assets(
+                alice.clone(),
+                0,
+                vec![TIIME]
+            ));
+            assert_eq!(Marketplace::accepted_assets(0), vec![TIIME]);
+
+            let event = MarketplaceEvent::MarketplaceAcceptedAssetsChanged {
+                marketplace_id: 0,
+                assets: vec![TIIME].try_into().unwrap(),
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+
+            assert_ok!(Marketplace::set_accepted_assets(alice.clone(), 0, vec![]));
+            assert_eq!(Marketplace::accepted_assets(0).len(), 0);
+        })
+}
+
+#[test]
+fn set_accepted_assets_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::set_accepted_assets(alice.clone(), 1001, vec![TIIME]);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not the marketplace owner
+            let ok = Marketplace::set_accepted_assets(bob.clone(), 0, vec![TIIME]);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+
+            // Unhappy too many assets
+            let ok = Marketplace::set_accepted_assets(alice.clone(), 0, vec![1, 2, 3]);
+            assert_noop!(ok, Error::<Test>::TooManyAcceptedAssets);
+        })
+}
+
+#[test]
+fn update_uri_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 10000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let fee = commission(25, 0);
+            let name = vec![50];
+            let kind = MPT::Public;
+            let uri = Some(vec![66]);
+            let updated_uri = Some(vec![67]);
+
+            let updated_info = MarketplaceInformation::new(
+                kind,
+                fee,
+                ALICE,
+                bounded(name.clone()),
+                updated_uri.clone().map(bounded),
+                uri.clone().map(bounded),
+                None,
+            );
+
+            assert_ok!(Marketplace::create(
+                alice.clone(),
+                kind.clone(),
+                fee,
+                name.clone(),
+                uri.clone(),
+                uri.clone(),
+                None,
+            ));
+            assert_ne!(
+                Marketplace::marketplaces(1)
+                    .unwrap()
+                    .uri
+                    .map(|x| x.into_inner()),
+                updated_uri.clone()
+            );
+            assert_ok!(Marketplace::set_uri(alice.clone(), 1, updated_uri.unwrap()));
+            assert_eq!(Marketplace::marketplaces(1), Some(updated_info));
+        })
+}
+
+#[test]
+fn update_uri_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 10000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let fee = commission(25, 0);
+            let name = vec![50];
+            let kind = MPT::Public;
+            let uri = Some(vec![66]);
+            let raw_too_short_uri: TextFormat = vec![];
+            let raw_too_long_uri: TextFormat = [0; 1001].to_vec();
+
+            assert_ok!(Marketplace::create(
+                alice.clone(),
+                kind.clone(),
+                fee,
+                name.clone(),
+                uri.clone(),
+                uri.clone(),
+                None
+            ));
+
+            let nok = Marketplace::set_uri(alice.clone(), 1, raw_too_short_uri);
+            assert_noop!(nok, Error::<Test>::TooShortUri);
+            let nok = Marketplace::set_uri(alice, 1, raw_too_long_uri);
+            assert_noop!(nok, Error::<Test>::TooLongUri);
+        })
+}
+
+#[test]
+fn update_logo_uri_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 10000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let fee = commission(25, 0);
+            let name = vec![50];
+            let kind = MPT::Public;
+            let uri = Some(vec![66]);
+            let updated_uri = Some(vec![67]);
+
+            let updated_info = MarketplaceInformation::new(
+                kind,
+                fee,
+                ALICE,
+                bounded(name.clone()),
+                uri.clone().map(bounded),
+                updated_uri.clone().map(bounded),
+                None,
+            );
+
+            assert_ok!(Marketplace::create(
+                alice.clone(),
+                kind.clone(),
+                fee,
+                name.clone(),
+                uri.clone(),
+                uri.clone(),
+                None,
+            ));
+            assert_ne!(
+                Marketplace::marketplaces(1)
+                    .unwrap()
+                    .uri
+                    .map(|x| x.into_inner()),
+                updated_uri.clone()
+            );
+
+            assert_ok!(Marketplace::set_logo_uri(
+                alice.clone(),
+                1,
+                updated_uri.unwrap()
+            ));
+            assert_eq!(Marketplace::marketplaces(1), Some(updated_info));
+        })
+}
+
+#[test]
+fn update_logo_uri_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 10000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let fee = commission(25, 0);
+            let name = vec![50];
+            let kind = MPT::Public;
+            let uri = Some(vec![66]);
+            let raw_too_short_uri: TextFormat = vec![];
+            let raw_too_long_uri: TextFormat = [0; 1001].to_vec();
+
+            assert_ok!(Marketplace::create(
+                alice.clone(),
+                kind.clone(),
+                fee,
+                name.clone(),
+                uri.clone(),
+                uri.clone(),
+                None
+            ));
+
+            let nok = Marketplace::set_logo_uri(alice.clone(), 1, raw_too_short_uri);
+            assert_noop!(nok, Error::<Test>::TooShortLogoUri);
+            let nok = Marketplace::set_logo_uri(alice, 1, raw_too_long_uri);
+            assert_noop!(nok, Error::<Test>::TooLongLogoUri);
+        })
+}
+
+#[test]
+fn add_account_to_disallow_list_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            // Happy path
+            let list = vec![];
+            let mkp_1 = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], list.clone());
+            assert_eq!(Marketplace::disallow_list(mkp_1, None, 10), list);
+
+            let ok = Marketplace::add_account_to_disallow_list(alice.clone(), mkp_1, BOB);
+            assert_ok!(ok);
+            let list = vec![BOB];
+            assert_eq!(Marketplace::disallow_list(mkp_1, None, 10), list);
+        })
+}
+
+#[test]
+fn add_account_to_disallow_list_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(BOB, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::add_account_to_disallow_list(bob.clone(), 1001, DAVE);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace admin
+            let ok = Marketplace::add_account_to_disallow_list(bob.clone(), 0, DAVE);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceAdmin);
+
+            // Unhappy unsupported marketplace type
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 0, vec![50], vec![]);
+            let ok = Marketplace::add_account_to_disallow_list(bob.clone(), mkp_id, DAVE);
+            assert_noop!(ok, Error::<Test>::UnsupportedMarketplace);
+        })
+}
+
+#[test]
+fn remove_account_from_disallow_list_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            // Happy path
+            let list = vec![BOB];
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], list.clone());
+            assert_eq!(Marketplace::disallow_list(mkp_id, None, 10), list);
+
+            let ok = Marketplace::remove_account_from_disallow_list(alice.clone(), mkp_id, BOB);
+            assert_ok!(ok);
+            let list: Vec<u64> = vec![];
+            assert_eq!(Marketplace::disallow_list(mkp_id, None, 10), list);
+        })
+}
+
+#[test]
+fn remove_account_from_disallow_list_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(BOB, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::remove_account_from_disallow_list(bob.clone(), 1001, DAVE);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace admin
+            let ok = Marketplace::remove_account_from_disallow_list(bob.clone(), 0, DAVE);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceAdmin);
+
+            // Unhappy unsupported marketplace type
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 0, vec![50], vec![]);
+            let ok = Marketplace::remove_account_from_disallow_list(bob.clone(), mkp_id, DAVE);
+            assert_noop!(ok, Error::<Test>::UnsupportedMarketplace);
+        })
+}
+
+#[test]
+fn add_accounts_to_disallow_list_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![BOB]);
+
+            // Accounts that are already on the list stay on it once
+            let accounts = vec![DAVE, BOB];
+            let ok = Marketplace::add_accounts_to_disallow_list(alice.clone(), mkp_id, accounts);
+            assert_ok!(ok);
+            assert_eq!(
+                Marketplace::disallow_list(mkp_id, None, 10),
+                vec![BOB, DAVE]
+            );
+
+            let event = MarketplaceEvent::AccountsAddedToDisallowList {
+                marketplace_id: mkp_id,
+                accounts: vec![DAVE, BOB],
+            };
+            let event = Event::Marketplace(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+}
+
+#[test]
+fn add_accounts_to_disallow_list_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(BOB, 1000), (DAVE, 1000)])
+        .build()
+        .execute_with(|| {
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::add_accounts_to_disallow_list(bob.clone(), 1001, vec![DAVE]);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace admin
+            let ok = Marketplace::add_accounts_to_disallow_list(bob.clone(), 0, vec![DAVE]);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceAdmin);
+
+            // Unhappy unsupported marketplace type
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 0, vec![50], vec![]);
+            let ok = Marketplace::add_accounts_to_disallow_list(bob.clone(), mkp_id, vec![DAVE]);
+            assert_noop!(ok, Error::<Test>::UnsupportedMarketplace);
+
+            // Unhappy too many accounts
+            let mkp_id = help::create_mkp(bob.clone(), MPT::Public, 0, vec![50], vec![]);
+            let accounts = vec![ALICE, BOB, DAVE, 4];
+            let ok = Marketplace::add_accounts_to_disallow_list(bob.clone(), mkp_id, accounts);
+            assert_noop!(ok, Error::<Test>::TooManyAccounts);
+        })
+}
+
+#[test]
+fn remove_accounts_from_disallow_list_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let list = vec![BOB, DAVE];
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], list);
+
+            // Accounts that are not on the list are ignored
+            let accounts = vec![BOB, 4];
+            let ok =
+                Marketplace::remove_accounts_from_disallow_list(alice.clone(), mkp_id, accounts);
+            assert_ok!(ok);
+            assert_eq!(Marketplace::disallow_list(mkp_id, None, 10), vec![DAVE]);
+
+            // Removed accounts can list again
+            assert_ok!(Marketplace::is_allowed_to_list(mkp_id, BOB));
+            let ok = Marketplace::is_allowed_to_list(mkp_id, DAVE);
+            assert_noop!(ok, Error::<Test>::NotAllowedToList);
+        })
+}
+
+#[test]
+fn set_description_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 10000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let fee = commission(25, 0);
+            let name = vec![50];
+            let kind = MPT::Public;
+            let uri = Some(vec![66]);
+            let description = Some(vec![66]);
+            let updated_description = Some(vec![67]);
+
+            let updated_info = MarketplaceInformation::new(
+                kind,
+                fee,
+                ALICE,
+                bounded(name.clone()),
+                uri.clone().map(bounded),
+                uri.clone().map(bounded),
+                updated_description.clone().map(bounded),
+            );
+
+            assert_ok!(Marketplace::create(
+                alice.clone(),
+                kind.clone(),
+                fee,
+                name.clone(),
+                uri.clone(),
+                uri.clone(),
+                description.clone(),
+            ));
+
+            assert_ok!(Marketplace::set_description(
+                alice.clone(),
+                1,
+                updated_description.unwrap()
+            ));
+            assert_eq!(Marketplace::marketplaces(1), Some(updated_info));
+        })
+}
+
+#[test]
+fn set_description_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 10000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+
+            let fee = commission(25, 0);
+            let name = vec![50];
+            let kind = MPT::Public;
+            let uri = Some(vec![66]);
+            let raw_too_short_description: TextFormat = vec![];
+            let raw_too_long_description: TextFormat = [0; 501].to_vec();
+
+            assert_ok!(Marketplace::create(
+                alice.clone(),
+                kind.clone(),
+                fee,
+                name.clone(),
+                uri.clone(),
+                uri.clone(),
+                None
+            ));
+
+            let nok = Marketplace::set_description(alice.clone(), 1, raw_too_short_description);
+            assert_noop!(nok, Error::<Test>::TooShortDescription);
+            let nok = Marketplace::set_description(alice, 1, raw_too_long_description);
+            assert_noop!(nok, Error::<Test>::TooLongDescription);
+        })
+}
+
+#[test]
+fn grant_role_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], vec![]);
+
+            // Happy path
+            let ok = Marketplace::grant_role(alice.clone(), mkp_id, BOB, MPR::Admin);
+            assert_ok!(ok);
+            assert_eq!(Marketplace::marketplace_role(mkp_id, BOB), Some(MPR::Admin));
+            let event = MarketplaceEvent::MarketplaceRoleGranted {
+                marketplace_id: mkp_id,
+                account_id: BOB,
+                role: MPR::Admin,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+
+            // Admins manage the metadata and the lists
+            assert_ok!(Marketplace::set_name(bob.clone(), mkp_id, vec![51]));
+            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().name, vec![51]);
+            let ok = Marketplace::add_account_to_allow_list(bob.clone(), mkp_id, DAVE);
+            assert_ok!(ok);
+            assert_eq!(Marketplace::allow_list(mkp_id, None, 10), vec![DAVE]);
+            assert!(<Marketplace as MarketplaceTrait<u64>>::has_role(
+                mkp_id,
+                &BOB,
+                MPR::Curator
+            ));
+
+            // Granting a new role replaces the old one
+            let ok = Marketplace::grant_role(alice.clone(), mkp_id, BOB, MPR::Curator);
+            assert_ok!(ok);
+            let ok = Marketplace::set_name(bob.clone(), mkp_id, vec![52]);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceAdmin);
+        })
+}
+
+#[test]
+fn grant_role_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], vec![]);
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::grant_role(alice.clone(), 1001, BOB, MPR::Admin);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy not marketplace owner, even for admins
+            assert_ok!(Marketplace::grant_role(alice, mkp_id, BOB, MPR::Admin));
+            let ok = Marketplace::grant_role(bob.clone(), mkp_id, DAVE, MPR::Admin);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+        })
+}
+
+#[test]
+fn revoke_role_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], vec![]);
+
+            // Happy path owner revokes
+            assert_ok!(Marketplace::grant_role(
+                alice.clone(),
+                mkp_id,
+                BOB,
+                MPR::Admin
+            ));
+            assert_ok!(Marketplace::revoke_role(alice.clone(), mkp_id, BOB));
+            assert_eq!(Marketplace::marketplace_role(mkp_id, BOB), None);
+            let event = MarketplaceEvent::MarketplaceRoleRevoked {
+                marketplace_id: mkp_id,
+                account_id: BOB,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+            let ok = Marketplace::set_name(bob.clone(), mkp_id, vec![51]);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceAdmin);
+
+            // Happy path account gives up its own role
+            assert_ok!(Marketplace::grant_role(
+                alice.clone(),
+                mkp_id,
+                BOB,
+                MPR::Curator
+            ));
+            assert_ok!(Marketplace::revoke_role(bob.clone(), mkp_id, BOB));
+            assert_eq!(Marketplace::marketplace_role(mkp_id, BOB), None);
+        })
+}
+
+#[test]
+fn revoke_role_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], vec![]);
+
+            // Unhappy unknown marketplace
+            let ok = Marketplace::revoke_role(alice.clone(), 1001, BOB);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy role not found
+            let ok = Marketplace::revoke_role(alice.clone(), mkp_id, BOB);
+            assert_noop!(ok, Error::<Test>::RoleNotFound);
+
+            // Unhappy not marketplace owner
+            assert_ok!(Marketplace::grant_role(
+                alice.clone(),
+                mkp_id,
+                BOB,
+                MPR::Admin
+            ));
+            assert_ok!(Marketplace::grant_role(alice, mkp_id, DAVE, MPR::Curator));
+            let ok = Marketplace::revoke_role(bob.clone(), mkp_id, DAVE);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+        })
+}
+
+#[test]
+fn take_down_listing_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![]);
+            let nft_id = help::create_nft_and_lock_series(bob.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list(
+                bob.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                Some(mkp_id),
+                None,
+                None,
+                vec![],
+            );
+            assert_ok!(ok);
+
+            // Happy path
+            assert_ok!(Marketplace::grant_role(
+                alice.clone(),
+                mkp_id,
+                DAVE,
+                MPR::Curator
+            ));
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
+            assert_ok!(Marketplace::take_down_listing(dave, nft_id));
+            assert_eq!(Marketplace::nft_for_sale(nft_id), None);
+            assert_eq!(
+                <NFTs as NFTTrait>::is_locked(nft_id, NFTLockReason::ListedForSale),
+                Some(false)
+            );
+            let event = MarketplaceEvent::NftTakenDown {
+                nft_id,
+                marketplace_id: mkp_id,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+        })
+}
+
+#[test]
+fn take_down_listing_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![]);
+            let nft_id = help::create_nft_and_lock_series(bob.clone(), vec![50], vec![50]);
+
+            // Unhappy NFT not for sale
+            let ok = Marketplace::take_down_listing(alice.clone(), nft_id);
+            assert_noop!(ok, Error::<Test>::NftNotForSale);
+
+            // Unhappy not marketplace curator
+            let ok = Marketplace::list(
+                bob.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                Some(mkp_id),
+                None,
+                None,
+                vec![],
+            );
+            assert_ok!(ok);
+            let ok = Marketplace::take_down_listing(bob.clone(), nft_id);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceCurator);
+        })
+}
+
+#[test]
+fn take_down_bundle_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![]);
+            let nft_id_1 = help::create_nft(bob.clone(), vec![50], Some(vec![50]));
+            let nft_id_2 = help::create_nft_and_lock_series(bob.clone(), vec![50], vec![50]);
+            let nft_ids = vec![nft_id_1, nft_id_2];
+            let ok = Marketplace::list_bundle(
+                bob.clone(),
+                nft_ids.clone(),
+                50,
+                PaymentAsset::Native,
+                Some(mkp_id),
+            );
+            assert_ok!(ok);
+            let bundle_id = Marketplace::bundle_id_generator();
+
+            // Happy path
+            assert_ok!(Marketplace::grant_role(
+                alice.clone(),
+                mkp_id,
+                DAVE,
+                MPR::Curator
+            ));
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
+            assert_ok!(Marketplace::take_down_bundle(dave, bundle_id));
+            assert_eq!(Marketplace::bundles(bundle_id), None);
+            assert!(!MarketplaceBundles::<Test>::contains_key(mkp_id, bundle_id));
+            for nft_id in &nft_ids {
+                assert_eq!(Marketplace::nft_bundle(nft_id), None);
+                assert_eq!(
+                    <NFTs as NFTTrait>::is_locked(*nft_id, NFTLockReason::ListedForSale),
+                    Some(false)
+                );
+            }
+            let event = MarketplaceEvent::BundleTakenDown {
+                bundle_id,
+                marketplace_id: mkp_id,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
         })
 }
 
 #[test]
-fn remove_account_from_allow_list_happy() {
+fn take_down_bundle_unhappy() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
-
-            // Happy path
-            let list = vec![BOB];
-            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], list.clone());
-            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().allow_list, list);
-
-            let ok = Marketplace::remove_account_from_allow_list(alice.clone(), mkp_id, BOB);
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![]);
+            let nft_id = help::create_nft_and_lock_series(bob.clone(), vec![50], vec![50]);
+
+            // Unhappy unknown bundle
+            let ok = Marketplace::take_down_bundle(alice.clone(), 1);
+            assert_noop!(ok, Error::<Test>::UnknownBundle);
+
+            // Unhappy not marketplace curator
+            let ok = Marketplace::list_bundle(
+                bob.clone(),
+                vec![nft_id],
+                50,
+                PaymentAsset::Native,
+                Some(mkp_id),
+            );
             assert_ok!(ok);
-            let list: Vec<u64> = vec![];
-            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().allow_list, list);
+            let bundle_id = Marketplace::bundle_id_generator();
+            let ok = Marketplace::take_down_bundle(bob.clone(), bundle_id);
+            assert_noop!(ok, Error::<Test>::NotMarketplaceCurator);
         })
 }
 
 #[test]
-fn remove_account_from_allow_list_unhappy() {
+fn close_marketplace_happy() {
     ExtBuilder::default()
-        .caps(vec![(BOB, 1000), (DAVE, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
             let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![DAVE]);
+            assert_ok!(Marketplace::grant_role(
+                alice.clone(),
+                mkp_id,
+                BOB,
+                MPR::Curator
+            ));
+            assert_ok!(Marketplace::set_accepted_assets(
+                alice.clone(),
+                mkp_id,
+                vec![TIIME]
+            ));
+            let nft_id = help::create_nft_and_lock_series(bob.clone(), vec![50], vec![50]);
+            let ok = Marketplace::list(
+                bob.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                Some(mkp_id),
+                None,
+                None,
+                vec![],
+            );
+            assert_ok!(ok);
+            let bundled_nft_id = help::create_nft_and_lock_series(bob.clone(), vec![50], vec![51]);
+            assert_ok!(Marketplace::list_bundle(
+                bob.clone(),
+                vec![bundled_nft_id],
+                50,
+                PaymentAsset::Native,
+                Some(mkp_id)
+            ));
+            let bundle_id = Marketplace::bundle_id_generator();
 
-            // Unhappy unknown marketplace
-            let ok = Marketplace::remove_account_from_allow_list(bob.clone(), 1001, DAVE);
-            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
-
-            // Unhappy not marketplace owner
-            let ok = Marketplace::remove_account_from_allow_list(bob.clone(), 0, DAVE);
-            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+            // Happy path
+            assert_ok!(Marketplace::close_marketplace(alice.clone(), mkp_id, 10));
+            assert_eq!(Marketplace::marketplaces(mkp_id), None);
+            assert_eq!(Marketplace::bundles(bundle_id), None);
+            assert_eq!(Marketplace::nft_bundle(bundled_nft_id), None);
+            assert_eq!(Marketplace::closing_marketplaces(mkp_id), None);
+            assert_eq!(Marketplace::accepted_assets(mkp_id).len(), 0);
+            assert_eq!(Marketplace::disallow_list(mkp_id, None, 10).len(), 0);
+            assert_eq!(Marketplace::marketplace_role(mkp_id, BOB), None);
+            assert_eq!(Marketplace::nft_for_sale(nft_id), None);
+            assert_eq!(
+                <NFTs as NFTTrait>::is_locked(nft_id, NFTLockReason::ListedForSale),
+                Some(false)
+            );
+            let event = MarketplaceEvent::MarketplaceClosed {
+                marketplace_id: mkp_id,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
 
-            // Unhappy unsupported marketplace type
-            let mkp_id = help::create_mkp(bob.clone(), MPT::Public, 0, vec![50], vec![]);
-            let ok = Marketplace::remove_account_from_allow_list(bob.clone(), mkp_id, DAVE);
-            assert_noop!(ok, Error::<Test>::UnsupportedMarketplace);
+            // Happy path root
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], vec![]);
+            assert_ok!(Marketplace::close_marketplace(
+                RawOrigin::Root.into(),
+                mkp_id,
+                1
+            ));
+            assert_eq!(Marketplace::marketplaces(mkp_id), None);
         })
 }
 
 #[test]
-fn set_owner_happy() {
+fn close_marketplace_paged() {
     ExtBuilder::default()
         .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
-
-            // Happy path
-            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, vec![50], vec![]);
-            assert_ok!(Marketplace::set_owner(alice.clone(), mkp_id, BOB));
-            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().owner, BOB);
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![]);
+            let series_id = vec![50];
+            let nft_ids = vec![
+                help::create_nft(bob.clone(), vec![50], Some(series_id.clone())),
+                help::create_nft(bob.clone(), vec![50], Some(series_id.clone())),
+                help::create_nft(bob.clone(), vec![50], Some(series_id.clone())),
+            ];
+            help::finish_series(bob.clone(), series_id);
+            for nft_id in &nft_ids[..2] {
+                let ok = Marketplace::list(
+                    bob.clone(),
+                    *nft_id,
+                    50,
+                    PaymentAsset::Native,
+                    Some(mkp_id),
+                    None,
+                    None,
+                    vec![],
+                );
+                assert_ok!(ok);
+            }
+
+            // First page closes the marketplace to new listings
+            assert_ok!(Marketplace::close_marketplace(alice.clone(), mkp_id, 1));
+            assert_eq!(Marketplace::closing_marketplaces(mkp_id), Some(()));
+            assert!(Marketplace::marketplaces(mkp_id).is_some());
+            let ok = Marketplace::list(
+                bob.clone(),
+                nft_ids[2],
+                50,
+                PaymentAsset::Native,
+                Some(mkp_id),
+                None,
+                None,
+                vec![],
+            );
+            assert_noop!(ok, Error::<Test>::MarketplaceClosing);
+            let for_sale = nft_ids
+                .iter()
+                .filter(|x| Marketplace::nft_for_sale(**x).is_some());
+            assert_eq!(for_sale.count(), 1);
+
+            // Second page takes down the last sale
+            assert_ok!(Marketplace::close_marketplace(alice.clone(), mkp_id, 1));
+            assert!(Marketplace::marketplaces(mkp_id).is_some());
+
+            // Third page finds nothing left and removes the marketplace
+            assert_ok!(Marketplace::close_marketplace(alice.clone(), mkp_id, 1));
+            assert_eq!(Marketplace::marketplaces(mkp_id), None);
+            assert!(nft_ids
+                .iter()
+                .all(|x| Marketplace::nft_for_sale(*x).is_none()));
+            assert_eq!(MarketplaceSales::<Test>::iter_prefix(mkp_id).count(), 0);
         })
 }
 
 #[test]
-fn set_owner_unhappy() {
+fn close_marketplace_unhappy() {
     ExtBuilder::default()
-        .caps(vec![(BOB, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
             let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![]);
 
             // Unhappy unknown marketplace
-            let ok = Marketplace::set_owner(bob.clone(), 1001, DAVE);
+            let ok = Marketplace::close_marketplace(alice.clone(), 1001, 10);
             assert_noop!(ok, Error::<Test>::UnknownMarketplace);
 
-            // Unhappy not marketplace owner
-            let ok = Marketplace::set_owner(bob.clone(), 0, DAVE);
+            // Unhappy not marketplace owner, even for admins
+            assert_ok!(Marketplace::grant_role(
+                alice.clone(),
+                mkp_id,
+                BOB,
+                MPR::Admin
+            ));
+            let ok = Marketplace::close_marketplace(bob.clone(), mkp_id, 10);
             assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
         })
 }
 
 #[test]
-fn set_market_type_happy() {
+fn make_offer_happy() {
     ExtBuilder::default()
         .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
 
-            let kind = MPT::Public;
-            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], vec![]);
-            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().kind, kind);
-
-            // Happy path Public to Private
-            let kind = MPT::Private;
-            assert_ok!(Marketplace::set_market_type(alice.clone(), mkp_id, kind));
-            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().kind, kind);
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
 
-            // Happy path Private to Public
-            let kind = MPT::Public;
-            assert_ok!(Marketplace::set_market_type(alice.clone(), mkp_id, kind));
-            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().kind, kind);
+            // Happy path on an unlisted NFT
+            assert_ok!(Marketplace::make_offer(bob.clone(), nft_id, 50, None, 10));
+
+            let target = OfferTarget::NFT(nft_id);
+            let offer = Offer::new(50, 0, 10);
+            assert_eq!(Marketplace::offers(&target, BOB), Some(offer));
+            let expiring = Marketplace::offer_expirations(10).to_vec();
+            assert_eq!(expiring, vec![(target.clone(), BOB)]);
+            assert_eq!(Balances::reserved_balance(BOB), 50);
+            assert_eq!(Balances::free_balance(BOB), 950);
+
+            let event = MarketplaceEvent::OfferMade {
+                target,
+                buyer: BOB,
+                price: 50,
+                marketplace_id: 0,
+                expiration: 10,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
         })
 }
 
 #[test]
-fn set_market_type_unhappy() {
+fn make_offer_unhappy() {
     ExtBuilder::default()
-        .caps(vec![(BOB, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
             let bob: mock::Origin = RawOrigin::Signed(BOB).into();
 
-            let kind = MPT::Public;
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+
+            // Unhappy bad origin
+            let ok = Marketplace::make_offer(RawOrigin::None.into(), nft_id, 50, None, 10);
+            assert_noop!(ok, BadOrigin);
+
+            // Unhappy unknown NFT
+            let ok = Marketplace::make_offer(bob.clone(), 1001, 50, None, 10);
+            assert_noop!(ok, Error::<Test>::UnknownNFT);
+
+            // Unhappy offer on own NFT
+            let ok = Marketplace::make_offer(alice.clone(), nft_id, 50, None, 10);
+            assert_noop!(ok, Error::<Test>::NftAlreadyOwned);
 
             // Unhappy unknown marketplace
-            let ok = Marketplace::set_market_type(bob.clone(), 1001, kind);
+            let ok = Marketplace::make_offer(bob.clone(), nft_id, 50, Some(10001), 10);
             assert_noop!(ok, Error::<Test>::UnknownMarketplace);
 
-            // Unhappy not marketplace owner
-            let ok = Marketplace::set_market_type(bob.clone(), 0, kind);
-            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+            // Unhappy expiration in the past
+            let ok = Marketplace::make_offer(bob.clone(), nft_id, 50, None, 1);
+            assert_noop!(ok, Error::<Test>::OfferExpirationIsInThePast);
+
+            // Unhappy not enough funds
+            let ok = Marketplace::make_offer(bob.clone(), nft_id, 10001, None, 10);
+            assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
+
+            // Unhappy offer already made
+            assert_ok!(Marketplace::make_offer(bob.clone(), nft_id, 50, None, 10));
+            let ok = Marketplace::make_offer(bob.clone(), nft_id, 60, None, 20);
+            assert_noop!(ok, Error::<Test>::OfferAlreadyExists);
+
+            // Unhappy series id too long
+            let ok = Marketplace::make_series_offer(bob.clone(), vec![1; 100], 50, None, 10);
+            assert_noop!(ok, Error::<Test>::TooLongSeriesId);
+
+            // Unhappy too many offers expiring at the same block
+            assert_ok!(Marketplace::make_series_offer(
+                bob.clone(),
+                vec![50],
+                50,
+                None,
+                10
+            ));
+            let other = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![51]);
+            let ok = Marketplace::make_offer(bob.clone(), other, 50, None, 10);
+            assert_noop!(ok, Error::<Test>::TooManyOffersAtBlock);
         })
 }
 
 #[test]
-fn set_name_happy() {
+fn withdraw_offer_happy() {
     ExtBuilder::default()
         .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
 
-            // Happy path
-            let name = vec![50];
-            let mkp_id = help::create_mkp(alice.clone(), MPT::Private, 0, name.clone(), vec![]);
-            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().name, name);
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            assert_ok!(Marketplace::make_offer(bob.clone(), nft_id, 50, None, 10));
+            assert_ok!(Marketplace::make_series_offer(
+                bob.clone(),
+                vec![50],
+                30,
+                None,
+                10
+            ));
 
-            let name = vec![51];
-            assert_ok!(Marketplace::set_name(alice.clone(), mkp_id, name.clone()));
-            assert_eq!(Marketplace::marketplaces(mkp_id).unwrap().name, name);
+            // Happy path
+            assert_ok!(Marketplace::withdraw_offer(bob.clone(), nft_id));
+            let target = OfferTarget::NFT(nft_id);
+            assert_eq!(Marketplace::offers(&target, BOB), None);
+            let series_target = OfferTarget::Series(bounded(vec![50]));
+            let expiring = Marketplace::offer_expirations(10).to_vec();
+            assert_eq!(expiring, vec![(series_target, BOB)]);
+            assert_eq!(Balances::reserved_balance(BOB), 30);
+
+            assert_ok!(Marketplace::withdraw_series_offer(bob.clone(), vec![50]));
+            let target = OfferTarget::Series(bounded(vec![50]));
+            assert_eq!(Marketplace::offers(&target, BOB), None);
+            assert!(Marketplace::offer_expirations(10).is_empty());
+            assert_eq!(Balances::reserved_balance(BOB), 0);
+            assert_eq!(Balances::free_balance(BOB), 1000);
+
+            let event = MarketplaceEvent::OfferWithdrawn { target, buyer: BOB };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
         })
 }
 
 #[test]
-fn set_name_unhappy() {
+fn withdraw_offer_unhappy() {
     ExtBuilder::default()
-        .caps(vec![(BOB, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
             let bob: mock::Origin = RawOrigin::Signed(BOB).into();
 
-            // Unhappy too short name
-            let ok = Marketplace::set_name(bob.clone(), 0, vec![]);
-            assert_noop!(ok, Error::<Test>::TooShortMarketplaceName);
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
 
-            // Unhappy too long name
-            let ok = Marketplace::set_name(bob.clone(), 0, vec![1, 2, 3, 4, 5, 6]);
-            assert_noop!(ok, Error::<Test>::TooLongMarketplaceName);
+            // Unhappy bad origin
+            let ok = Marketplace::withdraw_offer(RawOrigin::None.into(), nft_id);
+            assert_noop!(ok, BadOrigin);
 
-            // Unhappy unknown marketplace
-            let ok = Marketplace::set_name(bob.clone(), 1001, vec![51]);
-            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+            // Unhappy no offer
+            let ok = Marketplace::withdraw_offer(bob.clone(), nft_id);
+            assert_noop!(ok, Error::<Test>::OfferNotFound);
 
-            // Unhappy not marketplace owner
-            let ok = Marketplace::set_name(bob.clone(), 0, vec![51]);
-            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+            let ok = Marketplace::withdraw_series_offer(bob.clone(), vec![50]);
+            assert_noop!(ok, Error::<Test>::OfferNotFound);
         })
 }
 
 #[test]
-fn set_marketplace_mint_fee_happy() {
-    ExtBuilder::default().build().execute_with(|| {
-        // Happy path
-        let old_mint_fee = Marketplace::marketplace_mint_fee();
-        let new_mint_fee = 654u128;
-        assert_eq!(Marketplace::marketplace_mint_fee(), old_mint_fee);
-
-        let ok = Marketplace::set_marketplace_mint_fee(mock::Origin::root(), new_mint_fee);
-        assert_ok!(ok);
-        assert_eq!(Marketplace::marketplace_mint_fee(), new_mint_fee);
-    })
-}
-
-#[test]
-fn set_marketplace_mint_fee_unhappy() {
+fn offers_expire() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 10000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
 
-            // Unhappy non root user tries to modify the mint fee
-            let ok = Marketplace::set_marketplace_mint_fee(alice.clone(), 654);
-            assert_noop!(ok, BadOrigin);
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            assert_ok!(Marketplace::make_offer(bob.clone(), nft_id, 50, None, 10));
+            assert_ok!(Marketplace::make_series_offer(
+                bob.clone(),
+                vec![50],
+                30,
+                None,
+                20
+            ));
+
+            Marketplace::on_initialize(10);
+            let target = OfferTarget::NFT(nft_id);
+            assert_eq!(Marketplace::offers(&target, BOB), None);
+            assert_eq!(Balances::reserved_balance(BOB), 30);
+
+            let event = MarketplaceEvent::OfferExpired { target, buyer: BOB };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
+
+            Marketplace::on_initialize(20);
+            let target = OfferTarget::Series(bounded(vec![50]));
+            assert_eq!(Marketplace::offers(&target, BOB), None);
+            assert_eq!(Balances::reserved_balance(BOB), 0);
+            assert_eq!(Balances::free_balance(BOB), 1000);
         })
 }
 
 #[test]
-fn set_commission_fee_happy() {
+fn accept_offer_happy() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
 
-            let fee = 10;
-            let id = help::create_mkp(alice.clone(), MPT::Public, fee, vec![50], vec![]);
-            assert_eq!(Marketplace::marketplaces(id).unwrap().commission_fee, fee);
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Private, 10, vec![0], vec![ALICE]);
+            let alice_before = Balances::free_balance(ALICE);
+            let dave_before = Balances::free_balance(DAVE);
+
+            assert_ok!(Marketplace::make_offer(
+                bob.clone(),
+                nft_id,
+                50,
+                Some(mkt_id),
+                10
+            ));
 
             // Happy path
-            let fee = 15;
-            assert_ok!(Marketplace::set_commission_fee(alice.clone(), id, fee));
-            assert_eq!(Marketplace::marketplaces(id).unwrap().commission_fee, fee);
+            assert_ok!(Marketplace::accept_offer(alice.clone(), nft_id, BOB));
+            assert_eq!(<NFTs as NFTTrait>::owner(nft_id), Some(BOB));
+
+            let target = OfferTarget::NFT(nft_id);
+            assert_eq!(Marketplace::offers(&target, BOB), None);
+            assert!(Marketplace::offer_expirations(10).is_empty());
+
+            // The price is split like for a sale
+            assert_eq!(Balances::reserved_balance(BOB), 0);
+            assert_eq!(Balances::free_balance(BOB), 950);
+            assert_eq!(Balances::free_balance(ALICE), alice_before + 45);
+            assert_eq!(Balances::free_balance(DAVE), dave_before + 5);
+
+            let event = MarketplaceEvent::OfferAccepted {
+                target,
+                nft_id,
+                buyer: BOB,
+                seller_cut: 45,
+                marketplace_cut: 5,
+                royalty_cut: 0,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
+            );
         })
 }
 
 #[test]
-fn set_commission_fee_unhappy() {
+fn accept_offer_on_listed_nft() {
     ExtBuilder::default()
-        .caps(vec![(BOB, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
             let bob: mock::Origin = RawOrigin::Signed(BOB).into();
 
-            // Unhappy commission fee is more than 100
-            let ok = Marketplace::set_commission_fee(bob.clone(), 0, 101);
-            assert_noop!(ok, Error::<Test>::InvalidCommissionFeeValue);
-
-            // Unhappy unknown marketplace
-            let ok = Marketplace::set_commission_fee(bob.clone(), 1001, 15);
-            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            assert_ok!(Marketplace::list(
+                alice.clone(),
+                nft_id,
+                100,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![]
+            ));
+            assert_ok!(Marketplace::make_offer(bob.clone(), nft_id, 50, None, 10));
 
-            // Unhappy not marketplace owner
-            let ok = Marketplace::set_commission_fee(bob.clone(), 0, 15);
-            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+            assert_ok!(Marketplace::accept_offer(alice.clone(), nft_id, BOB));
+            assert_eq!(<NFTs as NFTTrait>::owner(nft_id), Some(BOB));
+            assert_eq!(Marketplace::nft_for_sale(nft_id), None);
+            assert_eq!(
+                <NFTs as NFTTrait>::is_locked(nft_id, NFTLockReason::ListedForSale),
+                Some(false)
+            );
+            assert_eq!(Balances::free_balance(BOB), 950);
         })
 }
 
 #[test]
-fn update_uri_happy() {
+fn accept_series_offer_happy() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 10000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
 
-            let fee = 25;
-            let name = vec![50];
-            let kind = MPT::Public;
-            let uri = Some(vec![66]);
-            let updated_uri = Some(vec![67]);
-
-            let updated_info = MarketplaceInformation::new(
-                kind,
-                fee,
-                ALICE,
-                vec![],
-                vec![],
-                name.clone(),
-                updated_uri.clone(),
-                uri.clone(),
-                None,
-            );
+            // Dave holds an NFT of a series created by Alice
+            let nft_id = help::create_nft(alice.clone(), vec![50], Some(vec![50]));
+            help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            assert_ok!(NFTs::transfer(alice.clone(), nft_id, DAVE));
 
-            assert_ok!(Marketplace::create(
-                alice.clone(),
-                kind.clone(),
-                fee,
-                name.clone(),
-                uri.clone(),
-                uri.clone(),
+            assert_ok!(Marketplace::make_series_offer(
+                bob.clone(),
+                vec![50],
+                50,
                 None,
+                10
             ));
-            assert_ne!(
-                Marketplace::marketplaces(1).unwrap().uri,
-                updated_uri.clone()
-            );
-            assert_ok!(Marketplace::set_uri(alice.clone(), 1, updated_uri.unwrap()));
-            assert_eq!(Marketplace::marketplaces(1), Some(updated_info));
+
+            // Unhappy NFT of another series
+            let other_id = help::create_nft_and_lock_series(dave.clone(), vec![50], vec![51]);
+            let ok = Marketplace::accept_series_offer(dave.clone(), other_id, BOB);
+            assert_noop!(ok, Error::<Test>::OfferNotFound);
+
+            // Happy path
+            let dave_before = Balances::free_balance(DAVE);
+            assert_ok!(Marketplace::accept_series_offer(dave.clone(), nft_id, BOB));
+            assert_eq!(<NFTs as NFTTrait>::owner(nft_id), Some(BOB));
+            assert_eq!(Balances::free_balance(DAVE), dave_before + 50);
+
+            // The offer can only be accepted once
+            let target = OfferTarget::Series(bounded(vec![50]));
+            assert_eq!(Marketplace::offers(&target, BOB), None);
+            let ok = Marketplace::accept_series_offer(alice.clone(), nft_id + 1, BOB);
+            assert_noop!(ok, Error::<Test>::OfferNotFound);
         })
 }
 
 #[test]
-fn update_uri_unhappy() {
+fn accept_offer_unhappy() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 10000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
 
-            let fee = 25;
-            let name = vec![50];
-            let kind = MPT::Public;
-            let uri = Some(vec![66]);
-            let raw_too_short_uri: TextFormat = vec![];
-            let raw_too_long_uri: TextFormat = [0; 1001].to_vec();
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            assert_ok!(Marketplace::make_offer(bob.clone(), nft_id, 50, None, 10));
 
-            assert_ok!(Marketplace::create(
-                alice.clone(),
-                kind.clone(),
-                fee,
-                name.clone(),
-                uri.clone(),
-                uri.clone(),
-                None
-            ));
+            // Unhappy bad origin
+            let ok = Marketplace::accept_offer(RawOrigin::None.into(), nft_id, BOB);
+            assert_noop!(ok, BadOrigin);
 
-            let nok = Marketplace::set_uri(alice.clone(), 1, raw_too_short_uri);
-            assert_noop!(nok, Error::<Test>::TooShortUri);
-            let nok = Marketplace::set_uri(alice, 1, raw_too_long_uri);
-            assert_noop!(nok, Error::<Test>::TooLongUri);
+            // Unhappy unknown NFT
+            let ok = Marketplace::accept_offer(alice.clone(), 1001, BOB);
+            assert_noop!(ok, Error::<Test>::UnknownNFT);
+
+            // Unhappy not the NFT owner
+            let ok = Marketplace::accept_offer(bob.clone(), nft_id, BOB);
+            assert_noop!(ok, Error::<Test>::NotNftOwner);
+
+            // Unhappy no offer
+            let ok = Marketplace::accept_offer(alice.clone(), nft_id, DAVE);
+            assert_noop!(ok, Error::<Test>::OfferNotFound);
+
+            // Unhappy locked NFT
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::Capsule).unwrap();
+            let ok = Marketplace::accept_offer(alice.clone(), nft_id, BOB);
+            assert_noop!(ok, Error::<Test>::NftIsLocked);
+
+            // Unhappy draft series
+            let nft_id = help::create_nft(alice.clone(), vec![50], None);
+            assert_ok!(Marketplace::make_offer(bob.clone(), nft_id, 50, None, 10));
+            let ok = Marketplace::accept_offer(alice.clone(), nft_id, BOB);
+            assert_noop!(ok, Error::<Test>::SeriesNotCompleted);
         })
 }
 
 #[test]
-fn update_logo_uri_happy() {
+fn fill_order_happy() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 10000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000), (DAVE, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
 
-            let fee = 25;
-            let name = vec![50];
-            let kind = MPT::Public;
-            let uri = Some(vec![66]);
-            let updated_uri = Some(vec![67]);
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Private, 10, vec![0], vec![ALICE]);
+            let alice_before = Balances::free_balance(ALICE);
+            let dave_before = Balances::free_balance(DAVE);
 
-            let updated_info = MarketplaceInformation::new(
-                kind,
-                fee,
-                ALICE,
-                vec![],
-                vec![],
-                name.clone(),
-                uri.clone(),
-                updated_uri.clone(),
-                None,
-            );
+            let order = Order {
+                nft_id,
+                price: 50,
+                marketplace_id: mkt_id,
+                max_commission: commission(10, 0),
+                expiry: 10,
+                nonce: 0,
+            };
+            let signature = help::sign_order(ALICE, &order);
 
-            assert_ok!(Marketplace::create(
-                alice.clone(),
-                kind.clone(),
-                fee,
-                name.clone(),
-                uri.clone(),
-                uri.clone(),
-                None,
-            ));
-            assert_ne!(
-                Marketplace::marketplaces(1).unwrap().uri,
-                updated_uri.clone()
+            // Happy path
+            let ok = Marketplace::fill_order(bob.clone(), ALICE, order.clone(), signature.clone());
+            assert_ok!(ok);
+            assert_eq!(<NFTs as NFTTrait>::owner(nft_id), Some(BOB));
+            assert_eq!(UsedOrderNonces::<Test>::contains_key(ALICE, 0), true);
+
+            // The price is split like for a sale
+            assert_eq!(Balances::free_balance(BOB), 950);
+            assert_eq!(Balances::free_balance(ALICE), alice_before + 45);
+            assert_eq!(Balances::free_balance(DAVE), dave_before + 5);
+
+            let event = MarketplaceEvent::OrderFilled {
+                seller: ALICE,
+                nonce: 0,
+                nft_id,
+                owner: BOB,
+                seller_cut: 45,
+                marketplace_cut: 5,
+                royalty_cut: 0,
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
             );
 
-            assert_ok!(Marketplace::set_logo_uri(
-                alice.clone(),
-                1,
-                updated_uri.unwrap()
-            ));
-            assert_eq!(Marketplace::marketplaces(1), Some(updated_info));
+            // Unhappy an order can only be filled once
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, order, signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderNonce);
         })
 }
 
 #[test]
-fn update_logo_uri_unhappy() {
+fn fill_order_unhappy() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 10000)])
+        .caps(vec![(ALICE, 1000), (BOB, 10), (DAVE, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
 
-            let fee = 25;
-            let name = vec![50];
-            let kind = MPT::Public;
-            let uri = Some(vec![66]);
-            let raw_too_short_uri: TextFormat = vec![];
-            let raw_too_long_uri: TextFormat = [0; 1001].to_vec();
+            let nft_id = help::create_nft_and_lock_series(alice.clone(), vec![50], vec![50]);
+            let bob_nft_id = help::create_nft_and_lock_series(bob.clone(), vec![50], vec![51]);
+            let draft_nft_id = help::create_nft(alice.clone(), vec![50], Some(vec![52]));
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Private, 0, vec![0], vec![]);
+            let order = Order {
+                nft_id,
+                price: 50,
+                marketplace_id: 0,
+                max_commission: commission(0, 0),
+                expiry: 10,
+                nonce: 0,
+            };
+
+            // Unhappy the seller cannot fill its own order
+            let signature = help::sign_order(ALICE, &order);
+            let ok = Marketplace::fill_order(alice.clone(), ALICE, order.clone(), signature);
+            assert_noop!(ok, Error::<Test>::NftAlreadyOwned);
+
+            // Unhappy signed by another account
+            let signature = help::sign_order(BOB, &order);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, order.clone(), signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderSignature);
+
+            // Unhappy the order was changed after it was signed
+            let signature = help::sign_order(ALICE, &order);
+            let changed = Order {
+                price: 1,
+                ..order.clone()
+            };
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, changed, signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderSignature);
+
+            // Unhappy signature over the bare encoding of the order
+            let signature = TestSignature(ALICE, order.encode());
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, order.clone(), signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderSignature);
+
+            // Unhappy order signed for another chain
+            let payload = order.signing_payload(&H256::repeat_byte(1));
+            let signature = TestSignature(ALICE, payload);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, order.clone(), signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderSignature);
+
+            // Unhappy expired order
+            System::set_block_number(11);
+            let signature = help::sign_order(ALICE, &order);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, order.clone(), signature);
+            assert_noop!(ok, Error::<Test>::OrderExpired);
+            System::set_block_number(1);
+
+            // Unhappy cancelled order
+            let cancelled = Order {
+                nonce: 1,
+                ..order.clone()
+            };
+            assert_ok!(Marketplace::cancel_order(alice.clone(), 1));
+            let signature = help::sign_order(ALICE, &cancelled);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, cancelled, signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderNonce);
+
+            // Unhappy order cancelled with all the lower nonces
+            let cancelled = Order {
+                nonce: 2,
+                ..order.clone()
+            };
+            assert_ok!(Marketplace::cancel_orders(alice.clone(), 3));
+            let signature = help::sign_order(ALICE, &cancelled);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, cancelled, signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderNonce);
+            let order = Order { nonce: 3, ..order };
+
+            // Unhappy the seller does not own the nft
+            let other = Order {
+                nft_id: bob_nft_id,
+                ..order.clone()
+            };
+            let signature = help::sign_order(ALICE, &other);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, other, signature);
+            assert_noop!(ok, Error::<Test>::NotNftOwner);
 
-            assert_ok!(Marketplace::create(
-                alice.clone(),
-                kind.clone(),
-                fee,
-                name.clone(),
-                uri.clone(),
-                uri.clone(),
-                None
-            ));
+            // Unhappy unknown nft
+            let other = Order {
+                nft_id: 10001,
+                ..order.clone()
+            };
+            let signature = help::sign_order(ALICE, &other);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, other, signature);
+            assert_noop!(ok, Error::<Test>::UnknownNFT);
 
-            let nok = Marketplace::set_logo_uri(alice.clone(), 1, raw_too_short_uri);
-            assert_noop!(nok, Error::<Test>::TooShortLogoUri);
-            let nok = Marketplace::set_logo_uri(alice, 1, raw_too_long_uri);
-            assert_noop!(nok, Error::<Test>::TooLongLogoUri);
+            // Unhappy series not completed
+            let other = Order {
+                nft_id: draft_nft_id,
+                ..order.clone()
+            };
+            let signature = help::sign_order(ALICE, &other);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, other, signature);
+            assert_noop!(ok, Error::<Test>::SeriesNotCompleted);
+
+            // Unhappy unknown marketplace
+            let other = Order {
+                marketplace_id: 10001,
+                ..order.clone()
+            };
+            let signature = help::sign_order(ALICE, &other);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, other, signature);
+            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+
+            // Unhappy seller not allowed on the marketplace
+            let other = Order {
+                marketplace_id: mkt_id,
+                ..order.clone()
+            };
+            let signature = help::sign_order(ALICE, &other);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, other, signature);
+            assert_noop!(ok, Error::<Test>::NotAllowedToList);
+
+            // Unhappy the commission was raised after the order was signed
+            let mkt_id = help::create_mkp(dave.clone(), MPT::Public, 10, vec![1], vec![]);
+            let other = Order {
+                marketplace_id: mkt_id,
+                max_commission: commission(10, 0),
+                ..order.clone()
+            };
+            let ok = Marketplace::set_commission_fee(dave.clone(), mkt_id, commission(20, 0));
+            assert_ok!(ok);
+            let signature = help::sign_order(ALICE, &other);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, other.clone(), signature);
+            assert_noop!(ok, Error::<Test>::CommissionAboveOrder);
+            let ok = Marketplace::set_commission_fee(dave.clone(), mkt_id, commission(10, 1));
+            assert_ok!(ok);
+            let signature = help::sign_order(ALICE, &other);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, other, signature);
+            assert_noop!(ok, Error::<Test>::CommissionAboveOrder);
+
+            // Unhappy not enough caps
+            let signature = help::sign_order(ALICE, &order);
+            let ok = Marketplace::fill_order(bob.clone(), ALICE, order.clone(), signature);
+            assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
+
+            // Unhappy the nft is locked
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::Capsule).unwrap();
+            let signature = help::sign_order(ALICE, &order);
+            let ok = Marketplace::fill_order(dave.clone(), ALICE, order.clone(), signature);
+            assert_noop!(ok, Error::<Test>::NftIsLocked);
         })
 }
 
 #[test]
-fn add_account_to_disallow_list_happy() {
+fn cancel_order_happy() {
     ExtBuilder::default()
         .caps(vec![(ALICE, 1000)])
         .build()
@@ -820,169 +3910,330 @@ fn add_account_to_disallow_list_happy() {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
 
             // Happy path
-            let list = vec![];
-            let mkp_1 = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], list.clone());
+            assert_ok!(Marketplace::cancel_order(alice.clone(), 5));
+            assert_eq!(UsedOrderNonces::<Test>::contains_key(ALICE, 5), true);
+            let event = MarketplaceEvent::OrderCancelled {
+                seller: ALICE,
+                nonce: 5,
+            };
             assert_eq!(
-                Marketplace::marketplaces(mkp_1).unwrap().disallow_list,
-                list
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
             );
 
-            let ok = Marketplace::add_account_to_disallow_list(alice.clone(), mkp_1, BOB);
-            assert_ok!(ok);
-            let list = vec![BOB];
+            // Happy path every lower nonce
+            assert_ok!(Marketplace::cancel_orders(alice.clone(), 3));
+            assert_eq!(Marketplace::order_nonce(ALICE), 3);
+            let event = MarketplaceEvent::OrdersCancelled {
+                seller: ALICE,
+                nonce: 3,
+            };
             assert_eq!(
-                Marketplace::marketplaces(mkp_1).unwrap().disallow_list,
-                list
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
             );
         })
 }
 
 #[test]
-fn add_account_to_disallow_list_unhappy() {
+fn cancel_order_unhappy() {
     ExtBuilder::default()
-        .caps(vec![(BOB, 1000), (DAVE, 1000)])
+        .caps(vec![(ALICE, 1000)])
         .build()
         .execute_with(|| {
-            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            assert_ok!(Marketplace::cancel_order(alice.clone(), 5));
+            assert_ok!(Marketplace::cancel_orders(alice.clone(), 3));
 
-            // Unhappy unknown marketplace
-            let ok = Marketplace::add_account_to_disallow_list(bob.clone(), 1001, DAVE);
-            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
+            // Unhappy already cancelled
+            let ok = Marketplace::cancel_order(alice.clone(), 5);
+            assert_noop!(ok, Error::<Test>::InvalidOrderNonce);
 
-            // Unhappy not marketplace owner
-            let ok = Marketplace::add_account_to_disallow_list(bob.clone(), 0, DAVE);
-            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+            // Unhappy cancelled with the lower nonces
+            let ok = Marketplace::cancel_order(alice.clone(), 2);
+            assert_noop!(ok, Error::<Test>::InvalidOrderNonce);
 
-            // Unhappy unsupported marketplace type
-            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 0, vec![50], vec![]);
-            let ok = Marketplace::add_account_to_disallow_list(bob.clone(), mkp_id, DAVE);
-            assert_noop!(ok, Error::<Test>::UnsupportedMarketplace);
+            // Unhappy the nonce can only go up
+            let ok = Marketplace::cancel_orders(alice.clone(), 3);
+            assert_noop!(ok, Error::<Test>::InvalidOrderNonce);
         })
 }
 
 #[test]
-fn remove_account_from_disallow_list_happy() {
+fn redeem_voucher_happy() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
-            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let alice_before = Balances::free_balance(ALICE);
+
+            let voucher = MintVoucher {
+                ipfs_reference: bounded(vec![50]),
+                series_id: Some(vec![50]),
+                price: 100,
+                royalty: Permill::from_percent(10),
+                expiry: 10,
+                nonce: 0,
+            };
+            let signature = help::sign_voucher(ALICE, &voucher);
 
             // Happy path
-            let list = vec![BOB];
-            let mkp_id = help::create_mkp(alice.clone(), MPT::Public, 0, vec![50], list.clone());
+            let ok =
+                Marketplace::redeem_voucher(bob.clone(), ALICE, voucher.clone(), signature.clone());
+            assert_ok!(ok);
+            let nft_id = NFTs::nft_id_generator() - 1;
+            let nft = NFTs::data(nft_id).unwrap();
+            assert_eq!(nft.owner, BOB);
+            assert_eq!(nft.creator, ALICE);
+            assert_eq!(nft.royalty, Permill::from_percent(10));
+            assert_eq!(UsedOrderNonces::<Test>::contains_key(ALICE, 0), true);
+
+            // The series named by the voucher did not exist and is created completed
             assert_eq!(
-                Marketplace::marketplaces(mkp_id).unwrap().disallow_list,
-                list
+                <NFTs as NFTTrait>::is_nft_in_completed_series(nft_id),
+                Some(true)
+            );
+            let ok = Marketplace::list(
+                bob.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![],
             );
-
-            let ok = Marketplace::remove_account_from_disallow_list(alice.clone(), mkp_id, BOB);
             assert_ok!(ok);
-            let list: Vec<u64> = vec![];
+
+            // The creator pays the mint fee out of the price
+            let mint_fee = NFTs::nft_mint_fee();
+            assert_eq!(Balances::free_balance(BOB), 900);
+            assert_eq!(Balances::free_balance(ALICE), alice_before + 100 - mint_fee);
+
+            let event = MarketplaceEvent::VoucherRedeemed {
+                creator: ALICE,
+                nonce: 0,
+                nft_id,
+                owner: BOB,
+                price: 100,
+            };
             assert_eq!(
-                Marketplace::marketplaces(mkp_id).unwrap().disallow_list,
-                list
+                System::events().last().unwrap().event,
+                Event::Marketplace(event)
             );
+
+            // Unhappy a voucher can only be redeemed once
+            let ok = Marketplace::redeem_voucher(bob.clone(), ALICE, voucher, signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderNonce);
         })
 }
 
 #[test]
-fn remove_account_from_disallow_list_unhappy() {
+fn redeem_voucher_without_series() {
     ExtBuilder::default()
-        .caps(vec![(BOB, 1000), (DAVE, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
             let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let alice_before = Balances::free_balance(ALICE);
 
-            // Unhappy unknown marketplace
-            let ok = Marketplace::remove_account_from_disallow_list(bob.clone(), 1001, DAVE);
-            assert_noop!(ok, Error::<Test>::UnknownMarketplace);
-
-            // Unhappy not marketplace owner
-            let ok = Marketplace::remove_account_from_disallow_list(bob.clone(), 0, DAVE);
-            assert_noop!(ok, Error::<Test>::NotMarketplaceOwner);
+            let mint_fee = NFTs::nft_mint_fee();
+            let voucher = MintVoucher {
+                ipfs_reference: bounded(vec![50]),
+                series_id: None,
+                price: mint_fee,
+                royalty: Permill::from_percent(10),
+                expiry: 10,
+                nonce: 0,
+            };
+            let signature = help::sign_voucher(ALICE, &voucher);
+
+            // Happy path a price equal to the mint fee is enough
+            assert_ok!(Marketplace::redeem_voucher(
+                bob.clone(),
+                ALICE,
+                voucher,
+                signature
+            ));
+            let nft_id = NFTs::nft_id_generator() - 1;
+            assert_eq!(NFTs::data(nft_id).unwrap().owner, BOB);
+            assert_eq!(Balances::free_balance(ALICE), alice_before);
 
-            // Unhappy unsupported marketplace type
-            let mkp_id = help::create_mkp(bob.clone(), MPT::Private, 0, vec![50], vec![]);
-            let ok = Marketplace::remove_account_from_disallow_list(bob.clone(), mkp_id, DAVE);
-            assert_noop!(ok, Error::<Test>::UnsupportedMarketplace);
+            // The series of the nft is completed so that it can be sold
+            assert_eq!(
+                <NFTs as NFTTrait>::is_nft_in_completed_series(nft_id),
+                Some(true)
+            );
+            let ok = Marketplace::list(
+                bob.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![],
+            );
+            assert_ok!(ok);
         })
 }
 
 #[test]
-fn set_description_happy() {
+fn redeem_voucher_with_draft_series() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 10000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
 
-            let fee = 25;
-            let name = vec![50];
-            let kind = MPT::Public;
-            let uri = Some(vec![66]);
-            let description = Some(vec![66]);
-            let updated_description = Some(vec![67]);
-
-            let updated_info = MarketplaceInformation::new(
-                kind,
-                fee,
-                ALICE,
-                vec![],
-                vec![],
-                name.clone(),
-                uri.clone(),
-                uri.clone(),
-                updated_description.clone(),
-            );
-
-            assert_ok!(Marketplace::create(
+            let series_id = vec![51];
+            let ok = NFTs::create(
                 alice.clone(),
-                kind.clone(),
-                fee,
-                name.clone(),
-                uri.clone(),
-                uri.clone(),
-                description.clone(),
+                vec![50],
+                Some(series_id.clone()),
+                Permill::zero(),
+            );
+            assert_ok!(ok);
+            let draft_nft_id = NFTs::nft_id_generator() - 1;
+
+            let voucher = MintVoucher {
+                ipfs_reference: bounded(vec![50]),
+                series_id: Some(series_id),
+                price: 100,
+                royalty: Permill::from_percent(10),
+                expiry: 10,
+                nonce: 0,
+            };
+            let signature = help::sign_voucher(ALICE, &voucher);
+            assert_ok!(Marketplace::redeem_voucher(
+                bob.clone(),
+                ALICE,
+                voucher,
+                signature
             ));
+            let nft_id = NFTs::nft_id_generator() - 1;
 
-            assert_ok!(Marketplace::set_description(
-                alice.clone(),
-                1,
-                updated_description.unwrap()
-            ));
-            assert_eq!(Marketplace::marketplaces(1), Some(updated_info));
+            // The draft series named by the voucher is completed
+            assert_eq!(
+                <NFTs as NFTTrait>::is_nft_in_completed_series(nft_id),
+                Some(true)
+            );
+            assert_eq!(
+                <NFTs as NFTTrait>::is_nft_in_completed_series(draft_nft_id),
+                Some(true)
+            );
+            let ok = Marketplace::list(
+                bob.clone(),
+                nft_id,
+                50,
+                PaymentAsset::Native,
+                None,
+                None,
+                None,
+                vec![],
+            );
+            assert_ok!(ok);
         })
 }
 
 #[test]
-fn set_description_unhappy() {
+fn redeem_voucher_unhappy() {
     ExtBuilder::default()
-        .caps(vec![(ALICE, 10000)])
+        .caps(vec![(ALICE, 1000), (BOB, 10), (DAVE, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let bob: mock::Origin = RawOrigin::Signed(BOB).into();
+            let dave: mock::Origin = RawOrigin::Signed(DAVE).into();
 
-            let fee = 25;
-            let name = vec![50];
-            let kind = MPT::Public;
-            let uri = Some(vec![66]);
-            let raw_too_short_description: TextFormat = vec![];
-            let raw_too_long_description: TextFormat = [0; 501].to_vec();
+            help::create_nft(dave.clone(), vec![50], Some(vec![51]));
+            let voucher = MintVoucher {
+                ipfs_reference: bounded(vec![50]),
+                series_id: None,
+                price: 100,
+                royalty: Permill::from_percent(10),
+                expiry: 10,
+                nonce: 0,
+            };
+
+            // Unhappy the creator cannot redeem its own voucher
+            let signature = help::sign_voucher(ALICE, &voucher);
+            let ok = Marketplace::redeem_voucher(alice.clone(), ALICE, voucher.clone(), signature);
+            assert_noop!(ok, Error::<Test>::NftAlreadyOwned);
+
+            // Unhappy signed by another account
+            let signature = help::sign_voucher(BOB, &voucher);
+            let ok = Marketplace::redeem_voucher(dave.clone(), ALICE, voucher.clone(), signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderSignature);
+
+            // Unhappy the voucher was changed after it was signed
+            let signature = help::sign_voucher(ALICE, &voucher);
+            let changed = MintVoucher {
+                price: 1,
+                ..voucher.clone()
+            };
+            let ok = Marketplace::redeem_voucher(dave.clone(), ALICE, changed, signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderSignature);
+
+            // Unhappy voucher signed for another chain
+            let payload = voucher.signing_payload(&H256::repeat_byte(1));
+            let signature = TestSignature(ALICE, payload);
+            let ok = Marketplace::redeem_voucher(dave.clone(), ALICE, voucher.clone(), signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderSignature);
+
+            // Unhappy the signature of an order cannot be used for a voucher
+            let order = Order {
+                nft_id: 0,
+                price: 100,
+                marketplace_id: 0,
+                max_commission: commission(0, 0),
+                expiry: 10,
+                nonce: 0,
+            };
+            let signature = help::sign_order(ALICE, &order);
+            let ok = Marketplace::redeem_voucher(dave.clone(), ALICE, voucher.clone(), signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderSignature);
+
+            // Unhappy expired voucher
+            System::set_block_number(11);
+            let signature = help::sign_voucher(ALICE, &voucher);
+            let ok = Marketplace::redeem_voucher(dave.clone(), ALICE, voucher.clone(), signature);
+            assert_noop!(ok, Error::<Test>::OrderExpired);
+            System::set_block_number(1);
+
+            // Unhappy cancelled voucher
+            assert_ok!(Marketplace::cancel_order(alice.clone(), 0));
+            let signature = help::sign_voucher(ALICE, &voucher);
+            let ok = Marketplace::redeem_voucher(dave.clone(), ALICE, voucher.clone(), signature);
+            assert_noop!(ok, Error::<Test>::InvalidOrderNonce);
+            let voucher = MintVoucher {
+                nonce: 1,
+                ..voucher
+            };
+
+            // Unhappy the price does not cover the mint fee
+            let cheap = MintVoucher {
+                price: NFTs::nft_mint_fee() - 1,
+                ..voucher.clone()
+            };
+            let signature = help::sign_voucher(ALICE, &cheap);
+            let ok = Marketplace::redeem_voucher(dave.clone(), ALICE, cheap, signature);
+            assert_noop!(ok, Error::<Test>::VoucherPriceBelowMintFee);
 
-            assert_ok!(Marketplace::create(
-                alice.clone(),
-                kind.clone(),
-                fee,
-                name.clone(),
-                uri.clone(),
-                uri.clone(),
-                None
-            ));
+            // Unhappy not enough caps
+            let signature = help::sign_voucher(ALICE, &voucher);
+            let ok = Marketplace::redeem_voucher(bob.clone(), ALICE, voucher.clone(), signature);
+            assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
 
-            let nok = Marketplace::set_description(alice.clone(), 1, raw_too_short_description);
-            assert_noop!(nok, Error::<Test>::TooShortDescription);
-            let nok = Marketplace::set_description(alice, 1, raw_too_long_description);
-            assert_noop!(nok, Error::<Test>::TooLongDescription);
+            // Unhappy the series belongs to someone else
+            let other = MintVoucher {
+                series_id: Some(vec![51]),
+                ..voucher.clone()
+            };
+            let signature = help::sign_voucher(ALICE, &other);
+            let ok = Marketplace::redeem_voucher(dave.clone(), ALICE, other, signature);
+            assert_noop!(ok, NFTError::<Test>::NotTheSeriesOwner);
         })
 }
@@ -1,84 +1,391 @@
-/* use super::mock::*;
-use crate::migrations::v6::v6;
-use crate::migrations::v7::v7;
+use super::mock::*;
+use crate::migrations::v2::{v1, v2};
+use crate::migrations::v4::{v3, v4};
+use crate::migrations::v5::v5;
+use crate::migrations::v7::v6;
+use crate::migrations::v8::v7;
+use crate::{
+    index_key, BundleInformation, Bundles, ListingDeadlines, MarketplaceBundles,
+    MarketplaceInformation, MarketplaceSales, Marketplaces, NFTsForSale, SaleInformation,
+};
+use frame_support::assert_ok;
+use frame_support::storage::unhashed;
 use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+use frame_support::BoundedVec;
+use std::convert::TryInto;
+use ternoa_primitives::marketplace::{MarketplaceId, MarketplaceType, PaymentAsset};
 
-pub type MPT6 = v6::MarketplaceType;
-pub type MPT7 = v7::MarketplaceType;
+mod version_2 {
+    use super::*;
 
-mod version_7 {
+    fn insert_v1_marketplace(id: MarketplaceId, allow_list: Vec<u64>, name: Vec<u8>) {
+        let info = v1::MarketplaceInformation {
+            kind: MarketplaceType::Private,
+            commission_fee: 10,
+            owner: ALICE,
+            allow_list,
+            disallow_list: vec![],
+            name,
+            uri: Some(vec![65; 5]),
+            logo_uri: None,
+            description: None,
+        };
+        unhashed::put(&Marketplaces::<Test>::hashed_key_for(id), &info);
+    }
+
+    #[test]
+    fn upgrade_from_v1_to_v2() {
+        ExtBuilder::default().build().execute_with(|| {
+            insert_v1_marketplace(1, vec![BOB], vec![50]);
+            insert_v1_marketplace(2, vec![1, 2, 3, 4, 5], vec![51; 5]);
+
+            StorageVersion::put::<Marketplace>(&StorageVersion::new(1));
+            let weight = <Marketplace as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Marketplace>(), StorageVersion::new(9));
+
+            let expected = MarketplaceInformation::new(
+                MarketplaceType::Private,
+                commission(10, 0),
+                ALICE,
+                bounded(vec![50]),
+                Some(bounded(vec![65; 5])),
+                None,
+                None,
+            );
+            assert_eq!(Marketplace::marketplaces(1), Some(expected));
+
+            assert_eq!(Marketplace::allow_list(1, None, 10), vec![BOB]);
+
+            let longest = Marketplace::marketplaces(2).unwrap();
+            assert_eq!(longest.name, vec![51; 5]);
+            assert_eq!(Marketplace::allow_list(2, None, 10), vec![1, 2, 3, 4, 5]);
+        })
+    }
+
+    #[test]
+    fn upgrade_from_v1_to_v2_is_aborted_when_something_does_not_fit() {
+        ExtBuilder::default().build().execute_with(|| {
+            // MaxNameLen is 5
+            insert_v1_marketplace(1, vec![BOB], vec![50]);
+            insert_v1_marketplace(2, vec![BOB], vec![51; 7]);
+
+            StorageVersion::put::<Marketplace>(&StorageVersion::new(1));
+            let weight = <Marketplace as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Marketplace>(), StorageVersion::new(1));
+
+            for id in [1, 2] {
+                let key = Marketplaces::<Test>::hashed_key_for(id);
+                let old: v1::MarketplaceInformation<u64> = unhashed::get(&key).unwrap();
+                assert_eq!(old.allow_list, vec![BOB]);
+            }
+        })
+    }
+}
+
+mod version_3 {
     use super::*;
 
-    fn create_mp(
-        id: v6::MarketplaceId,
-        owner: u64,
-        kind: v6::MarketplaceType,
-        fee: u8,
-        list: Vec<u64>,
-        name: Vec<u8>,
-    ) {
-        v6::insert_marketplace::<Test>(id, owner, kind, fee, list, name);
+    fn insert_v2_marketplace(id: MarketplaceId, commission_fee: u8) {
+        let info = v2::MarketplaceInformation::<Test> {
+            kind: MarketplaceType::Public,
+            commission_fee,
+            owner: ALICE,
+            allow_list: Default::default(),
+            disallow_list: vec![BOB].try_into().unwrap(),
+            name: bounded(vec![50]),
+            uri: None,
+            logo_uri: None,
+            description: Some(bounded(vec![66])),
+        };
+        unhashed::put(&Marketplaces::<Test>::hashed_key_for(id), &info);
     }
 
-    fn check_mp(
-        id: v6::MarketplaceId,
-        owner: u64,
-        kind: v7::MarketplaceType,
-        fee: u8,
-        list: Vec<u64>,
-        name: Vec<u8>,
-        map: &v7::StorageMarketplaces<Test>,
-    ) {
-        let empty: Vec<u64> = vec![];
-
-        // Old
-        assert_eq!(map.get(&id).unwrap().owner, owner);
-        assert_eq!(map.get(&id).unwrap().kind, kind);
-        assert_eq!(map.get(&id).unwrap().commission_fee, fee);
-        assert_eq!(map.get(&id).unwrap().allow_list, list);
-        assert_eq!(map.get(&id).unwrap().name, name);
-
-        // New
-        assert_eq!(map.get(&id).unwrap().disallow_list, empty);
-        assert_eq!(map.get(&id).unwrap().uri, None);
-        assert_eq!(map.get(&id).unwrap().logo_uri, None);
+    #[test]
+    fn upgrade_from_v2_to_v3() {
+        ExtBuilder::default().build().execute_with(|| {
+            insert_v2_marketplace(1, 30);
+            insert_v2_marketplace(2, 0);
+            insert_v2_marketplace(3, 100);
+
+            StorageVersion::put::<Marketplace>(&StorageVersion::new(2));
+            let weight = <Marketplace as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Marketplace>(), StorageVersion::new(9));
+
+            let expected = MarketplaceInformation::new(
+                MarketplaceType::Public,
+                commission(30, 0),
+                ALICE,
+                bounded(vec![50]),
+                None,
+                None,
+                Some(bounded(vec![66])),
+            );
+            assert_eq!(Marketplace::marketplaces(1), Some(expected));
+            assert_eq!(Marketplace::disallow_list(1, None, 10), vec![BOB]);
+
+            let fee = Marketplace::marketplaces(2).unwrap().commission_fee;
+            assert_eq!(fee, commission(0, 0));
+            let fee = Marketplace::marketplaces(3).unwrap().commission_fee;
+            assert_eq!(fee, commission(100, 0));
+        })
+    }
+}
+
+mod version_4 {
+    use super::*;
+
+    fn insert_v3_sale(nft_id: u32, marketplace_id: MarketplaceId) {
+        let sale = v3::SaleInformation {
+            account_id: BOB,
+            price: 100u128,
+            marketplace_id,
+        };
+        unhashed::put(&NFTsForSale::<Test>::hashed_key_for(nft_id), &sale);
+    }
+
+    #[test]
+    fn upgrade_from_v3_to_v4() {
+        ExtBuilder::default().build().execute_with(|| {
+            let alice: Origin = frame_system::RawOrigin::Signed(ALICE).into();
+            let fee = commission(15, 2);
+            assert_ok!(Marketplace::set_commission_fee(alice, 0, fee));
+
+            insert_v3_sale(1, 0);
+            insert_v3_sale(2, 1001);
+
+            StorageVersion::put::<Marketplace>(&StorageVersion::new(3));
+            let weight = <Marketplace as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Marketplace>(), StorageVersion::new(9));
+
+            let expected = SaleInformation::new(
+                BOB,
+                100,
+                PaymentAsset::Native,
+                0,
+                fee,
+                None,
+                None,
+                Default::default(),
+            );
+            assert_eq!(Marketplace::nft_for_sale(1), Some(expected));
+
+            // Sales on unknown marketplaces keep no commission
+            let expected = SaleInformation::new(
+                BOB,
+                100,
+                PaymentAsset::Native,
+                1001,
+                commission(0, 0),
+                None,
+                None,
+                Default::default(),
+            );
+            assert_eq!(Marketplace::nft_for_sale(2), Some(expected));
+        })
+    }
+}
+
+mod version_5 {
+    use super::*;
+
+    #[test]
+    fn upgrade_from_v4_to_v5() {
+        ExtBuilder::default().build().execute_with(|| {
+            let sale = v4::SaleInformation::<Test> {
+                account_id: BOB,
+                price: 100,
+                marketplace_id: 0,
+                commission_fee: commission(15, 2),
+            };
+            v4::NFTsForSale::<Test>::insert(1, sale);
+
+            StorageVersion::put::<Marketplace>(&StorageVersion::new(4));
+            let weight = <Marketplace as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Marketplace>(), StorageVersion::new(9));
+
+            let expected = SaleInformation::new(
+                BOB,
+                100,
+                PaymentAsset::Native,
+                0,
+                commission(15, 2),
+                None,
+                None,
+                Default::default(),
+            );
+            assert_eq!(Marketplace::nft_for_sale(1), Some(expected));
+            assert_eq!(ListingDeadlines::<Test>::iter().count(), 0);
+        })
+    }
+}
+
+mod version_6 {
+    use super::*;
+
+    #[test]
+    fn upgrade_from_v5_to_v6() {
+        ExtBuilder::default().build().execute_with(|| {
+            let sale = v5::SaleInformation::<Test> {
+                account_id: BOB,
+                price: 100,
+                marketplace_id: 0,
+                commission_fee: commission(15, 2),
+                start_block: Some(5),
+                end_block: Some(10),
+            };
+            v5::NFTsForSale::<Test>::insert(1, sale);
+
+            StorageVersion::put::<Marketplace>(&StorageVersion::new(5));
+            let weight = <Marketplace as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Marketplace>(), StorageVersion::new(9));
+
+            let expected = SaleInformation::new(
+                BOB,
+                100,
+                PaymentAsset::Native,
+                0,
+                commission(15, 2),
+                Some(5),
+                Some(10),
+                Default::default(),
+            );
+            assert_eq!(Marketplace::nft_for_sale(1), Some(expected));
+        })
+    }
+}
+
+mod version_7 {
+    use super::*;
+
+    fn insert_v6_marketplace(id: MarketplaceId, kind: MarketplaceType, list: Vec<u64>) {
+        let list: BoundedVec<u64, _> = list.try_into().unwrap();
+        let (allow_list, disallow_list) = match kind {
+            MarketplaceType::Private => (list, Default::default()),
+            MarketplaceType::Public => (Default::default(), list),
+        };
+        let info = v6::MarketplaceInformation::<Test> {
+            kind,
+            commission_fee: commission(10, 1),
+            owner: ALICE,
+            allow_list,
+            disallow_list,
+            name: bounded(vec![50]),
+            uri: None,
+            logo_uri: None,
+            description: Some(bounded(vec![66])),
+        };
+        v6::Marketplaces::<Test>::insert(id, info);
     }
 
     #[test]
     fn upgrade_from_v6_to_v7() {
         ExtBuilder::default().build().execute_with(|| {
-            create_mp(0, ALICE, MPT6::Private, 0, vec![], "Charma".into());
-            create_mp(1, ALICE, MPT6::Public, 1, vec![1], "Charme".into());
-            create_mp(2, ALICE, MPT6::Private, 2, vec![2], "Chari".into());
-            create_mp(3, BOB, MPT6::Public, 3, vec![3], "Squirt".into());
-            create_mp(4, BOB, MPT6::Private, 4, vec![4], "Wartor".into());
+            insert_v6_marketplace(1, MarketplaceType::Private, vec![BOB, DAVE]);
+            insert_v6_marketplace(2, MarketplaceType::Public, vec![DAVE]);
 
             StorageVersion::put::<Marketplace>(&StorageVersion::new(6));
             let weight = <Marketplace as OnRuntimeUpgrade>::on_runtime_upgrade();
             assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Marketplace>(), StorageVersion::new(9));
 
-            let mps = v7::get_marketplaces::<Test>();
-            let mp_mint_fee = v7::get_nft_mint_fee::<Test>();
+            let expected = MarketplaceInformation::new(
+                MarketplaceType::Private,
+                commission(10, 1),
+                ALICE,
+                bounded(vec![50]),
+                None,
+                None,
+                Some(bounded(vec![66])),
+            );
+            assert_eq!(Marketplace::marketplaces(1), Some(expected));
+            assert_eq!(Marketplace::allow_list(1, None, 10), vec![BOB, DAVE]);
+            assert!(Marketplace::disallow_list(1, None, 10).is_empty());
 
-            // Check Marketplaces
-            assert_eq!(mps.len(), 5);
-            check_mp(0, ALICE, MPT7::Private, 0, vec![], "Charma".into(), &mps);
-            check_mp(1, ALICE, MPT7::Public, 1, vec![1], "Charme".into(), &mps);
-            check_mp(2, ALICE, MPT7::Private, 2, vec![2], "Chari".into(), &mps);
-            check_mp(3, BOB, MPT7::Public, 3, vec![3], "Squirt".into(), &mps);
-            check_mp(4, BOB, MPT7::Private, 4, vec![4], "Wartor".into(), &mps);
+            assert!(Marketplace::allow_list(2, None, 10).is_empty());
+            assert_eq!(Marketplace::disallow_list(2, None, 10), vec![DAVE]);
+        })
+    }
+}
 
-            // Check NFT mint fee
-            assert_eq!(mp_mint_fee, 10000000000000000000000);
+mod version_8 {
+    use super::*;
+
+    #[test]
+    fn upgrade_from_v7_to_v8() {
+        ExtBuilder::default().build().execute_with(|| {
+            let sale = v7::SaleInformation::<Test> {
+                account_id: BOB,
+                price: 100,
+                asset: PaymentAsset::Asset(TIIME),
+                marketplace_id: 0,
+                commission_fee: commission(15, 2),
+                start_block: Some(5),
+                end_block: None,
+            };
+            v7::NFTsForSale::<Test>::insert(1, sale);
+
+            StorageVersion::put::<Marketplace>(&StorageVersion::new(7));
+            let weight = <Marketplace as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Marketplace>(), StorageVersion::new(9));
+
+            let expected = SaleInformation::new(
+                BOB,
+                100,
+                PaymentAsset::Asset(TIIME),
+                0,
+                commission(15, 2),
+                Some(5),
+                None,
+                Default::default(),
+            );
+            assert_eq!(Marketplace::nft_for_sale(1), Some(expected));
+            assert!(!Marketplace::nft_for_sale(1).unwrap().is_private());
         })
     }
 }
 
-#[test]
-fn upgrade_from_latest_to_latest() {
-    ExtBuilder::default().build().execute_with(|| {
-        let weight = <NFTs as OnRuntimeUpgrade>::on_runtime_upgrade();
-        assert_eq!(weight, 0);
-    })
+mod version_9 {
+    use super::*;
+
+    #[test]
+    fn upgrade_from_v8_to_v9() {
+        ExtBuilder::default().build().execute_with(|| {
+            let sale = SaleInformation::new(
+                BOB,
+                100,
+                PaymentAsset::Native,
+                1,
+                commission(10, 0),
+                None,
+                None,
+                Default::default(),
+            );
+            NFTsForSale::<Test>::insert(3, sale);
+
+            let bundle = BundleInformation {
+                account_id: BOB,
+                nft_ids: vec![4, 5].try_into().unwrap(),
+                price: 100,
+                asset: PaymentAsset::Native,
+                marketplace_id: 1,
+                commission_fee: commission(10, 0),
+            };
+            Bundles::<Test>::insert(7, bundle);
+
+            StorageVersion::put::<Marketplace>(&StorageVersion::new(8));
+            let weight = <Marketplace as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Marketplace>(), StorageVersion::new(9));
+
+            assert_eq!(MarketplaceSales::<Test>::get(1, index_key(3)), Some(false));
+            assert!(MarketplaceBundles::<Test>::contains_key(1, 7));
+        })
+    }
 }
- */
@@ -1,11 +1,17 @@
-use crate::{self as ternoa_marketplace, Config, MarketplaceInformation};
+use crate::{self as ternoa_marketplace, Config, MintVoucherOf};
 use frame_support::parameter_types;
-use frame_support::traits::{ConstU32, Contains, GenesisBuild};
+use frame_support::traits::{ConstU128, ConstU32, Contains, GenesisBuild, Get};
+use frame_support::weights::constants::RocksDbWeight;
+use frame_support::BoundedVec;
+use frame_system::EnsureRoot;
 use sp_core::H256;
-use sp_runtime::testing::Header;
+use sp_runtime::testing::{Header, TestSignature, UintAuthorityId};
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
-use ternoa_primitives::marketplace::MarketplaceType;
-use ternoa_primitives::nfts::{NFTData, NFTSeriesDetails};
+use sp_runtime::Permill;
+use std::convert::TryInto;
+use ternoa_primitives::marketplace::{MarketplaceCommission, MarketplaceType, Order};
+use ternoa_primitives::nfts::{NFTId, NFTSeriesDetails, NFTSeriesId};
+use ternoa_primitives::TextFormat;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -14,6 +20,8 @@ pub const ALICE: u64 = 1;
 pub const BOB: u64 = 2;
 pub const DAVE: u64 = 3;
 
+pub const TIIME: u32 = 1;
+
 frame_support::construct_runtime!(
     pub enum Test where
         Block = Block,
@@ -22,6 +30,7 @@ frame_support::construct_runtime!(
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
         Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>, Config<T>},
         NFTs: ternoa_nfts::{Pallet, Call, Storage, Event<T>, Config<T>},
         Marketplace: ternoa_marketplace::{Pallet, Call, Event<T>},
     }
@@ -50,7 +59,7 @@ impl frame_system::Config for Test {
     type BaseCallFilter = TestBaseCallFilter;
     type BlockWeights = ();
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Origin = Origin;
     type Index = u64;
     type BlockNumber = u64;
@@ -91,17 +100,43 @@ impl pallet_balances::Config for Test {
     type MaxLocks = MaxLocks;
 }
 
+impl pallet_assets::Config for Test {
+    type Event = Event;
+    type Balance = u128;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<u64>;
+    type AssetDeposit = ConstU128<0>;
+    type AssetAccountDeposit = ConstU128<0>;
+    type MetadataDepositBase = ConstU128<0>;
+    type MetadataDepositPerByte = ConstU128<0>;
+    type ApprovalDeposit = ConstU128<0>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
+}
+
 parameter_types! {
     pub const MinUriLen: u16 = 1;
     pub const MaxUriLen: u16 = 5;
     pub const MinIpfsLen: u16 = 1;
     pub const MaxIpfsLen: u16 = 5;
+    pub const MaxSeriesIdLen: u16 = 5;
     pub const MinDescriptionLen: u16 = 1;
     pub const MaxDescriptionLen: u16 = 500;
     pub const MinNameLen: u16 = 1;
     pub const MaxNameLen: u16 = 5;
 }
 
+parameter_types! {
+    pub const BatchMintDiscount: Permill = Permill::from_percent(0);
+    pub const UseStorageDeposits: bool = false;
+    pub const DepositBase: u128 = 0;
+    pub const DepositPerByte: u128 = 0;
+    pub const DepositFollowsOwner: bool = true;
+}
+
 impl ternoa_nfts::Config for Test {
     type Event = Event;
     type WeightInfo = ();
@@ -109,28 +144,62 @@ impl ternoa_nfts::Config for Test {
     type FeesCollector = ();
     type MinIpfsLen = MinIpfsLen;
     type MaxIpfsLen = MaxIpfsLen;
+    type IPFSReferenceValidator = ();
+    type MaxSeriesIdLen = MaxSeriesIdLen;
+    type MaxBatchSize = ConstU32<5>;
+    type BatchMintDiscount = BatchMintDiscount;
+    type UseStorageDeposits = UseStorageDeposits;
+    type DepositBase = DepositBase;
+    type DepositPerByte = DepositPerByte;
+    type DepositFollowsOwner = DepositFollowsOwner;
+    type ForceOrigin = EnsureRoot<u64>;
+    type NFTReleaseHandler = Marketplace;
+    type MaxRentalsPerBlock = ConstU32<5>;
 }
 
 impl Config for Test {
     type Event = Event;
     type Currency = Balances;
     type NFTs = NFTs;
+    type MarketplaceCloseHandler = ();
     type WeightInfo = ();
     type FeesCollector = ();
+    type AssetId = u32;
+    type Assets = Assets;
     type MinNameLen = MinNameLen;
     type MaxNameLen = MaxNameLen;
     type MinUriLen = MinUriLen;
     type MaxUriLen = MaxUriLen;
     type MinDescriptionLen = MinDescriptionLen;
     type MaxDescriptionLen = MaxDescriptionLen;
+    type MaxAccountsPerCall = ConstU32<3>;
+    type MaxListingsPerBlock = ConstU32<3>;
+    type MaxOffersPerBlock = ConstU32<2>;
+    type MaxAcceptedAssets = ConstU32<2>;
+    type MaxBundleSize = ConstU32<3>;
+    type OrderSignature = TestSignature;
+    type OrderSigner = UintAuthorityId;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = OrderSigning;
+}
+
+/// Signs orders in benchmarks with an account that no test uses.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct OrderSigning;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl ternoa_marketplace::BenchmarkHelper<u64, TestSignature> for OrderSigning {
+    fn sign(message: &[u8]) -> (u64, TestSignature) {
+        (100, TestSignature(100, message.to_vec()))
+    }
 }
 
 pub struct ExtBuilder {
-    nfts: Vec<(u32, NFTData<u64>)>,
+    nfts: Vec<(NFTId, u64, u64, TextFormat, NFTSeriesId, Permill)>,
     series: Vec<(Vec<u8>, NFTSeriesDetails<u64>)>,
     caps_endowed_accounts: Vec<(u64, u128)>,
     tiime_endowed_accounts: Vec<(u64, u128)>,
-    marketplaces: Vec<(u64, MarketplaceType, u8, Vec<u8>)>,
+    marketplaces: Vec<(u64, MarketplaceType, MarketplaceCommission<u128>, Vec<u8>)>,
 }
 
 impl Default for ExtBuilder {
@@ -171,6 +240,18 @@ impl ExtBuilder {
         .assimilate_storage(&mut t)
         .unwrap();
 
+        pallet_assets::GenesisConfig::<Test> {
+            assets: vec![(TIIME, ALICE, true, 1)],
+            metadata: vec![],
+            accounts: self
+                .tiime_endowed_accounts
+                .into_iter()
+                .map(|(account, balance)| (TIIME, account, balance))
+                .collect(),
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
         ternoa_nfts::GenesisConfig::<Test> {
             nfts: self.nfts,
             series: self.series,
@@ -181,34 +262,14 @@ impl ExtBuilder {
 
         let mut marketplaces = vec![(
             0,
-            MarketplaceInformation::new(
-                MarketplaceType::Public,
-                0,
-                ALICE,
-                Default::default(),
-                vec![],
-                "Ternoa marketplace".into(),
-                None,
-                None,
-                None,
-            ),
+            ALICE,
+            MarketplaceType::Public,
+            commission(0, 0),
+            "Caps".into(),
         )];
         let mut i = 1;
         for market in self.marketplaces {
-            marketplaces.push((
-                i,
-                MarketplaceInformation::new(
-                    market.1,
-                    market.2,
-                    market.0,
-                    vec![],
-                    vec![],
-                    market.3,
-                    None,
-                    None,
-                    None,
-                ),
-            ));
+            marketplaces.push((i, market.0, market.1, market.2, market.3));
 
             i += 1;
         }
@@ -250,7 +311,12 @@ pub mod help {
         ipfs_reference: TextFormat,
         series_id: Option<NFTSeriesId>,
     ) -> NFTId {
-        assert_ok!(NFTs::create(owner, ipfs_reference, series_id));
+        assert_ok!(NFTs::create(
+            owner,
+            ipfs_reference,
+            series_id,
+            Permill::zero()
+        ));
         return NFTs::nft_id_generator() - 1;
     }
 
@@ -275,7 +341,7 @@ pub mod help {
         assert_ok!(Marketplace::create(
             owner.clone(),
             kind,
-            fee,
+            commission(fee.into(), 0),
             name,
             None,
             None,
@@ -302,6 +368,23 @@ pub mod help {
     pub fn finish_series(owner: Origin, series_id: Vec<u8>) {
         assert_ok!(NFTs::finish_series(owner, series_id));
     }
+
+    pub fn sign_order(seller: u64, order: &Order<u128, u64>) -> TestSignature {
+        TestSignature(seller, order.signing_payload(&System::block_hash(0)))
+    }
+
+    pub fn sign_voucher(creator: u64, voucher: &MintVoucherOf<Test>) -> TestSignature {
+        TestSignature(creator, voucher.signing_payload(&System::block_hash(0)))
+    }
+}
+
+/// Commission of `percent` percent plus a `flat` fee.
+pub fn commission(percent: u32, flat: u128) -> MarketplaceCommission<u128> {
+    MarketplaceCommission::new(Permill::from_percent(percent), flat)
+}
+
+pub fn bounded<S: Get<u32>>(value: Vec<u8>) -> BoundedVec<u8, S> {
+    value.try_into().unwrap()
 }
 
 #[allow(dead_code)]
@@ -1,23 +1,41 @@
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::{ConstU32, Get};
+use frame_support::{BoundedVec, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound};
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
-use ternoa_primitives::marketplace::MarketplaceId;
+use sp_std::fmt::Debug;
+use ternoa_primitives::marketplace::{MarketplaceCommission, MarketplaceId, PaymentAsset};
+use ternoa_primitives::nfts::NFTId;
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+/// Max number of accounts a listing can be reserved to.
+pub type MaxBuyers = ConstU32<10>;
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct SaleInformation<AccountId, Balance>
+pub struct SaleInformation<AccountId, Balance, BlockNumber, AssetId>
 where
     Balance: Clone + Default,
 {
     pub account_id: AccountId,
     pub price: Balance,
+    /// Token the price is paid with.
+    pub asset: PaymentAsset<AssetId>,
     pub marketplace_id: MarketplaceId,
+    /// Commission of the marketplace at the time the NFT was listed.
+    pub commission_fee: MarketplaceCommission<Balance>,
+    /// Block from which the NFT can be bought. Right away if not set.
+    pub start_block: Option<BlockNumber>,
+    /// Block at which the NFT is unlisted. Never if not set.
+    pub end_block: Option<BlockNumber>,
+    /// Accounts that can buy the NFT. Anyone if empty.
+    pub buyers: BoundedVec<AccountId, MaxBuyers>,
 }
 
-impl<AccountId, Balance> Default for SaleInformation<AccountId, Balance>
+impl<AccountId, Balance, BlockNumber, AssetId> Default
+    for SaleInformation<AccountId, Balance, BlockNumber, AssetId>
 where
     AccountId: Clone + Default,
     Balance: Clone + Default,
@@ -26,24 +44,114 @@ where
         Self {
             account_id: Default::default(),
             price: Default::default(),
+            asset: Default::default(),
             marketplace_id: Default::default(),
+            commission_fee: Default::default(),
+            start_block: None,
+            end_block: None,
+            buyers: Default::default(),
         }
     }
 }
 
-impl<AccountId, Balance> SaleInformation<AccountId, Balance>
+impl<AccountId, Balance, BlockNumber, AssetId>
+    SaleInformation<AccountId, Balance, BlockNumber, AssetId>
 where
     Balance: Clone + Default,
 {
     pub fn new(
         account_id: AccountId,
         price: Balance,
+        asset: PaymentAsset<AssetId>,
         marketplace_id: MarketplaceId,
-    ) -> SaleInformation<AccountId, Balance> {
+        commission_fee: MarketplaceCommission<Balance>,
+        start_block: Option<BlockNumber>,
+        end_block: Option<BlockNumber>,
+        buyers: BoundedVec<AccountId, MaxBuyers>,
+    ) -> SaleInformation<AccountId, Balance, BlockNumber, AssetId> {
         Self {
             account_id,
+            price,
+            asset,
+            marketplace_id,
+            commission_fee,
+            start_block,
+            end_block,
+            buyers,
+        }
+    }
+
+    /// Return whether the NFT is reserved to some accounts.
+    pub fn is_private(&self) -> bool {
+        !self.buyers.is_empty()
+    }
+
+    /// Return whether an account can buy the NFT.
+    pub fn can_buy(&self, account_id: &AccountId) -> bool
+    where
+        AccountId: PartialEq,
+    {
+        !self.is_private() || self.buyers.contains(account_id)
+    }
+}
+
+pub type BundleId = u32;
+
+/// NFTs of a same seller that are sold together for one price.
+#[derive(
+    CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(Limit))]
+#[codec(mel_bound(AccountId: MaxEncodedLen, Balance: MaxEncodedLen, AssetId: MaxEncodedLen))]
+pub struct BundleInformation<AccountId, Balance, AssetId, Limit>
+where
+    AccountId: Clone + PartialEq + Debug,
+    Balance: Clone + PartialEq + Debug,
+    AssetId: Clone + PartialEq + Debug,
+    Limit: Get<u32>,
+{
+    pub account_id: AccountId,
+    pub nft_ids: BoundedVec<NFTId, Limit>,
+    pub price: Balance,
+    /// Token the price is paid with.
+    pub asset: PaymentAsset<AssetId>,
+    pub marketplace_id: MarketplaceId,
+    /// Commission of the marketplace at the time the bundle was listed.
+    pub commission_fee: MarketplaceCommission<Balance>,
+}
+
+/// What an offer is made on: a single NFT or any NFT of a series.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum OfferTarget<SeriesId> {
+    NFT(NFTId),
+    Series(SeriesId),
+}
+
+/// An offer to buy an NFT. The price is reserved from the buyer until the
+/// offer is accepted, withdrawn or expires.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Offer<Balance, BlockNumber> {
+    pub price: Balance,
+    pub marketplace_id: MarketplaceId,
+    pub expiration: BlockNumber,
+}
+
+impl<Balance, BlockNumber> Offer<Balance, BlockNumber> {
+    pub fn new(price: Balance, marketplace_id: MarketplaceId, expiration: BlockNumber) -> Self {
+        Self {
             price,
             marketplace_id,
+            expiration,
         }
     }
 }
+
+/// Signs orders in benchmarks, where the signature scheme of the runtime is
+/// not known.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId, Signature> {
+    /// Sign `message` and return the account that signed it.
+    fn sign(message: &[u8]) -> (AccountId, Signature);
+}
@@ -0,0 +1,26 @@
+//! Runtime API definition for the NFTs pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+use ternoa_primitives::nfts::{NFTId, NFTSeriesId};
+
+sp_api::decl_runtime_apis! {
+    pub trait NFTsApi<AccountId>
+    where
+        AccountId: Codec,
+    {
+        /// Return up to `limit` NFT ids owned by an account, in ascending order,
+        /// starting after `start_after`.
+        fn nfts_by_owner(owner: AccountId, start_after: Option<NFTId>, limit: u32) -> Vec<NFTId>;
+
+        /// Return up to `limit` NFT ids that are part of a series, in ascending
+        /// order, starting after `start_after`.
+        fn nfts_by_series(
+            series_id: NFTSeriesId,
+            start_after: Option<NFTId>,
+            limit: u32,
+        ) -> Vec<NFTId>;
+    }
+}
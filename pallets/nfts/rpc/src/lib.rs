@@ -0,0 +1,106 @@
+//! RPC interface for the NFTs pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use ternoa_primitives::nfts::NFTId;
+
+pub use ternoa_nfts_rpc_runtime_api::NFTsApi as NFTsRuntimeApi;
+
+/// Maximum number of NFT ids that can be returned by a single call.
+pub const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Error code returned when the runtime call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+#[rpc]
+pub trait NFTsApi<BlockHash, AccountId> {
+    /// Return a page of the NFT ids owned by an account.
+    #[rpc(name = "nfts_nftsByOwner")]
+    fn nfts_by_owner(
+        &self,
+        owner: AccountId,
+        start_after: Option<NFTId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<NFTId>>;
+
+    /// Return a page of the NFT ids that are part of a series.
+    #[rpc(name = "nfts_nftsBySeries")]
+    fn nfts_by_series(
+        &self,
+        series_id: String,
+        start_after: Option<NFTId>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<NFTId>>;
+}
+
+/// Implements the NFTsApi RPC trait for interacting with the NFTs pallet.
+pub struct NFTs<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> NFTs<C, B> {
+    /// Create new `NFTs` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId> NFTsApi<<Block as BlockT>::Hash, AccountId> for NFTs<C, Block>
+where
+    Block: BlockT,
+    C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: NFTsRuntimeApi<Block, AccountId>,
+    AccountId: Codec,
+{
+    fn nfts_by_owner(
+        &self,
+        owner: AccountId,
+        start_after: Option<NFTId>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<NFTId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.nfts_by_owner(&at, owner, start_after, page_size(limit))
+            .map_err(runtime_error)
+    }
+
+    fn nfts_by_series(
+        &self,
+        series_id: String,
+        start_after: Option<NFTId>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<NFTId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.nfts_by_series(&at, series_id.into_bytes(), start_after, page_size(limit))
+            .map_err(runtime_error)
+    }
+}
+
+fn page_size(limit: Option<u32>) -> u32 {
+    limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE)
+}
+
+fn runtime_error(err: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: "Unable to query NFTs.".into(),
+        data: Some(format!("{:?}", err).into()),
+    }
+}
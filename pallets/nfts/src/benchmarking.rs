@@ -25,11 +25,26 @@ pub fn prepare_benchmarks<T: Config>() {
     let series_id = vec![SERIES_ID];
     assert_ok!(NFTs::<T>::create(
         RawOrigin::Signed(alice.clone()).into(),
-        vec![1],
+        ipfs_reference::<T>(),
         Some(series_id.clone()),
+        Permill::zero(),
     ));
 }
 
+// Leaves room for a single rental to end at the same block as the rentals
+// made by the benchmarks, which last 100 blocks.
+pub fn fill_rental_deadline<T: Config>() {
+    let end_block = frame_system::Pallet::<T>::block_number() + 100u32.into();
+    let expired: Vec<NFTId> = (1..T::MaxRentalsPerBlock::get())
+        .map(|i| NFTId::MAX - i)
+        .collect();
+    RentalDeadlines::<T>::insert(end_block, BoundedVec::try_from(expired).unwrap());
+}
+
+pub fn ipfs_reference<T: Config>() -> TextFormat {
+    T::IPFSReferenceValidator::benchmark_reference()
+}
+
 pub fn get_account<T: Config>(name: &'static str) -> T::AccountId {
     let account: T::AccountId = benchmark_account(name, 0, 0);
     account
@@ -45,11 +60,25 @@ benchmarks! {
         let alice: T::AccountId = get_account::<T>("ALICE");
         let nft_id = NFTs::<T>::nft_id_generator();
 
-    }: _(RawOrigin::Signed(alice.clone()), vec![55], None)
+    }: _(RawOrigin::Signed(alice.clone()), ipfs_reference::<T>(), None, Permill::from_percent(10))
     verify {
         assert_eq!(NFTs::<T>::data(nft_id).unwrap().owner, alice);
     }
 
+    create_batch {
+        let s in 1 .. T::MaxBatchSize::get();
+
+        prepare_benchmarks::<T>();
+        let alice: T::AccountId = get_account::<T>("ALICE");
+        let nft_id = NFTs::<T>::nft_id_generator();
+        let ipfs_references: Vec<Vec<u8>> = (0..s).map(|_| ipfs_reference::<T>()).collect();
+
+    }: _(RawOrigin::Signed(alice.clone()), ipfs_references, None, Permill::from_percent(10))
+    verify {
+        assert_eq!(NFTs::<T>::nft_id_generator(), nft_id + s);
+        assert_eq!(NFTs::<T>::data(nft_id + s - 1).unwrap().owner, alice);
+    }
+
     transfer {
         prepare_benchmarks::<T>();
 
@@ -75,10 +104,11 @@ benchmarks! {
         prepare_benchmarks::<T>();
 
         let series_id: Vec<u8> = vec![SERIES_ID];
+        let bounded_series_id: SeriesIdOf<T> = series_id.clone().try_into().unwrap();
 
-    }: _(origin::<T>("ALICE"), series_id.clone())
+    }: _(origin::<T>("ALICE"), series_id)
     verify {
-        assert_eq!(NFTs::<T>::series(&series_id).unwrap().draft, false);
+        assert_eq!(NFTs::<T>::series(&bounded_series_id).unwrap().draft, false);
     }
 
     set_nft_mint_fee {
@@ -93,15 +123,90 @@ benchmarks! {
         assert_eq!(NFTs::<T>::nft_mint_fee(), new_mint_fee.into());
     }
 
-    lend {
+    offer_rental {
+        prepare_benchmarks::<T>();
+
+    }: _(origin::<T>("ALICE"), NFT_ID, 10u32.into(), 100u32.into(), Some(1000u32.into()))
+    verify {
+        assert!(NFTs::<T>::rental_offers(NFT_ID).is_some());
+    }
+
+    cancel_rental_offer {
         prepare_benchmarks::<T>();
+        assert_ok!(NFTs::<T>::offer_rental(origin::<T>("ALICE").into(), NFT_ID, 10u32.into(), 100u32.into(), None));
 
+    }: _(origin::<T>("ALICE"), NFT_ID)
+    verify {
+        assert!(NFTs::<T>::rental_offers(NFT_ID).is_none());
+    }
+
+    rent {
+        prepare_benchmarks::<T>();
+        assert_ok!(NFTs::<T>::offer_rental(origin::<T>("ALICE").into(), NFT_ID, 10u32.into(), 100u32.into(), Some(1000u32.into())));
+        fill_rental_deadline::<T>();
         let bob: T::AccountId = get_account::<T>("BOB");
 
-    }: _(origin::<T>("ALICE"), NFT_ID, Some(bob.clone()))
+    }: _(origin::<T>("BOB"), NFT_ID)
     verify {
         assert_eq!(NFTs::<T>::data(NFT_ID).unwrap().viewer, Some(bob));
     }
+
+    end_rental {
+        prepare_benchmarks::<T>();
+        assert_ok!(NFTs::<T>::offer_rental(origin::<T>("ALICE").into(), NFT_ID, 10u32.into(), 100u32.into(), Some(1000u32.into())));
+        fill_rental_deadline::<T>();
+        assert_ok!(NFTs::<T>::rent(origin::<T>("BOB").into(), NFT_ID));
+
+    }: _(origin::<T>("BOB"), NFT_ID)
+    verify {
+        assert_eq!(NFTs::<T>::data(NFT_ID).unwrap().viewer, None);
+    }
+
+    // The work of the release handlers is charged on top of the force benchmarks,
+    // through `NFTReleaseHandler::max_release_weight`.
+    force_transfer {
+        prepare_benchmarks::<T>();
+        assert_ok!(NFTs::<T>::offer_rental(origin::<T>("ALICE").into(), NFT_ID, 10u32.into(), 100u32.into(), Some(1000u32.into())));
+        fill_rental_deadline::<T>();
+        assert_ok!(NFTs::<T>::rent(origin::<T>("BOB").into(), NFT_ID));
+        let bob: T::AccountId = get_account::<T>("BOB");
+        let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
+
+    }: _(T::ForceOrigin::successful_origin(), NFT_ID, bob_lookup)
+    verify {
+        assert_eq!(NFTs::<T>::data(NFT_ID).unwrap().owner, bob);
+    }
+
+    force_burn {
+        prepare_benchmarks::<T>();
+        assert_ok!(NFTs::<T>::offer_rental(origin::<T>("ALICE").into(), NFT_ID, 10u32.into(), 100u32.into(), Some(1000u32.into())));
+        fill_rental_deadline::<T>();
+        assert_ok!(NFTs::<T>::rent(origin::<T>("BOB").into(), NFT_ID));
+
+    }: _(T::ForceOrigin::successful_origin(), NFT_ID)
+    verify {
+        assert_eq!(NFTs::<T>::data(NFT_ID), None);
+    }
+
+    freeze {
+        prepare_benchmarks::<T>();
+        assert_ok!(NFTs::<T>::offer_rental(origin::<T>("ALICE").into(), NFT_ID, 10u32.into(), 100u32.into(), Some(1000u32.into())));
+        fill_rental_deadline::<T>();
+        assert_ok!(NFTs::<T>::rent(origin::<T>("BOB").into(), NFT_ID));
+
+    }: _(T::ForceOrigin::successful_origin(), NFT_ID)
+    verify {
+        assert_eq!(NFTs::<T>::data(NFT_ID).unwrap().lock, Some(NFTLockReason::Frozen));
+    }
+
+    thaw {
+        prepare_benchmarks::<T>();
+        assert_ok!(NFTs::<T>::freeze(T::ForceOrigin::successful_origin(), NFT_ID));
+
+    }: _(T::ForceOrigin::successful_origin(), NFT_ID)
+    verify {
+        assert_eq!(NFTs::<T>::data(NFT_ID).unwrap().lock, None);
+    }
 }
 
 impl_benchmark_test_suite!(
@@ -2,11 +2,19 @@ use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
 
 pub trait WeightInfo {
     fn create() -> Weight;
+    fn create_batch(s: u32) -> Weight;
     fn transfer() -> Weight;
     fn burn() -> Weight;
     fn finish_series() -> Weight;
     fn set_nft_mint_fee() -> Weight;
-    fn lend() -> Weight;
+    fn offer_rental() -> Weight;
+    fn cancel_rental_offer() -> Weight;
+    fn rent() -> Weight;
+    fn end_rental() -> Weight;
+    fn force_transfer() -> Weight;
+    fn force_burn() -> Weight;
+    fn freeze() -> Weight;
+    fn thaw() -> Weight;
 }
 
 impl WeightInfo for () {
@@ -16,25 +24,53 @@ impl WeightInfo for () {
     // Storage: Nfts SeriesIdGenerator (r:1 w:1)
     // Storage: Nfts Series (r:1 w:1)
     // Storage: Nfts Data (r:0 w:1)
+    // Storage: Nfts NftsByOwner (r:0 w:1)
+    // Storage: Nfts NftsBySeries (r:0 w:1)
+    // Storage: Nfts Deposits (r:0 w:1)
     fn create() -> Weight {
         (78_531_000 as Weight)
             .saturating_add(DbWeight::get().reads(5 as Weight))
-            .saturating_add(DbWeight::get().writes(5 as Weight))
+            .saturating_add(DbWeight::get().writes(8 as Weight))
+    }
+    // Storage: Nfts NftMintFee (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Nfts SeriesIdGenerator (r:1 w:1)
+    // Storage: Nfts Series (r:1 w:1)
+    // Storage: Nfts NftIdGenerator (r:1 w:1)
+    // Storage: Nfts Data (r:0 w:1)
+    // Storage: Nfts NftsByOwner (r:0 w:1)
+    // Storage: Nfts NftsBySeries (r:0 w:1)
+    // Storage: Nfts Deposits (r:0 w:1)
+    fn create_batch(s: u32) -> Weight {
+        (45_000_000 as Weight)
+            // Standard Error: 3_000
+            .saturating_add((20_330_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+            .saturating_add(DbWeight::get().writes((5 as Weight).saturating_mul(s as Weight)))
     }
     // Storage: Nfts Data (r:1 w:1)
     // Storage: Nfts Series (r:1 w:0)
     // Storage: Capsules Capsules (r:1 w:0)
+    // Storage: Nfts NftsByOwner (r:0 w:2)
+    // Storage: Nfts Deposits (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
     fn transfer() -> Weight {
         (34_711_000 as Weight)
-            .saturating_add(DbWeight::get().reads(3 as Weight))
-            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().reads(6 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
     }
     // Storage: Nfts Data (r:1 w:1)
     // Storage: Capsules Capsules (r:1 w:0)
+    // Storage: Nfts NftsByOwner (r:0 w:1)
+    // Storage: Nfts NftsBySeries (r:0 w:1)
+    // Storage: Nfts Deposits (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
     fn burn() -> Weight {
         (29_880_000 as Weight)
-            .saturating_add(DbWeight::get().reads(2 as Weight))
-            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
     }
     // Storage: Nfts Series (r:1 w:1)
     fn finish_series() -> Weight {
@@ -46,9 +82,74 @@ impl WeightInfo for () {
     fn set_nft_mint_fee() -> Weight {
         (17_980_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
     }
+    // Storage: Nfts Data (r:1 w:0)
+    // Storage: Nfts RentalOffers (r:0 w:1)
+    fn offer_rental() -> Weight {
+        (16_210_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nfts RentalOffers (r:1 w:1)
+    fn cancel_rental_offer() -> Weight {
+        (14_520_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nfts RentalOffers (r:1 w:1)
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Nfts Rentals (r:0 w:1)
+    // Storage: Nfts RentalDeadlines (r:1 w:1)
+    fn rent() -> Weight {
+        (41_330_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    // Storage: Nfts Rentals (r:1 w:1)
+    // Storage: Nfts RentalDeadlines (r:1 w:1)
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
+    fn end_rental() -> Weight {
+        (52_870_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    // Storage: Nfts Data (r:2 w:2)
+    // Storage: Nfts Rentals (r:1 w:1)
+    // Storage: Nfts RentalDeadlines (r:1 w:1)
+    // Storage: Nfts NftsByOwner (r:0 w:2)
+    // Storage: Nfts Deposits (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
+    fn force_transfer() -> Weight {
+        (68_240_000 as Weight)
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+    // Storage: Nfts Data (r:2 w:2)
+    // Storage: Nfts Rentals (r:1 w:1)
+    // Storage: Nfts RentalDeadlines (r:1 w:1)
+    // Storage: Nfts NftsByOwner (r:0 w:1)
+    // Storage: Nfts NftsBySeries (r:0 w:1)
+    // Storage: Nfts RentalOffers (r:0 w:1)
+    // Storage: Nfts Deposits (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
+    fn force_burn() -> Weight {
+        (66_910_000 as Weight)
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(10 as Weight))
+    }
+    // Storage: Nfts Data (r:3 w:3)
+    // Storage: Nfts Rentals (r:1 w:1)
+    // Storage: Nfts RentalDeadlines (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
+    fn freeze() -> Weight {
+        (61_480_000 as Weight)
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(7 as Weight))
+    }
     // Storage: Nfts Data (r:1 w:1)
-    fn lend() -> Weight {
-        (14_760_000 as Weight)
+    fn thaw() -> Weight {
+        (17_350_000 as Weight)
             .saturating_add(DbWeight::get().reads(1 as Weight))
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
@@ -10,19 +10,28 @@ mod default_weights;
 mod migrations;
 
 pub use default_weights::WeightInfo;
-use frame_support::dispatch::{DispatchErrorWithPostInfo, DispatchResult};
+use frame_support::dispatch::{DispatchError, DispatchErrorWithPostInfo, DispatchResult};
 use frame_system::Origin;
 pub use pallet::*;
 
 use frame_support::pallet_prelude::ensure;
-use frame_support::traits::StorageVersion;
+use frame_support::traits::{BalanceStatus, EnsureOrigin, Get, ReservableCurrency, StorageVersion};
+use frame_support::weights::Weight;
+use frame_support::BoundedVec;
+use sp_runtime::traits::{Saturating, UniqueSaturatedInto, Zero};
+use sp_runtime::{PerThing, Permill};
+use sp_std::convert::{TryFrom, TryInto};
 use sp_std::vec;
 use sp_std::vec::Vec;
+use ternoa_common::helpers::{bounded_text, U16ToU32};
 use ternoa_common::traits;
-use ternoa_primitives::nfts::{NFTData, NFTId, NFTSeriesDetails, NFTSeriesId};
+use ternoa_common::traits::{NFTReleaseHandler, ReferenceValidator};
+use ternoa_primitives::nfts::{
+    NFTData, NFTId, NFTLockReason, NFTSeriesDetails, NFTSeriesId, Rental, RentalTerms,
+};
 use ternoa_primitives::TextFormat;
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -32,7 +41,6 @@ pub mod pallet {
     use frame_support::{pallet_prelude::*, transactional};
     use frame_system::pallet_prelude::*;
     use sp_runtime::traits::StaticLookup;
-    use ternoa_common::helpers::check_bounds;
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -41,8 +49,8 @@ pub mod pallet {
 
         type WeightInfo: WeightInfo;
 
-        /// Currency used to bill minting fees
-        type Currency: Currency<Self::AccountId>;
+        /// Currency used to bill minting fees and to hold deposits and rental payments
+        type Currency: ReservableCurrency<Self::AccountId>;
 
         /// What we do with additional fees
         type FeesCollector: OnUnbalanced<NegativeImbalanceOf<Self>>;
@@ -54,6 +62,51 @@ pub mod pallet {
         /// Max Uri len
         #[pallet::constant]
         type MaxIpfsLen: Get<u16>;
+
+        /// Check that IPFS references are well formed
+        type IPFSReferenceValidator: ReferenceValidator;
+
+        /// Max series id len
+        #[pallet::constant]
+        type MaxSeriesIdLen: Get<u16>;
+
+        /// Maximum number of NFTs that can be created in a single batch.
+        #[pallet::constant]
+        type MaxBatchSize: Get<u32>;
+
+        /// Discount applied to the total mint fee of a batch. Zero means that
+        /// the mint fee is paid for every created NFT.
+        #[pallet::constant]
+        type BatchMintDiscount: Get<Permill>;
+
+        /// Whether minting reserves a storage deposit that is given back on
+        /// burn. When false, the mint fee is charged and burnt instead.
+        #[pallet::constant]
+        type UseStorageDeposits: Get<bool>;
+
+        /// Base deposit reserved for every minted NFT.
+        #[pallet::constant]
+        type DepositBase: Get<BalanceOf<Self>>;
+
+        /// Deposit reserved for every byte of the IPFS reference of an NFT.
+        #[pallet::constant]
+        type DepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Whether the storage deposit of an NFT is handed over to the new
+        /// owner when the NFT is transferred.
+        #[pallet::constant]
+        type DepositFollowsOwner: Get<bool>;
+
+        /// Origin allowed to burn, transfer, freeze and thaw any NFT
+        type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Pallets that lock NFTs and need to let go of them when the force
+        /// origin steps in
+        type NFTReleaseHandler: NFTReleaseHandler;
+
+        /// Maximum number of rentals that can end at the same block.
+        #[pallet::constant]
+        type MaxRentalsPerBlock: Get<u32>;
     }
 
     pub type BalanceOf<T> =
@@ -62,17 +115,55 @@ pub mod pallet {
         <T as frame_system::Config>::AccountId,
     >>::NegativeImbalance;
 
+    pub type IPFSLengthLimitOf<T> = U16ToU32<<T as Config>::MaxIpfsLen>;
+    pub type SeriesIdLengthLimitOf<T> = U16ToU32<<T as Config>::MaxSeriesIdLen>;
+    pub type IPFSReferenceOf<T> = BoundedVec<u8, IPFSLengthLimitOf<T>>;
+    pub type SeriesIdOf<T> = BoundedVec<u8, SeriesIdLengthLimitOf<T>>;
+    /// NFT id as it is stored in the owner and series indexes. It is big-endian
+    /// and not hashed so that the indexes are iterated in ascending id order.
+    pub type IndexKey = [u8; sp_std::mem::size_of::<NFTId>()];
+    pub type NFTDataOf<T> = NFTData<
+        <T as frame_system::Config>::AccountId,
+        IPFSLengthLimitOf<T>,
+        SeriesIdLengthLimitOf<T>,
+    >;
+    pub type RentalTermsOf<T> = RentalTerms<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        BalanceOf<T>,
+    >;
+    pub type RentalOf<T> = Rental<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        BalanceOf<T>,
+    >;
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        /*         fn on_runtime_upgrade() -> frame_support::weights::Weight {
+        /// Give back the NFTs whose rental ends at this block.
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let expired = RentalDeadlines::<T>::take(now);
+
+            for nft_id in expired.iter() {
+                if let Err(err) = Self::close_rental(*nft_id, now) {
+                    log::error!(target: "runtime::nfts", "Rental {} could not be closed: {:?}", nft_id, err);
+                }
+            }
+
+            let count = expired.len() as Weight;
+            T::DbWeight::get()
+                .reads_writes(1, 1)
+                .saturating_add(T::WeightInfo::end_rental().saturating_mul(count))
+        }
+
+        fn on_runtime_upgrade() -> frame_support::weights::Weight {
             migrations::migrate::<T>()
-        } */
+        }
     }
 
     #[pallet::call]
@@ -80,6 +171,8 @@ pub mod pallet {
         /// Create a new NFT with the provided details. An ID will be auto
         /// generated and logged as an event, The caller of this function
         /// will become the owner of the new NFT.
+        /// The royalty is the share of every secondary sale that will be paid
+        /// back to the caller (the creator).
         #[pallet::weight(T::WeightInfo::create())]
         // have to be transactional otherwise we could make people pay the mint
         // even if the creation fails.
@@ -88,54 +181,124 @@ pub mod pallet {
             origin: OriginFor<T>,
             ipfs_reference: TextFormat,
             series_id: Option<NFTSeriesId>,
+            royalty: Permill,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
-            check_bounds(
-                ipfs_reference.len(),
-                (T::MinIpfsLen::get(), Error::<T>::IPFSReferenceIsTooShort),
-                (T::MaxIpfsLen::get(), Error::<T>::IPFSReferenceIsTooLong),
-            )?;
+            let bounded_ipfs_reference = Self::bound_ipfs_reference(ipfs_reference.clone())?;
+            let series_id = series_id.map(Self::bound_series_id).transpose()?;
 
             // Checks
-            // The Caller needs to pay the NFT Mint fee.
-            let mint_fee = NftMintFee::<T>::get();
-            let reason = WithdrawReasons::FEE;
-            let imbalance = T::Currency::withdraw(&who, mint_fee, reason, KeepAlive)?;
-            T::FeesCollector::on_unbalanced(imbalance);
+            // The Caller needs to pay either the NFT Mint fee or the storage deposit.
+            let (mint_fee, deposit) = if T::UseStorageDeposits::get() {
+                let deposit = Self::mint_deposit(&bounded_ipfs_reference);
+                T::Currency::reserve(&who, deposit)?;
+                (Zero::zero(), deposit)
+            } else {
+                let mint_fee = NftMintFee::<T>::get();
+                let reason = WithdrawReasons::FEE;
+                let imbalance = T::Currency::withdraw(&who, mint_fee, reason, KeepAlive)?;
+                T::FeesCollector::on_unbalanced(imbalance);
+                (mint_fee, Zero::zero())
+            };
 
             // Check if the series exists. If it exists and the caller is not the owner throw error.
-            let mut series_exists = false;
-            if let Some(id) = &series_id {
-                if let Some(series) = Series::<T>::get(id) {
-                    ensure!(series.owner == who, Error::<T>::NotTheSeriesOwner);
-                    ensure!(
-                        series.draft,
-                        Error::<T>::CannotCreateNFTsWithCompletedSeries
-                    );
-                    series_exists = true;
-                }
-            }
+            let series_exists = Self::check_series(&who, &series_id)?;
 
             // Execute
-            let nft_id = Self::generate_nft_id();
-            let series_id = series_id.unwrap_or_else(|| Self::generate_series_id());
-
-            let value =
-                NFTData::new_default(who.clone(), ipfs_reference.clone(), series_id.clone());
+            let series_id = match series_id {
+                Some(series_id) => series_id,
+                None => Self::generate_series_id()?,
+            };
+            let nft_id = Self::mint(&who, bounded_ipfs_reference, &series_id, royalty);
+            Self::record_deposit(nft_id, &who, deposit);
 
-            // Save
-            Data::<T>::insert(nft_id, value);
             if !series_exists {
-                Series::<T>::insert(series_id.clone(), NFTSeriesDetails::new(who.clone(), true));
+                Series::<T>::insert(&series_id, NFTSeriesDetails::new(who.clone(), true));
             }
 
             let event = Event::NFTCreated {
                 nft_id,
                 owner: who,
-                series_id,
+                series_id: series_id.into_inner(),
                 ipfs_reference,
                 mint_fee,
+                deposit,
+                royalty,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Create multiple NFTs at once. All of them end up in the same series
+        /// and the mint fee is billed once for the whole batch, with the
+        /// configured discount applied.
+        #[pallet::weight(T::WeightInfo::create_batch(ipfs_references.len() as u32))]
+        #[transactional]
+        pub fn create_batch(
+            origin: OriginFor<T>,
+            ipfs_references: Vec<TextFormat>,
+            series_id: Option<NFTSeriesId>,
+            royalty: Permill,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!ipfs_references.is_empty(), Error::<T>::EmptyBatch);
+            ensure!(
+                ipfs_references.len() <= T::MaxBatchSize::get() as usize,
+                Error::<T>::BatchIsTooLarge
+            );
+            let ipfs_references = ipfs_references
+                .into_iter()
+                .map(Self::bound_ipfs_reference)
+                .collect::<Result<Vec<_>, _>>()?;
+            let series_id = series_id.map(Self::bound_series_id).transpose()?;
+
+            // The Caller needs to pay the NFT Mint fee for the whole batch, or
+            // a storage deposit for each NFT.
+            let (mint_fee, deposits) = if T::UseStorageDeposits::get() {
+                let deposits: Vec<BalanceOf<T>> =
+                    ipfs_references.iter().map(Self::mint_deposit).collect();
+                (Zero::zero(), deposits)
+            } else {
+                let mint_fee = Self::batch_mint_fee(ipfs_references.len() as u32);
+                let reason = WithdrawReasons::FEE;
+                let imbalance = T::Currency::withdraw(&who, mint_fee, reason, KeepAlive)?;
+                T::FeesCollector::on_unbalanced(imbalance);
+                (mint_fee, Vec::new())
+            };
+            let deposit = deposits
+                .iter()
+                .fold(BalanceOf::<T>::zero(), |acc, x| acc.saturating_add(*x));
+            T::Currency::reserve(&who, deposit)?;
+
+            let series_exists = Self::check_series(&who, &series_id)?;
+
+            // Execute
+            let series_id = match series_id {
+                Some(series_id) => series_id,
+                None => Self::generate_series_id()?,
+            };
+            let nft_ids: Vec<NFTId> = ipfs_references
+                .into_iter()
+                .map(|ipfs_reference| Self::mint(&who, ipfs_reference, &series_id, royalty))
+                .collect();
+            for (nft_id, deposit) in nft_ids.iter().zip(deposits) {
+                Self::record_deposit(*nft_id, &who, deposit);
+            }
+
+            if !series_exists {
+                Series::<T>::insert(&series_id, NFTSeriesDetails::new(who.clone(), true));
+            }
+
+            let event = Event::NFTBatchCreated {
+                nft_ids,
+                owner: who,
+                series_id: series_id.into_inner(),
+                mint_fee,
+                deposit,
+                royalty,
             };
             Self::deposit_event(event);
 
@@ -157,19 +320,7 @@ pub mod pallet {
             let series = Series::<T>::get(&data.series_id).ok_or(Error::<T>::SeriesNotFound)?;
 
             ensure!(data.owner == who, Error::<T>::NotTheNFTOwner);
-            ensure!(
-                !data.listed_for_sale,
-                Error::<T>::CannotTransferNFTsListedForSale
-            );
-            ensure!(
-                !data.converted_to_capsule,
-                Error::<T>::CannotTransferCapsules
-            );
-            ensure!(
-                !data.in_transmission,
-                Error::<T>::CannotTransferNFTsInTransmission
-            );
-            ensure!(data.viewer.is_none(), Error::<T>::CannotTransferLentNFTs);
+            Self::ensure_unlocked(&data)?;
             ensure!(
                 !series.draft,
                 Error::<T>::CannotTransferNFTsInUncompletedSeries
@@ -177,6 +328,8 @@ pub mod pallet {
 
             data.owner = to.clone();
             Data::<T>::insert(id, data);
+            Self::move_owner_index(id, &who, &to);
+            Self::move_deposit(id, &to);
 
             let event = Event::NFTTransferred {
                 nft_id: id,
@@ -198,18 +351,9 @@ pub mod pallet {
             let data = Data::<T>::get(id).ok_or(Error::<T>::NFTNotFound)?;
 
             ensure!(data.owner == who, Error::<T>::NotTheNFTOwner);
-            ensure!(
-                !data.listed_for_sale,
-                Error::<T>::CannotBurnNFTsListedForSale
-            );
-            ensure!(!data.converted_to_capsule, Error::<T>::CannotBurnCapsules);
-            ensure!(
-                !data.in_transmission,
-                Error::<T>::CannotBurnNFTsInTransmission
-            );
-            ensure!(data.viewer.is_none(), Error::<T>::CannotBurnLentNFTs);
+            Self::ensure_unlocked(&data)?;
 
-            Data::<T>::remove(id);
+            Self::remove_nft(id, &data);
             Self::deposit_event(Event::NFTBurned { nft_id: id });
 
             Ok(().into())
@@ -223,8 +367,9 @@ pub mod pallet {
             series_id: NFTSeriesId,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            let bounded_series_id = Self::bound_series_id(series_id.clone())?;
 
-            Series::<T>::mutate(&series_id, |x| -> DispatchResult {
+            Series::<T>::mutate(&bounded_series_id, |x| -> DispatchResult {
                 let series = x.as_mut().ok_or(Error::<T>::SeriesNotFound)?;
                 ensure!(series.owner == who, Error::<T>::NotTheSeriesOwner);
 
@@ -252,36 +397,216 @@ pub mod pallet {
             Ok(().into())
         }
 
-        #[pallet::weight(T::WeightInfo::lend())]
-        pub fn lend(
+        /// Offer an NFT for rent. Anyone but the owner can then rent it for
+        /// `duration` blocks by paying `price_per_block` for every block,
+        /// plus an optional deposit that is given back once the rental ends.
+        /// Offering again replaces the previous terms.
+        #[pallet::weight(T::WeightInfo::offer_rental())]
+        pub fn offer_rental(
             origin: OriginFor<T>,
             nft_id: NFTId,
-            viewer: Option<T::AccountId>,
+            price_per_block: BalanceOf<T>,
+            duration: T::BlockNumber,
+            deposit: Option<BalanceOf<T>>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
+            let data = Data::<T>::get(nft_id).ok_or(Error::<T>::NFTNotFound)?;
+            ensure!(data.owner == who, Error::<T>::NotTheNFTOwner);
+            Self::ensure_unlocked(&data)?;
+            ensure!(!duration.is_zero(), Error::<T>::InvalidRentalDuration);
+
+            let deposit = deposit.unwrap_or_else(Zero::zero);
+            let terms = RentalTerms::new(who, price_per_block, duration, deposit);
+            RentalOffers::<T>::insert(nft_id, terms);
+
+            let event = Event::RentalOffered {
+                nft_id,
+                price_per_block,
+                duration,
+                deposit,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Withdraw a rental offer.
+        #[pallet::weight(T::WeightInfo::cancel_rental_offer())]
+        pub fn cancel_rental_offer(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let terms = RentalOffers::<T>::get(nft_id).ok_or(Error::<T>::RentalOfferNotFound)?;
+            ensure!(terms.owner == who, Error::<T>::NotTheNFTOwner);
+
+            RentalOffers::<T>::remove(nft_id);
+
+            Self::deposit_event(Event::RentalOfferCancelled { nft_id });
+
+            Ok(().into())
+        }
+
+        /// Rent an NFT under the terms offered by its owner. The full price and
+        /// the deposit are reserved from the caller, who becomes the viewer of
+        /// the NFT until the rental ends.
+        #[pallet::weight(T::WeightInfo::rent())]
+        #[transactional]
+        pub fn rent(origin: OriginFor<T>, nft_id: NFTId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let terms = RentalOffers::<T>::get(nft_id).ok_or(Error::<T>::RentalOfferNotFound)?;
+            ensure!(terms.owner != who, Error::<T>::CannotRentOwnNFT);
+
             Data::<T>::try_mutate(nft_id, |maybe_data| -> DispatchResult {
                 let data = maybe_data.as_mut().ok_or(Error::<T>::NFTNotFound)?;
+                // The NFT changed hands since the offer was made.
+                ensure!(data.owner == terms.owner, Error::<T>::RentalOfferIsOutdated);
+                Self::ensure_unlocked(data)?;
 
-                ensure!(data.owner == who, Error::<T>::NotTheNFTOwner);
-                ensure!(
-                    !data.listed_for_sale,
-                    Error::<T>::CannotLendNFTsListedForSale
-                );
-                ensure!(!data.converted_to_capsule, Error::<T>::CannotLendCapsules);
-                ensure!(
-                    !data.in_transmission,
-                    Error::<T>::CannotLendNFTsInTransmission
-                );
+                data.lock = Some(NFTLockReason::Rented);
+                data.viewer = Some(who.clone());
 
-                data.viewer = viewer.clone();
+                Ok(())
+            })?;
 
-                Ok(().into())
+            let price = Self::rental_price(terms.price_per_block, terms.duration);
+            T::Currency::reserve(&who, price.saturating_add(terms.deposit))?;
+
+            let start_block = frame_system::Pallet::<T>::block_number();
+            let end_block = start_block.saturating_add(terms.duration);
+            let rental = Rental::new(
+                terms.owner,
+                who.clone(),
+                terms.price_per_block,
+                start_block,
+                end_block,
+                terms.deposit,
+            );
+
+            RentalDeadlines::<T>::try_mutate(end_block, |x| {
+                x.try_push(nft_id)
+                    .map_err(|_| Error::<T>::TooManyRentalsAtBlock)
             })?;
+            RentalOffers::<T>::remove(nft_id);
+            Rentals::<T>::insert(nft_id, rental);
+
+            let event = Event::NFTRented {
+                nft_id,
+                renter: who,
+                end_block,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// End a rental before its deadline. The renter only pays for the
+        /// blocks that have passed and gets back the rest.
+        #[pallet::weight(T::WeightInfo::end_rental())]
+        #[transactional]
+        pub fn end_rental(origin: OriginFor<T>, nft_id: NFTId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let rental = Rentals::<T>::get(nft_id).ok_or(Error::<T>::RentalNotFound)?;
+            ensure!(rental.renter == who, Error::<T>::NotTheRenter);
+
+            let now = frame_system::Pallet::<T>::block_number();
+            Self::close_rental(nft_id, now)?;
+
+            Ok(().into())
+        }
 
-            let event = Event::NFTLent { nft_id, viewer };
+        /// Transfer any NFT to another account. Whatever holds the NFT (a
+        /// sale, an auction, a capsule, a rental, ...) is unwound first.
+        /// The shares of a fractionalized NFT are cancelled without compensation.
+        /// A frozen NFT stays frozen. Must be called by the force origin.
+        #[pallet::weight(
+            T::WeightInfo::force_transfer()
+                .saturating_add(T::NFTReleaseHandler::max_release_weight())
+        )]
+        #[transactional]
+        pub fn force_transfer(
+            origin: OriginFor<T>,
+            id: NFTId,
+            to: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResultWithPostInfo {
+            T::ForceOrigin::ensure_origin(origin)?;
+            let to = T::Lookup::lookup(to)?;
+
+            let (mut data, released) = Self::release(id)?;
+            let old_owner = sp_std::mem::replace(&mut data.owner, to.clone());
+            Data::<T>::insert(id, data);
+            Self::move_owner_index(id, &old_owner, &to);
+            Self::move_deposit(id, &to);
+
+            let event = Event::NFTForceTransferred {
+                nft_id: id,
+                old_owner,
+                new_owner: to,
+            };
             Self::deposit_event(event);
 
+            Ok(Some(T::WeightInfo::force_transfer().saturating_add(released)).into())
+        }
+
+        /// Burn any NFT. Whatever holds the NFT is unwound first and the
+        /// storage deposit goes back to its holder. The shares of a
+        /// fractionalized NFT are cancelled without compensation.
+        /// Must be called by the force origin.
+        #[pallet::weight(
+            T::WeightInfo::force_burn().saturating_add(T::NFTReleaseHandler::max_release_weight())
+        )]
+        #[transactional]
+        pub fn force_burn(origin: OriginFor<T>, id: NFTId) -> DispatchResultWithPostInfo {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            let (data, released) = Self::release(id)?;
+            Self::remove_nft(id, &data);
+
+            Self::deposit_event(Event::NFTForceBurned { nft_id: id });
+
+            Ok(Some(T::WeightInfo::force_burn().saturating_add(released)).into())
+        }
+
+        /// Freeze an NFT so that nothing can be done with it until it is
+        /// thawed. Whatever holds the NFT is unwound first. The shares of a
+        /// fractionalized NFT are cancelled without compensation.
+        /// Must be called by the force origin.
+        #[pallet::weight(
+            T::WeightInfo::freeze().saturating_add(T::NFTReleaseHandler::max_release_weight())
+        )]
+        #[transactional]
+        pub fn freeze(origin: OriginFor<T>, id: NFTId) -> DispatchResultWithPostInfo {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            let data = Data::<T>::get(id).ok_or(Error::<T>::NFTNotFound)?;
+            ensure!(
+                !data.is_locked_for(NFTLockReason::Frozen),
+                Error::<T>::NFTIsFrozen
+            );
+
+            let (mut data, released) = Self::release(id)?;
+            data.lock = Some(NFTLockReason::Frozen);
+            Data::<T>::insert(id, data);
+
+            Self::deposit_event(Event::NFTFrozen { nft_id: id });
+
+            Ok(Some(T::WeightInfo::freeze().saturating_add(released)).into())
+        }
+
+        /// Give back a frozen NFT to its owner.
+        /// Must be called by the force origin.
+        #[pallet::weight(T::WeightInfo::thaw())]
+        pub fn thaw(origin: OriginFor<T>, id: NFTId) -> DispatchResultWithPostInfo {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            <Self as traits::NFTTrait>::unlock(id, NFTLockReason::Frozen)?;
+
+            Self::deposit_event(Event::NFTThawed { nft_id: id });
+
             Ok(().into())
         }
     }
@@ -296,6 +621,17 @@ pub mod pallet {
             series_id: NFTSeriesId,
             ipfs_reference: TextFormat,
             mint_fee: BalanceOf<T>,
+            deposit: BalanceOf<T>,
+            royalty: Permill,
+        },
+        /// A batch of NFTs was created.
+        NFTBatchCreated {
+            nft_ids: Vec<NFTId>,
+            owner: T::AccountId,
+            series_id: NFTSeriesId,
+            mint_fee: BalanceOf<T>,
+            deposit: BalanceOf<T>,
+            royalty: Permill,
         },
         /// An NFT was transferred to someone else.
         NFTTransferred {
@@ -309,40 +645,58 @@ pub mod pallet {
         SeriesFinished { series_id: NFTSeriesId },
         /// Nft mint fee changed.
         NFTMintFeeUpdated { fee: BalanceOf<T> },
-        /// An NFT was lent to someone else or it was returned.
-        NFTLent {
+        /// An NFT was offered for rent.
+        RentalOffered {
+            nft_id: NFTId,
+            price_per_block: BalanceOf<T>,
+            duration: T::BlockNumber,
+            deposit: BalanceOf<T>,
+        },
+        /// A rental offer was withdrawn.
+        RentalOfferCancelled { nft_id: NFTId },
+        /// An NFT was rented.
+        NFTRented {
             nft_id: NFTId,
-            viewer: Option<T::AccountId>,
+            renter: T::AccountId,
+            end_block: T::BlockNumber,
         },
+        /// A rental ended and the NFT went back to its owner.
+        RentalEnded {
+            nft_id: NFTId,
+            renter: T::AccountId,
+            paid: BalanceOf<T>,
+            refunded: BalanceOf<T>,
+        },
+        /// An NFT was transferred by the force origin.
+        NFTForceTransferred {
+            nft_id: NFTId,
+            old_owner: T::AccountId,
+            new_owner: T::AccountId,
+        },
+        /// An NFT was burned by the force origin.
+        NFTForceBurned { nft_id: NFTId },
+        /// An NFT was frozen.
+        NFTFrozen { nft_id: NFTId },
+        /// A frozen NFT was given back to its owner.
+        NFTThawed { nft_id: NFTId },
     }
 
     #[pallet::error]
     pub enum Error<T> {
         /// Operation not allowed because the NFT is a capsule.
-        CannotTransferCapsules,
-        /// Operation not allowed because the NFT is a capsule.
-        CannotBurnCapsules,
-        /// Operation not allowed because the NFT is a capsule.
-        CannotLendCapsules,
-
-        /// Operation not allowed because the NFT is listed for sale.
-        CannotTransferNFTsListedForSale,
+        NFTIsCapsule,
         /// Operation not allowed because the NFT is listed for sale.
-        CannotBurnNFTsListedForSale,
-        /// Operation not allowed because the NFT is listed for sale.
-        CannotLendNFTsListedForSale,
-
+        NFTIsListedForSale,
         /// Operation not allowed because the NFT is in transmission.
-        CannotTransferNFTsInTransmission,
-        /// Operation not allowed because the NFT is in transmission.
-        CannotBurnNFTsInTransmission,
-        /// Operation not allowed because the NFT is in transmission.
-        CannotLendNFTsInTransmission,
-
-        /// Operation is not allowed because the NFT is lent.
-        CannotTransferLentNFTs,
-        /// Operation is not allowed because the NFT is lent.
-        CannotBurnLentNFTs,
+        NFTIsInTransmission,
+        /// Operation is not allowed because the NFT is rented.
+        NFTIsRented,
+        /// Operation is not allowed because the NFT is fractionalized.
+        NFTIsFractionalized,
+        /// Operation is not allowed because the NFT is frozen.
+        NFTIsFrozen,
+        /// The NFT is not locked for the given reason.
+        NFTIsNotLocked,
 
         /// Operation is not allowed because the series is in draft.
         CannotTransferNFTsInUncompletedSeries,
@@ -352,6 +706,8 @@ pub mod pallet {
         IPFSReferenceIsTooShort,
         /// Ipfs reference is too long.
         IPFSReferenceIsTooLong,
+        /// Ipfs reference is not a valid CID.
+        InvalidIPFSReference,
         /// No NFT was found with that NFT id.
         NFTNotFound,
         /// This function can only be called by the owner of the NFT.
@@ -360,6 +716,26 @@ pub mod pallet {
         NotTheSeriesOwner,
         /// Series not Found.
         SeriesNotFound,
+        /// Series id is too long.
+        SeriesIdIsTooLong,
+        /// A batch needs to contain at least one NFT.
+        EmptyBatch,
+        /// The batch contains more NFTs than allowed.
+        BatchIsTooLarge,
+        /// A rental needs to last at least one block.
+        InvalidRentalDuration,
+        /// No rental offer was found for that NFT.
+        RentalOfferNotFound,
+        /// The NFT changed owner since the rental offer was made.
+        RentalOfferIsOutdated,
+        /// The owner of an NFT cannot rent it.
+        CannotRentOwnNFT,
+        /// The NFT is not rented.
+        RentalNotFound,
+        /// This function can only be called by the renter of the NFT.
+        NotTheRenter,
+        /// Too many rentals already end at that block.
+        TooManyRentalsAtBlock,
     }
 
     /// The number of NFTs managed by this pallet
@@ -370,19 +746,56 @@ pub mod pallet {
     /// Data related to NFTs.
     #[pallet::storage]
     #[pallet::getter(fn data)]
-    pub type Data<T: Config> =
-        StorageMap<_, Blake2_128Concat, NFTId, NFTData<T::AccountId>, OptionQuery>;
+    pub type Data<T: Config> = StorageMap<_, Blake2_128Concat, NFTId, NFTDataOf<T>, OptionQuery>;
 
     /// Data related to NFT Series.
     #[pallet::storage]
     #[pallet::getter(fn series)]
     pub type Series<T: Config> =
-        StorageMap<_, Blake2_128Concat, NFTSeriesId, NFTSeriesDetails<T::AccountId>, OptionQuery>;
+        StorageMap<_, Blake2_128Concat, SeriesIdOf<T>, NFTSeriesDetails<T::AccountId>, OptionQuery>;
+
+    /// Index of the NFTs owned by an account.
+    #[pallet::storage]
+    pub type NftsByOwner<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Identity, IndexKey, (), OptionQuery>;
+
+    /// Index of the NFTs that are part of a series.
+    #[pallet::storage]
+    pub type NftsBySeries<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, SeriesIdOf<T>, Identity, IndexKey, (), OptionQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn series_id_generator)]
     pub type SeriesIdGenerator<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+    /// Rental terms offered by NFT owners.
+    #[pallet::storage]
+    #[pallet::getter(fn rental_offers)]
+    pub type RentalOffers<T: Config> =
+        StorageMap<_, Blake2_128Concat, NFTId, RentalTermsOf<T>, OptionQuery>;
+
+    /// Ongoing rentals.
+    #[pallet::storage]
+    #[pallet::getter(fn rentals)]
+    pub type Rentals<T: Config> = StorageMap<_, Blake2_128Concat, NFTId, RentalOf<T>, OptionQuery>;
+
+    /// Rentals indexed by the block at which they end.
+    #[pallet::storage]
+    #[pallet::getter(fn rental_deadlines)]
+    pub type RentalDeadlines<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::BlockNumber,
+        BoundedVec<NFTId, T::MaxRentalsPerBlock>,
+        ValueQuery,
+    >;
+
+    /// Storage deposits of NFTs along with the account that holds them.
+    #[pallet::storage]
+    #[pallet::getter(fn deposits)]
+    pub type Deposits<T: Config> =
+        StorageMap<_, Blake2_128Concat, NFTId, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
     /// Host much does it cost to mint a NFT (extra fee on top of the tx fees)
     #[pallet::storage]
     #[pallet::getter(fn nft_mint_fee)]
@@ -390,7 +803,14 @@ pub mod pallet {
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
-        pub nfts: Vec<(NFTId, NFTData<T::AccountId>)>,
+        pub nfts: Vec<(
+            NFTId,
+            T::AccountId,
+            T::AccountId,
+            TextFormat,
+            NFTSeriesId,
+            Permill,
+        )>,
         pub series: Vec<(NFTSeriesId, NFTSeriesDetails<T::AccountId>)>,
         pub nft_mint_fee: BalanceOf<T>,
     }
@@ -413,14 +833,34 @@ pub mod pallet {
                 .clone()
                 .into_iter()
                 .for_each(|(series_id, series)| {
+                    let series_id: SeriesIdOf<T> =
+                        series_id.try_into().expect("Series id is too long");
                     Series::<T>::insert(series_id, series);
                 });
 
             let mut current_nft_id: NFTId = 0;
-            self.nfts.clone().into_iter().for_each(|(nft_id, data)| {
-                Data::<T>::insert(nft_id, data);
-                current_nft_id = current_nft_id.max(nft_id);
-            });
+            self.nfts.clone().into_iter().for_each(
+                |(nft_id, owner, creator, ipfs_reference, series_id, royalty)| {
+                    let ipfs_reference = ipfs_reference
+                        .try_into()
+                        .expect("IPFS reference is too long");
+                    let series_id = series_id.try_into().expect("Series id is too long");
+                    let data = NFTData::new(
+                        owner,
+                        creator,
+                        ipfs_reference,
+                        series_id,
+                        None,
+                        None,
+                        royalty,
+                    );
+
+                    NftsByOwner::<T>::insert(&data.owner, index_key(nft_id), ());
+                    NftsBySeries::<T>::insert(&data.series_id, index_key(nft_id), ());
+                    Data::<T>::insert(nft_id, data);
+                    current_nft_id = current_nft_id.max(nft_id);
+                },
+            );
 
             if !self.nfts.is_empty() {
                 current_nft_id += 1;
@@ -435,13 +875,18 @@ pub mod pallet {
 
 impl<T: Config> traits::NFTTrait for Pallet<T> {
     type AccountId = T::AccountId;
+    type Balance = BalanceOf<T>;
+    type IPFSLengthLimit = IPFSLengthLimitOf<T>;
+    type SeriesIdLengthLimit = SeriesIdLengthLimitOf<T>;
 
     fn set_owner(id: NFTId, owner: &Self::AccountId) -> DispatchResult {
         Data::<T>::try_mutate(id, |data| -> DispatchResult {
             let data = data.as_mut().ok_or(Error::<T>::NFTNotFound)?;
+            Self::move_owner_index(id, &data.owner, owner);
             data.owner = owner.clone();
             Ok(())
         })?;
+        Self::move_deposit(id, owner);
 
         Ok(())
     }
@@ -450,6 +895,14 @@ impl<T: Config> traits::NFTTrait for Pallet<T> {
         Some(Data::<T>::get(id)?.owner)
     }
 
+    fn set_royalty(id: NFTId, royalty: Permill) -> DispatchResult {
+        Data::<T>::try_mutate(id, |data| -> DispatchResult {
+            let data = data.as_mut().ok_or(Error::<T>::NFTNotFound)?;
+            data.royalty = royalty;
+            Ok(())
+        })
+    }
+
     fn is_nft_in_completed_series(id: NFTId) -> Option<bool> {
         let series_id = Data::<T>::get(id)?.series_id;
         Some(!Series::<T>::get(series_id)?.draft)
@@ -460,99 +913,344 @@ impl<T: Config> traits::NFTTrait for Pallet<T> {
         ipfs_reference: TextFormat,
         series_id: Option<NFTSeriesId>,
     ) -> Result<NFTId, DispatchErrorWithPostInfo> {
-        Self::create(Origin::<T>::Signed(owner).into(), ipfs_reference, series_id)?;
+        Self::create(
+            Origin::<T>::Signed(owner).into(),
+            ipfs_reference,
+            series_id,
+            Permill::zero(),
+        )?;
         return Ok(Self::nft_id_generator() - 1);
     }
 
-    fn get_nft(id: NFTId) -> Option<NFTData<Self::AccountId>> {
+    fn mint_fee() -> Self::Balance {
+        // Storage deposits are reserved instead of being paid.
+        if T::UseStorageDeposits::get() {
+            Zero::zero()
+        } else {
+            NftMintFee::<T>::get()
+        }
+    }
+
+    fn get_nft(id: NFTId) -> Option<NFTDataOf<T>> {
         Data::<T>::get(id)
     }
 
     fn benchmark_lock_series(series_id: NFTSeriesId) {
+        let series_id = Self::bound_series_id(series_id).unwrap();
         Series::<T>::mutate(&series_id, |x| {
             x.as_mut().unwrap().draft = false;
         });
     }
 
-    fn set_listed_for_sale(id: NFTId, value: bool) -> DispatchResult {
+    fn benchmark_create_nft(owner: &Self::AccountId) -> NFTId {
+        let series_id = Self::generate_series_id().unwrap();
+        Series::<T>::insert(&series_id, NFTSeriesDetails::new(owner.clone(), true));
+        Self::mint(owner, Default::default(), &series_id, Permill::zero())
+    }
+
+    fn lock(id: NFTId, reason: NFTLockReason) -> DispatchResult {
         Data::<T>::try_mutate(id, |data| -> DispatchResult {
             let data = data.as_mut().ok_or(Error::<T>::NFTNotFound)?;
-            data.listed_for_sale = value;
+            Self::ensure_unlocked(data)?;
+            data.lock = Some(reason);
             Ok(())
-        })?;
-
-        Ok(())
+        })
     }
 
-    fn is_listed_for_sale(id: NFTId) -> Option<bool> {
-        let nft = Data::<T>::get(id);
-        if let Some(nft) = nft {
-            return Some(nft.listed_for_sale);
-        }
+    fn unlock(id: NFTId, reason: NFTLockReason) -> DispatchResult {
+        Data::<T>::try_mutate(id, |data| -> DispatchResult {
+            let data = data.as_mut().ok_or(Error::<T>::NFTNotFound)?;
+            ensure!(data.is_locked_for(reason), Error::<T>::NFTIsNotLocked);
+            data.lock = None;
+            if reason == NFTLockReason::Rented {
+                data.viewer = None;
+            }
+            Ok(())
+        })
+    }
 
-        return None;
+    fn is_locked(id: NFTId, reason: NFTLockReason) -> Option<bool> {
+        Some(Data::<T>::get(id)?.is_locked_for(reason))
     }
 
-    fn set_in_transmission(id: NFTId, value: bool) -> DispatchResult {
-        Data::<T>::try_mutate(id, |data| -> DispatchResult {
-            let data = data.as_mut().ok_or(Error::<T>::NFTNotFound)?;
-            data.in_transmission = value;
+    fn set_series_completion(series_id: &NFTSeriesId, value: bool) -> DispatchResult {
+        let series_id = Self::bound_series_id(series_id.clone())?;
+        Series::<T>::try_mutate(&series_id, |x| -> DispatchResult {
+            let series = x.as_mut().ok_or(Error::<T>::SeriesNotFound)?;
+            series.draft = !value;
             Ok(())
         })?;
 
         Ok(())
     }
+}
 
-    fn is_in_transmission(id: NFTId) -> Option<bool> {
-        let nft = Data::<T>::get(id);
-        if let Some(nft) = nft {
-            return Some(nft.in_transmission);
-        }
+impl<T: Config> Pallet<T> {
+    /// Return up to `limit` NFT ids owned by an account, in ascending order,
+    /// starting after `start_after`.
+    pub fn nfts_by_owner(
+        owner: &T::AccountId,
+        start_after: Option<NFTId>,
+        limit: u32,
+    ) -> Vec<NFTId> {
+        let keys = match start_after {
+            Some(id) => {
+                let start = NftsByOwner::<T>::hashed_key_for(owner, index_key(id));
+                NftsByOwner::<T>::iter_key_prefix_from(owner, start)
+            }
+            None => NftsByOwner::<T>::iter_key_prefix(owner),
+        };
 
-        return None;
+        paginate(keys, limit)
     }
 
-    fn set_converted_to_capsule(id: NFTId, value: bool) -> DispatchResult {
-        Data::<T>::try_mutate(id, |d| -> DispatchResult {
-            let data = d.as_mut().ok_or(Error::<T>::NFTNotFound)?;
-            data.converted_to_capsule = value;
-            Ok(())
-        })?;
+    /// Return up to `limit` NFT ids that are part of a series, in ascending
+    /// order, starting after `start_after`.
+    pub fn nfts_by_series(
+        series_id: &NFTSeriesId,
+        start_after: Option<NFTId>,
+        limit: u32,
+    ) -> Vec<NFTId> {
+        // A series id that does not fit the bound cannot have any NFT.
+        let series_id = match SeriesIdOf::<T>::try_from(series_id.clone()) {
+            Ok(series_id) => series_id,
+            Err(_) => return Vec::new(),
+        };
+
+        let keys = match start_after {
+            Some(id) => {
+                let start = NftsBySeries::<T>::hashed_key_for(&series_id, index_key(id));
+                NftsBySeries::<T>::iter_key_prefix_from(&series_id, start)
+            }
+            None => NftsBySeries::<T>::iter_key_prefix(&series_id),
+        };
 
-        Ok(())
+        paginate(keys, limit)
+    }
+
+    /// Return the mint fee that needs to be paid to create `count` NFTs at once.
+    pub fn batch_mint_fee(count: u32) -> BalanceOf<T> {
+        let total = NftMintFee::<T>::get().saturating_mul(count.into());
+        total.saturating_sub(T::BatchMintDiscount::get().mul_floor(total))
+    }
+
+    /// Return the storage deposit that is reserved to mint an NFT.
+    pub fn mint_deposit(ipfs_reference: &IPFSReferenceOf<T>) -> BalanceOf<T> {
+        let bytes = T::DepositPerByte::get().saturating_mul((ipfs_reference.len() as u32).into());
+        T::DepositBase::get().saturating_add(bytes)
     }
 
-    fn is_converted_to_capsule(id: NFTId) -> Option<bool> {
-        let nft = Data::<T>::get(id);
-        if let Some(nft) = nft {
-            return Some(nft.converted_to_capsule);
+    /// Keep track of the storage deposit reserved by `depositor` for an NFT.
+    pub(crate) fn record_deposit(id: NFTId, depositor: &T::AccountId, deposit: BalanceOf<T>) {
+        if !deposit.is_zero() {
+            Deposits::<T>::insert(id, (depositor.clone(), deposit));
+        }
+    }
+
+    /// Hand the storage deposit of an NFT over to its new owner. The deposit
+    /// stays where it is if the new owner account cannot receive it.
+    fn move_deposit(id: NFTId, new_owner: &T::AccountId) {
+        if !T::DepositFollowsOwner::get() {
+            return;
         }
 
-        return None;
+        Deposits::<T>::mutate_exists(id, |maybe_deposit| {
+            let (depositor, deposit) = match maybe_deposit {
+                Some(x) if x.0 != *new_owner => x,
+                _ => return,
+            };
+            let status = BalanceStatus::Reserved;
+            if let Ok(unpaid) =
+                T::Currency::repatriate_reserved(depositor, new_owner, *deposit, status)
+            {
+                // Whatever could not be moved is no longer reserved by anyone.
+                *depositor = new_owner.clone();
+                *deposit = deposit.saturating_sub(unpaid);
+            }
+        });
     }
 
-    fn set_series_completion(series_id: &NFTSeriesId, value: bool) -> DispatchResult {
-        Series::<T>::try_mutate(series_id, |x| -> DispatchResult {
-            let series = x.as_mut().ok_or(Error::<T>::SeriesNotFound)?;
-            series.draft = !value;
-            Ok(())
-        })?;
+    /// Price of renting an NFT for a number of blocks.
+    fn rental_price(price_per_block: BalanceOf<T>, blocks: T::BlockNumber) -> BalanceOf<T> {
+        let blocks: u32 = blocks.unique_saturated_into();
+        price_per_block.saturating_mul(blocks.into())
+    }
+
+    /// End a rental at block `now`. The owner gets paid for the blocks that
+    /// have passed and the renter gets back the rest of the price along with
+    /// the deposit. Nothing is written before the NFT is unlocked, so a
+    /// failure leaves the storage untouched.
+    fn close_rental(nft_id: NFTId, now: T::BlockNumber) -> DispatchResult {
+        let rental = Rentals::<T>::get(nft_id).ok_or(Error::<T>::RentalNotFound)?;
+        <Self as traits::NFTTrait>::unlock(nft_id, NFTLockReason::Rented)?;
+
+        Rentals::<T>::remove(nft_id);
+        RentalDeadlines::<T>::mutate_exists(rental.end_block, |x| {
+            if let Some(expired) = x {
+                expired.retain(|id| *id != nft_id);
+                if expired.is_empty() {
+                    *x = None;
+                }
+            }
+        });
+
+        let end_block = now.min(rental.end_block);
+        let used_blocks = end_block.saturating_sub(rental.start_block);
+        let rented_blocks = rental.end_block.saturating_sub(rental.start_block);
+        let paid = Self::rental_price(rental.price_per_block, used_blocks);
+        let price = Self::rental_price(rental.price_per_block, rented_blocks);
+        let refunded = price.saturating_sub(paid).saturating_add(rental.deposit);
+
+        // If the owner account is gone the renter keeps what could not be paid.
+        let renter = &rental.renter;
+        let unpaid =
+            T::Currency::repatriate_reserved(renter, &rental.owner, paid, BalanceStatus::Free)
+                .unwrap_or(paid);
+        let paid = paid.saturating_sub(unpaid);
+        let refunded = refunded.saturating_add(unpaid);
+        T::Currency::unreserve(renter, refunded);
+
+        let event = Event::RentalEnded {
+            nft_id,
+            renter: rental.renter,
+            paid,
+            refunded,
+        };
+        Self::deposit_event(event);
 
         Ok(())
     }
 
-    fn set_viewer(id: NFTId, value: Option<Self::AccountId>) -> DispatchResult {
-        Data::<T>::try_mutate(id, |maybe_data| -> DispatchResult {
-            let data = maybe_data.as_mut().ok_or(Error::<T>::NFTNotFound)?;
-            data.viewer = value;
-            Ok(())
+    /// Take an NFT back from whatever locks it so that the force origin can
+    /// act on it. Rentals are closed here, the other locks are handed over to
+    /// the release handlers. Frozen NFTs are returned as they are and stay
+    /// frozen until they are thawed. Return the NFT data once the lock is gone,
+    /// along with the weight used by the handlers.
+    fn release(id: NFTId) -> Result<(NFTDataOf<T>, Weight), DispatchError> {
+        let data = Data::<T>::get(id).ok_or(Error::<T>::NFTNotFound)?;
+        let released = match data.lock {
+            Some(NFTLockReason::Frozen) => return Ok((data, 0)),
+            None => 0,
+            Some(NFTLockReason::Rented) => {
+                let now = frame_system::Pallet::<T>::block_number();
+                Self::close_rental(id, now)?;
+                0
+            }
+            Some(reason) => T::NFTReleaseHandler::release_nft(id, reason)?,
+        };
+
+        // Handlers are not required to unlock the NFT themselves.
+        let data = Data::<T>::try_mutate(id, |x| -> Result<NFTDataOf<T>, DispatchError> {
+            let data = x.as_mut().ok_or(Error::<T>::NFTNotFound)?;
+            data.lock = None;
+            Ok(data.clone())
         })?;
 
-        Ok(())
+        Ok((data, released))
+    }
+
+    /// Remove an NFT along with its indexes, its rental offer and its storage
+    /// deposit.
+    fn remove_nft(id: NFTId, data: &NFTDataOf<T>) {
+        Data::<T>::remove(id);
+        NftsByOwner::<T>::remove(&data.owner, index_key(id));
+        NftsBySeries::<T>::remove(&data.series_id, index_key(id));
+        RentalOffers::<T>::remove(id);
+        if let Some((depositor, deposit)) = Deposits::<T>::take(id) {
+            T::Currency::unreserve(&depositor, deposit);
+        }
+    }
+
+    /// Make sure that an NFT is not locked. Every operation that needs the
+    /// NFT to be free goes through this check.
+    pub fn ensure_unlocked(data: &NFTDataOf<T>) -> DispatchResult {
+        let error = match data.lock {
+            None => return Ok(()),
+            Some(NFTLockReason::ListedForSale) => Error::<T>::NFTIsListedForSale,
+            Some(NFTLockReason::InTransmission) => Error::<T>::NFTIsInTransmission,
+            Some(NFTLockReason::Capsule) => Error::<T>::NFTIsCapsule,
+            Some(NFTLockReason::Rented) => Error::<T>::NFTIsRented,
+            Some(NFTLockReason::Fractionalized) => Error::<T>::NFTIsFractionalized,
+            Some(NFTLockReason::Frozen) => Error::<T>::NFTIsFrozen,
+            Some(NFTLockReason::PendingTransmission) => Error::<T>::NFTIsInTransmission,
+        };
+
+        Err(error.into())
+    }
+
+    /// Check the length and format of an IPFS reference and turn it into
+    /// its stored form.
+    fn bound_ipfs_reference(ipfs_reference: TextFormat) -> Result<IPFSReferenceOf<T>, Error<T>> {
+        let ipfs_reference: IPFSReferenceOf<T> = bounded_text(
+            ipfs_reference,
+            (T::MinIpfsLen::get(), Error::<T>::IPFSReferenceIsTooShort),
+            (T::MaxIpfsLen::get(), Error::<T>::IPFSReferenceIsTooLong),
+        )?;
+        ensure!(
+            T::IPFSReferenceValidator::is_valid(&ipfs_reference),
+            Error::<T>::InvalidIPFSReference
+        );
+
+        Ok(ipfs_reference)
+    }
+
+    /// Turn a series id into its stored form.
+    fn bound_series_id(series_id: NFTSeriesId) -> Result<SeriesIdOf<T>, Error<T>> {
+        series_id
+            .try_into()
+            .map_err(|_| Error::<T>::SeriesIdIsTooLong)
+    }
+
+    /// Make sure that `who` can add NFTs to the series. Return whether the
+    /// series already exists.
+    fn check_series(
+        who: &T::AccountId,
+        series_id: &Option<SeriesIdOf<T>>,
+    ) -> Result<bool, DispatchError> {
+        if let Some(id) = series_id {
+            if let Some(series) = Series::<T>::get(id) {
+                ensure!(series.owner == *who, Error::<T>::NotTheSeriesOwner);
+                ensure!(
+                    series.draft,
+                    Error::<T>::CannotCreateNFTsWithCompletedSeries
+                );
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Store a new NFT owned and created by `who`.
+    fn mint(
+        who: &T::AccountId,
+        ipfs_reference: IPFSReferenceOf<T>,
+        series_id: &SeriesIdOf<T>,
+        royalty: Permill,
+    ) -> NFTId {
+        let nft_id = Self::generate_nft_id();
+        let value = NFTData::new(
+            who.clone(),
+            who.clone(),
+            ipfs_reference,
+            series_id.clone(),
+            None,
+            None,
+            royalty,
+        );
+
+        Data::<T>::insert(nft_id, value);
+        NftsByOwner::<T>::insert(who, index_key(nft_id), ());
+        NftsBySeries::<T>::insert(series_id, index_key(nft_id), ());
+
+        nft_id
+    }
+
+    fn move_owner_index(id: NFTId, old_owner: &T::AccountId, new_owner: &T::AccountId) {
+        NftsByOwner::<T>::remove(old_owner, index_key(id));
+        NftsByOwner::<T>::insert(new_owner, index_key(id), ());
     }
-}
 
-impl<T: Config> Pallet<T> {
     fn generate_nft_id() -> NFTId {
         let nft_id = NftIdGenerator::<T>::get();
         let next_id = nft_id
@@ -563,10 +1261,10 @@ impl<T: Config> Pallet<T> {
         return nft_id;
     }
 
-    fn generate_series_id() -> NFTSeriesId {
+    fn generate_series_id() -> Result<SeriesIdOf<T>, Error<T>> {
         let mut id = SeriesIdGenerator::<T>::get();
         loop {
-            let id_vec = u32_to_text(id);
+            let id_vec = Self::bound_series_id(u32_to_text(id))?;
             if !Series::<T>::contains_key(&id_vec) {
                 break;
             }
@@ -579,10 +1277,23 @@ impl<T: Config> Pallet<T> {
                 .expect("If u32 is not enough we should crash for safety; qed."),
         );
 
-        return u32_to_text(id);
+        Self::bound_series_id(u32_to_text(id))
     }
 }
 
+/// Key of an NFT in the owner and series indexes.
+pub(crate) fn index_key(id: NFTId) -> IndexKey {
+    id.to_be_bytes()
+}
+
+/// Read the first `limit` NFT ids of an index. The keys are already in
+/// ascending order, so only the returned entries are read.
+fn paginate(keys: impl Iterator<Item = IndexKey>, limit: u32) -> Vec<NFTId> {
+    keys.take(limit as usize)
+        .map(NFTId::from_be_bytes)
+        .collect()
+}
+
 fn u32_to_text(num: u32) -> Vec<u8> {
     let mut vec: Vec<u8> = vec![];
     let mut dc: usize = 0;
@@ -1,28 +1,88 @@
-/* pub mod v5;
+pub mod v2;
+pub mod v3;
+pub mod v4;
+pub mod v5;
 pub mod v6;
+pub mod v7;
 
 use crate::{Config, Pallet};
-use frame_support::traits::StorageVersion;
+use codec::Decode;
+use frame_support::storage::migration::storage_key_iter;
+use frame_support::traits::{PalletInfoAccess, StorageVersion};
 use frame_support::weights::Weight;
+use frame_support::Blake2_128Concat;
+use ternoa_primitives::nfts::NFTId;
 
 pub fn migrate<T: Config>() -> Weight {
     let mut weight: Weight = 0;
-    let storage_version = StorageVersion::get::<Pallet<T>>();
 
-    if storage_version == 6 {
-        log::info!(target: "runtime::nfts", "Nfts pallet: migration was run",);
-        return weight;
+    if StorageVersion::get::<Pallet<T>>() == 1 {
+        log::info!(target: "runtime::nfts", "Nfts pallet: migrating to StorageVersion V2");
+
+        weight = weight.saturating_add(v2::migrate::<T>());
+        StorageVersion::new(2).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::nfts", "Nfts pallet: migration to StorageVersion V2 done");
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 2 {
+        log::info!(target: "runtime::nfts", "Nfts pallet: migrating to StorageVersion V3");
+
+        weight = weight.saturating_add(v3::migrate::<T>());
+        StorageVersion::new(3).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::nfts", "Nfts pallet: migration to StorageVersion V3 done");
     }
 
-    if storage_version == 5 {
+    if StorageVersion::get::<Pallet<T>>() == 3 {
+        log::info!(target: "runtime::nfts", "Nfts pallet: migrating to StorageVersion V4");
+
+        weight = weight.saturating_add(v4::migrate::<T>());
+        StorageVersion::new(4).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::nfts", "Nfts pallet: migration to StorageVersion V4 done");
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 4 {
+        log::info!(target: "runtime::nfts", "Nfts pallet: migrating to StorageVersion V5");
+
+        match v5::migrate::<T>() {
+            Ok(used) => {
+                weight = weight.saturating_add(used);
+                StorageVersion::new(5).put::<Pallet<T>>();
+
+                log::info!(target: "runtime::nfts", "Nfts pallet: migration to StorageVersion V5 done");
+            }
+            Err(used) => {
+                weight = weight.saturating_add(used);
+
+                log::error!(target: "runtime::nfts", "Nfts pallet: migration to StorageVersion V5 aborted");
+            }
+        }
+    }
+
+    if StorageVersion::get::<Pallet<T>>() == 5 {
         log::info!(target: "runtime::nfts", "Nfts pallet: migrating to StorageVersion V6");
 
-        weight = v6::migrate::<T>();
+        weight = weight.saturating_add(v6::migrate::<T>());
         StorageVersion::new(6).put::<Pallet<T>>();
 
         log::info!(target: "runtime::nfts", "Nfts pallet: migration to StorageVersion V6 done");
     }
 
+    if StorageVersion::get::<Pallet<T>>() == 6 {
+        log::info!(target: "runtime::nfts", "Nfts pallet: migrating to StorageVersion V7");
+
+        weight = weight.saturating_add(v7::migrate::<T>());
+        StorageVersion::new(7).put::<Pallet<T>>();
+
+        log::info!(target: "runtime::nfts", "Nfts pallet: migration to StorageVersion V7 done");
+    }
+
     weight
 }
- */
+
+/// Iterate over the NFTs while they are still stored with an older layout.
+fn iter_old_data<T: Config, Old: Decode>() -> impl Iterator<Item = (NFTId, Old)> {
+    storage_key_iter::<NFTId, Old, Blake2_128Concat>(Pallet::<T>::name().as_bytes(), b"Data")
+}
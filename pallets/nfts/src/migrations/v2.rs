@@ -0,0 +1,49 @@
+use super::{iter_old_data, v4::v3};
+use crate::{Config, Data};
+use frame_support::storage::unhashed;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use sp_runtime::Permill;
+
+pub mod v1 {
+    use codec::{Decode, Encode};
+    use sp_runtime::RuntimeDebug;
+    use ternoa_primitives::nfts::NFTSeriesId;
+    use ternoa_primitives::TextFormat;
+
+    // NFTData as it was stored before royalties were introduced
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+    pub struct NFTData<AccountId> {
+        pub owner: AccountId,
+        pub creator: AccountId,
+        pub ipfs_reference: TextFormat,
+        pub series_id: NFTSeriesId,
+        pub listed_for_sale: bool,
+        pub in_transmission: bool,
+        pub converted_to_capsule: bool,
+        pub viewer: Option<AccountId>,
+    }
+}
+
+/// Adds a zero royalty to every existing NFT.
+pub fn migrate<T: Config>() -> Weight {
+    let mut count: u64 = 0;
+
+    for (nft_id, old) in iter_old_data::<T, v1::NFTData<T::AccountId>>() {
+        count += 1;
+        let data = v3::NFTData {
+            owner: old.owner,
+            creator: old.creator,
+            ipfs_reference: old.ipfs_reference,
+            series_id: old.series_id,
+            listed_for_sale: old.listed_for_sale,
+            in_transmission: old.in_transmission,
+            converted_to_capsule: old.converted_to_capsule,
+            viewer: old.viewer,
+            royalty: Permill::zero(),
+        };
+        unhashed::put(&Data::<T>::hashed_key_for(nft_id), &data);
+    }
+
+    T::DbWeight::get().reads_writes(count, count)
+}
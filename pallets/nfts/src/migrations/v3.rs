@@ -0,0 +1,22 @@
+use super::{iter_old_data, v4::v3};
+use crate::{index_key, Config, NftsByOwner, NftsBySeries, SeriesIdOf};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use sp_std::convert::TryInto;
+
+/// Builds the owner and series indexes from the existing NFTs.
+pub fn migrate<T: Config>() -> Weight {
+    let mut count: u64 = 0;
+
+    for (nft_id, data) in iter_old_data::<T, v3::NFTData<T::AccountId>>() {
+        count += 1;
+        NftsByOwner::<T>::insert(&data.owner, index_key(nft_id), ());
+        // Series ids that are too long are indexed once they are bounded.
+        let series_id: Result<SeriesIdOf<T>, _> = data.series_id.try_into();
+        if let Ok(series_id) = series_id {
+            NftsBySeries::<T>::insert(series_id, index_key(nft_id), ());
+        }
+    }
+
+    T::DbWeight::get().reads_writes(count, count.saturating_mul(2))
+}
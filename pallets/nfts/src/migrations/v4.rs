@@ -0,0 +1,70 @@
+use super::{iter_old_data, v5::v4};
+use crate::{Config, Data};
+use frame_support::storage::unhashed;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use ternoa_primitives::nfts::NFTLockReason;
+
+pub mod v3 {
+    use codec::{Decode, Encode};
+    use sp_runtime::{Permill, RuntimeDebug};
+    use ternoa_primitives::nfts::NFTSeriesId;
+    use ternoa_primitives::TextFormat;
+
+    // NFTData as it was stored before the lock flags were merged into a lock reason
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+    pub struct NFTData<AccountId> {
+        pub owner: AccountId,
+        pub creator: AccountId,
+        pub ipfs_reference: TextFormat,
+        pub series_id: NFTSeriesId,
+        pub listed_for_sale: bool,
+        pub in_transmission: bool,
+        pub converted_to_capsule: bool,
+        pub viewer: Option<AccountId>,
+        pub royalty: Permill,
+    }
+}
+
+/// Replaces the lock flags of every NFT with a single lock reason. If more
+/// than one flag is set, capsules win over sales, sales over transmissions
+/// and transmissions over loans.
+pub fn migrate<T: Config>() -> Weight {
+    let mut count: u64 = 0;
+
+    for (nft_id, old) in iter_old_data::<T, v3::NFTData<T::AccountId>>() {
+        count += 1;
+
+        let lock = if old.converted_to_capsule {
+            Some(NFTLockReason::Capsule)
+        } else if old.listed_for_sale {
+            Some(NFTLockReason::ListedForSale)
+        } else if old.in_transmission {
+            Some(NFTLockReason::InTransmission)
+        } else if old.viewer.is_some() {
+            // Loans were stored as `Lent`, which became `Rented` with the same encoding.
+            Some(NFTLockReason::Rented)
+        } else {
+            None
+        };
+
+        // A viewer only makes sense while the NFT is lent.
+        let viewer = match lock {
+            Some(NFTLockReason::Rented) => old.viewer,
+            _ => None,
+        };
+
+        let data = v4::NFTData {
+            owner: old.owner,
+            creator: old.creator,
+            ipfs_reference: old.ipfs_reference,
+            series_id: old.series_id,
+            lock,
+            viewer,
+            royalty: old.royalty,
+        };
+        unhashed::put(&Data::<T>::hashed_key_for(nft_id), &data);
+    }
+
+    T::DbWeight::get().reads_writes(count, count)
+}
@@ -1,107 +1,108 @@
-/* pub mod v5 {
-    use crate::Config;
+use super::iter_old_data;
+use crate::{
+    index_key, Config, Data, IPFSLengthLimitOf, NFTData, NftsBySeries, Pallet, Series,
+    SeriesIdLengthLimitOf, SeriesIdOf,
+};
+use frame_support::storage::migration::storage_key_iter;
+use frame_support::traits::{Get, PalletInfoAccess};
+use frame_support::weights::Weight;
+use frame_support::Blake2_128Concat;
+use sp_std::vec::Vec;
+use ternoa_common::helpers::{fits_bound, truncate_to_bound};
+use ternoa_primitives::nfts::{NFTSeriesDetails, NFTSeriesId};
+
+pub mod v4 {
     use codec::{Decode, Encode};
-    use frame_support::Blake2_128Concat;
-    #[cfg(feature = "std")]
-    use serde::{Deserialize, Serialize};
-    use sp_runtime::RuntimeDebug;
-    use sp_std::collections::btree_map::BTreeMap;
-    use sp_std::vec::Vec;
-
-    // Define all types that were used for v5
-
-    pub type NFTSeriesId = u32;
-    pub type NFTId = u32;
-
-    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
-    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-    pub struct NFTSeriesDetails<AccountId, NFTId> {
-        // Series owner.
-        pub owner: AccountId,
-        // NFTs that are part of the same series.
-        pub nfts: Vec<NFTId>,
-    }
+    use sp_runtime::{Permill, RuntimeDebug};
+    use ternoa_primitives::nfts::{NFTLockReason, NFTSeriesId};
+    use ternoa_primitives::TextFormat;
 
-    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
-    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    // NFTData as it was stored before its fields were bounded
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
     pub struct NFTData<AccountId> {
         pub owner: AccountId,
-        pub details: NFTDetails,
-        /// Set to true to prevent further modifications to the details struct
-        pub sealed: bool,
-        /// Set to true to prevent changes to the owner variable
-        pub locked: bool,
+        pub creator: AccountId,
+        pub ipfs_reference: TextFormat,
+        pub series_id: NFTSeriesId,
+        pub lock: Option<NFTLockReason>,
+        pub viewer: Option<AccountId>,
+        pub royalty: Permill,
     }
+}
 
-    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, Debug)]
-    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-    pub struct NFTDetails {
-        /// ASCII encoded URI to fetch additional metadata.
-        pub offchain_uri: Vec<u8>,
-        /// The series id that this nft belongs to.
-        pub series_id: NFTSeriesId,
-        /// Capsule flag.
-        pub is_capsule: bool,
+/// Bounds the IPFS references and the series ids. Nothing is changed if one of
+/// them does not fit: every offending entry is logged and an error is returned
+/// so that the storage version stays the same.
+pub fn migrate<T: Config>() -> Result<Weight, Weight> {
+    let mut reads: u64 = 0;
+    let mut writes: u64 = 0;
+    let mut fits = true;
+
+    for (series_id, _) in storage_key_iter::<
+        NFTSeriesId,
+        NFTSeriesDetails<T::AccountId>,
+        Blake2_128Concat,
+    >(Pallet::<T>::name().as_bytes(), b"Series")
+    {
+        reads += 1;
+
+        if !fits_bound::<_, SeriesIdLengthLimitOf<T>>(&series_id) {
+            log::error!(target: "runtime::nfts", "Series {:?} does not fit", series_id);
+            fits = false;
+        }
     }
 
-    frame_support::generate_storage_alias!(
-        Nfts, Series<T: Config> => Map<
-            (Blake2_128Concat, NFTSeriesId),
-            NFTSeriesDetails<T::AccountId, NFTId>
-        >
-    );
-
-    frame_support::generate_storage_alias!(
-        Nfts, Data<T: Config> => Map<
-            (Blake2_128Concat, NFTId),
-            NFTData<T::AccountId>
-        >
-    );
-
-    pub type StorageSeries<AccountId> = BTreeMap<NFTSeriesId, NFTSeriesDetails<AccountId, NFTId>>;
-    pub type StorageNFTs<AccountId> = BTreeMap<NFTId, NFTData<AccountId>>;
-
-    pub fn get_series<T: Config>() -> StorageSeries<T::AccountId> {
-        Series::<T>::iter().map(|x| x).collect()
+    for (nft_id, old) in iter_old_data::<T, v4::NFTData<T::AccountId>>() {
+        reads += 1;
+
+        if !fits_bound::<_, IPFSLengthLimitOf<T>>(&old.ipfs_reference)
+            || !fits_bound::<_, SeriesIdLengthLimitOf<T>>(&old.series_id)
+        {
+            log::error!(target: "runtime::nfts", "NFT {} does not fit", nft_id);
+            fits = false;
+        }
     }
 
-    pub fn get_nfts<T: Config>() -> StorageNFTs<T::AccountId> {
-        Data::<T>::iter().map(|x| x).collect()
+    if !fits {
+        return Err(T::DbWeight::get().reads(reads));
     }
 
-    #[allow(dead_code)]
-    pub fn insert_series<T: Config>(
-        series_id: NFTSeriesId,
-        details: NFTSeriesDetails<T::AccountId, NFTId>,
-    ) {
+    let series: Vec<(NFTSeriesId, NFTSeriesDetails<T::AccountId>)> =
+        storage_key_iter::<NFTSeriesId, NFTSeriesDetails<T::AccountId>, Blake2_128Concat>(
+            Pallet::<T>::name().as_bytes(),
+            b"Series",
+        )
+        .drain()
+        .collect();
+
+    // Everything has been checked above, so nothing gets truncated from here on.
+    for (series_id, details) in series {
+        reads += 1;
+        writes += 2;
+
+        let series_id: SeriesIdOf<T> = truncate_to_bound(series_id);
         Series::<T>::insert(series_id, details);
     }
 
-    #[allow(dead_code)]
-    pub fn insert_nft<T: Config>(
-        owner: T::AccountId,
-        nft_id: NFTId,
-        offchain_uri: Vec<u8>,
-        series_id: NFTSeriesId,
-    ) {
-        let details = NFTDetails {
-            offchain_uri,
-            series_id,
-            is_capsule: false,
-        };
-        let data = NFTData {
-            owner,
-            details,
-            locked: false,
-            sealed: false,
-        };
-
-        Data::<T>::insert(nft_id, data);
-    }
+    NftsBySeries::<T>::remove_all(None);
 
-    pub fn kill_storage<T: Config>() {
-        Series::<T>::remove_all(None);
-        Data::<T>::remove_all(None);
-    }
+    Data::<T>::translate::<v4::NFTData<T::AccountId>, _>(|nft_id, old| {
+        reads += 1;
+        writes += 2;
+
+        let data = NFTData::new(
+            old.owner,
+            old.creator,
+            truncate_to_bound(old.ipfs_reference),
+            truncate_to_bound(old.series_id),
+            old.lock,
+            old.viewer,
+            old.royalty,
+        );
+        NftsBySeries::<T>::insert(&data.series_id, index_key(nft_id), ());
+
+        Some(data)
+    });
+
+    Ok(T::DbWeight::get().reads_writes(reads, writes))
 }
- */
@@ -0,0 +1,34 @@
+use crate::{Config, Data, Deposits, Pallet};
+use frame_support::traits::{Get, ReservableCurrency};
+use frame_support::weights::Weight;
+
+/// NFTs used to be paid for with a burnt mint fee. When the runtime uses
+/// storage deposits, the deposit of every existing NFT is reserved from its
+/// owner. Owners that cannot afford it keep their NFTs without a deposit.
+pub fn migrate<T: Config>() -> Weight {
+    let mut reads: u64 = 0;
+    let mut writes: u64 = 0;
+
+    if !T::UseStorageDeposits::get() {
+        return 0;
+    }
+
+    for (nft_id, data) in Data::<T>::iter() {
+        reads += 2;
+        if Deposits::<T>::contains_key(nft_id) {
+            continue;
+        }
+
+        let deposit = Pallet::<T>::mint_deposit(&data.ipfs_reference);
+        writes += 1;
+        if T::Currency::reserve(&data.owner, deposit).is_err() {
+            log::info!(target: "runtime::nfts", "NFT {} has no storage deposit", nft_id);
+            continue;
+        }
+
+        writes += 1;
+        Pallet::<T>::record_deposit(nft_id, &data.owner, deposit);
+    }
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
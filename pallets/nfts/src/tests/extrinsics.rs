@@ -1,31 +1,39 @@
 use super::mock::*;
 use crate::tests::mock;
-use crate::{Error, Event as NFTsEvent, NFTData, NFTSeriesDetails};
+use crate::{
+    index_key, Error, Event as NFTsEvent, NFTData, NFTSeriesDetails, NftsByOwner, SeriesIdOf,
+    WeightInfo,
+};
 use frame_support::error::BadOrigin;
+use frame_support::traits::Get;
+use frame_support::weights::GetDispatchInfo;
 use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
 use pallet_balances::Error as BalanceError;
+use sp_runtime::Permill;
 use ternoa_common::traits::NFTTrait;
+use ternoa_primitives::nfts::{NFTId, NFTLockReason, Rental, RentalTerms};
 
 fn origin(account: u64) -> mock::Origin {
     RawOrigin::Signed(account).into()
 }
 
-/* fn root() -> mock::Origin {
+fn root() -> mock::Origin {
     RawOrigin::Root.into()
-} */
+}
 
 mod transfer {
     use super::*;
 
     #[test]
-    fn cannot_transfer_lent_nfts() {
-        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+    fn cannot_transfer_rented_nfts() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
             let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
-            assert_ok!(NFTs::set_viewer(nft_id, Some(BOB)));
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None));
+            assert_ok!(NFTs::rent(origin(BOB), nft_id));
 
             let ok = NFTs::transfer(origin(ALICE), nft_id, BOB);
-            assert_noop!(ok, Error::<Test>::CannotTransferLentNFTs);
+            assert_noop!(ok, Error::<Test>::NFTIsRented);
         })
     }
 }
@@ -34,35 +42,40 @@ mod burn {
     use super::*;
 
     #[test]
-    fn cannot_burn_lent_nfts() {
-        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+    fn cannot_burn_rented_nfts() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
             let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
-            assert_ok!(NFTs::set_viewer(nft_id, Some(BOB)));
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None));
+            assert_ok!(NFTs::rent(origin(BOB), nft_id));
 
             let ok = NFTs::burn(origin(ALICE), nft_id);
-            assert_noop!(ok, Error::<Test>::CannotBurnLentNFTs);
+            assert_noop!(ok, Error::<Test>::NFTIsRented);
         })
     }
 }
 
-mod lend {
+mod offer_rental {
     use super::*;
 
     #[test]
-    fn lend() {
+    fn offer_rental() {
         ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
             let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
-            let mut nft = NFTs::data(nft_id).unwrap();
-            let viewer = Some(BOB);
 
-            assert_ok!(NFTs::lend(origin(ALICE), nft_id, viewer.clone()));
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 2, 10, Some(5)));
 
             // Storage
-            nft.viewer = viewer.clone();
-            assert_eq!(NFTs::data(nft_id), Some(nft));
+            let terms = RentalTerms::new(ALICE, 2, 10, 5);
+            assert_eq!(NFTs::rental_offers(nft_id), Some(terms));
+            assert_eq!(NFTs::data(nft_id).unwrap().lock, None);
 
             // Event
-            let event = NFTsEvent::NFTLent { nft_id, viewer };
+            let event = NFTsEvent::RentalOffered {
+                nft_id,
+                price_per_block: 2,
+                duration: 10,
+                deposit: 5,
+            };
             let event = Event::NFTs(event);
             assert_eq!(System::events().last().unwrap().event, event);
         })
@@ -71,7 +84,7 @@ mod lend {
     #[test]
     fn nft_not_found() {
         ExtBuilder::new_build(vec![]).execute_with(|| {
-            let ok = NFTs::lend(origin(ALICE), INVALID_NFT_ID, None);
+            let ok = NFTs::offer_rental(origin(ALICE), INVALID_NFT_ID, 1, 10, None);
             assert_noop!(ok, Error::<Test>::NFTNotFound);
         })
     }
@@ -81,41 +94,707 @@ mod lend {
         ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
             let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
 
-            let ok = NFTs::lend(origin(BOB), nft_id, None);
+            let ok = NFTs::offer_rental(origin(BOB), nft_id, 1, 10, None);
             assert_noop!(ok, Error::<Test>::NotTheNFTOwner);
         })
     }
 
     #[test]
-    fn cannot_lend_nfts_listed_for_sale() {
+    fn invalid_rental_duration() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+
+            let ok = NFTs::offer_rental(origin(ALICE), nft_id, 1, 0, None);
+            assert_noop!(ok, Error::<Test>::InvalidRentalDuration);
+        })
+    }
+
+    #[test]
+    fn cannot_offer_nfts_listed_for_sale() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::ListedForSale));
+
+            let ok = NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None);
+            assert_noop!(ok, Error::<Test>::NFTIsListedForSale);
+        })
+    }
+
+    #[test]
+    fn cannot_offer_capsules() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::Capsule));
+
+            let ok = NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None);
+            assert_noop!(ok, Error::<Test>::NFTIsCapsule);
+        })
+    }
+
+    #[test]
+    fn cannot_offer_nfts_in_transmission() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::InTransmission));
+
+            let ok = NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None);
+            assert_noop!(ok, Error::<Test>::NFTIsInTransmission);
+        })
+    }
+}
+
+mod cancel_rental_offer {
+    use super::*;
+
+    #[test]
+    fn cancel_rental_offer() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None));
+
+            assert_ok!(NFTs::cancel_rental_offer(origin(ALICE), nft_id));
+
+            // Storage
+            assert_eq!(NFTs::rental_offers(nft_id), None);
+
+            // Event
+            let event = NFTsEvent::RentalOfferCancelled { nft_id };
+            let event = Event::NFTs(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+    }
+
+    #[test]
+    fn rental_offer_not_found() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+
+            let ok = NFTs::cancel_rental_offer(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::RentalOfferNotFound);
+        })
+    }
+
+    #[test]
+    fn not_the_nft_owner() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None));
+
+            let ok = NFTs::cancel_rental_offer(origin(BOB), nft_id);
+            assert_noop!(ok, Error::<Test>::NotTheNFTOwner);
+        })
+    }
+}
+
+mod rent {
+    use super::*;
+
+    #[test]
+    fn rent() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            let mut nft = NFTs::data(nft_id).unwrap();
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 2, 10, Some(5)));
+
+            assert_ok!(NFTs::rent(origin(BOB), nft_id));
+
+            // Storage
+            nft.viewer = Some(BOB);
+            nft.lock = Some(NFTLockReason::Rented);
+            assert_eq!(NFTs::data(nft_id), Some(nft));
+            assert_eq!(NFTs::rental_offers(nft_id), None);
+            let rental = Rental::new(ALICE, BOB, 2, 1, 11, 5);
+            assert_eq!(NFTs::rentals(nft_id), Some(rental));
+            assert_eq!(NFTs::rental_deadlines(11), vec![nft_id]);
+            assert_eq!(Balances::reserved_balance(BOB), 25);
+            assert_eq!(Balances::free_balance(BOB), 75);
+
+            // Event
+            let event = NFTsEvent::NFTRented {
+                nft_id,
+                renter: BOB,
+                end_block: 11,
+            };
+            let event = Event::NFTs(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+    }
+
+    #[test]
+    fn rental_offer_not_found() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+
+            let ok = NFTs::rent(origin(BOB), nft_id);
+            assert_noop!(ok, Error::<Test>::RentalOfferNotFound);
+        })
+    }
+
+    #[test]
+    fn cannot_rent_own_nft() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None));
+
+            let ok = NFTs::rent(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::CannotRentOwnNFT);
+        })
+    }
+
+    #[test]
+    fn rental_offer_is_outdated() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None));
+            assert_ok!(<NFTs as NFTTrait>::set_owner(nft_id, &CHAD));
+
+            let ok = NFTs::rent(origin(BOB), nft_id);
+            assert_noop!(ok, Error::<Test>::RentalOfferIsOutdated);
+        })
+    }
+
+    #[test]
+    fn not_enough_balance() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 10)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 2, 10, None));
+
+            let ok = NFTs::rent(origin(BOB), nft_id);
+            assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
+        })
+    }
+
+    #[test]
+    fn too_many_rentals_at_block() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            // MaxRentalsPerBlock is 2
+            for _ in 0..2 {
+                let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+                assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None));
+                assert_ok!(NFTs::rent(origin(BOB), nft_id));
+            }
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None));
+
+            let ok = NFTs::rent(origin(BOB), nft_id);
+            assert_noop!(ok, Error::<Test>::TooManyRentalsAtBlock);
+
+            // Another deadline block still has room
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 9, None));
+            assert_ok!(NFTs::rent(origin(BOB), nft_id));
+        })
+    }
+}
+
+mod end_rental {
+    use super::*;
+
+    #[test]
+    fn end_rental() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            let nft = NFTs::data(nft_id).unwrap();
+            let alice_balance = Balances::free_balance(ALICE);
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 2, 10, Some(5)));
+            assert_ok!(NFTs::rent(origin(BOB), nft_id));
+
+            run_to_block(4);
+            assert_ok!(NFTs::end_rental(origin(BOB), nft_id));
+
+            // Storage
+            assert_eq!(NFTs::data(nft_id), Some(nft));
+            assert_eq!(NFTs::rentals(nft_id), None);
+            assert_eq!(NFTs::rental_deadlines(11), vec![]);
+            assert_eq!(Balances::reserved_balance(BOB), 0);
+            assert_eq!(Balances::free_balance(BOB), 94);
+            assert_eq!(Balances::free_balance(ALICE), alice_balance + 6);
+
+            // Event
+            let event = NFTsEvent::RentalEnded {
+                nft_id,
+                renter: BOB,
+                paid: 6,
+                refunded: 19,
+            };
+            let event = Event::NFTs(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+    }
+
+    #[test]
+    fn rental_expires() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            let nft = NFTs::data(nft_id).unwrap();
+            let alice_balance = Balances::free_balance(ALICE);
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 2, 10, Some(5)));
+            assert_ok!(NFTs::rent(origin(BOB), nft_id));
+
+            run_to_block(10);
+            assert!(NFTs::rentals(nft_id).is_some());
+            run_to_block(11);
+
+            // Storage
+            assert_eq!(NFTs::data(nft_id), Some(nft));
+            assert_eq!(NFTs::rentals(nft_id), None);
+            assert_eq!(NFTs::rental_deadlines(11), vec![]);
+            assert_eq!(Balances::reserved_balance(BOB), 0);
+            assert_eq!(Balances::free_balance(BOB), 80);
+            assert_eq!(Balances::free_balance(ALICE), alice_balance + 20);
+        })
+    }
+
+    #[test]
+    fn rental_not_found() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+
+            let ok = NFTs::end_rental(origin(BOB), nft_id);
+            assert_noop!(ok, Error::<Test>::RentalNotFound);
+        })
+    }
+
+    #[test]
+    fn not_the_renter() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None));
+            assert_ok!(NFTs::rent(origin(BOB), nft_id));
+
+            let ok = NFTs::end_rental(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::NotTheRenter);
+        })
+    }
+}
+
+mod force_transfer {
+    use super::*;
+
+    #[test]
+    fn force_transfer() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::ListedForSale).unwrap();
+
+            assert_ok!(NFTs::force_transfer(root(), nft_id, BOB));
+
+            // Storage
+            let nft = NFTs::data(nft_id).unwrap();
+            assert_eq!(nft.owner, BOB);
+            assert_eq!(nft.lock, None);
+            assert_eq!(NFTs::nfts_by_owner(&ALICE, None, 10), vec![]);
+            assert_eq!(NFTs::nfts_by_owner(&BOB, None, 10), vec![nft_id]);
+            let released = vec![(nft_id, NFTLockReason::ListedForSale)];
+            assert_eq!(ReleasedNFTs::get(), released);
+
+            // Event
+            let event = NFTsEvent::NFTForceTransferred {
+                nft_id,
+                old_owner: ALICE,
+                new_owner: BOB,
+            };
+            let event = Event::NFTs(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+    }
+
+    #[test]
+    fn force_transfer_ends_rental() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            let alice_balance = Balances::free_balance(ALICE);
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 2, 10, Some(5)));
+            assert_ok!(NFTs::rent(origin(BOB), nft_id));
+
+            run_to_block(4);
+            assert_ok!(NFTs::force_transfer(root(), nft_id, CHAD));
+
+            let nft = NFTs::data(nft_id).unwrap();
+            assert_eq!(nft.owner, CHAD);
+            assert_eq!(nft.viewer, None);
+            assert_eq!(NFTs::rentals(nft_id), None);
+            assert_eq!(Balances::reserved_balance(BOB), 0);
+            assert_eq!(Balances::free_balance(BOB), 94);
+            assert_eq!(Balances::free_balance(ALICE), alice_balance + 6);
+            assert!(ReleasedNFTs::get().is_empty());
+        })
+    }
+
+    #[test]
+    fn force_transfer_charges_the_release_handlers() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            let max = <() as WeightInfo>::force_transfer() + RELEASE_WEIGHT;
+            let call = mock::Call::NFTs(crate::Call::force_transfer {
+                id: nft_id,
+                to: BOB,
+            });
+            assert_eq!(call.get_dispatch_info().weight, max);
+
+            // Nothing is released while the NFT is not locked
+            let info = NFTs::force_transfer(root(), nft_id, BOB).unwrap();
+            assert_eq!(info.actual_weight, Some(max - RELEASE_WEIGHT));
+
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::ListedForSale).unwrap();
+            let info = NFTs::force_transfer(root(), nft_id, ALICE).unwrap();
+            assert_eq!(info.actual_weight, Some(max));
+        })
+    }
+
+    #[test]
+    fn force_transfer_moves_deposit() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            UseStorageDeposits::set(&true);
+            assert_ok!(NFTs::create(origin(ALICE), vec![1], None, Permill::zero()));
+
+            assert_ok!(NFTs::force_transfer(root(), 0, BOB));
+
+            assert_eq!(NFTs::deposits(0), Some((BOB, 6)));
+            assert_eq!(Balances::reserved_balance(ALICE), 0);
+            assert_eq!(Balances::reserved_balance(BOB), 6);
+        })
+    }
+
+    #[test]
+    fn bad_origin() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+
+            let ok = NFTs::force_transfer(origin(ALICE), nft_id, BOB);
+            assert_noop!(ok, BadOrigin);
+        })
+    }
+
+    #[test]
+    fn nft_not_found() {
+        ExtBuilder::new_build(vec![]).execute_with(|| {
+            let ok = NFTs::force_transfer(root(), INVALID_NFT_ID, BOB);
+            assert_noop!(ok, Error::<Test>::NFTNotFound);
+        })
+    }
+}
+
+mod force_burn {
+    use super::*;
+
+    #[test]
+    fn force_burn() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            UseStorageDeposits::set(&true);
+            assert_ok!(NFTs::create(origin(ALICE), vec![1], None, Permill::zero()));
+            <NFTs as NFTTrait>::lock(0, NFTLockReason::Capsule).unwrap();
+
+            assert_ok!(NFTs::force_burn(root(), 0));
+
+            // Storage
+            assert_eq!(NFTs::data(0), None);
+            assert_eq!(NFTs::deposits(0), None);
+            assert_eq!(NFTs::nfts_by_owner(&ALICE, None, 10), vec![]);
+            assert_eq!(Balances::free_balance(ALICE), 100);
+            assert_eq!(ReleasedNFTs::get(), vec![(0, NFTLockReason::Capsule)]);
+
+            // Event
+            let event = Event::NFTs(NFTsEvent::NFTForceBurned { nft_id: 0 });
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+    }
+
+    #[test]
+    fn force_burn_removes_rental_offer() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None));
+
+            assert_ok!(NFTs::force_burn(root(), nft_id));
+            assert_eq!(NFTs::rental_offers(nft_id), None);
+        })
+    }
+
+    #[test]
+    fn bad_origin() {
         ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
             let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
-            assert_ok!(NFTs::set_listed_for_sale(nft_id, true));
 
-            let ok = NFTs::lend(origin(ALICE), nft_id, None);
-            assert_noop!(ok, Error::<Test>::CannotLendNFTsListedForSale);
+            let ok = NFTs::force_burn(origin(ALICE), nft_id);
+            assert_noop!(ok, BadOrigin);
         })
     }
+}
+
+mod freeze {
+    use super::*;
 
     #[test]
-    fn cannot_lend_capsules() {
+    fn freeze() {
         ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
             let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
-            assert_ok!(NFTs::set_converted_to_capsule(nft_id, true));
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::InTransmission).unwrap();
+
+            assert_ok!(NFTs::freeze(root(), nft_id));
+
+            // Storage
+            let lock = NFTs::data(nft_id).unwrap().lock;
+            assert_eq!(lock, Some(NFTLockReason::Frozen));
+            let released = vec![(nft_id, NFTLockReason::InTransmission)];
+            assert_eq!(ReleasedNFTs::get(), released);
 
-            let ok = NFTs::lend(origin(ALICE), nft_id, None);
-            assert_noop!(ok, Error::<Test>::CannotLendCapsules);
+            // Event
+            let event = Event::NFTs(NFTsEvent::NFTFrozen { nft_id });
+            assert_eq!(System::events().last().unwrap().event, event);
         })
     }
 
     #[test]
-    fn cannot_lend_nfts_in_transmission() {
+    fn frozen_nfts_cannot_be_used() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::freeze(root(), nft_id));
+
+            let ok = NFTs::burn(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::NFTIsFrozen);
+            let ok = NFTs::offer_rental(origin(ALICE), nft_id, 1, 10, None);
+            assert_noop!(ok, Error::<Test>::NFTIsFrozen);
+            let ok = <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::ListedForSale);
+            assert_noop!(ok, Error::<Test>::NFTIsFrozen);
+        })
+    }
+
+    #[test]
+    fn already_frozen() {
         ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
             let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
-            assert_ok!(NFTs::set_in_transmission(nft_id, true));
+            assert_ok!(NFTs::freeze(root(), nft_id));
 
-            let ok = NFTs::lend(origin(ALICE), nft_id, None);
-            assert_noop!(ok, Error::<Test>::CannotLendNFTsInTransmission);
+            let ok = NFTs::freeze(root(), nft_id);
+            assert_noop!(ok, Error::<Test>::NFTIsFrozen);
+        })
+    }
+
+    #[test]
+    fn frozen_nfts_stay_frozen_when_forced() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::freeze(root(), nft_id));
+
+            assert_ok!(NFTs::force_transfer(root(), nft_id, BOB));
+            let nft = NFTs::data(nft_id).unwrap();
+            assert_eq!(nft.owner, BOB);
+            assert_eq!(nft.lock, Some(NFTLockReason::Frozen));
+            assert!(ReleasedNFTs::get().is_empty());
+
+            let ok = NFTs::transfer(origin(BOB), nft_id, ALICE);
+            assert_noop!(ok, Error::<Test>::NFTIsFrozen);
+
+            assert_ok!(NFTs::thaw(root(), nft_id));
+            assert_ok!(NFTs::transfer(origin(BOB), nft_id, ALICE));
+        })
+    }
+
+    #[test]
+    fn frozen_nfts_can_be_force_burned() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::freeze(root(), nft_id));
+
+            assert_ok!(NFTs::force_burn(root(), nft_id));
+            assert_eq!(NFTs::data(nft_id), None);
+            assert!(ReleasedNFTs::get().is_empty());
+        })
+    }
+
+    #[test]
+    fn bad_origin() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+
+            let ok = NFTs::freeze(origin(ALICE), nft_id);
+            assert_noop!(ok, BadOrigin);
+        })
+    }
+}
+
+mod thaw {
+    use super::*;
+
+    #[test]
+    fn thaw() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::freeze(root(), nft_id));
+
+            assert_ok!(NFTs::thaw(root(), nft_id));
+
+            // Storage
+            assert_eq!(NFTs::data(nft_id).unwrap().lock, None);
+            assert_ok!(NFTs::burn(origin(ALICE), nft_id));
+
+            // Event
+            let event = Event::NFTs(NFTsEvent::NFTThawed { nft_id });
+            assert!(System::events().iter().any(|x| x.event == event));
+        })
+    }
+
+    #[test]
+    fn not_frozen() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::Capsule).unwrap();
+
+            let ok = NFTs::thaw(root(), nft_id);
+            assert_noop!(ok, Error::<Test>::NFTIsNotLocked);
+        })
+    }
+
+    #[test]
+    fn bad_origin() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            let nft_id = NFTs::create_nft(ALICE, vec![0], None).unwrap();
+            assert_ok!(NFTs::freeze(root(), nft_id));
+
+            let ok = NFTs::thaw(origin(ALICE), nft_id);
+            assert_noop!(ok, BadOrigin);
+        })
+    }
+}
+
+mod deposits {
+    use super::*;
+
+    fn create_completed_nft(owner: u64, ipfs_reference: Vec<u8>) -> NFTId {
+        assert_ok!(NFTs::create(
+            origin(owner),
+            ipfs_reference,
+            None,
+            Permill::zero()
+        ));
+        let nft_id = NFTs::nft_id_generator() - 1;
+        let series_id = NFTs::data(nft_id).unwrap().series_id.into_inner();
+        assert_ok!(NFTs::finish_series(origin(owner), series_id));
+        nft_id
+    }
+
+    #[test]
+    fn create_reserves_deposit() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            UseStorageDeposits::set(&true);
+
+            assert_ok!(NFTs::create(
+                origin(ALICE),
+                vec![1, 2, 3],
+                None,
+                Permill::zero()
+            ));
+
+            // Storage
+            let deposit = DepositBase::get() + 3 * DepositPerByte::get();
+            assert_eq!(NFTs::deposits(0), Some((ALICE, deposit)));
+            assert_eq!(Balances::reserved_balance(ALICE), deposit);
+            assert_eq!(Balances::free_balance(ALICE), 100 - deposit);
+            assert_eq!(Balances::free_balance(COLLECTOR), 0);
+
+            // Event
+            let event = NFTsEvent::NFTCreated {
+                nft_id: 0,
+                owner: ALICE,
+                series_id: NFTs::data(0).unwrap().series_id.into_inner(),
+                ipfs_reference: vec![1, 2, 3],
+                mint_fee: 0,
+                deposit,
+                royalty: Permill::zero(),
+            };
+            let event = Event::NFTs(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+    }
+
+    #[test]
+    fn create_batch_reserves_deposits() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            UseStorageDeposits::set(&true);
+            let ipfs_references = vec![vec![1], vec![1, 2]];
+
+            let ok = NFTs::create_batch(origin(ALICE), ipfs_references, None, Permill::zero());
+            assert_ok!(ok);
+
+            let deposit = DepositBase::get() + DepositPerByte::get();
+            assert_eq!(NFTs::deposits(0), Some((ALICE, deposit)));
+            let deposit = DepositBase::get() + 2 * DepositPerByte::get();
+            assert_eq!(NFTs::deposits(1), Some((ALICE, deposit)));
+            assert_eq!(Balances::reserved_balance(ALICE), 13);
+        })
+    }
+
+    #[test]
+    fn not_enough_balance() {
+        ExtBuilder::new_build(vec![(ALICE, 7)]).execute_with(|| {
+            UseStorageDeposits::set(&true);
+
+            let ok = NFTs::create(origin(ALICE), vec![1, 2, 3], None, Permill::zero());
+            assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
+        })
+    }
+
+    #[test]
+    fn transfer_moves_deposit() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            UseStorageDeposits::set(&true);
+            let nft_id = create_completed_nft(ALICE, vec![1]);
+
+            assert_ok!(NFTs::transfer(origin(ALICE), nft_id, BOB));
+
+            assert_eq!(NFTs::deposits(nft_id), Some((BOB, 6)));
+            assert_eq!(Balances::reserved_balance(ALICE), 0);
+            assert_eq!(Balances::free_balance(ALICE), 94);
+            assert_eq!(Balances::reserved_balance(BOB), 6);
+            assert_eq!(Balances::free_balance(BOB), 100);
+        })
+    }
+
+    #[test]
+    fn transfer_keeps_deposit() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 100)]).execute_with(|| {
+            UseStorageDeposits::set(&true);
+            DepositFollowsOwner::set(&false);
+            let nft_id = create_completed_nft(ALICE, vec![1]);
+
+            assert_ok!(NFTs::transfer(origin(ALICE), nft_id, BOB));
+            assert_eq!(NFTs::deposits(nft_id), Some((ALICE, 6)));
+            assert_eq!(Balances::reserved_balance(ALICE), 6);
+
+            // The deposit goes back to the account that reserved it.
+            assert_ok!(NFTs::burn(origin(BOB), nft_id));
+            assert_eq!(NFTs::deposits(nft_id), None);
+            assert_eq!(Balances::reserved_balance(ALICE), 0);
+            assert_eq!(Balances::free_balance(ALICE), 100);
+        })
+    }
+
+    #[test]
+    fn burn_unreserves_deposit() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            UseStorageDeposits::set(&true);
+            assert_ok!(NFTs::create(origin(ALICE), vec![1], None, Permill::zero()));
+
+            assert_ok!(NFTs::burn(origin(ALICE), 0));
+
+            assert_eq!(NFTs::deposits(0), None);
+            assert_eq!(Balances::reserved_balance(ALICE), 0);
+            assert_eq!(Balances::free_balance(ALICE), 100);
+        })
+    }
+
+    #[test]
+    fn burnt_fee_model() {
+        ExtBuilder::new_build(vec![(ALICE, 100)]).execute_with(|| {
+            assert_ok!(NFTs::create(origin(ALICE), vec![1], None, Permill::zero()));
+
+            assert_eq!(NFTs::deposits(0), None);
+            assert_eq!(Balances::reserved_balance(ALICE), 0);
+            assert_eq!(Balances::free_balance(ALICE), 100 - NFT_MINT_FEE);
+            assert_eq!(Balances::free_balance(COLLECTOR), NFT_MINT_FEE);
+
+            assert_ok!(NFTs::burn(origin(ALICE), 0));
+            assert_eq!(Balances::free_balance(ALICE), 100 - NFT_MINT_FEE);
         })
     }
 }
@@ -134,13 +813,14 @@ fn create_happy() {
 
             // Happy path NFT with series
             let series = NFTSeriesDetails::new(ALICE, true);
-            let data = NFTData::new_default(ALICE, vec![1], vec![50]);
+            let data = NFTData::new_default(ALICE, bounded(vec![1]), bounded(vec![50]));
             let alice_balance = Balances::free_balance(ALICE);
 
             let ok = NFTs::create(
                 alice.clone(),
-                data.ipfs_reference.clone(),
-                Some(data.series_id.clone()),
+                data.ipfs_reference.to_vec(),
+                Some(data.series_id.to_vec()),
+                Permill::zero(),
             );
             assert_ok!(ok);
 
@@ -154,10 +834,10 @@ fn create_happy() {
             );
 
             // Happy path NFT without series
-            let data = NFTData::new_default(ALICE, vec![0], vec![48]);
+            let data = NFTData::new_default(ALICE, bounded(vec![0]), bounded(vec![48]));
             let series = NFTSeriesDetails::new(ALICE, true);
 
-            let ok = NFTs::create(alice.clone(), vec![0], None);
+            let ok = NFTs::create(alice.clone(), vec![0], None, Permill::zero());
             assert_ok!(ok);
 
             assert_eq!(NFTs::series(&data.series_id), Some(series));
@@ -180,22 +860,36 @@ fn create_unhappy() {
             let bob: mock::Origin = RawOrigin::Signed(BOB).into();
 
             // Unhappy too short name
-            let ok = NFTs::create(alice.clone(), vec![], None);
+            let ok = NFTs::create(alice.clone(), vec![], None, Permill::zero());
             assert_noop!(ok, Error::<Test>::IPFSReferenceIsTooShort);
 
             // Unhappy too long name
-            let ok = NFTs::create(alice.clone(), vec![1, 2, 3, 4, 5, 6], None);
+            let ok = NFTs::create(alice.clone(), vec![1, 2, 3, 4, 5, 6], None, Permill::zero());
             assert_noop!(ok, Error::<Test>::IPFSReferenceIsTooLong);
 
+            // Unhappy malformed ipfs reference
+            let ok = NFTs::create(
+                alice.clone(),
+                INVALID_REFERENCE.to_vec(),
+                None,
+                Permill::zero(),
+            );
+            assert_noop!(ok, Error::<Test>::InvalidIPFSReference);
+
+            // Unhappy too long series id
+            let series_id = Some(vec![1; MaxSeriesIdLen::get() as usize + 1]);
+            let ok = NFTs::create(alice.clone(), vec![1], series_id, Permill::zero());
+            assert_noop!(ok, Error::<Test>::SeriesIdIsTooLong);
+
             // Unhappy not enough caps to mint an NFT
-            let ok = NFTs::create(alice.clone(), vec![1], None);
+            let ok = NFTs::create(alice.clone(), vec![1], None, Permill::zero());
             assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
 
             // Unhappy not the owner of series
             let series_id = Some(vec![50]);
             <NFTs as NFTTrait>::create_nft(CHAD, vec![50], series_id.clone()).unwrap();
 
-            let ok = NFTs::create(bob.clone(), vec![1], series_id);
+            let ok = NFTs::create(bob.clone(), vec![1], series_id, Permill::zero());
             assert_noop!(ok, Error::<Test>::NotTheSeriesOwner);
             assert_eq!(Balances::free_balance(BOB), 100);
 
@@ -204,11 +898,155 @@ fn create_unhappy() {
             <NFTs as NFTTrait>::create_nft(BOB, vec![50], series_id.clone()).unwrap();
             NFTs::finish_series(bob.clone(), series_id.clone().unwrap()).unwrap();
 
-            let ok = NFTs::create(bob.clone(), vec![1], series_id.clone());
+            let ok = NFTs::create(bob.clone(), vec![1], series_id.clone(), Permill::zero());
             assert_noop!(ok, Error::<Test>::CannotCreateNFTsWithCompletedSeries);
         })
 }
 
+#[test]
+fn create_with_royalty() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let royalty = Permill::from_percent(10);
+
+            assert_ok!(NFTs::create(origin(ALICE), vec![1], None, royalty));
+
+            let nft = NFTs::data(0).unwrap();
+            assert_eq!(nft.creator, ALICE);
+            assert_eq!(nft.royalty, royalty);
+
+            let event = NFTsEvent::NFTCreated {
+                nft_id: 0,
+                owner: ALICE,
+                series_id: nft.series_id.into_inner(),
+                ipfs_reference: vec![1],
+                mint_fee: NFT_MINT_FEE,
+                deposit: 0,
+                royalty,
+            };
+            let event = Event::NFTs(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+}
+
+#[test]
+fn create_batch_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice_balance = Balances::free_balance(ALICE);
+            let series_id = vec![50];
+            let ipfs_references = vec![vec![1], vec![2], vec![3]];
+            let royalty = Permill::from_percent(5);
+
+            let ok = NFTs::create_batch(
+                origin(ALICE),
+                ipfs_references.clone(),
+                Some(series_id.clone()),
+                royalty,
+            );
+            assert_ok!(ok);
+
+            // Per-item fee
+            let mint_fee = NFT_MINT_FEE * 3;
+            assert_eq!(Balances::free_balance(ALICE), alice_balance - mint_fee);
+
+            assert_eq!(NFTs::nft_id_generator(), 3);
+            let bounded_series_id: SeriesIdOf<Test> = bounded(series_id.clone());
+            assert_eq!(
+                NFTs::series(&bounded_series_id),
+                Some(NFTSeriesDetails::new(ALICE, true))
+            );
+            for (nft_id, ipfs_reference) in ipfs_references.into_iter().enumerate() {
+                let nft = NFTs::data(nft_id as u32).unwrap();
+                assert_eq!(nft.owner, ALICE);
+                assert_eq!(nft.ipfs_reference, ipfs_reference);
+                assert_eq!(nft.series_id, series_id);
+                assert_eq!(nft.royalty, royalty);
+            }
+            assert_eq!(NFTs::nfts_by_series(&series_id, None, 10), vec![0, 1, 2]);
+
+            let event = NFTsEvent::NFTBatchCreated {
+                nft_ids: vec![0, 1, 2],
+                owner: ALICE,
+                series_id,
+                mint_fee,
+                deposit: 0,
+                royalty,
+            };
+            let event = Event::NFTs(event);
+            assert_eq!(System::events().last().unwrap().event, event);
+        })
+}
+
+#[test]
+fn create_batch_discounted() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            BatchMintDiscount::set(&Permill::from_percent(50));
+            let alice_balance = Balances::free_balance(ALICE);
+            let ipfs_references = vec![vec![1], vec![2], vec![3], vec![4]];
+
+            assert_eq!(NFTs::batch_mint_fee(4), NFT_MINT_FEE * 2);
+            assert_ok!(NFTs::create_batch(
+                origin(ALICE),
+                ipfs_references,
+                None,
+                Permill::zero()
+            ));
+
+            assert_eq!(
+                Balances::free_balance(ALICE),
+                alice_balance - NFT_MINT_FEE * 2
+            );
+            assert_eq!(NFTs::nft_id_generator(), 4);
+            BatchMintDiscount::set(&Permill::zero());
+        })
+}
+
+#[test]
+fn create_batch_unhappy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 25), (BOB, 1000)])
+        .build()
+        .execute_with(|| {
+            // Unhappy empty batch
+            let ok = NFTs::create_batch(origin(ALICE), vec![], None, Permill::zero());
+            assert_noop!(ok, Error::<Test>::EmptyBatch);
+
+            // Unhappy too many NFTs
+            let ipfs_references = vec![vec![1]; MaxBatchSize::get() as usize + 1];
+            let ok = NFTs::create_batch(origin(ALICE), ipfs_references, None, Permill::zero());
+            assert_noop!(ok, Error::<Test>::BatchIsTooLarge);
+
+            // Unhappy one of the references is too long
+            let ipfs_references = vec![vec![1], vec![1, 2, 3, 4, 5, 6]];
+            let ok = NFTs::create_batch(origin(ALICE), ipfs_references, None, Permill::zero());
+            assert_noop!(ok, Error::<Test>::IPFSReferenceIsTooLong);
+
+            // Unhappy one of the references is malformed
+            let ipfs_references = vec![vec![1], INVALID_REFERENCE.to_vec()];
+            let ok = NFTs::create_batch(origin(ALICE), ipfs_references, None, Permill::zero());
+            assert_noop!(ok, Error::<Test>::InvalidIPFSReference);
+
+            // Unhappy not enough caps to pay for the whole batch
+            let ipfs_references = vec![vec![1], vec![2], vec![3]];
+            let ok = NFTs::create_batch(origin(ALICE), ipfs_references, None, Permill::zero());
+            assert_noop!(ok, BalanceError::<Test>::InsufficientBalance);
+
+            // Unhappy not the owner of series
+            let series_id = Some(vec![50]);
+            <NFTs as NFTTrait>::create_nft(ALICE, vec![50], series_id.clone()).unwrap();
+            let ok = NFTs::create_batch(origin(BOB), vec![vec![1]], series_id, Permill::zero());
+            assert_noop!(ok, Error::<Test>::NotTheSeriesOwner);
+        })
+}
+
 #[test]
 fn transfer_happy() {
     ExtBuilder::default()
@@ -252,24 +1090,24 @@ fn transfer_unhappy() {
 
             // Unhappy NFT is listed for sale
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![0], None).unwrap();
-            <NFTs as NFTTrait>::set_listed_for_sale(nft_id, true).unwrap();
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::ListedForSale).unwrap();
 
             let ok = NFTs::transfer(alice.clone(), nft_id, BOB);
-            assert_noop!(ok, Error::<Test>::CannotTransferNFTsListedForSale);
+            assert_noop!(ok, Error::<Test>::NFTIsListedForSale);
 
             // Unhappy NFT is converted to a capsule
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![0], None).unwrap();
-            <NFTs as NFTTrait>::set_converted_to_capsule(nft_id, true).unwrap();
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::Capsule).unwrap();
 
             let ok = NFTs::transfer(alice.clone(), nft_id, BOB);
-            assert_noop!(ok, Error::<Test>::CannotTransferCapsules);
+            assert_noop!(ok, Error::<Test>::NFTIsCapsule);
 
             // Unhappy NFT is in transmission
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![0], None).unwrap();
-            <NFTs as NFTTrait>::set_in_transmission(nft_id, true).unwrap();
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::InTransmission).unwrap();
 
             let ok = NFTs::transfer(alice.clone(), nft_id, BOB);
-            assert_noop!(ok, Error::<Test>::CannotTransferNFTsInTransmission);
+            assert_noop!(ok, Error::<Test>::NFTIsInTransmission);
         })
 }
 
@@ -309,17 +1147,17 @@ fn burn_unhappy() {
 
             // Unhappy listed for sale
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![1], Some(vec![2])).unwrap();
-            <NFTs as NFTTrait>::set_listed_for_sale(nft_id, true).unwrap();
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::ListedForSale).unwrap();
 
             let ok = NFTs::burn(alice.clone(), nft_id);
-            assert_noop!(ok, Error::<Test>::CannotBurnNFTsListedForSale);
+            assert_noop!(ok, Error::<Test>::NFTIsListedForSale);
 
             // Unhappy converted to capsule
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![1], Some(vec![2])).unwrap();
-            <NFTs as NFTTrait>::set_converted_to_capsule(nft_id, true).unwrap();
+            <NFTs as NFTTrait>::lock(nft_id, NFTLockReason::Capsule).unwrap();
 
             let ok = NFTs::burn(alice.clone(), nft_id);
-            assert_noop!(ok, Error::<Test>::CannotBurnCapsules);
+            assert_noop!(ok, Error::<Test>::NFTIsCapsule);
         })
 }
 
@@ -333,11 +1171,12 @@ fn finish_series_happy() {
 
             // Happy path transfer
             let series_id = vec![50];
+            let bounded_series_id: SeriesIdOf<Test> = bounded(series_id.clone());
             <NFTs as NFTTrait>::create_nft(ALICE, vec![1], Some(series_id.clone())).unwrap();
-            assert_eq!(NFTs::series(&series_id).unwrap().draft, true);
+            assert_eq!(NFTs::series(&bounded_series_id).unwrap().draft, true);
 
             assert_ok!(NFTs::finish_series(alice.clone(), series_id.clone()));
-            assert_eq!(NFTs::series(&series_id).unwrap().draft, false);
+            assert_eq!(NFTs::series(&bounded_series_id).unwrap().draft, false);
         })
 }
 
@@ -388,3 +1227,66 @@ fn set_nft_mint_fee_unhappy() {
             assert_noop!(ok, BadOrigin);
         })
 }
+
+#[test]
+fn owner_index_is_kept_in_sync() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let series_id = vec![50];
+            let nft_1 =
+                <NFTs as NFTTrait>::create_nft(ALICE, vec![1], Some(series_id.clone())).unwrap();
+            let nft_2 =
+                <NFTs as NFTTrait>::create_nft(ALICE, vec![1], Some(series_id.clone())).unwrap();
+            assert_eq!(NFTs::nfts_by_owner(&ALICE, None, 10), vec![nft_1, nft_2]);
+            assert_eq!(
+                NFTs::nfts_by_series(&series_id, None, 10),
+                vec![nft_1, nft_2]
+            );
+
+            // Transfer
+            assert_ok!(NFTs::finish_series(origin(ALICE), series_id.clone()));
+            assert_ok!(NFTs::transfer(origin(ALICE), nft_1, BOB));
+            assert_eq!(NFTs::nfts_by_owner(&ALICE, None, 10), vec![nft_2]);
+            assert_eq!(NFTs::nfts_by_owner(&BOB, None, 10), vec![nft_1]);
+
+            // Burn
+            assert_ok!(NFTs::burn(origin(BOB), nft_1));
+            assert!(NFTs::nfts_by_owner(&BOB, None, 10).is_empty());
+            assert_eq!(NFTs::nfts_by_series(&series_id, None, 10), vec![nft_2]);
+        })
+}
+
+#[test]
+fn nfts_by_owner_pagination() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            for _ in 0..5 {
+                <NFTs as NFTTrait>::create_nft(ALICE, vec![1], None).unwrap();
+            }
+
+            assert_eq!(NFTs::nfts_by_owner(&ALICE, None, 2), vec![0, 1]);
+            assert_eq!(NFTs::nfts_by_owner(&ALICE, Some(1), 2), vec![2, 3]);
+            assert_eq!(NFTs::nfts_by_owner(&ALICE, Some(3), 2), vec![4]);
+            assert!(NFTs::nfts_by_owner(&ALICE, Some(4), 2).is_empty());
+            assert!(NFTs::nfts_by_owner(&BOB, None, 2).is_empty());
+        })
+}
+
+#[test]
+fn nfts_by_owner_pagination_follows_id_order() {
+    ExtBuilder::default().build().execute_with(|| {
+        for id in [256, 1, 65_536, 2] {
+            NftsByOwner::<Test>::insert(ALICE, index_key(id), ());
+        }
+
+        assert_eq!(NFTs::nfts_by_owner(&ALICE, None, 3), vec![1, 2, 256]);
+        assert_eq!(NFTs::nfts_by_owner(&ALICE, Some(2), 3), vec![256, 65_536]);
+        // The page can start after an NFT that is not in the index
+        assert_eq!(NFTs::nfts_by_owner(&ALICE, Some(100), 1), vec![256]);
+        assert!(NFTs::nfts_by_owner(&ALICE, Some(65_536), 3).is_empty());
+    })
+}
@@ -1,6 +1,7 @@
 use super::mock::*;
 use crate::{GenesisConfig, NFTData};
 use frame_support::traits::GenesisBuild;
+use sp_runtime::Permill;
 
 #[test]
 fn register_nfts() {
@@ -10,10 +11,12 @@ fn register_nfts() {
 
     let nft_id = 100;
     let mint_fee = 10;
-    let data = NFTData::new_default(ALICE, vec![1], vec![48]);
+    let royalty = Permill::from_percent(10);
+    let mut data = NFTData::new_default(ALICE, bounded(vec![1]), bounded(vec![48]));
+    data.royalty = royalty;
 
     GenesisConfig::<Test> {
-        nfts: vec![(nft_id, data.clone())],
+        nfts: vec![(nft_id, ALICE, ALICE, vec![1], vec![48], royalty)],
         series: vec![],
         nft_mint_fee: mint_fee,
     }
@@ -26,5 +29,7 @@ fn register_nfts() {
         assert_eq!(NFTs::series_id_generator(), 0);
         assert_eq!(NFTs::data(nft_id), Some(data));
         assert_eq!(NFTs::nft_mint_fee(), mint_fee);
+        assert_eq!(NFTs::nfts_by_owner(&ALICE, None, 10), vec![nft_id]);
+        assert_eq!(NFTs::nfts_by_series(&vec![48], None, 10), vec![nft_id]);
     });
 }
@@ -1,90 +1,272 @@
-/* use super::mock::*;
-use crate::migrations::v5::v5;
-use crate::migrations::v6::v6;
-use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+use super::mock::*;
+use crate::migrations::{v2::v1, v4::v3, v5::v4};
+use crate::{Data, NftsByOwner, NftsBySeries, SeriesIdOf};
+use codec::Encode;
+use frame_support::storage::migration::{have_storage_value, put_storage_value};
+use frame_support::storage::unhashed;
+use frame_support::traits::{Get, OnRuntimeUpgrade, StorageVersion};
+use frame_support::{Blake2_128Concat, StorageHasher};
+use sp_runtime::Permill;
+use ternoa_primitives::nfts::{NFTData, NFTId, NFTLockReason, NFTSeriesDetails};
 
-mod version_6 {
+fn insert_v3_nft(id: NFTId, data: v3::NFTData<u64>) {
+    unhashed::put(&Data::<Test>::hashed_key_for(id), &data);
+}
+
+fn v3_nft(owner: u64, series_id: Vec<u8>) -> v3::NFTData<u64> {
+    v3::NFTData {
+        owner,
+        creator: owner,
+        ipfs_reference: vec![48],
+        series_id,
+        listed_for_sale: false,
+        in_transmission: false,
+        converted_to_capsule: false,
+        viewer: None,
+        royalty: Permill::zero(),
+    }
+}
+
+mod version_2 {
     use super::*;
 
-    fn create_nft(owner: u64, id: v5::NFTId, uri: Vec<u8>, series_id: v5::NFTSeriesId) {
-        v5::insert_nft::<Test>(owner, id, uri, series_id);
-        if series_id != 0 {
-            let nfts = Vec::new();
-            let details = v5::NFTSeriesDetails { owner, nfts };
-            v5::insert_series::<Test>(series_id, details)
-        }
+    fn insert_old_nft(id: NFTId, owner: u64, creator: u64, listed_for_sale: bool) {
+        let data = v1::NFTData {
+            owner,
+            creator,
+            ipfs_reference: vec![48],
+            series_id: vec![49],
+            listed_for_sale,
+            in_transmission: false,
+            converted_to_capsule: false,
+            viewer: None,
+        };
+        unhashed::put(&Data::<Test>::hashed_key_for(id), &data);
     }
 
-    fn check_nft(
-        owner: u64,
-        id: v6::NFTId,
-        uri: Vec<u8>,
-        series_id: v6::NFTSeriesId,
-        map: &v6::StorageNFTs<u64>,
-    ) {
-        assert_eq!(map.get(&id).unwrap().owner, owner);
-        assert_eq!(map.get(&id).unwrap().ipfs_reference, uri);
-        assert_eq!(map.get(&id).unwrap().series_id, series_id);
-        assert_eq!(map.get(&id).unwrap().locked, false);
+    #[test]
+    fn upgrade_from_v1_to_v2() {
+        ExtBuilder::default().build().execute_with(|| {
+            insert_old_nft(0, ALICE, ALICE, false);
+            insert_old_nft(1, BOB, ALICE, true);
+
+            StorageVersion::put::<NFTs>(&StorageVersion::new(1));
+            let weight = <NFTs as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<NFTs>(), StorageVersion::new(7));
+
+            let mut expected = NFTData::new_default(ALICE, bounded(vec![48]), bounded(vec![49]));
+            assert_eq!(NFTs::data(0), Some(expected.clone()));
+
+            expected.owner = BOB;
+            expected.lock = Some(NFTLockReason::ListedForSale);
+            assert_eq!(NFTs::data(1), Some(expected));
+            assert_eq!(NFTs::data(1).unwrap().royalty, Permill::zero());
+        })
     }
+}
+
+mod version_3 {
+    use super::*;
+
+    #[test]
+    fn upgrade_from_v2_to_v3() {
+        ExtBuilder::default().build().execute_with(|| {
+            insert_v3_nft(0, v3_nft(ALICE, vec![49]));
+            insert_v3_nft(1, v3_nft(ALICE, vec![49]));
+            insert_v3_nft(2, v3_nft(BOB, vec![50]));
+            insert_v3_nft(256, v3_nft(ALICE, vec![49]));
+
+            StorageVersion::put::<NFTs>(&StorageVersion::new(2));
+            let weight = <NFTs as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<NFTs>(), StorageVersion::new(7));
 
-    fn check_series(owner: u64, id: v6::NFTSeriesId, map: &v6::StorageSeries<u64>) {
-        assert_eq!(map.get(&id).unwrap().owner, owner);
-        assert_eq!(map.get(&id).unwrap().draft, false);
+            // The indexes are read back in id order
+            assert_eq!(NFTs::nfts_by_owner(&ALICE, None, 10), vec![0, 1, 256]);
+            assert_eq!(NFTs::nfts_by_owner(&BOB, None, 10), vec![2]);
+            assert_eq!(NFTs::nfts_by_series(&vec![49], None, 10), vec![0, 1, 256]);
+            assert_eq!(NFTs::nfts_by_series(&vec![49], Some(0), 1), vec![1]);
+            assert_eq!(NFTs::nfts_by_series(&vec![50], None, 10), vec![2]);
+            assert_eq!(NftsByOwner::<Test>::iter().count(), 4);
+            assert_eq!(NftsBySeries::<Test>::iter().count(), 4);
+        })
     }
+}
+
+mod version_4 {
+    use super::*;
+
+    #[test]
+    fn upgrade_from_v3_to_v4() {
+        ExtBuilder::default().build().execute_with(|| {
+            insert_v3_nft(0, v3_nft(ALICE, vec![49]));
+
+            let mut capsule = v3_nft(ALICE, vec![49]);
+            capsule.converted_to_capsule = true;
+            capsule.in_transmission = true;
+            insert_v3_nft(1, capsule);
+
+            let mut listed = v3_nft(ALICE, vec![49]);
+            listed.listed_for_sale = true;
+            insert_v3_nft(2, listed);
+
+            let mut lent = v3_nft(ALICE, vec![49]);
+            lent.viewer = Some(BOB);
+            lent.royalty = Permill::from_percent(10);
+            insert_v3_nft(3, lent);
+
+            StorageVersion::put::<NFTs>(&StorageVersion::new(3));
+            let weight = <NFTs as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<NFTs>(), StorageVersion::new(7));
+
+            let expected = NFTData::new_default(ALICE, bounded(vec![48]), bounded(vec![49]));
+            assert_eq!(NFTs::data(0), Some(expected.clone()));
+            assert_eq!(NFTs::data(1).unwrap().lock, Some(NFTLockReason::Capsule));
+            assert_eq!(
+                NFTs::data(2).unwrap().lock,
+                Some(NFTLockReason::ListedForSale)
+            );
+
+            // Lent NFTs are given back to their owner by the V6 migration
+            let lent = NFTs::data(3).unwrap();
+            assert_eq!(lent.lock, None);
+            assert_eq!(lent.viewer, None);
+            assert_eq!(lent.royalty, Permill::from_percent(10));
+        })
+    }
+}
+
+mod version_5 {
+    use super::*;
+
+    fn insert_v4_nft(id: NFTId, ipfs_reference: Vec<u8>, series_id: Vec<u8>) {
+        let data = v4::NFTData {
+            owner: ALICE,
+            creator: ALICE,
+            ipfs_reference,
+            series_id,
+            lock: None,
+            viewer: None,
+            royalty: Permill::zero(),
+        };
+        unhashed::put(&Data::<Test>::hashed_key_for(id), &data);
+    }
+
+    fn insert_v4_series(series_id: Vec<u8>) {
+        let hash = Blake2_128Concat::hash(&series_id.encode());
+        let details = NFTSeriesDetails::new(ALICE, true);
+        put_storage_value(b"NFTs", b"Series", &hash, details);
+    }
+
+    #[test]
+    fn upgrade_from_v4_to_v5() {
+        ExtBuilder::default().build().execute_with(|| {
+            insert_v4_nft(0, vec![48], vec![49]);
+            insert_v4_nft(1, vec![48; 5], vec![50; 5]);
+            insert_v4_series(vec![49]);
+            insert_v4_series(vec![50; 5]);
+
+            StorageVersion::put::<NFTs>(&StorageVersion::new(4));
+            let weight = <NFTs as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<NFTs>(), StorageVersion::new(7));
+
+            let expected = NFTData::new_default(ALICE, bounded(vec![48]), bounded(vec![49]));
+            assert_eq!(NFTs::data(0), Some(expected));
+
+            let longest = NFTs::data(1).unwrap();
+            assert_eq!(longest.ipfs_reference, vec![48; 5]);
+            assert_eq!(longest.series_id, vec![50; 5]);
+
+            let series_id: SeriesIdOf<Test> = bounded(vec![50; 5]);
+            assert_eq!(
+                NFTs::series(&series_id),
+                Some(NFTSeriesDetails::new(ALICE, true))
+            );
+            assert_eq!(NFTs::nfts_by_series(&vec![49], None, 10), vec![0]);
+            assert_eq!(NFTs::nfts_by_series(&vec![50; 5], None, 10), vec![1]);
+        })
+    }
+
+    #[test]
+    fn upgrade_from_v4_to_v5_is_aborted_when_something_does_not_fit() {
+        ExtBuilder::default().build().execute_with(|| {
+            // MaxIpfsLen and MaxSeriesIdLen are 5
+            insert_v4_nft(0, vec![48], vec![49]);
+            insert_v4_nft(1, vec![48; 7], vec![49]);
+            insert_v4_series(vec![49]);
+            insert_v4_series(vec![50; 7]);
+
+            StorageVersion::put::<NFTs>(&StorageVersion::new(4));
+            let weight = <NFTs as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<NFTs>(), StorageVersion::new(4));
+
+            let key = Data::<Test>::hashed_key_for(1);
+            let old: v4::NFTData<u64> = unhashed::get(&key).unwrap();
+            assert_eq!(old.ipfs_reference, vec![48; 7]);
+            let hash = Blake2_128Concat::hash(&vec![50u8; 7].encode());
+            assert!(have_storage_value(b"NFTs", b"Series", &hash));
+        })
+    }
+}
+
+mod version_6 {
+    use super::*;
 
     #[test]
     fn upgrade_from_v5_to_v6() {
         ExtBuilder::default().build().execute_with(|| {
-            create_nft(ALICE, 0, vec![48], 1);
-            create_nft(ALICE, 1, vec![48], 2);
-            create_nft(ALICE, 2, vec![48], 0);
-            create_nft(BOB, 3, vec![48], 0);
-            create_nft(ALICE, 4, vec![48], 1);
-            create_nft(BOB, 5, vec![48], 4);
-            create_nft(ALICE, 6, vec![48], 0);
-            create_nft(ALICE, 7, vec![48], 0);
+            let nft = NFTData::new_default(ALICE, bounded(vec![48]), bounded(vec![49]));
+            Data::<Test>::insert(0, nft.clone());
+
+            let mut lent = nft.clone();
+            lent.lock = Some(NFTLockReason::Rented);
+            lent.viewer = Some(BOB);
+            Data::<Test>::insert(1, lent);
+
+            let mut listed = nft.clone();
+            listed.lock = Some(NFTLockReason::ListedForSale);
+            Data::<Test>::insert(2, listed.clone());
 
             StorageVersion::put::<NFTs>(&StorageVersion::new(5));
             let weight = <NFTs as OnRuntimeUpgrade>::on_runtime_upgrade();
             assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<NFTs>(), StorageVersion::new(7));
 
-            let new_nfts = v6::get_nfts::<Test>();
-            let new_series = v6::get_series::<Test>();
-            let nft_mint_fee = v6::get_nft_mint_fee::<Test>();
-
-            // Check NFTs
-            assert_eq!(new_nfts.len(), 8);
-            check_nft(ALICE, 0, vec![48], "1".into(), &new_nfts);
-            check_nft(ALICE, 1, vec![48], "2".into(), &new_nfts);
-            check_nft(ALICE, 2, vec![48], "0".into(), &new_nfts);
-            check_nft(BOB, 3, vec![48], "3".into(), &new_nfts);
-            check_nft(ALICE, 4, vec![48], "1".into(), &new_nfts);
-            check_nft(BOB, 5, vec![48], "4".into(), &new_nfts);
-            check_nft(ALICE, 6, vec![48], "5".into(), &new_nfts);
-            check_nft(ALICE, 7, vec![48], "6".into(), &new_nfts);
-
-            // Check Series
-            assert_eq!(new_series.len(), 7);
-            check_series(ALICE, "0".into(), &new_series);
-            check_series(ALICE, "1".into(), &new_series);
-            check_series(ALICE, "2".into(), &new_series);
-            check_series(BOB, "3".into(), &new_series);
-            check_series(BOB, "4".into(), &new_series);
-            check_series(ALICE, "5".into(), &new_series);
-            check_series(ALICE, "6".into(), &new_series);
-
-            // Check NFT mint fee
-            assert_eq!(nft_mint_fee, 10000000000000000000);
+            assert_eq!(NFTs::data(0), Some(nft.clone()));
+            assert_eq!(NFTs::data(1), Some(nft));
+            assert_eq!(NFTs::data(2), Some(listed));
         })
     }
 }
 
-#[test]
-fn upgrade_from_latest_to_latest() {
-    ExtBuilder::default().build().execute_with(|| {
-        let weight = <NFTs as OnRuntimeUpgrade>::on_runtime_upgrade();
-        assert_eq!(weight, 0);
-    })
+mod version_7 {
+    use super::*;
+
+    #[test]
+    fn upgrade_from_v6_to_v7() {
+        ExtBuilder::new_build(vec![(ALICE, 100), (BOB, 1)]).execute_with(|| {
+            UseStorageDeposits::set(&true);
+
+            let nft = NFTData::new_default(ALICE, bounded(vec![48]), bounded(vec![49]));
+            Data::<Test>::insert(0, nft.clone());
+            let poor = NFTData::new_default(BOB, bounded(vec![48]), bounded(vec![49]));
+            Data::<Test>::insert(1, poor);
+
+            StorageVersion::put::<NFTs>(&StorageVersion::new(6));
+            let weight = <NFTs as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<NFTs>(), StorageVersion::new(7));
+
+            // Owners that cannot afford the deposit keep their NFTs without one.
+            let deposit = DepositBase::get() + DepositPerByte::get();
+            assert_eq!(NFTs::deposits(0), Some((ALICE, deposit)));
+            assert_eq!(Balances::reserved_balance(ALICE), deposit);
+            assert_eq!(NFTs::deposits(1), None);
+            assert_eq!(NFTs::data(1).unwrap().owner, BOB);
+        })
+    }
 }
- */
@@ -1,10 +1,18 @@
 use crate::{self as ternoa_nfts, Config, NegativeImbalanceOf};
+use frame_support::dispatch::DispatchError;
 use frame_support::parameter_types;
-use frame_support::traits::{ConstU32, Contains, Currency, GenesisBuild};
+use frame_support::traits::{ConstU32, Contains, Currency, GenesisBuild, Get, Hooks};
+use frame_support::weights::constants::RocksDbWeight;
+use frame_support::weights::Weight;
+use frame_support::BoundedVec;
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
-use ternoa_primitives::nfts::NFTId;
+use sp_runtime::Permill;
+use std::convert::TryInto;
+use ternoa_common::traits::{NFTReleaseHandler, ReferenceValidator};
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -47,7 +55,7 @@ impl frame_system::Config for Test {
     type BaseCallFilter = TestBaseCallFilter;
     type BlockWeights = ();
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Origin = Origin;
     type Index = u64;
     type BlockNumber = u64;
@@ -90,6 +98,14 @@ impl pallet_balances::Config for Test {
 parameter_types! {
     pub const MinIpfsLen: u16 = 1;
     pub const MaxIpfsLen: u16 = 5;
+    pub const MaxSeriesIdLen: u16 = 5;
+    pub const MaxBatchSize: u32 = 5;
+    pub static BatchMintDiscount: Permill = Permill::zero();
+    pub static UseStorageDeposits: bool = false;
+    pub const DepositBase: Balance = 5;
+    pub const DepositPerByte: Balance = 1;
+    pub static DepositFollowsOwner: bool = true;
+    pub const MaxRentalsPerBlock: u32 = 2;
 }
 
 impl Config for Test {
@@ -99,6 +115,17 @@ impl Config for Test {
     type FeesCollector = MockFeeCollector;
     type MinIpfsLen = MinIpfsLen;
     type MaxIpfsLen = MaxIpfsLen;
+    type IPFSReferenceValidator = MockReferenceValidator;
+    type MaxSeriesIdLen = MaxSeriesIdLen;
+    type MaxBatchSize = MaxBatchSize;
+    type BatchMintDiscount = BatchMintDiscount;
+    type UseStorageDeposits = UseStorageDeposits;
+    type DepositBase = DepositBase;
+    type DepositPerByte = DepositPerByte;
+    type DepositFollowsOwner = DepositFollowsOwner;
+    type ForceOrigin = EnsureRoot<u64>;
+    type NFTReleaseHandler = MockReleaseHandler;
+    type MaxRentalsPerBlock = MaxRentalsPerBlock;
 }
 
 pub struct MockFeeCollector;
@@ -108,6 +135,41 @@ impl frame_support::traits::OnUnbalanced<NegativeImbalanceOf<Test>> for MockFeeC
     }
 }
 
+/// Reference that `MockReferenceValidator` refuses.
+pub const INVALID_REFERENCE: &[u8] = b"bad";
+
+pub struct MockReferenceValidator;
+impl ReferenceValidator for MockReferenceValidator {
+    fn is_valid(reference: &[u8]) -> bool {
+        reference != INVALID_REFERENCE
+    }
+
+    fn benchmark_reference() -> Vec<u8> {
+        vec![1]
+    }
+}
+
+parameter_types! {
+    pub static ReleasedNFTs: Vec<(NFTId, NFTLockReason)> = vec![];
+}
+
+pub const RELEASE_WEIGHT: Weight = 1_000;
+
+/// Keep track of the NFTs that the force origin took back.
+pub struct MockReleaseHandler;
+impl NFTReleaseHandler for MockReleaseHandler {
+    fn release_nft(id: NFTId, reason: NFTLockReason) -> Result<Weight, DispatchError> {
+        let mut released = ReleasedNFTs::get();
+        released.push((id, reason));
+        ReleasedNFTs::set(&released);
+        Ok(RELEASE_WEIGHT)
+    }
+
+    fn max_release_weight() -> Weight {
+        RELEASE_WEIGHT
+    }
+}
+
 // Do not use the `0` account id since this would be the default value
 // for our account id. This would mess with some tests.
 pub const ALICE: u64 = 1;
@@ -118,6 +180,11 @@ pub const COLLECTOR: u64 = 99;
 pub const NFT_MINT_FEE: Balance = 10;
 pub const INVALID_NFT_ID: NFTId = 1001;
 
+/// Turn a vector into one of the bounded vectors that are stored on chain.
+pub fn bounded<S: Get<u32>>(value: Vec<u8>) -> BoundedVec<u8, S> {
+    value.try_into().unwrap()
+}
+
 pub struct ExtBuilder {
     balances: Vec<(u64, Balance)>,
 }
@@ -179,3 +246,13 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
     t.into()
 }
+
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        NFTs::on_finalize(System::block_number());
+        System::on_finalize(System::block_number());
+        System::set_block_number(System::block_number() + 1);
+        System::on_initialize(System::block_number());
+        NFTs::on_initialize(System::block_number());
+    }
+}
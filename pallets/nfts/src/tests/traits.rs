@@ -3,20 +3,23 @@ use crate::tests::mock;
 use crate::Error;
 use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
+use sp_runtime::Permill;
 use ternoa_common::traits::NFTTrait;
+use ternoa_primitives::nfts::NFTLockReason;
 
-/* #[test]
+#[test]
 fn lock_happy() {
     ExtBuilder::default()
         .caps(vec![(ALICE, 100)])
         .build()
         .execute_with(|| {
-            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
-
             // Happy path
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![1], None).unwrap();
-            assert_ok!(NFTs::lock(nft_id));
-            assert_eq!(NFTs::data(nft_id).unwrap().locked, true);
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::ListedForSale));
+            assert_eq!(
+                NFTs::data(nft_id).unwrap().lock,
+                Some(NFTLockReason::ListedForSale)
+            );
         })
 }
 
@@ -26,15 +29,17 @@ fn lock_unhappy() {
         .caps(vec![(ALICE, 100)])
         .build()
         .execute_with(|| {
-            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
-
             // Unhappy already locked
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![1], None).unwrap();
-            assert_ok!(NFTs::lock(nft_id));
-            assert_noop!(NFTs::lock(nft_id), Error::<Test>::Locked);
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::Capsule));
+            let ok = NFTs::lock(nft_id, NFTLockReason::ListedForSale);
+            assert_noop!(ok, Error::<Test>::NFTIsCapsule);
+            let ok = NFTs::lock(nft_id, NFTLockReason::Capsule);
+            assert_noop!(ok, Error::<Test>::NFTIsCapsule);
 
             // Unhappy invalid NFT Id
-            assert_noop!(NFTs::lock(1001), Error::<Test>::UnknownNFT);
+            let ok = NFTs::lock(1001, NFTLockReason::Capsule);
+            assert_noop!(ok, Error::<Test>::NFTNotFound);
         })
 }
 
@@ -44,50 +49,58 @@ fn unlock_happy() {
         .caps(vec![(ALICE, 100)])
         .build()
         .execute_with(|| {
-            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
-
             // Happy path
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![1], None).unwrap();
-            assert_ok!(NFTs::lock(nft_id));
-            assert_eq!(NFTs::unlock(nft_id), true);
-            assert_eq!(NFTs::data(nft_id).unwrap().locked, false);
-
-            // Happy double unlock
-            assert_eq!(NFTs::unlock(nft_id), true);
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::InTransmission));
+            assert_ok!(NFTs::unlock(nft_id, NFTLockReason::InTransmission));
+            assert_eq!(NFTs::data(nft_id).unwrap().lock, None);
         })
 }
 
 #[test]
 fn unlock_unhappy() {
-    ExtBuilder::default().build().execute_with(|| {
-        // Unhappy invalid NFT Id
-        assert_eq!(NFTs::unlock(1001), false);
-    })
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 100)])
+        .build()
+        .execute_with(|| {
+            // Unhappy not locked
+            let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![1], None).unwrap();
+            let ok = NFTs::unlock(nft_id, NFTLockReason::Capsule);
+            assert_noop!(ok, Error::<Test>::NFTIsNotLocked);
+
+            // Unhappy locked for another reason
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::ListedForSale));
+            let ok = NFTs::unlock(nft_id, NFTLockReason::Capsule);
+            assert_noop!(ok, Error::<Test>::NFTIsNotLocked);
+
+            // Unhappy invalid NFT Id
+            let ok = NFTs::unlock(1001, NFTLockReason::Capsule);
+            assert_noop!(ok, Error::<Test>::NFTNotFound);
+        })
 }
 
 #[test]
-fn locked_happy() {
+fn is_locked_happy() {
     ExtBuilder::default()
         .caps(vec![(ALICE, 100)])
         .build()
         .execute_with(|| {
-            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
-
             // Happy path
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![1], None).unwrap();
-            assert_eq!(NFTs::locked(nft_id), Some(false));
-            assert_ok!(NFTs::lock(nft_id));
-            assert_eq!(NFTs::locked(nft_id), Some(true));
+            assert_eq!(NFTs::is_locked(nft_id, NFTLockReason::Capsule), Some(false));
+            assert_ok!(NFTs::lock(nft_id, NFTLockReason::Capsule));
+            assert_eq!(NFTs::is_locked(nft_id, NFTLockReason::Capsule), Some(true));
+            assert_eq!(NFTs::is_locked(nft_id, NFTLockReason::Rented), Some(false));
         })
 }
 
 #[test]
-fn locked_unhappy() {
+fn is_locked_unhappy() {
     ExtBuilder::default().build().execute_with(|| {
         // Unhappy invalid NFT Id
-        assert_eq!(NFTs::locked(1001), None);
+        assert_eq!(NFTs::is_locked(1001, NFTLockReason::Capsule), None);
     })
-} */
+}
 
 #[test]
 fn set_owner_happy() {
@@ -99,6 +112,8 @@ fn set_owner_happy() {
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![1], None).unwrap();
             assert_ok!(NFTs::set_owner(nft_id, &BOB));
             assert_eq!(NFTs::data(nft_id).unwrap().owner, BOB);
+            assert!(NFTs::nfts_by_owner(&ALICE, None, 10).is_empty());
+            assert_eq!(NFTs::nfts_by_owner(&BOB, None, 10), vec![nft_id]);
         })
 }
 
@@ -113,6 +128,29 @@ fn set_owner_unhappy() {
         })
 }
 
+#[test]
+fn set_royalty_happy() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 100)])
+        .build()
+        .execute_with(|| {
+            // Happy path
+            let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![1], None).unwrap();
+            let royalty = Permill::from_percent(10);
+            assert_ok!(NFTs::set_royalty(nft_id, royalty));
+            assert_eq!(NFTs::data(nft_id).unwrap().royalty, royalty);
+        })
+}
+
+#[test]
+fn set_royalty_unhappy() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Unhappy Unknown NFT
+        let ok = NFTs::set_royalty(1000, Permill::from_percent(10));
+        assert_noop!(ok, Error::<Test>::NFTNotFound);
+    })
+}
+
 #[test]
 fn owner_happy() {
     ExtBuilder::default()
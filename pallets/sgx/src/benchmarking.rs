@@ -1,12 +1,15 @@
 use crate::{
-    BalanceOf, Call, Cluster, ClusterId, ClusterIdGenerator, ClusterIndex, ClusterRegistry, Config,
-    Enclave, EnclaveId, EnclaveIdGenerator, EnclaveIndex, EnclaveRegistry, Pallet,
+    BalanceOf, Call, Cluster, ClusterId, ClusterIdGenerator, ClusterIndex, ClusterOf,
+    ClusterRegistry, Config, Enclave, EnclaveId, EnclaveIdGenerator, EnclaveIndex, EnclaveOf,
+    EnclaveRegistry, Pallet,
 };
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
 use frame_support::traits::Currency;
 use frame_system::RawOrigin;
 use sp_runtime::traits::{Bounded, StaticLookup};
+use sp_std::convert::TryInto;
 use sp_std::prelude::*;
+use ternoa_common::traits::ReferenceValidator;
 use ternoa_primitives::TextFormat;
 
 use crate::Pallet as Sgx;
@@ -14,9 +17,9 @@ use crate::Pallet as Sgx;
 benchmarks! {
     register_enclave {
         let alice: T::AccountId = whitelisted_caller();
-        let uri: TextFormat = vec![1];
+        let uri: TextFormat = T::UriValidator::benchmark_reference();
         let enclave_id: EnclaveId = 0;
-        let enclave = Enclave::new(uri.clone());
+        let enclave: EnclaveOf<T> = Enclave::new(uri.clone().try_into().unwrap());
 
         T::Currency::make_free_balance_be(&alice, BalanceOf::<T>::max_value());
     }: _(RawOrigin::Signed(alice.clone().into()), uri.clone())
@@ -30,7 +33,7 @@ benchmarks! {
 
     assign_enclave {
         let alice: T::AccountId = whitelisted_caller();
-        let uri: TextFormat = vec![1];
+        let uri: TextFormat = T::UriValidator::benchmark_reference();
         let enclave_id: EnclaveId = 0;
         let cluster_id: ClusterId = 0;
 
@@ -46,7 +49,7 @@ benchmarks! {
 
     unassign_enclave {
         let alice: T::AccountId = whitelisted_caller();
-        let uri: TextFormat = vec![1];
+        let uri: TextFormat = T::UriValidator::benchmark_reference();
         let enclave_id: EnclaveId = 0;
         let cluster_id: ClusterId = 0;
         let empty: Vec<EnclaveId> = vec![];
@@ -64,9 +67,9 @@ benchmarks! {
 
     update_enclave {
         let alice: T::AccountId = whitelisted_caller();
-        let uri: TextFormat = vec![1];
+        let uri: TextFormat = T::UriValidator::benchmark_reference();
         let enclave_id: EnclaveId = 0;
-        let new_uri: TextFormat = vec![0, 1, 2];
+        let new_uri: TextFormat = T::UriValidator::benchmark_reference();
 
         T::Currency::make_free_balance_be(&alice, BalanceOf::<T>::max_value());
 
@@ -80,7 +83,7 @@ benchmarks! {
         let alice: T::AccountId = whitelisted_caller();
         let bob: T::AccountId = account("bob", 0, 0);
         let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
-        let uri: TextFormat = vec![1];
+        let uri: TextFormat = T::UriValidator::benchmark_reference();
         T::Currency::make_free_balance_be(&alice, BalanceOf::<T>::max_value());
 
         drop(Sgx::<T>::register_enclave(RawOrigin::Signed(alice.clone()).into(), uri.clone()));
@@ -91,7 +94,7 @@ benchmarks! {
     }
 
     create_cluster {
-        let cluster = Cluster::new(Default::default());
+        let cluster: ClusterOf<T> = Cluster::new(Default::default());
         let cluster_id: ClusterId = 0;
     }: _(RawOrigin::Root)
     verify {
@@ -102,7 +105,6 @@ benchmarks! {
     }
 
     remove_cluster {
-        let cluster = Cluster::new(Default::default());
         let cluster_id: ClusterId = 0;
 
         drop(Sgx::<T>::create_cluster(RawOrigin::Root.into()));
@@ -7,6 +7,7 @@ mod benchmarking;
 mod tests;
 
 mod default_weights;
+mod migrations;
 mod types;
 
 use frame_support::dispatch::DispatchResultWithPostInfo;
@@ -17,7 +18,7 @@ use default_weights::WeightInfo;
 use frame_support::traits::StorageVersion;
 
 /// The current storage version.
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -28,11 +29,15 @@ pub mod pallet {
     use frame_support::transactional;
     use frame_system::pallet_prelude::*;
     use sp_runtime::traits::StaticLookup;
-    use ternoa_common::helpers::check_bounds;
+    use sp_std::convert::TryInto;
+    use ternoa_common::helpers::{bounded_text, U16ToU32};
+    use ternoa_common::traits::ReferenceValidator;
     use ternoa_primitives::TextFormat;
 
     pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type EnclaveOf<T> = Enclave<U16ToU32<<T as Config>::MaxUriLen>>;
+    pub type ClusterOf<T> = Cluster<<T as Config>::ClusterSize>;
     pub(crate) type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
         <T as frame_system::Config>::AccountId,
     >>::NegativeImbalance;
@@ -66,16 +71,22 @@ pub mod pallet {
         /// Max Uri len
         #[pallet::constant]
         type MaxUriLen: Get<u16>;
+
+        /// Check that enclave URIs are well formed
+        type UriValidator: ReferenceValidator;
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -89,12 +100,12 @@ pub mod pallet {
             api_uri: TextFormat,
         ) -> DispatchResultWithPostInfo {
             let account = ensure_signed(origin)?;
-
-            check_bounds(
-                api_uri.len(),
+            let bounded_api_uri = bounded_text(
+                api_uri.clone(),
                 (T::MinUriLen::get(), Error::<T>::UriTooShort),
                 (T::MaxUriLen::get(), Error::<T>::UriTooLong),
             )?;
+            ensure!(T::UriValidator::is_valid(&api_uri), Error::<T>::InvalidUri);
 
             ensure!(
                 !EnclaveIndex::<T>::contains_key(&account),
@@ -110,7 +121,7 @@ pub mod pallet {
             )?;
             T::FeesCollector::on_unbalanced(imbalance);
 
-            let enclave = Enclave::new(api_uri.clone());
+            let enclave: EnclaveOf<T> = Enclave::new(bounded_api_uri);
 
             EnclaveIndex::<T>::insert(account.clone(), enclave_id);
             EnclaveRegistry::<T>::insert(enclave_id, enclave);
@@ -139,11 +150,10 @@ pub mod pallet {
 
             ClusterRegistry::<T>::mutate(cluster_id, |cluster_opt| {
                 if let Some(cluster) = cluster_opt {
-                    if cluster.enclaves.len() >= T::ClusterSize::get() as usize {
-                        return Err(Error::<T>::ClusterIsAlreadyFull);
-                    }
-
-                    cluster.enclaves.push(enclave_id);
+                    cluster
+                        .enclaves
+                        .try_push(enclave_id)
+                        .map_err(|_| Error::<T>::ClusterIsAlreadyFull)?;
                     ClusterIndex::<T>::insert(enclave_id, cluster_id);
 
                     Ok(())
@@ -192,16 +202,16 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let account = ensure_signed(origin)?;
             let enclave_id = EnclaveIndex::<T>::get(&account).ok_or(Error::<T>::NotEnclaveOwner)?;
-
-            check_bounds(
-                api_uri.len(),
+            let bounded_api_uri = bounded_text(
+                api_uri.clone(),
                 (T::MinUriLen::get(), Error::<T>::UriTooShort),
                 (T::MaxUriLen::get(), Error::<T>::UriTooLong),
             )?;
+            ensure!(T::UriValidator::is_valid(&api_uri), Error::<T>::InvalidUri);
 
             EnclaveRegistry::<T>::mutate(enclave_id, |enclave| -> DispatchResult {
                 let enclave = enclave.as_mut().ok_or(Error::<T>::UnknownEnclaveId)?;
-                enclave.api_uri = api_uri.clone();
+                enclave.api_uri = bounded_api_uri;
 
                 Ok(())
             })?;
@@ -253,7 +263,7 @@ pub mod pallet {
 
             let id = ClusterIdGenerator::<T>::get();
             let new_id = id.checked_add(1).ok_or(Error::<T>::ClusterIdOverflow)?;
-            let cluster = Cluster::new(Default::default());
+            let cluster: ClusterOf<T> = Cluster::new(Default::default());
 
             ClusterRegistry::<T>::insert(id, cluster);
             ClusterIdGenerator::<T>::put(new_id);
@@ -329,6 +339,7 @@ pub mod pallet {
         PublicKeyAlreadyTiedToACluster,
         UriTooShort,
         UriTooLong,
+        InvalidUri,
         EnclaveIdOverflow,
         ClusterIdOverflow,
         ClusterIsAlreadyFull,
@@ -344,7 +355,7 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn enclave_registry)]
     pub type EnclaveRegistry<T: Config> =
-        StorageMap<_, Blake2_128Concat, EnclaveId, Enclave, OptionQuery>;
+        StorageMap<_, Blake2_128Concat, EnclaveId, EnclaveOf<T>, OptionQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn enclave_id_generator)]
@@ -361,7 +372,7 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn cluster_registry)]
     pub type ClusterRegistry<T: Config> =
-        StorageMap<_, Blake2_128Concat, ClusterId, Cluster, OptionQuery>;
+        StorageMap<_, Blake2_128Concat, ClusterId, ClusterOf<T>, OptionQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn cluster_id_generator)]
@@ -398,7 +409,8 @@ pub mod pallet {
 
             for enclave in enclaves {
                 EnclaveIndex::<T>::insert(enclave.0, enclave.1);
-                EnclaveRegistry::<T>::insert(enclave.1, Enclave { api_uri: enclave.2 });
+                let api_uri = enclave.2.try_into().expect("Enclave uri is too long");
+                EnclaveRegistry::<T>::insert(enclave.1, Enclave::new(api_uri));
             }
 
             let clusters = self.clusters.clone();
@@ -410,7 +422,8 @@ pub mod pallet {
                 for enclave_id in cluster.1.iter() {
                     ClusterIndex::<T>::insert(*enclave_id, cluster.0);
                 }
-                ClusterRegistry::<T>::insert(cluster.0, Cluster::new(cluster.1));
+                let enclaves = cluster.1.try_into().expect("Cluster is too big");
+                ClusterRegistry::<T>::insert(cluster.0, Cluster::new(enclaves));
             }
         }
     }
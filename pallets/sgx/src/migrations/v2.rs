@@ -0,0 +1,81 @@
+use crate::{
+    Cluster, ClusterIndex, ClusterRegistry, Config, Enclave, EnclaveId, EnclaveRegistry, Pallet,
+};
+use frame_support::storage::migration::storage_key_iter;
+use frame_support::traits::{Get, PalletInfoAccess};
+use frame_support::weights::Weight;
+use frame_support::Blake2_128Concat;
+use ternoa_common::helpers::{fits_bound, truncate_to_bound, U16ToU32};
+
+pub mod v1 {
+    use crate::EnclaveId;
+    use codec::{Decode, Encode};
+    use sp_runtime::RuntimeDebug;
+    use sp_std::vec::Vec;
+    use ternoa_primitives::TextFormat;
+
+    // Enclave as it was stored before its uri was bounded
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Enclave {
+        pub api_uri: TextFormat,
+    }
+
+    // Cluster as it was stored before its enclave list was bounded
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Cluster {
+        pub enclaves: Vec<EnclaveId>,
+    }
+}
+
+/// Bounds the enclave uris and the cluster enclave lists. Enclaves that do not
+/// fit in their cluster are unassigned. Nothing is changed if an uri does not
+/// fit: every offending enclave is logged and an error is returned so that the
+/// storage version stays the same.
+pub fn migrate<T: Config>() -> Result<Weight, Weight> {
+    let mut reads: u64 = 0;
+    let mut writes: u64 = 0;
+    let mut fits = true;
+
+    for (enclave_id, old) in storage_key_iter::<EnclaveId, v1::Enclave, Blake2_128Concat>(
+        Pallet::<T>::name().as_bytes(),
+        b"EnclaveRegistry",
+    ) {
+        reads += 1;
+
+        if !fits_bound::<_, U16ToU32<T::MaxUriLen>>(&old.api_uri) {
+            log::error!(target: "runtime::sgx", "Enclave {} uri does not fit", enclave_id);
+            fits = false;
+        }
+    }
+
+    if !fits {
+        return Err(T::DbWeight::get().reads(reads));
+    }
+
+    // Every uri has been checked above, so none of them gets truncated.
+    EnclaveRegistry::<T>::translate::<v1::Enclave, _>(|_, old| {
+        reads += 1;
+        writes += 1;
+
+        Some(Enclave::new(truncate_to_bound(old.api_uri)))
+    });
+
+    let limit = T::ClusterSize::get() as usize;
+    ClusterRegistry::<T>::translate::<v1::Cluster, _>(|cluster_id, mut old| {
+        reads += 1;
+        writes += 1;
+
+        if old.enclaves.len() > limit {
+            log::warn!(target: "runtime::sgx", "Cluster {} lost some enclaves", cluster_id);
+
+            for enclave_id in old.enclaves.drain(limit..) {
+                ClusterIndex::<T>::remove(enclave_id);
+                writes += 1;
+            }
+        }
+
+        Some(Cluster::new(truncate_to_bound(old.enclaves)))
+    });
+
+    Ok(T::DbWeight::get().reads_writes(reads, writes))
+}
@@ -8,6 +8,7 @@ use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
 use pallet_balances::Error as BalanceError;
 use sp_runtime::traits::BadOrigin;
+use std::convert::TryInto;
 use ternoa_primitives::TextFormat;
 
 #[test]
@@ -29,7 +30,7 @@ fn register_enclave() {
             assert_ok!(Sgx::register_enclave(alice.clone(), uri.clone()));
             assert_eq!(Balances::free_balance(ALICE), 95);
 
-            let enclave = Enclave::new(uri.clone());
+            let enclave = Enclave::new(uri.clone().try_into().unwrap());
             let enclave_id: EnclaveId = 0;
             assert!(EnclaveRegistry::<Test>::contains_key(enclave_id));
             assert_eq!(EnclaveRegistry::<Test>::get(enclave_id), Some(enclave));
@@ -50,8 +51,12 @@ fn register_enclave() {
             assert_noop!(ok, Error::<Test>::UriTooShort);
 
             // Dave should NOT be able to create an enclave if the uri is too long.
-            let ok = Sgx::register_enclave(dave, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+            let ok = Sgx::register_enclave(dave.clone(), vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
             assert_noop!(ok, Error::<Test>::UriTooLong);
+
+            // Dave should NOT be able to create an enclave if the uri is malformed.
+            let ok = Sgx::register_enclave(dave, INVALID_REFERENCE.to_vec());
+            assert_noop!(ok, Error::<Test>::InvalidUri);
         })
 }
 
@@ -142,7 +147,7 @@ fn update_enclave() {
 
             // Alice should be able to update her enclave.
             let uri: TextFormat = vec![0, 1];
-            let enclave = Enclave::new(uri.clone());
+            let enclave = Enclave::new(uri.clone().try_into().unwrap());
             assert_ok!(Sgx::update_enclave(alice.clone(), uri.clone()));
             assert_eq!(EnclaveRegistry::<Test>::get(enclave_id), Some(enclave));
 
@@ -154,6 +159,10 @@ fn update_enclave() {
             let ok = Sgx::update_enclave(alice.clone(), vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
             assert_noop!(ok, Error::<Test>::UriTooLong);
 
+            // Alice should NOT be able to update an enclave if the uri is malformed.
+            let ok = Sgx::update_enclave(alice.clone(), INVALID_REFERENCE.to_vec());
+            assert_noop!(ok, Error::<Test>::InvalidUri);
+
             // Bob should NOT be able to update his enclave if he doesn't have one.
             let ok = Sgx::update_enclave(bob.clone(), uri.clone());
             assert_noop!(ok, Error::<Test>::NotEnclaveOwner);
@@ -221,7 +230,7 @@ fn remove_cluster() {
             let bob: mock::Origin = RawOrigin::Signed(BOB).into();
             let uri: TextFormat = vec![1];
             let cluster_id: ClusterId = 0;
-            let cluster = Cluster::new(vec![0, 1]);
+            let cluster = Cluster::new(vec![0, 1].try_into().unwrap());
 
             assert_ok!(Sgx::create_cluster(RawOrigin::Root.into()));
             assert_ok!(Sgx::register_enclave(alice.clone(), uri.clone()));
@@ -0,0 +1,60 @@
+use super::mock::*;
+use crate::migrations::v2::v1;
+use crate::{ClusterIndex, ClusterRegistry, EnclaveRegistry};
+use frame_support::storage::unhashed;
+use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+mod version_2 {
+    use super::*;
+
+    #[test]
+    fn upgrade_from_v1_to_v2() {
+        ExtBuilder::default().build().execute_with(|| {
+            let enclave = v1::Enclave {
+                api_uri: vec![1; 5],
+            };
+            unhashed::put(&EnclaveRegistry::<Test>::hashed_key_for(0), &enclave);
+
+            // ClusterSize is 2 so the last enclave does not fit
+            let cluster = v1::Cluster {
+                enclaves: vec![0, 1, 2],
+            };
+            unhashed::put(&ClusterRegistry::<Test>::hashed_key_for(0), &cluster);
+            for enclave_id in 0..3 {
+                ClusterIndex::<Test>::insert(enclave_id, 0);
+            }
+
+            StorageVersion::put::<Sgx>(&StorageVersion::new(1));
+            let weight = <Sgx as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Sgx>(), StorageVersion::new(2));
+
+            let enclave = EnclaveRegistry::<Test>::get(0).unwrap();
+            assert_eq!(enclave.api_uri, vec![1; 5]);
+
+            let cluster = ClusterRegistry::<Test>::get(0).unwrap();
+            assert_eq!(cluster.enclaves, vec![0, 1]);
+            assert_eq!(ClusterIndex::<Test>::get(1), Some(0));
+            assert_eq!(ClusterIndex::<Test>::get(2), None);
+        })
+    }
+
+    #[test]
+    fn upgrade_from_v1_to_v2_is_aborted_when_an_uri_does_not_fit() {
+        ExtBuilder::default().build().execute_with(|| {
+            // MaxUriLen is 5
+            let enclave = v1::Enclave {
+                api_uri: vec![1; 7],
+            };
+            unhashed::put(&EnclaveRegistry::<Test>::hashed_key_for(0), &enclave);
+
+            StorageVersion::put::<Sgx>(&StorageVersion::new(1));
+            let weight = <Sgx as OnRuntimeUpgrade>::on_runtime_upgrade();
+            assert_ne!(weight, 0);
+            assert_eq!(StorageVersion::get::<Sgx>(), StorageVersion::new(1));
+
+            let key = EnclaveRegistry::<Test>::hashed_key_for(0);
+            assert_eq!(unhashed::get::<v1::Enclave>(&key), Some(enclave));
+        })
+    }
+}
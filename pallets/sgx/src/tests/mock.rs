@@ -1,11 +1,13 @@
 use crate::{self as ternoa_sgx, Config};
 use frame_support::traits::Contains;
+use frame_support::weights::constants::RocksDbWeight;
 use frame_support::{parameter_types, traits::ConstU32};
 use sp_core::H256;
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
 };
+use ternoa_common::traits::ReferenceValidator;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -45,7 +47,7 @@ impl frame_system::Config for Test {
     type BaseCallFilter = TestBaseCallFilter;
     type BlockWeights = ();
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Origin = Origin;
     type Index = u64;
     type BlockNumber = u64;
@@ -102,6 +104,21 @@ impl Config for Test {
     type ClusterSize = ClusterSize;
     type MinUriLen = MinUriLen;
     type MaxUriLen = MaxUriLen;
+    type UriValidator = MockReferenceValidator;
+}
+
+/// Reference that `MockReferenceValidator` refuses.
+pub const INVALID_REFERENCE: &[u8] = b"bad";
+
+pub struct MockReferenceValidator;
+impl ReferenceValidator for MockReferenceValidator {
+    fn is_valid(reference: &[u8]) -> bool {
+        reference != INVALID_REFERENCE
+    }
+
+    fn benchmark_reference() -> Vec<u8> {
+        vec![1]
+    }
 }
 
 // Do not use the `0` account id since this would be the default value
@@ -1,2 +1,3 @@
 mod dispatchables;
+mod migration;
 pub mod mock;
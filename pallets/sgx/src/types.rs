@@ -1,35 +1,61 @@
-#[cfg(feature = "std")]
-use serde::{Deserialize, Serialize};
-
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use frame_support::{BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
-use sp_std::vec::Vec;
-use ternoa_primitives::TextFormat;
 
 pub type EnclaveId = u32;
 pub type ClusterId = u32;
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct Enclave {
-    pub api_uri: TextFormat,
+#[derive(
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    RuntimeDebugNoBound,
+    Encode,
+    Decode,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(UriLengthLimit))]
+pub struct Enclave<UriLengthLimit>
+where
+    UriLengthLimit: Get<u32>,
+{
+    pub api_uri: BoundedVec<u8, UriLengthLimit>,
 }
 
-impl Enclave {
-    pub fn new(api_uri: TextFormat) -> Self {
+impl<UriLengthLimit> Enclave<UriLengthLimit>
+where
+    UriLengthLimit: Get<u32>,
+{
+    pub fn new(api_uri: BoundedVec<u8, UriLengthLimit>) -> Self {
         Self { api_uri }
     }
 }
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct Cluster {
-    pub enclaves: Vec<EnclaveId>,
+#[derive(
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    RuntimeDebugNoBound,
+    Encode,
+    Decode,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(ClusterSizeLimit))]
+pub struct Cluster<ClusterSizeLimit>
+where
+    ClusterSizeLimit: Get<u32>,
+{
+    pub enclaves: BoundedVec<EnclaveId, ClusterSizeLimit>,
 }
 
-impl Cluster {
-    pub fn new(enclaves: Vec<EnclaveId>) -> Self {
+impl<ClusterSizeLimit> Cluster<ClusterSizeLimit>
+where
+    ClusterSizeLimit: Get<u32>,
+{
+    pub fn new(enclaves: BoundedVec<EnclaveId, ClusterSizeLimit>) -> Self {
         Self { enclaves }
     }
 }
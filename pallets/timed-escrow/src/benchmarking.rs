@@ -2,51 +2,75 @@
 
 use crate::{Call, Config, Pallet};
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::assert_ok;
 use frame_system::{Pallet as SystemModule, RawOrigin};
 use sp_runtime::traits::StaticLookup;
 use sp_std::prelude::*;
 use ternoa_common::traits::NFTTrait;
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
 
 use crate::Pallet as TimedEscrow;
 
+pub fn prepare_benchmarks<T: Config>() -> NFTId {
+    let alice: T::AccountId = account("ALICE", 0, 0);
+    let nft_id = T::NFTs::benchmark_create_nft(&alice);
+
+    let series_id = T::NFTs::get_nft(nft_id).unwrap().series_id.to_vec();
+    T::NFTs::benchmark_lock_series(series_id);
+
+    nft_id
+}
+
+pub fn create_escrow<T: Config>(nft_id: NFTId) -> T::BlockNumber {
+    let alice: T::AccountId = account("ALICE", 0, 0);
+    let bob: T::AccountId = account("BOB", 0, 0);
+    let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob);
+    let at = SystemModule::<T>::block_number() + 10u32.into();
+
+    assert_ok!(TimedEscrow::<T>::create(
+        RawOrigin::Signed(alice).into(),
+        nft_id,
+        bob_lookup,
+        at
+    ));
+
+    at
+}
+
 benchmarks! {
     create {
         let alice: T::AccountId = account("ALICE", 0, 0);
         let bob: T::AccountId = account("BOB", 0, 0);
         let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
 
-        let nft_id = 100;
+        let nft_id = prepare_benchmarks::<T>();
         let at = SystemModule::<T>::block_number() + 10u32.into();
 
     }: _(RawOrigin::Signed(alice), nft_id, bob_lookup, at)
     verify {
-        assert_eq!(T::NFTs::is_in_transmission(nft_id), Some(true));
+        assert_eq!(T::NFTs::is_locked(nft_id, NFTLockReason::InTransmission), Some(true));
+        assert_eq!(TimedEscrow::<T>::escrows(nft_id), Some(bob));
     }
 
     cancel {
         let alice: T::AccountId = account("ALICE", 0, 0);
-        let bob: T::AccountId = account("BOB", 0, 0);
-        let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
 
-        let nft_id = 100;
-        let at = SystemModule::<T>::block_number() + 10u32.into();
+        let nft_id = prepare_benchmarks::<T>();
+        create_escrow::<T>(nft_id);
 
-        drop(TimedEscrow::<T>::create(RawOrigin::Signed(alice.clone()).into(), nft_id, bob_lookup, at));
     }: _(RawOrigin::Signed(alice), nft_id)
     verify {
-        assert_eq!(T::NFTs::is_in_transmission(nft_id), Some(false));
+        assert_eq!(T::NFTs::is_locked(nft_id, NFTLockReason::InTransmission), Some(false));
+        assert_eq!(TimedEscrow::<T>::escrows(nft_id), None);
     }
 
     complete_transfer {
-        let alice: T::AccountId = account("ALICE", 0, 0);
         let bob: T::AccountId = account("BOB", 0, 0);
-        let bob_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(bob.clone());
 
-        let nft_id = 100;
-        let at = SystemModule::<T>::block_number() + 10u32.into();
+        let nft_id = prepare_benchmarks::<T>();
+        create_escrow::<T>(nft_id);
 
-        drop(TimedEscrow::<T>::create(RawOrigin::Signed(alice).into(), nft_id, bob_lookup, at));
-    }: _(RawOrigin::Root, bob.clone(), nft_id)
+    }: _(RawOrigin::Root, nft_id)
     verify {
         assert_eq!(T::NFTs::owner(nft_id), Some(bob));
     }
@@ -1,19 +1,37 @@
 use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
 
-impl crate::WeightInfo for () {
+pub trait WeightInfo {
+    fn create() -> Weight;
+    fn cancel() -> Weight;
+    fn complete_transfer() -> Weight;
+}
+
+impl WeightInfo for () {
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Nfts Series (r:1 w:0)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    // Storage: TimedEscrow Escrows (r:0 w:1)
     fn create() -> Weight {
         (126_000_000 as Weight)
-            .saturating_add(DbWeight::get().reads(3 as Weight))
-            .saturating_add(DbWeight::get().writes(3 as Weight))
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
     }
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: TimedEscrow Escrows (r:1 w:1)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
     fn cancel() -> Weight {
         (118_000_000 as Weight)
-            .saturating_add(DbWeight::get().reads(3 as Weight))
-            .saturating_add(DbWeight::get().writes(3 as Weight))
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
     }
+    // Storage: TimedEscrow Escrows (r:1 w:1)
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Nfts NftsByOwner (r:0 w:2)
     fn complete_transfer() -> Weight {
         (92_000_000 as Weight)
-            .saturating_add(DbWeight::get().reads(1 as Weight))
-            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
     }
 }
@@ -1,4 +1,4 @@
-/* #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
@@ -6,30 +6,31 @@ mod default_weights;
 #[cfg(test)]
 mod tests;
 
+pub use default_weights::WeightInfo;
 pub use pallet::*;
 
-use frame_support::traits::LockIdentifier;
+use codec::Encode;
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use frame_support::traits::schedule::Named as ScheduleNamed;
+use frame_support::traits::{Get, LockIdentifier, StorageVersion};
 use frame_support::weights::Weight;
-use ternoa_primitives::nfts::NFTId;
+use ternoa_common::traits::{NFTReleaseHandler, NFTTrait};
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
 
 /// Used for derivating scheduled tasks IDs
 const ESCROW_ID: LockIdentifier = *b"escrow  ";
 
-pub trait WeightInfo {
-    fn create() -> Weight;
-    fn cancel() -> Weight;
-    fn complete_transfer() -> Weight;
-}
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use frame_support::pallet_prelude::*;
-    use frame_support::traits::schedule::{DispatchTime, Named as ScheduleNamed};
+    use frame_support::traits::schedule::DispatchTime;
+    use frame_support::transactional;
     use frame_system::pallet_prelude::*;
     use frame_system::RawOrigin;
     use sp_runtime::traits::{Dispatchable, StaticLookup};
-    use ternoa_common::traits::NFTTrait;
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -49,7 +50,8 @@ pub mod pallet {
     }
 
     #[pallet::pallet]
-    #[pallet::without_storage_info]
+    #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
@@ -57,9 +59,10 @@ pub mod pallet {
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Create a timed transfer. This will lock the associated capsule until it gets
+        /// Create a timed transfer. This will lock the associated NFT until it gets
         /// transferred or canceled.
         #[pallet::weight(T::WeightInfo::create())]
+        #[transactional]
         pub fn create(
             origin: OriginFor<T>,
             nft_id: NFTId,
@@ -67,55 +70,64 @@ pub mod pallet {
             at: T::BlockNumber,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            let to_unlookup = T::Lookup::lookup(to)?;
+            let to = T::Lookup::lookup(to)?;
 
             let nft = T::NFTs::get_nft(nft_id).ok_or(Error::<T>::UnknownNFT)?;
             ensure!(nft.owner == who, Error::<T>::NotNFTOwner);
-            ensure!(!nft.listed_for_sale, Error::<T>::ListedForSale);
-            ensure!(!nft.in_transmission, Error::<T>::AlreadyInTransmission);
 
-            T::NFTs::set_in_transmission(nft_id, true)?;
+            let is_nft_in_completed_series =
+                T::NFTs::is_nft_in_completed_series(nft_id) == Some(true);
+            ensure!(is_nft_in_completed_series, Error::<T>::SeriesNotCompleted);
+
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(at > now, Error::<T>::BlockIsInThePast);
+
+            // Capsules, rented NFTs and NFTs that are already locked for any
+            // other reason cannot be locked again.
+            T::NFTs::lock(nft_id, NFTLockReason::InTransmission)?;
+
+            let ok = T::Scheduler::schedule_named(
+                (ESCROW_ID, nft_id).encode(),
+                DispatchTime::At(at),
+                None,
+                // priority was chosen arbitrarily, we made sure it is lower than runtime
+                // upgrades and democracy calls
+                100,
+                RawOrigin::Root.into(),
+                Call::complete_transfer { nft_id }.into(),
+            )
+            .is_ok();
+            ensure!(ok, Error::<T>::SchedulingFailed);
 
-            ensure!(
-                T::Scheduler::schedule_named(
-                    (ESCROW_ID, nft_id).encode(),
-                    DispatchTime::At(at),
-                    None,
-                    // priority was chosen arbitrarily, we made sure it is lower than runtime
-                    // upgrades and democracy calls
-                    100,
-                    RawOrigin::Root.into(),
-                    Call::complete_transfer {
-                        to: to_unlookup.clone(),
-                        nft_id: nft_id
-                    }
-                    .into()
-                )
-                .is_ok(),
-                Error::<T>::SchedulingFailed
-            );
+            Escrows::<T>::insert(nft_id, to.clone());
 
             Self::deposit_event(Event::TransferScheduled {
                 nft_id,
-                destination: to_unlookup,
+                destination: to,
                 block_number: at,
             });
 
             Ok(().into())
         }
 
-        /// Cancel a transfer that was previously created and unlocks the capsule.
+        /// Cancel a transfer that was previously created and unlocks the NFT.
         #[pallet::weight(T::WeightInfo::cancel())]
+        #[transactional]
         pub fn cancel(origin: OriginFor<T>, nft_id: NFTId) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
             let nft = T::NFTs::get_nft(nft_id).ok_or(Error::<T>::UnknownNFT)?;
             ensure!(nft.owner == who, Error::<T>::NotNFTOwner);
+            ensure!(
+                Escrows::<T>::contains_key(nft_id),
+                Error::<T>::TransferNotFound
+            );
 
             let ok = T::Scheduler::cancel_named((ESCROW_ID, nft_id).encode()).is_ok();
             ensure!(ok, Error::<T>::SchedulingFailed);
 
-            T::NFTs::set_in_transmission(nft_id, false)?;
+            T::NFTs::unlock(nft_id, NFTLockReason::InTransmission)?;
+            Escrows::<T>::remove(nft_id);
 
             Self::deposit_event(Event::TransferCanceled { nft_id });
 
@@ -123,22 +135,22 @@ pub mod pallet {
         }
 
         /// System only. Execute a transfer, called by the scheduler.
+        /// If the NFT cannot be handed over anymore, the transfer is dropped
+        /// and a `TransferFailed` event is emitted instead.
         #[pallet::weight(T::WeightInfo::complete_transfer())]
         pub fn complete_transfer(
             origin: OriginFor<T>,
-            to: T::AccountId,
             nft_id: NFTId,
         ) -> DispatchResultWithPostInfo {
-            // We do not verify anything else as the only way for this function
-            // to be called is if it was scheduled via either root action (trusted)
-            // or the call to `create` which will verify NFT ownership and locking
-            // status.
             ensure_root(origin)?;
 
-            T::NFTs::set_in_transmission(nft_id, false)?;
-            T::NFTs::set_owner(nft_id, &to)?;
+            let to = Escrows::<T>::take(nft_id).ok_or(Error::<T>::TransferNotFound)?;
 
-            Self::deposit_event(Event::TransferCompleted { nft_id });
+            let event = match Self::transfer(nft_id, &to) {
+                Ok(()) => Event::TransferCompleted { nft_id },
+                Err(error) => Event::TransferFailed { nft_id, error },
+            };
+            Self::deposit_event(event);
 
             Ok(().into())
         }
@@ -157,6 +169,8 @@ pub mod pallet {
         TransferCanceled { nft_id: NFTId },
         /// A transfer was executed and finalized.
         TransferCompleted { nft_id: NFTId },
+        /// A transfer could not be executed because the NFT was burned or moved.
+        TransferFailed { nft_id: NFTId, error: DispatchError },
     }
 
     #[pallet::error]
@@ -167,10 +181,48 @@ pub mod pallet {
         SchedulingFailed,
         /// Unknown NFT
         UnknownNFT,
-        /// TODO!
-        ListedForSale,
-        /// TODO!
-        AlreadyInTransmission,
+        /// NFTs from a draft series cannot be transferred.
+        SeriesNotCompleted,
+        /// The transfer block has already passed.
+        BlockIsInThePast,
+        /// No transfer is scheduled for this NFT.
+        TransferNotFound,
+    }
+
+    /// Recipients of the scheduled transfers.
+    #[pallet::storage]
+    #[pallet::getter(fn escrows)]
+    pub type Escrows<T: Config> = StorageMap<_, Blake2_128Concat, NFTId, T::AccountId, OptionQuery>;
+}
+
+impl<T: Config> Pallet<T> {
+    #[frame_support::transactional]
+    fn transfer(nft_id: NFTId, to: &T::AccountId) -> DispatchResult {
+        // Fails if the NFT was burned or is not held in escrow anymore.
+        T::NFTs::unlock(nft_id, NFTLockReason::InTransmission)?;
+        T::NFTs::set_owner(nft_id, to)
+    }
+}
+
+impl<T: Config> NFTReleaseHandler for Pallet<T> {
+    /// Cancel the scheduled transfer of an NFT.
+    fn release_nft(nft_id: NFTId, reason: NFTLockReason) -> Result<Weight, DispatchError> {
+        if reason != NFTLockReason::InTransmission {
+            return Ok(0);
+        }
+
+        if Escrows::<T>::take(nft_id).is_none() {
+            return Ok(T::DbWeight::get().reads(1));
+        }
+
+        // The transfer might already be executing.
+        let _ = T::Scheduler::cancel_named((ESCROW_ID, nft_id).encode());
+        Self::deposit_event(Event::TransferCanceled { nft_id });
+
+        Ok(T::WeightInfo::cancel())
+    }
+
+    fn max_release_weight() -> Weight {
+        T::WeightInfo::cancel()
     }
 }
- */
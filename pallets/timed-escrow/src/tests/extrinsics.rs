@@ -1,10 +1,31 @@
 use super::mock::*;
 use crate::tests::mock;
-use crate::Error;
+use crate::{Error, Event as TimedEscrowEvent};
 use frame_support::{assert_noop, assert_ok, error::BadOrigin, traits::OnInitialize};
 use frame_system::RawOrigin;
 use pallet_scheduler::Agenda as SchedulerAgenda;
+use sp_runtime::Permill;
 use ternoa_common::traits::NFTTrait;
+use ternoa_nfts::Error as NFTError;
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
+
+fn origin(account: u64) -> mock::Origin {
+    RawOrigin::Signed(account).into()
+}
+
+/// Create an NFT in a completed series.
+fn create_nft(owner: u64) -> NFTId {
+    let nft_id = NFTs::nft_id_generator();
+    let series_id = nft_id.to_le_bytes().to_vec();
+    assert_ok!(NFTs::create(
+        origin(owner),
+        vec![1],
+        Some(series_id.clone()),
+        Permill::zero()
+    ));
+    assert_ok!(NFTs::finish_series(origin(owner), series_id));
+    nft_id
+}
 
 #[test]
 fn create_happy() {
@@ -15,11 +36,12 @@ fn create_happy() {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
 
             // Happy path
-            let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![0], None).unwrap();
+            let nft_id = create_nft(ALICE);
             assert_ok!(TimedEscrow::create(alice.clone(), nft_id, BOB, 10));
 
-            let nft = <NFTs as NFTTrait>::get_nft(nft_id).unwrap();
-            assert_eq!(nft.in_transmission, true);
+            let is_locked = NFTs::is_locked(nft_id, NFTLockReason::InTransmission);
+            assert_eq!(is_locked, Some(true));
+            assert_eq!(TimedEscrow::escrows(nft_id), Some(BOB));
 
             // By default nothing is scheduled so checking if we have one element
             // inside the block's agenda should be enough to confirm that a transfer
@@ -29,9 +51,13 @@ fn create_happy() {
 
             // block 10
             Scheduler::on_initialize(10);
-            let nft = <NFTs as NFTTrait>::get_nft(nft_id).unwrap();
+            let nft = NFTs::get_nft(nft_id).unwrap();
             assert_eq!(nft.owner, BOB);
-            assert_eq!(nft.in_transmission, false);
+            assert_eq!(nft.lock, None);
+            assert_eq!(TimedEscrow::escrows(nft_id), None);
+
+            let event = Event::TimedEscrow(TimedEscrowEvent::TransferCompleted { nft_id });
+            assert!(System::events().iter().any(|x| x.event == event));
         });
 }
 
@@ -48,23 +74,42 @@ fn create_unhappy() {
             assert_noop!(ok, Error::<Test>::UnknownNFT);
 
             // Unhappy not nft owner
-            let nft_id = <NFTs as NFTTrait>::create_nft(BOB, vec![0], None).unwrap();
+            let nft_id = create_nft(BOB);
             let ok = TimedEscrow::create(alice.clone(), nft_id, BOB, 10);
             assert_noop!(ok, Error::<Test>::NotNFTOwner);
 
-            // Unhappy listed for sale
+            // Unhappy draft series
             let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![0], None).unwrap();
-            <NFTs as NFTTrait>::set_listed_for_sale(nft_id, true).unwrap();
+            let ok = TimedEscrow::create(alice.clone(), nft_id, BOB, 10);
+            assert_noop!(ok, Error::<Test>::SeriesNotCompleted);
+
+            // Unhappy block in the past
+            let nft_id = create_nft(ALICE);
+            let ok = TimedEscrow::create(alice.clone(), nft_id, BOB, 1);
+            assert_noop!(ok, Error::<Test>::BlockIsInThePast);
 
+            // Unhappy listed for sale
+            NFTs::lock(nft_id, NFTLockReason::ListedForSale).unwrap();
             let ok = TimedEscrow::create(alice.clone(), nft_id, BOB, 10);
-            assert_noop!(ok, Error::<Test>::ListedForSale);
+            assert_noop!(ok, NFTError::<Test>::NFTIsListedForSale);
 
-            // Unhappy already in transmission
-            let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![0], None).unwrap();
-            <NFTs as NFTTrait>::set_in_transmission(nft_id, true).unwrap();
+            // Unhappy capsule
+            let nft_id = create_nft(ALICE);
+            NFTs::lock(nft_id, NFTLockReason::Capsule).unwrap();
+            let ok = TimedEscrow::create(alice.clone(), nft_id, BOB, 10);
+            assert_noop!(ok, NFTError::<Test>::NFTIsCapsule);
 
+            // Unhappy rented
+            let nft_id = create_nft(ALICE);
+            NFTs::lock(nft_id, NFTLockReason::Rented).unwrap();
+            let ok = TimedEscrow::create(alice.clone(), nft_id, BOB, 10);
+            assert_noop!(ok, NFTError::<Test>::NFTIsRented);
+
+            // Unhappy already in transmission
+            let nft_id = create_nft(ALICE);
+            assert_ok!(TimedEscrow::create(alice.clone(), nft_id, BOB, 10));
             let ok = TimedEscrow::create(alice.clone(), nft_id, BOB, 10);
-            assert_noop!(ok, Error::<Test>::AlreadyInTransmission);
+            assert_noop!(ok, NFTError::<Test>::NFTIsInTransmission);
         });
 }
 
@@ -77,10 +122,12 @@ fn cancel_happy() {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
 
             // Happy path
-            let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![0], None).unwrap();
+            let nft_id = create_nft(ALICE);
             assert_ok!(TimedEscrow::create(alice.clone(), nft_id, BOB, 10));
             assert_ok!(TimedEscrow::cancel(alice.clone(), nft_id));
-            assert_eq!(<NFTs as NFTTrait>::is_in_transmission(nft_id), Some(false));
+            let is_locked = NFTs::is_locked(nft_id, NFTLockReason::InTransmission);
+            assert_eq!(is_locked, Some(false));
+            assert_eq!(TimedEscrow::escrows(nft_id), None);
 
             // We verified previously would fill the block's agenda. So canceling should
             // reset it to 0. However, due to how this is implemented in the scheduler
@@ -93,7 +140,7 @@ fn cancel_happy() {
 #[test]
 fn cancel_unhappy() {
     ExtBuilder::default()
-        .caps(vec![(BOB, 1000)])
+        .caps(vec![(ALICE, 1000), (BOB, 1000)])
         .build()
         .execute_with(|| {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
@@ -103,9 +150,14 @@ fn cancel_unhappy() {
             assert_noop!(ok, Error::<Test>::UnknownNFT);
 
             // Unhappy not nft owner
-            let nft_id = <NFTs as NFTTrait>::create_nft(BOB, vec![0], None).unwrap();
+            let nft_id = create_nft(BOB);
             let ok = TimedEscrow::cancel(alice.clone(), nft_id);
             assert_noop!(ok, Error::<Test>::NotNFTOwner);
+
+            // Unhappy no transfer scheduled
+            let nft_id = create_nft(ALICE);
+            let ok = TimedEscrow::cancel(alice.clone(), nft_id);
+            assert_noop!(ok, Error::<Test>::TransferNotFound);
         });
 }
 
@@ -119,13 +171,13 @@ fn complete_transfer_happy() {
             let root: mock::Origin = RawOrigin::Root.into();
 
             // Happy path
-            let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![0], None).unwrap();
+            let nft_id = create_nft(ALICE);
             assert_ok!(TimedEscrow::create(alice.clone(), nft_id, BOB, 10));
-            assert_ok!(TimedEscrow::complete_transfer(root, BOB, nft_id));
+            assert_ok!(TimedEscrow::complete_transfer(root, nft_id));
 
-            let nft = <NFTs as NFTTrait>::get_nft(nft_id).unwrap();
+            let nft = NFTs::get_nft(nft_id).unwrap();
             assert_eq!(nft.owner, BOB);
-            assert_eq!(nft.in_transmission, false);
+            assert_eq!(nft.lock, None);
         });
 }
 
@@ -138,15 +190,87 @@ fn complete_transfer_unhappy() {
             let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
             let root: mock::Origin = RawOrigin::Root.into();
 
-            let nft_id = <NFTs as NFTTrait>::create_nft(ALICE, vec![0], None).unwrap();
+            let nft_id = create_nft(ALICE);
             assert_ok!(TimedEscrow::create(alice.clone(), nft_id, BOB, 10));
 
             // Unhappy not root
-            let ok = TimedEscrow::complete_transfer(alice.clone(), BOB, nft_id);
+            let ok = TimedEscrow::complete_transfer(alice.clone(), nft_id);
             assert_noop!(ok, BadOrigin);
 
-            // Unhappy failed to set owner because wrong id was given
-            let ok = TimedEscrow::complete_transfer(root, BOB, 1001);
-            assert_noop!(ok, ternoa_nfts::Error::<Test>::NFTNotFound);
+            // Unhappy no transfer scheduled
+            let ok = TimedEscrow::complete_transfer(root, 1001);
+            assert_noop!(ok, Error::<Test>::TransferNotFound);
+        });
+}
+
+#[test]
+fn complete_transfer_of_unlocked_nft_fails() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let root: mock::Origin = RawOrigin::Root.into();
+
+            let nft_id = create_nft(ALICE);
+            assert_ok!(TimedEscrow::create(alice.clone(), nft_id, BOB, 10));
+
+            // The NFT got unlocked behind the back of the pallet.
+            NFTs::unlock(nft_id, NFTLockReason::InTransmission).unwrap();
+            assert_ok!(TimedEscrow::complete_transfer(root, nft_id));
+
+            assert_eq!(NFTs::owner(nft_id), Some(ALICE));
+            assert_eq!(TimedEscrow::escrows(nft_id), None);
+            let event = TimedEscrowEvent::TransferFailed {
+                nft_id,
+                error: NFTError::<Test>::NFTIsNotLocked.into(),
+            };
+            assert_eq!(
+                System::events().last().unwrap().event,
+                Event::TimedEscrow(event)
+            );
+        });
+}
+
+#[test]
+fn force_burn_cancels_transfer() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let root: mock::Origin = RawOrigin::Root.into();
+
+            let nft_id = create_nft(ALICE);
+            assert_ok!(TimedEscrow::create(alice.clone(), nft_id, BOB, 10));
+            assert_ok!(NFTs::force_burn(root, nft_id));
+
+            assert_eq!(TimedEscrow::escrows(nft_id), None);
+            assert!(SchedulerAgenda::<Test>::get(10)[0].is_none());
+
+            // Nothing happens at the scheduled block
+            Scheduler::on_initialize(10);
+            assert_eq!(NFTs::get_nft(nft_id), None);
+        });
+}
+
+#[test]
+fn force_transfer_cancels_transfer() {
+    ExtBuilder::default()
+        .caps(vec![(ALICE, 1000)])
+        .build()
+        .execute_with(|| {
+            let alice: mock::Origin = RawOrigin::Signed(ALICE).into();
+            let root: mock::Origin = RawOrigin::Root.into();
+
+            let nft_id = create_nft(ALICE);
+            assert_ok!(TimedEscrow::create(alice.clone(), nft_id, BOB, 10));
+            assert_ok!(NFTs::force_transfer(root, nft_id, CHARLIE));
+
+            let event = Event::TimedEscrow(TimedEscrowEvent::TransferCanceled { nft_id });
+            assert!(System::events().iter().any(|x| x.event == event));
+
+            Scheduler::on_initialize(10);
+            assert_eq!(NFTs::owner(nft_id), Some(CHARLIE));
         });
 }
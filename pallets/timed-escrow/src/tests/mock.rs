@@ -1,12 +1,11 @@
 use crate::{self as ternoa_timed_escrow, Config};
-use frame_benchmarking::account;
 use frame_support::traits::{ConstU32, Contains, EqualPrivilegeOnly, GenesisBuild};
 use frame_support::{parameter_types, weights::Weight};
 use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use sp_runtime::Permill;
 use sp_runtime::{testing::Header, Perbill};
-use ternoa_primitives::nfts::{NFTData, NFTSeriesDetails};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -102,11 +101,22 @@ impl pallet_scheduler::Config for Test {
     type MaxScheduledPerBlock = ();
     type WeightInfo = ();
     type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type PreimageProvider = ();
+    type NoPreimagePostponement = ();
 }
 
 parameter_types! {
     pub const MinIpfsLen: u16 = 1;
     pub const MaxIpfsLen: u16 = 5;
+    pub const MaxSeriesIdLen: u16 = 5;
+}
+
+parameter_types! {
+    pub const BatchMintDiscount: Permill = Permill::from_percent(0);
+    pub const UseStorageDeposits: bool = false;
+    pub const DepositBase: u64 = 0;
+    pub const DepositPerByte: u64 = 0;
+    pub const DepositFollowsOwner: bool = true;
 }
 
 impl ternoa_nfts::Config for Test {
@@ -116,6 +126,17 @@ impl ternoa_nfts::Config for Test {
     type FeesCollector = ();
     type MinIpfsLen = MinIpfsLen;
     type MaxIpfsLen = MaxIpfsLen;
+    type IPFSReferenceValidator = ();
+    type MaxSeriesIdLen = MaxSeriesIdLen;
+    type MaxBatchSize = ConstU32<5>;
+    type BatchMintDiscount = BatchMintDiscount;
+    type UseStorageDeposits = UseStorageDeposits;
+    type DepositBase = DepositBase;
+    type DepositPerByte = DepositPerByte;
+    type DepositFollowsOwner = DepositFollowsOwner;
+    type ForceOrigin = EnsureRoot<u64>;
+    type NFTReleaseHandler = TimedEscrow;
+    type MaxRentalsPerBlock = ConstU32<5>;
 }
 
 impl Config for Test {
@@ -131,16 +152,15 @@ impl Config for Test {
 // for our account id. This would mess with some tests.
 pub const ALICE: u64 = 1;
 pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
 
 pub struct ExtBuilder {
-    nfts: Vec<(u32, NFTData<u64>)>,
     endowed_accounts: Vec<(u64, u64)>,
 }
 
 impl Default for ExtBuilder {
     fn default() -> Self {
         ExtBuilder {
-            nfts: Vec::new(),
             endowed_accounts: Vec::new(),
         }
     }
@@ -153,8 +173,8 @@ impl ExtBuilder {
             .unwrap();
 
         ternoa_nfts::GenesisConfig::<Test> {
-            nfts: self.nfts,
-            series: Vec::new(),
+            nfts: Default::default(),
+            series: Default::default(),
             nft_mint_fee: 10,
         }
         .assimilate_storage(&mut t)
@@ -181,28 +201,8 @@ impl ExtBuilder {
 
 #[allow(dead_code)]
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    let mut t = frame_system::GenesisConfig::default()
+    let t = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
         .unwrap();
-
-    let alice = account("ALICE", 0, 0);
-    let bob = account("BOB", 0, 0);
-    let nft_data = NFTData::new(alice, ALICE, vec![0], vec![50], false, false, false);
-    let series_data = NFTSeriesDetails::new(alice, false);
-
-    pallet_balances::GenesisConfig::<Test> {
-        balances: vec![(alice, 10000), (bob, 10000)],
-    }
-    .assimilate_storage(&mut t)
-    .unwrap();
-
-    ternoa_nfts::GenesisConfig::<Test> {
-        nfts: vec![(100, nft_data)],
-        series: vec![(vec![50], series_data)],
-        nft_mint_fee: 10,
-    }
-    .assimilate_storage(&mut t)
-    .unwrap();
-
     t.into()
 }
@@ -0,0 +1,118 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as Transmission;
+use frame_benchmarking::{account as benchmark_account, benchmarks};
+use frame_support::traits::Hooks;
+use frame_support::{assert_ok, BoundedVec};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{One, StaticLookup};
+use sp_std::convert::{TryFrom, TryInto};
+use sp_std::prelude::*;
+
+pub fn get_account<T: Config>(name: &'static str) -> T::AccountId {
+    let account: T::AccountId = benchmark_account(name, 0, 0);
+    account
+}
+
+pub fn get_origin<T: Config>(name: &'static str) -> RawOrigin<T::AccountId> {
+    RawOrigin::Signed(get_account::<T>(name))
+}
+
+pub fn get_lookup<T: Config>(name: &'static str) -> <T::Lookup as StaticLookup>::Source {
+    T::Lookup::unlookup(get_account::<T>(name))
+}
+
+pub fn create_nft<T: Config>(owner: &T::AccountId) -> NFTId {
+    let nft_id = T::NFTs::benchmark_create_nft(owner);
+    let series_id = T::NFTs::get_nft(nft_id).unwrap().series_id.to_vec();
+    T::NFTs::benchmark_lock_series(series_id);
+
+    nft_id
+}
+
+pub fn prepare_benchmarks<T: Config>(protocol: TransmissionProtocolOf<T>) -> NFTId {
+    let alice: T::AccountId = get_account::<T>("ALICE");
+    let nft_id = create_nft::<T>(&alice);
+
+    assert_ok!(Transmission::<T>::set_transmission(
+        get_origin::<T>("ALICE").into(),
+        nft_id,
+        get_lookup::<T>("BOB"),
+        protocol,
+    ));
+
+    nft_id
+}
+
+/// Fill a deadline block up to one free slot.
+pub fn fill_deadline<T: Config>(deadline: T::BlockNumber) {
+    let due: Vec<NFTId> = (1..T::MaxTransmissionsPerBlock::get())
+        .map(|i| NFTId::MAX - i)
+        .collect();
+    Deadlines::<T>::insert(deadline, BoundedVec::try_from(due).unwrap());
+}
+
+pub fn guardians<T: Config>() -> Vec<T::AccountId> {
+    (0..T::MaxGuardians::get())
+        .map(|i| benchmark_account("GUARDIAN", i, 0))
+        .collect()
+}
+
+benchmarks! {
+    set_transmission {
+        let alice: T::AccountId = get_account::<T>("ALICE");
+        let nft_id = create_nft::<T>(&alice);
+        let protocol = TransmissionProtocol::OnInactivity(T::MaxBlockDuration::get());
+        let now = frame_system::Pallet::<T>::block_number();
+        fill_deadline::<T>(now + T::MaxBlockDuration::get());
+
+    }: _(get_origin::<T>("ALICE"), nft_id, get_lookup::<T>("BOB"), protocol)
+    verify {
+        assert!(Transmission::<T>::transmissions(nft_id).is_some());
+    }
+
+    remove_transmission {
+        let protocol = TransmissionProtocol::OnInactivity(T::MaxBlockDuration::get());
+        let nft_id = prepare_benchmarks::<T>(protocol);
+
+    }: _(get_origin::<T>("ALICE"), nft_id)
+    verify {
+        assert!(Transmission::<T>::transmissions(nft_id).is_none());
+    }
+
+    reset_timer {
+        let protocol = TransmissionProtocol::OnInactivity(T::MaxBlockDuration::get());
+        let nft_id = prepare_benchmarks::<T>(protocol);
+        frame_system::Pallet::<T>::set_block_number(One::one());
+        fill_deadline::<T>(T::MaxBlockDuration::get() + One::one());
+
+    }: _(get_origin::<T>("ALICE"), nft_id)
+    verify {
+        let deadline = T::MaxBlockDuration::get() + One::one();
+        assert_eq!(Transmission::<T>::transmissions(nft_id).unwrap().deadline, Some(deadline));
+    }
+
+    add_consent {
+        let guardians = guardians::<T>();
+        let guardian = guardians[0].clone();
+        let protocol = TransmissionProtocol::OnConsent {
+            guardians: guardians.try_into().unwrap(),
+            threshold: 1,
+        };
+        let nft_id = prepare_benchmarks::<T>(protocol);
+
+    }: _(RawOrigin::Signed(guardian), nft_id)
+    verify {
+        assert_eq!(T::NFTs::owner(nft_id), Some(get_account::<T>("BOB")));
+    }
+
+    complete_transmission {
+        let deadline = T::MaxBlockDuration::get();
+        let nft_id = prepare_benchmarks::<T>(TransmissionProtocol::AtBlock(deadline));
+
+    }: { Transmission::<T>::on_initialize(deadline); }
+    verify {
+        assert_eq!(T::NFTs::owner(nft_id), Some(get_account::<T>("BOB")));
+    }
+}
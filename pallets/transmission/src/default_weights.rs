@@ -0,0 +1,55 @@
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+pub trait WeightInfo {
+    fn set_transmission() -> Weight;
+    fn remove_transmission() -> Weight;
+    fn reset_timer() -> Weight;
+    fn add_consent() -> Weight;
+    fn complete_transmission() -> Weight;
+}
+
+impl WeightInfo for () {
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Transmission Transmissions (r:1 w:1)
+    // Storage: Transmission Deadlines (r:1 w:1)
+    fn set_transmission() -> Weight {
+        (42_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Nfts Data (r:1 w:1)
+    // Storage: Transmission Transmissions (r:1 w:1)
+    // Storage: Transmission Deadlines (r:1 w:1)
+    fn remove_transmission() -> Weight {
+        (38_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Nfts Data (r:1 w:0)
+    // Storage: Transmission Transmissions (r:1 w:1)
+    // Storage: Transmission Deadlines (r:2 w:2)
+    fn reset_timer() -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Transmission Transmissions (r:2 w:2)
+    // Storage: Nfts Data (r:3 w:2)
+    // Storage: Nfts NftsByOwner (r:0 w:2)
+    // Storage: Nfts Deposits (r:1 w:0)
+    fn add_consent() -> Weight {
+        (71_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(6 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+    }
+    // Storage: Transmission Transmissions (r:1 w:1)
+    // Storage: Transmission Deadlines (r:1 w:1)
+    // Storage: Nfts Data (r:3 w:2)
+    // Storage: Nfts NftsByOwner (r:0 w:2)
+    // Storage: Nfts Deposits (r:1 w:0)
+    fn complete_transmission() -> Weight {
+        (58_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(6 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+    }
+}
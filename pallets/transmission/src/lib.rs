@@ -0,0 +1,496 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod tests;
+
+mod default_weights;
+mod types;
+
+pub use default_weights::WeightInfo;
+pub use pallet::*;
+pub use types::*;
+
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use frame_support::traits::{Get, StorageVersion};
+use frame_support::weights::Weight;
+use frame_support::{ensure, transactional};
+use sp_runtime::traits::{Saturating, Zero};
+use ternoa_common::traits::{CapsuleTrait, NFTReleaseHandler, NFTTrait};
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::StaticLookup;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Because this pallet emits events, it depends on the runtime's definition of an event.
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// Weight values for this pallet
+        type WeightInfo: WeightInfo;
+
+        /// Pallet managing the NFTs that get transmitted
+        type NFTs: NFTTrait<AccountId = Self::AccountId>;
+
+        /// Pallet managing capsules, so that capsules are transmitted along
+        /// with their funds
+        type Capsules: CapsuleTrait<Self::AccountId> + NFTReleaseHandler;
+
+        /// Maximum number of guardians of a transmission.
+        #[pallet::constant]
+        type MaxGuardians: Get<u32>;
+
+        /// Maximum number of blocks a transmission can be delayed by.
+        #[pallet::constant]
+        type MaxBlockDuration: Get<Self::BlockNumber>;
+
+        /// Maximum number of transmissions that can be due at the same block.
+        #[pallet::constant]
+        type MaxTransmissionsPerBlock: Get<u32>;
+    }
+
+    pub type TransmissionProtocolOf<T> = TransmissionProtocol<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        <T as Config>::MaxGuardians,
+    >;
+    pub type TransmissionOf<T> = TransmissionData<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        <T as Config>::MaxGuardians,
+    >;
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Hand over the NFTs whose transmission is due at this block.
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let due = Deadlines::<T>::take(now);
+
+            for nft_id in due.iter() {
+                Self::complete_transmission(*nft_id);
+            }
+
+            let count = due.len() as Weight;
+            T::DbWeight::get()
+                .reads_writes(1, 1)
+                .saturating_add(T::WeightInfo::complete_transmission().saturating_mul(count))
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Set up the transmission of an NFT to `recipient`. The NFT is locked
+        /// until it is transmitted or until the transmission is removed.
+        /// Capsules cannot be reverted in the meantime and are transmitted with
+        /// their funds.
+        #[pallet::weight(T::WeightInfo::set_transmission())]
+        #[transactional]
+        pub fn set_transmission(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+            recipient: <T::Lookup as StaticLookup>::Source,
+            protocol: TransmissionProtocolOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let recipient = T::Lookup::lookup(recipient)?;
+
+            let nft = T::NFTs::get_nft(nft_id).ok_or(Error::<T>::UnknownNFT)?;
+            ensure!(nft.owner == who, Error::<T>::NotTheNFTOwner);
+            ensure!(recipient != who, Error::<T>::CannotTransmitToSelf);
+
+            let is_nft_in_completed_series =
+                T::NFTs::is_nft_in_completed_series(nft_id) == Some(true);
+            ensure!(is_nft_in_completed_series, Error::<T>::SeriesNotCompleted);
+
+            ensure!(
+                !Transmissions::<T>::contains_key(nft_id),
+                Error::<T>::AlreadyInTransmission
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let deadline = Self::deadline(&protocol, now)?;
+
+            let is_capsule = nft.is_locked_for(NFTLockReason::Capsule);
+            Self::lock(nft_id, is_capsule)?;
+
+            let data =
+                TransmissionData::new(recipient.clone(), protocol.clone(), deadline, is_capsule);
+            Transmissions::<T>::insert(nft_id, data);
+            if let Some(deadline) = deadline {
+                Self::insert_deadline(deadline, nft_id)?;
+            }
+
+            let event = Event::TransmissionSet {
+                nft_id,
+                recipient,
+                protocol,
+            };
+            Self::deposit_event(event);
+
+            Ok(().into())
+        }
+
+        /// Remove the transmission of an NFT and give the NFT back to its owner.
+        #[pallet::weight(T::WeightInfo::remove_transmission())]
+        #[transactional]
+        pub fn remove_transmission(
+            origin: OriginFor<T>,
+            nft_id: NFTId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                T::NFTs::owner(nft_id) == Some(who),
+                Error::<T>::NotTheNFTOwner
+            );
+            let data = Transmissions::<T>::get(nft_id).ok_or(Error::<T>::TransmissionNotFound)?;
+
+            Self::remove(nft_id, &data);
+            Self::unlock(nft_id, &data)?;
+
+            Self::deposit_event(Event::TransmissionRemoved { nft_id });
+
+            Ok(().into())
+        }
+
+        /// Show that the owner of an NFT is still active by restarting the
+        /// countdown of an inactivity based transmission.
+        #[pallet::weight(T::WeightInfo::reset_timer())]
+        pub fn reset_timer(origin: OriginFor<T>, nft_id: NFTId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                T::NFTs::owner(nft_id) == Some(who),
+                Error::<T>::NotTheNFTOwner
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let deadline = Transmissions::<T>::try_mutate(
+                nft_id,
+                |maybe_data| -> Result<T::BlockNumber, DispatchError> {
+                    let data = maybe_data
+                        .as_mut()
+                        .ok_or(Error::<T>::TransmissionNotFound)?;
+                    let delay = match data.protocol {
+                        TransmissionProtocol::OnInactivity(delay) => delay,
+                        _ => return Err(Error::<T>::TimerCannotBeReset.into()),
+                    };
+
+                    let deadline = now.saturating_add(delay);
+                    if data.deadline != Some(deadline) {
+                        Self::insert_deadline(deadline, nft_id)?;
+                        if let Some(old_deadline) = data.deadline {
+                            Self::remove_deadline(old_deadline, nft_id);
+                        }
+                    }
+                    data.deadline = Some(deadline);
+
+                    Ok(deadline)
+                },
+            )?;
+
+            Self::deposit_event(Event::TimerReset { nft_id, deadline });
+
+            Ok(().into())
+        }
+
+        /// Consent to the transmission of an NFT as one of its guardians. The
+        /// NFT is transmitted as soon as enough guardians consented.
+        #[pallet::weight(T::WeightInfo::add_consent())]
+        pub fn add_consent(origin: OriginFor<T>, nft_id: NFTId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let is_complete = Transmissions::<T>::try_mutate(
+                nft_id,
+                |maybe_data| -> Result<bool, DispatchError> {
+                    let data = maybe_data
+                        .as_mut()
+                        .ok_or(Error::<T>::TransmissionNotFound)?;
+                    let (guardians, threshold) = match &data.protocol {
+                        TransmissionProtocol::OnConsent {
+                            guardians,
+                            threshold,
+                        } => (guardians, *threshold),
+                        _ => return Err(Error::<T>::ConsentNotNeeded.into()),
+                    };
+
+                    ensure!(guardians.contains(&who), Error::<T>::NotAGuardian);
+                    ensure!(!data.consents.contains(&who), Error::<T>::AlreadyConsented);
+
+                    // There cannot be more consents than guardians.
+                    data.consents
+                        .try_push(who.clone())
+                        .map_err(|_| Error::<T>::NotAGuardian)?;
+
+                    Ok(data.consents.len() as u32 >= threshold)
+                },
+            )?;
+
+            let event = Event::ConsentAdded {
+                nft_id,
+                guardian: who,
+            };
+            Self::deposit_event(event);
+
+            if is_complete {
+                Self::complete_transmission(nft_id);
+            }
+
+            Ok(().into())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The transmission of an NFT was set up.
+        TransmissionSet {
+            nft_id: NFTId,
+            recipient: T::AccountId,
+            protocol: TransmissionProtocolOf<T>,
+        },
+        /// The transmission of an NFT was removed.
+        TransmissionRemoved { nft_id: NFTId },
+        /// The owner of an NFT restarted the inactivity countdown.
+        TimerReset {
+            nft_id: NFTId,
+            deadline: T::BlockNumber,
+        },
+        /// A guardian consented to the transmission of an NFT.
+        ConsentAdded {
+            nft_id: NFTId,
+            guardian: T::AccountId,
+        },
+        /// An NFT was handed over to its recipient.
+        TransmissionCompleted {
+            nft_id: NFTId,
+            recipient: T::AccountId,
+        },
+        /// An NFT could not be handed over and went back to its owner.
+        TransmissionFailed { nft_id: NFTId, error: DispatchError },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// No NFT was found with that NFT id.
+        UnknownNFT,
+        /// Operation is not permitted because the caller is not the NFT owner.
+        NotTheNFTOwner,
+        /// An NFT cannot be transmitted to its owner.
+        CannotTransmitToSelf,
+        /// The NFT already has a transmission.
+        AlreadyInTransmission,
+        /// The NFT does not have a transmission.
+        TransmissionNotFound,
+        /// The transmission block has already passed.
+        BlockIsInThePast,
+        /// The transmission is delayed by more blocks than allowed.
+        DurationIsTooLong,
+        /// The inactivity period needs to last at least one block.
+        InvalidInactivityPeriod,
+        /// The threshold needs to be between one and the number of guardians.
+        InvalidThreshold,
+        /// Every guardian can only be listed once.
+        DuplicateGuardians,
+        /// Only inactivity based transmissions have a timer.
+        TimerCannotBeReset,
+        /// The transmission does not depend on guardians.
+        ConsentNotNeeded,
+        /// The caller is not a guardian of the transmission.
+        NotAGuardian,
+        /// The guardian already consented.
+        AlreadyConsented,
+        /// NFTs from a draft series cannot be transmitted.
+        SeriesNotCompleted,
+        /// Too many transmissions are already due at the deadline block.
+        TooManyTransmissionsAtBlock,
+    }
+
+    /// Transmissions waiting to happen.
+    #[pallet::storage]
+    #[pallet::getter(fn transmissions)]
+    pub type Transmissions<T: Config> =
+        StorageMap<_, Blake2_128Concat, NFTId, TransmissionOf<T>, OptionQuery>;
+
+    /// Transmissions indexed by the block at which they are due.
+    #[pallet::storage]
+    #[pallet::getter(fn deadlines)]
+    pub type Deadlines<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::BlockNumber,
+        BoundedVec<NFTId, T::MaxTransmissionsPerBlock>,
+        ValueQuery,
+    >;
+}
+
+impl<T: Config> Pallet<T> {
+    /// Check a protocol and return the block at which it hands over the NFT,
+    /// if it depends on time.
+    fn deadline(
+        protocol: &TransmissionProtocolOf<T>,
+        now: T::BlockNumber,
+    ) -> Result<Option<T::BlockNumber>, Error<T>> {
+        let max_duration = T::MaxBlockDuration::get();
+
+        match protocol {
+            TransmissionProtocol::AtBlock(block) => {
+                ensure!(*block > now, Error::<T>::BlockIsInThePast);
+                let duration = block.saturating_sub(now);
+                ensure!(duration <= max_duration, Error::<T>::DurationIsTooLong);
+                Ok(Some(*block))
+            }
+            TransmissionProtocol::OnInactivity(delay) => {
+                ensure!(!delay.is_zero(), Error::<T>::InvalidInactivityPeriod);
+                ensure!(*delay <= max_duration, Error::<T>::DurationIsTooLong);
+                Ok(Some(now.saturating_add(*delay)))
+            }
+            TransmissionProtocol::OnConsent {
+                guardians,
+                threshold,
+            } => {
+                let count = guardians.len() as u32;
+                ensure!(
+                    *threshold > 0 && *threshold <= count,
+                    Error::<T>::InvalidThreshold
+                );
+
+                let mut unique = guardians.to_vec();
+                unique.sort();
+                unique.dedup();
+                ensure!(
+                    unique.len() == guardians.len(),
+                    Error::<T>::DuplicateGuardians
+                );
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// Lock an NFT for its transmission. Capsules trade their capsule lock for
+    /// the transmission lock so that they cannot be reverted in the meantime.
+    fn lock(nft_id: NFTId, is_capsule: bool) -> DispatchResult {
+        if is_capsule {
+            T::NFTs::unlock(nft_id, NFTLockReason::Capsule)?;
+        }
+        T::NFTs::lock(nft_id, NFTLockReason::PendingTransmission)
+    }
+
+    /// Remove the transmission lock of an NFT. Fails if this pallet does not
+    /// hold the lock. Capsules get their capsule lock back.
+    fn unlock(nft_id: NFTId, data: &TransmissionOf<T>) -> DispatchResult {
+        T::NFTs::unlock(nft_id, NFTLockReason::PendingTransmission)?;
+        if data.capsule {
+            T::NFTs::lock(nft_id, NFTLockReason::Capsule)?;
+        }
+
+        Ok(())
+    }
+
+    /// Schedule a transmission at its deadline block.
+    fn insert_deadline(deadline: T::BlockNumber, nft_id: NFTId) -> DispatchResult {
+        Deadlines::<T>::try_mutate(deadline, |x| {
+            x.try_push(nft_id)
+                .map_err(|_| Error::<T>::TooManyTransmissionsAtBlock.into())
+        })
+    }
+
+    /// Unschedule a transmission from its deadline block.
+    fn remove_deadline(deadline: T::BlockNumber, nft_id: NFTId) {
+        Deadlines::<T>::mutate_exists(deadline, |x| {
+            if let Some(due) = x {
+                due.retain(|id| *id != nft_id);
+                if due.is_empty() {
+                    *x = None;
+                }
+            }
+        });
+    }
+
+    /// Drop a transmission from the storage.
+    fn remove(nft_id: NFTId, data: &TransmissionOf<T>) {
+        Transmissions::<T>::remove(nft_id);
+        if let Some(deadline) = data.deadline {
+            Self::remove_deadline(deadline, nft_id);
+        }
+    }
+
+    /// Hand an NFT over to its recipient. If that is not possible anymore,
+    /// the transmission is dropped and the NFT stays with its owner.
+    fn complete_transmission(nft_id: NFTId) {
+        let data = match Transmissions::<T>::get(nft_id) {
+            Some(data) => data,
+            None => return,
+        };
+
+        let event = match Self::transmit(nft_id, &data) {
+            Ok(()) => Event::TransmissionCompleted {
+                nft_id,
+                recipient: data.recipient,
+            },
+            Err(error) => {
+                Self::remove(nft_id, &data);
+                let ok = Self::unlock(nft_id, &data);
+                debug_assert!(ok.is_ok());
+                Event::TransmissionFailed { nft_id, error }
+            }
+        };
+        Self::deposit_event(event);
+    }
+
+    #[transactional]
+    fn transmit(nft_id: NFTId, data: &TransmissionOf<T>) -> DispatchResult {
+        Self::remove(nft_id, data);
+        Self::unlock(nft_id, data)?;
+
+        if data.capsule {
+            T::Capsules::set_capsule_owner(nft_id, &data.recipient)
+        } else {
+            T::NFTs::set_owner(nft_id, &data.recipient)
+        }
+    }
+}
+
+impl<T: Config> NFTReleaseHandler for Pallet<T> {
+    /// Drop the transmission of an NFT. Capsules are reverted and their funds
+    /// go back to their owner.
+    fn release_nft(nft_id: NFTId, reason: NFTLockReason) -> Result<Weight, DispatchError> {
+        if reason != NFTLockReason::PendingTransmission {
+            return Ok(0);
+        }
+
+        match Transmissions::<T>::get(nft_id) {
+            Some(data) => {
+                Self::remove(nft_id, &data);
+                Self::deposit_event(Event::TransmissionRemoved { nft_id });
+
+                let mut weight = T::WeightInfo::remove_transmission();
+                if data.capsule {
+                    let released = T::Capsules::release_nft(nft_id, NFTLockReason::Capsule)?;
+                    weight = weight.saturating_add(released);
+                }
+                Ok(weight)
+            }
+            None => Ok(T::DbWeight::get().reads(1)),
+        }
+    }
+
+    fn max_release_weight() -> Weight {
+        T::WeightInfo::remove_transmission().saturating_add(T::Capsules::max_release_weight())
+    }
+}
@@ -0,0 +1,610 @@
+use super::mock::*;
+use crate::tests::mock;
+use crate::{Error, Event as TransmissionEvent, TransmissionData, TransmissionProtocol};
+use frame_support::error::BadOrigin;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::Permill;
+use ternoa_common::traits::NFTTrait;
+use ternoa_nfts::Error as NFTError;
+use ternoa_primitives::nfts::{NFTId, NFTLockReason};
+
+fn origin(account: u64) -> mock::Origin {
+    RawOrigin::Signed(account).into()
+}
+
+fn create_draft_nft(owner: u64) -> NFTId {
+    assert_ok!(TernoaNFTs::create(
+        origin(owner),
+        vec![1],
+        None,
+        Permill::zero()
+    ));
+    TernoaNFTs::nft_id_generator() - 1
+}
+
+fn create_nft(owner: u64) -> NFTId {
+    let nft_id = create_draft_nft(owner);
+    let series_id = TernoaNFTs::get_nft(nft_id).unwrap().series_id;
+    assert_ok!(TernoaNFTs::set_series_completion(&series_id, true));
+    nft_id
+}
+
+fn create_capsule(owner: u64) -> NFTId {
+    let nft_id = create_nft(owner);
+    assert_ok!(TernoaCapsules::create_from_nft(
+        origin(owner),
+        nft_id,
+        vec![60]
+    ));
+    nft_id
+}
+
+fn consent(guardians: Vec<u64>, threshold: u32) -> crate::TransmissionProtocolOf<Test> {
+    TransmissionProtocol::OnConsent {
+        guardians: bounded(guardians),
+        threshold,
+    }
+}
+
+fn last_event() -> mock::Event {
+    System::events().last().unwrap().event.clone()
+}
+
+mod set_transmission {
+    use super::*;
+
+    #[test]
+    fn set_transmission() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            let protocol = TransmissionProtocol::AtBlock(10);
+
+            let ok = Transmission::set_transmission(origin(ALICE), nft_id, BOB, protocol.clone());
+            assert_ok!(ok);
+
+            // Storage
+            let data = TransmissionData::new(BOB, protocol.clone(), Some(10), false);
+            assert_eq!(Transmission::transmissions(nft_id), Some(data));
+            assert_eq!(Transmission::deadlines(10).to_vec(), vec![nft_id]);
+            let is_locked = TernoaNFTs::is_locked(nft_id, NFTLockReason::PendingTransmission);
+            assert_eq!(is_locked, Some(true));
+
+            // Events
+            let event = TransmissionEvent::TransmissionSet {
+                nft_id,
+                recipient: BOB,
+                protocol,
+            };
+            assert_eq!(last_event(), Event::Transmission(event));
+        })
+    }
+
+    #[test]
+    fn nft_in_transmission_is_locked() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+
+            let ok = TernoaNFTs::transfer(origin(ALICE), nft_id, CHARLIE);
+            assert_noop!(ok, NFTError::<Test>::NFTIsInTransmission);
+
+            let ok = TernoaNFTs::burn(origin(ALICE), nft_id);
+            assert_noop!(ok, NFTError::<Test>::NFTIsInTransmission);
+        })
+    }
+
+    #[test]
+    fn set_transmission_unhappy() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            let protocol = TransmissionProtocol::AtBlock(10);
+
+            // Unhappy bad origin
+            let ok = Transmission::set_transmission(
+                RawOrigin::None.into(),
+                nft_id,
+                BOB,
+                protocol.clone(),
+            );
+            assert_noop!(ok, BadOrigin);
+
+            // Unhappy unknown NFT
+            let ok = Transmission::set_transmission(origin(ALICE), 1001, BOB, protocol.clone());
+            assert_noop!(ok, Error::<Test>::UnknownNFT);
+
+            // Unhappy not the NFT owner
+            let ok = Transmission::set_transmission(origin(BOB), nft_id, BOB, protocol.clone());
+            assert_noop!(ok, Error::<Test>::NotTheNFTOwner);
+
+            // Unhappy transmission to self
+            let ok = Transmission::set_transmission(origin(ALICE), nft_id, ALICE, protocol.clone());
+            assert_noop!(ok, Error::<Test>::CannotTransmitToSelf);
+
+            // Unhappy block in the past
+            let past = TransmissionProtocol::AtBlock(1);
+            let ok = Transmission::set_transmission(origin(ALICE), nft_id, BOB, past);
+            assert_noop!(ok, Error::<Test>::BlockIsInThePast);
+
+            // Unhappy block too far away
+            let far = TransmissionProtocol::AtBlock(1 + MaxBlockDuration::get() + 1);
+            let ok = Transmission::set_transmission(origin(ALICE), nft_id, BOB, far);
+            assert_noop!(ok, Error::<Test>::DurationIsTooLong);
+
+            // Unhappy inactivity period of zero blocks
+            let inactivity = TransmissionProtocol::OnInactivity(0);
+            let ok = Transmission::set_transmission(origin(ALICE), nft_id, BOB, inactivity);
+            assert_noop!(ok, Error::<Test>::InvalidInactivityPeriod);
+
+            // Unhappy inactivity period too long
+            let inactivity = TransmissionProtocol::OnInactivity(MaxBlockDuration::get() + 1);
+            let ok = Transmission::set_transmission(origin(ALICE), nft_id, BOB, inactivity);
+            assert_noop!(ok, Error::<Test>::DurationIsTooLong);
+
+            // Unhappy invalid thresholds
+            let ok =
+                Transmission::set_transmission(origin(ALICE), nft_id, BOB, consent(vec![BOB], 0));
+            assert_noop!(ok, Error::<Test>::InvalidThreshold);
+            let ok =
+                Transmission::set_transmission(origin(ALICE), nft_id, BOB, consent(vec![BOB], 2));
+            assert_noop!(ok, Error::<Test>::InvalidThreshold);
+
+            // Unhappy duplicate guardians
+            let protocol = consent(vec![BOB, BOB], 1);
+            let ok = Transmission::set_transmission(origin(ALICE), nft_id, BOB, protocol);
+            assert_noop!(ok, Error::<Test>::DuplicateGuardians);
+
+            // Unhappy already in transmission
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol.clone()
+            ));
+            let ok =
+                Transmission::set_transmission(origin(ALICE), nft_id, CHARLIE, protocol.clone());
+            assert_noop!(ok, Error::<Test>::AlreadyInTransmission);
+
+            // Unhappy NFT locked by another pallet
+            let nft_id = create_nft(ALICE);
+            assert_ok!(TernoaNFTs::lock(nft_id, NFTLockReason::InTransmission));
+            let ok = Transmission::set_transmission(origin(ALICE), nft_id, BOB, protocol.clone());
+            assert_noop!(ok, NFTError::<Test>::NFTIsInTransmission);
+
+            // Unhappy draft series
+            let nft_id = create_draft_nft(ALICE);
+            let ok = Transmission::set_transmission(origin(ALICE), nft_id, BOB, protocol);
+            assert_noop!(ok, Error::<Test>::SeriesNotCompleted);
+
+            // Unhappy too many transmissions due at the same block
+            let protocol = TransmissionProtocol::AtBlock(20);
+            for _ in 0..MaxTransmissionsPerBlock::get() {
+                let nft_id = create_nft(ALICE);
+                let ok =
+                    Transmission::set_transmission(origin(ALICE), nft_id, BOB, protocol.clone());
+                assert_ok!(ok);
+            }
+            let nft_id = create_nft(ALICE);
+            let ok = Transmission::set_transmission(origin(ALICE), nft_id, BOB, protocol);
+            assert_noop!(ok, Error::<Test>::TooManyTransmissionsAtBlock);
+        })
+    }
+}
+
+mod remove_transmission {
+    use super::*;
+
+    #[test]
+    fn remove_transmission() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+
+            assert_ok!(Transmission::remove_transmission(origin(ALICE), nft_id));
+
+            // Storage
+            assert_eq!(Transmission::transmissions(nft_id), None);
+            assert!(Transmission::deadlines(10).is_empty());
+            let is_locked = TernoaNFTs::is_locked(nft_id, NFTLockReason::PendingTransmission);
+            assert_eq!(is_locked, Some(false));
+
+            // Events
+            let event = TransmissionEvent::TransmissionRemoved { nft_id };
+            assert_eq!(last_event(), Event::Transmission(event));
+
+            // Nothing happens at the old deadline
+            run_to_block(10);
+            assert_eq!(TernoaNFTs::owner(nft_id), Some(ALICE));
+        })
+    }
+
+    #[test]
+    fn remove_transmission_unhappy() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+
+            // Unhappy bad origin
+            let ok = Transmission::remove_transmission(RawOrigin::None.into(), nft_id);
+            assert_noop!(ok, BadOrigin);
+
+            // Unhappy no transmission
+            let ok = Transmission::remove_transmission(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::TransmissionNotFound);
+
+            // Unhappy not the NFT owner
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+            let ok = Transmission::remove_transmission(origin(BOB), nft_id);
+            assert_noop!(ok, Error::<Test>::NotTheNFTOwner);
+        })
+    }
+}
+
+mod at_block {
+    use super::*;
+
+    #[test]
+    fn nft_is_transmitted_at_block() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+
+            run_to_block(9);
+            assert_eq!(TernoaNFTs::owner(nft_id), Some(ALICE));
+
+            run_to_block(10);
+
+            // Storage
+            assert_eq!(TernoaNFTs::owner(nft_id), Some(BOB));
+            assert_eq!(Transmission::transmissions(nft_id), None);
+            assert!(Transmission::deadlines(10).is_empty());
+            let is_locked = TernoaNFTs::is_locked(nft_id, NFTLockReason::PendingTransmission);
+            assert_eq!(is_locked, Some(false));
+
+            // Events
+            let event = TransmissionEvent::TransmissionCompleted {
+                nft_id,
+                recipient: BOB,
+            };
+            assert_eq!(last_event(), Event::Transmission(event));
+        })
+    }
+
+    #[test]
+    fn capsule_is_transmitted_with_its_funds() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_capsule(ALICE);
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+
+            // Capsules trade their own lock for the transmission one
+            let data = TransmissionData::new(BOB, protocol, Some(10), true);
+            assert_eq!(Transmission::transmissions(nft_id), Some(data));
+            let is_locked = TernoaNFTs::is_locked(nft_id, NFTLockReason::PendingTransmission);
+            assert_eq!(is_locked, Some(true));
+
+            run_to_block(10);
+
+            assert_eq!(TernoaNFTs::owner(nft_id), Some(BOB));
+            let is_locked = TernoaNFTs::is_locked(nft_id, NFTLockReason::Capsule);
+            assert_eq!(is_locked, Some(true));
+            let capsule = TernoaCapsules::capsules(nft_id).unwrap();
+            assert_eq!(capsule.owner, BOB);
+            assert_eq!(TernoaCapsules::ledgers(ALICE), None);
+            let ledger = TernoaCapsules::ledgers(BOB).unwrap();
+            assert_eq!(ledger.to_vec(), vec![(nft_id, CAPSULE_MINT_FEE)]);
+
+            // Bob can now manage the capsule
+            assert_ok!(TernoaCapsules::remove(origin(BOB), nft_id));
+        })
+    }
+
+    #[test]
+    fn capsule_in_transmission_cannot_be_removed() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_capsule(ALICE);
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+
+            let ok = TernoaCapsules::remove(origin(ALICE), nft_id);
+            assert_noop!(ok, NFTError::<Test>::NFTIsNotLocked);
+            let ok = TernoaNFTs::transfer(origin(ALICE), nft_id, CHARLIE);
+            assert_noop!(ok, NFTError::<Test>::NFTIsInTransmission);
+        })
+    }
+
+    #[test]
+    fn removed_transmission_gives_the_capsule_lock_back() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_capsule(ALICE);
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+            assert_ok!(Transmission::remove_transmission(origin(ALICE), nft_id));
+
+            let is_locked = TernoaNFTs::is_locked(nft_id, NFTLockReason::Capsule);
+            assert_eq!(is_locked, Some(true));
+            assert_ok!(TernoaCapsules::remove(origin(ALICE), nft_id));
+        })
+    }
+}
+
+mod on_inactivity {
+    use super::*;
+
+    #[test]
+    fn nft_is_transmitted_after_inactivity() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            let protocol = TransmissionProtocol::OnInactivity(5);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+            assert_eq!(Transmission::deadlines(6).to_vec(), vec![nft_id]);
+
+            run_to_block(6);
+
+            assert_eq!(TernoaNFTs::owner(nft_id), Some(BOB));
+            assert_eq!(Transmission::transmissions(nft_id), None);
+        })
+    }
+
+    #[test]
+    fn reset_timer() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            let protocol = TransmissionProtocol::OnInactivity(5);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+
+            run_to_block(4);
+            assert_ok!(Transmission::reset_timer(origin(ALICE), nft_id));
+
+            // Storage
+            assert!(Transmission::deadlines(6).is_empty());
+            assert_eq!(Transmission::deadlines(9).to_vec(), vec![nft_id]);
+            let data = Transmission::transmissions(nft_id).unwrap();
+            assert_eq!(data.deadline, Some(9));
+
+            // Events
+            let event = TransmissionEvent::TimerReset {
+                nft_id,
+                deadline: 9,
+            };
+            assert_eq!(last_event(), Event::Transmission(event));
+
+            // The NFT is only transmitted at the new deadline
+            run_to_block(8);
+            assert_eq!(TernoaNFTs::owner(nft_id), Some(ALICE));
+            run_to_block(9);
+            assert_eq!(TernoaNFTs::owner(nft_id), Some(BOB));
+        })
+    }
+
+    #[test]
+    fn reset_timer_unhappy() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+
+            // Unhappy bad origin
+            let ok = Transmission::reset_timer(RawOrigin::None.into(), nft_id);
+            assert_noop!(ok, BadOrigin);
+
+            // Unhappy no transmission
+            let ok = Transmission::reset_timer(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::TransmissionNotFound);
+
+            // Unhappy not an inactivity based transmission
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+            let ok = Transmission::reset_timer(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::TimerCannotBeReset);
+
+            // Unhappy not the NFT owner
+            let ok = Transmission::reset_timer(origin(BOB), nft_id);
+            assert_noop!(ok, Error::<Test>::NotTheNFTOwner);
+            // Unhappy too many transmissions due at the new deadline
+            let nft_id = create_nft(ALICE);
+            let protocol = TransmissionProtocol::OnInactivity(5);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+            for _ in 0..MaxTransmissionsPerBlock::get() {
+                let other = create_nft(ALICE);
+                let protocol = TransmissionProtocol::AtBlock(9);
+                let ok = Transmission::set_transmission(origin(ALICE), other, BOB, protocol);
+                assert_ok!(ok);
+            }
+            run_to_block(4);
+            let ok = Transmission::reset_timer(origin(ALICE), nft_id);
+            assert_noop!(ok, Error::<Test>::TooManyTransmissionsAtBlock);
+        })
+    }
+}
+
+mod on_consent {
+    use super::*;
+
+    #[test]
+    fn nft_is_transmitted_once_threshold_is_reached() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            let protocol = consent(vec![BOB, CHARLIE, DAVE], 2);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+
+            assert_ok!(Transmission::add_consent(origin(CHARLIE), nft_id));
+
+            // Storage
+            let data = Transmission::transmissions(nft_id).unwrap();
+            assert_eq!(data.consents.to_vec(), vec![CHARLIE]);
+            assert_eq!(TernoaNFTs::owner(nft_id), Some(ALICE));
+
+            // Events
+            let event = TransmissionEvent::ConsentAdded {
+                nft_id,
+                guardian: CHARLIE,
+            };
+            assert_eq!(last_event(), Event::Transmission(event));
+
+            assert_ok!(Transmission::add_consent(origin(DAVE), nft_id));
+
+            // Storage
+            assert_eq!(TernoaNFTs::owner(nft_id), Some(BOB));
+            assert_eq!(Transmission::transmissions(nft_id), None);
+
+            // Events
+            let event = TransmissionEvent::TransmissionCompleted {
+                nft_id,
+                recipient: BOB,
+            };
+            assert_eq!(last_event(), Event::Transmission(event));
+        })
+    }
+
+    #[test]
+    fn add_consent_unhappy() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+
+            // Unhappy bad origin
+            let ok = Transmission::add_consent(RawOrigin::None.into(), nft_id);
+            assert_noop!(ok, BadOrigin);
+
+            // Unhappy no transmission
+            let ok = Transmission::add_consent(origin(BOB), nft_id);
+            assert_noop!(ok, Error::<Test>::TransmissionNotFound);
+
+            // Unhappy consent not needed
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+            let ok = Transmission::add_consent(origin(BOB), nft_id);
+            assert_noop!(ok, Error::<Test>::ConsentNotNeeded);
+            assert_ok!(Transmission::remove_transmission(origin(ALICE), nft_id));
+
+            // Unhappy not a guardian
+            let protocol = consent(vec![BOB, CHARLIE], 2);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+            let ok = Transmission::add_consent(origin(DAVE), nft_id);
+            assert_noop!(ok, Error::<Test>::NotAGuardian);
+
+            // Unhappy already consented
+            assert_ok!(Transmission::add_consent(origin(CHARLIE), nft_id));
+            let ok = Transmission::add_consent(origin(CHARLIE), nft_id);
+            assert_noop!(ok, Error::<Test>::AlreadyConsented);
+        })
+    }
+}
+
+mod force_burn {
+    use super::*;
+
+    #[test]
+    fn force_burn_removes_transmission() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_nft(ALICE);
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+
+            assert_ok!(TernoaNFTs::force_burn(RawOrigin::Root.into(), nft_id));
+
+            assert_eq!(Transmission::transmissions(nft_id), None);
+            assert!(Transmission::deadlines(10).is_empty());
+            assert_eq!(TernoaNFTs::get_nft(nft_id), None);
+        })
+    }
+
+    #[test]
+    fn force_burn_refunds_capsule_in_transmission() {
+        ExtBuilder::default().build().execute_with(|| {
+            let nft_id = create_capsule(ALICE);
+            let protocol = TransmissionProtocol::AtBlock(10);
+            assert_ok!(Transmission::set_transmission(
+                origin(ALICE),
+                nft_id,
+                BOB,
+                protocol
+            ));
+            let balance = Balances::free_balance(ALICE);
+
+            assert_ok!(TernoaNFTs::force_burn(RawOrigin::Root.into(), nft_id));
+
+            assert_eq!(Transmission::transmissions(nft_id), None);
+            assert_eq!(TernoaCapsules::capsules(nft_id), None);
+            assert_eq!(TernoaCapsules::ledgers(ALICE), None);
+            assert_eq!(Balances::free_balance(ALICE), balance + CAPSULE_MINT_FEE);
+        })
+    }
+}
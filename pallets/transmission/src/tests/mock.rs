@@ -0,0 +1,220 @@
+use crate::{self as ternoa_transmission, Config};
+use frame_support::traits::{ConstU32, Contains, GenesisBuild, OnFinalize, OnInitialize};
+use frame_support::{parameter_types, BoundedVec, PalletId};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::testing::Header;
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use sp_runtime::Permill;
+use std::convert::TryInto;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+        TernoaNFTs: ternoa_nfts::{Pallet, Call, Storage, Event<T>, Config<T>},
+        TernoaCapsules: ternoa_capsules::{Pallet, Call, Storage, Event<T>, Config<T>},
+        Transmission: ternoa_transmission::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+pub struct TestBaseCallFilter;
+impl Contains<Call> for TestBaseCallFilter {
+    fn contains(c: &Call) -> bool {
+        match *c {
+            // For benchmarking, this acts as a noop call
+            Call::System(frame_system::Call::remark { .. }) => true,
+            // For tests
+            _ => false,
+        }
+    }
+}
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub BlockWeights: frame_system::limits::BlockWeights =
+        frame_system::limits::BlockWeights::simple_max(1024);
+}
+impl frame_system::Config for Test {
+    type BaseCallFilter = TestBaseCallFilter;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Call = Call;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u128>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u128 = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = MaxLocks;
+    type MaxReserves = MaxReserves;
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u128;
+    type DustRemoval = ();
+    type Event = Event;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MinIpfsLen: u16 = 1;
+    pub const MaxIpfsLen: u16 = 5;
+    pub const MaxSeriesIdLen: u16 = 5;
+    pub const BatchMintDiscount: Permill = Permill::from_percent(0);
+    pub const UseStorageDeposits: bool = false;
+    pub const DepositBase: u128 = 0;
+    pub const DepositPerByte: u128 = 0;
+    pub const DepositFollowsOwner: bool = true;
+}
+
+impl ternoa_nfts::Config for Test {
+    type Event = Event;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type FeesCollector = ();
+    type MinIpfsLen = MinIpfsLen;
+    type MaxIpfsLen = MaxIpfsLen;
+    type IPFSReferenceValidator = ();
+    type MaxSeriesIdLen = MaxSeriesIdLen;
+    type MaxBatchSize = ConstU32<5>;
+    type BatchMintDiscount = BatchMintDiscount;
+    type UseStorageDeposits = UseStorageDeposits;
+    type DepositBase = DepositBase;
+    type DepositPerByte = DepositPerByte;
+    type DepositFollowsOwner = DepositFollowsOwner;
+    type ForceOrigin = EnsureRoot<u64>;
+    type NFTReleaseHandler = (TernoaCapsules, Transmission);
+    type MaxRentalsPerBlock = ConstU32<5>;
+}
+
+parameter_types! {
+    pub const CapsulePalletId: PalletId = PalletId(*b"mockcaps");
+}
+
+impl ternoa_capsules::Config for Test {
+    type Event = Event;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type NFTTrait = TernoaNFTs;
+    type PalletId = CapsulePalletId;
+    type MinIpfsLen = MinIpfsLen;
+    type MaxIpfsLen = MaxIpfsLen;
+    type IPFSReferenceValidator = ();
+    type MaxCapsulesPerAccount = ConstU32<2>;
+}
+
+parameter_types! {
+    pub const MaxGuardians: u32 = 3;
+    pub const MaxBlockDuration: u64 = 100;
+    pub const MaxTransmissionsPerBlock: u32 = 2;
+}
+
+impl Config for Test {
+    type Event = Event;
+    type WeightInfo = ();
+    type NFTs = TernoaNFTs;
+    type Capsules = TernoaCapsules;
+    type MaxGuardians = MaxGuardians;
+    type MaxBlockDuration = MaxBlockDuration;
+    type MaxTransmissionsPerBlock = MaxTransmissionsPerBlock;
+}
+
+// Do not use the `0` account id since this would be the default value
+// for our account id. This would mess with some tests.
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+pub const DAVE: u64 = 4;
+
+pub const CAPSULE_MINT_FEE: u128 = 100;
+
+pub struct ExtBuilder {
+    endowed_accounts: Vec<(u64, u128)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        ExtBuilder {
+            endowed_accounts: vec![(ALICE, 1000), (BOB, 1000)],
+        }
+    }
+}
+
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        pallet_balances::GenesisConfig::<Test> {
+            balances: self.endowed_accounts,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        ternoa_nfts::GenesisConfig::<Test> {
+            nfts: Default::default(),
+            series: Default::default(),
+            nft_mint_fee: 10,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        ternoa_capsules::GenesisConfig::<Test> {
+            capsule_mint_fee: CAPSULE_MINT_FEE,
+            ..Default::default()
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        let mut ext = sp_io::TestExternalities::new(t);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+}
+
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        Transmission::on_finalize(System::block_number());
+        System::on_finalize(System::block_number());
+        System::set_block_number(System::block_number() + 1);
+        System::on_initialize(System::block_number());
+        Transmission::on_initialize(System::block_number());
+    }
+}
+
+/// Turn a vector into one of the bounded vectors that are stored on chain.
+pub fn bounded<T>(value: Vec<T>) -> BoundedVec<T, MaxGuardians> {
+    value.try_into().unwrap()
+}
@@ -0,0 +1,90 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use frame_support::{BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
+use scale_info::TypeInfo;
+use sp_std::fmt::Debug;
+
+/// Condition under which an NFT is handed over to its recipient.
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    RuntimeDebugNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxGuardians))]
+#[codec(mel_bound(AccountId: MaxEncodedLen, BlockNumber: MaxEncodedLen))]
+pub enum TransmissionProtocol<AccountId, BlockNumber, MaxGuardians>
+where
+    AccountId: Clone + PartialEq + Debug,
+    BlockNumber: Clone + PartialEq + Debug,
+    MaxGuardians: Get<u32>,
+{
+    /// The NFT is transmitted at the given block.
+    AtBlock(BlockNumber),
+    /// The NFT is transmitted once its owner has been inactive for the given
+    /// number of blocks. The owner can reset the countdown at any time.
+    OnInactivity(BlockNumber),
+    /// The NFT is transmitted once `threshold` of the guardians consented.
+    OnConsent {
+        guardians: BoundedVec<AccountId, MaxGuardians>,
+        threshold: u32,
+    },
+}
+
+/// An NFT waiting to be transmitted.
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    RuntimeDebugNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxGuardians))]
+#[codec(mel_bound(AccountId: MaxEncodedLen, BlockNumber: MaxEncodedLen))]
+pub struct TransmissionData<AccountId, BlockNumber, MaxGuardians>
+where
+    AccountId: Clone + PartialEq + Debug,
+    BlockNumber: Clone + PartialEq + Debug,
+    MaxGuardians: Get<u32>,
+{
+    // Account that receives the NFT
+    pub recipient: AccountId,
+    // Condition that triggers the transmission
+    pub protocol: TransmissionProtocol<AccountId, BlockNumber, MaxGuardians>,
+    // Block at which the NFT is transmitted, for the protocols based on time
+    pub deadline: Option<BlockNumber>,
+    // Guardians that consented to the transmission
+    pub consents: BoundedVec<AccountId, MaxGuardians>,
+    // Whether the NFT is a capsule, which gets its capsule lock back once the
+    // transmission is over
+    pub capsule: bool,
+}
+
+impl<AccountId, BlockNumber, MaxGuardians> TransmissionData<AccountId, BlockNumber, MaxGuardians>
+where
+    AccountId: Clone + PartialEq + Debug,
+    BlockNumber: Clone + PartialEq + Debug,
+    MaxGuardians: Get<u32>,
+{
+    pub fn new(
+        recipient: AccountId,
+        protocol: TransmissionProtocol<AccountId, BlockNumber, MaxGuardians>,
+        deadline: Option<BlockNumber>,
+        capsule: bool,
+    ) -> Self {
+        Self {
+            recipient,
+            protocol,
+            deadline,
+            consents: Default::default(),
+            capsule,
+        }
+    }
+}
@@ -2,7 +2,9 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use frame_support::{BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -10,8 +12,9 @@ use sp_runtime::RuntimeDebug;
 use sp_runtime::{
     generic,
     traits::{BlakeTwo256, IdentifyAccount, Verify},
-    MultiSignature, OpaqueExtrinsic,
+    MultiSignature, OpaqueExtrinsic, Permill,
 };
+use sp_std::fmt::Debug;
 use sp_std::vec::Vec;
 
 /// An index to a block.
@@ -53,48 +56,194 @@ pub mod marketplace {
     /// The type of marketplace Id
     pub type MarketplaceId = u32;
 
-    /// Type of marketplace commission
-    pub type MarketplaceCommission = u8;
+    /// Commission taken by a marketplace on each sale: a share of the price
+    /// plus a flat amount.
+    #[derive(
+        Encode, Decode, Copy, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct MarketplaceCommission<Balance> {
+        pub rate: Permill,
+        pub flat: Balance,
+    }
+
+    impl<Balance> MarketplaceCommission<Balance> {
+        pub fn new(rate: Permill, flat: Balance) -> Self {
+            Self { rate, flat }
+        }
+    }
+
+    /// Token a sale is paid with: the native token of the chain or one of
+    /// the assets of the assets pallet.
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub enum PaymentAsset<AssetId> {
+        Native,
+        Asset(AssetId),
+    }
+
+    impl<AssetId> Default for PaymentAsset<AssetId> {
+        fn default() -> Self {
+            Self::Native
+        }
+    }
 
-    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
     pub enum MarketplaceType {
         Public,
         Private,
     }
 
-    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    /// Role that the owner of a marketplace can grant to other accounts.
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub enum MarketplaceRole {
+        /// Can change the metadata and the account lists of the marketplace.
+        Admin,
+        /// Can take down listings of the marketplace and cancel its auctions
+        /// before they start.
+        Curator,
+    }
+
+    impl MarketplaceRole {
+        /// Return whether this role also grants the rights of `role`. Admins can
+        /// do everything curators can.
+        pub fn includes(&self, role: MarketplaceRole) -> bool {
+            *self == role || *self == MarketplaceRole::Admin
+        }
+    }
+
+    /// Nonce of an order, unique among the orders of a seller.
+    pub type OrderNonce = u64;
+
+    /// Sale of an NFT that its owner signs off-chain instead of listing it.
+    /// Anyone holding the order and the signature of its signing payload can
+    /// buy the NFT on chain until the order expires.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-    pub struct MarketplaceInformation<AccountId> {
+    pub struct Order<Balance, BlockNumber> {
+        pub nft_id: crate::nfts::NFTId,
+        /// Price in Caps.
+        pub price: Balance,
+        pub marketplace_id: MarketplaceId,
+        /// Highest commission the seller agreed to. The order cannot be filled
+        /// while the marketplace takes more.
+        pub max_commission: MarketplaceCommission<Balance>,
+        /// Last block at which the order can be filled.
+        pub expiry: BlockNumber,
+        pub nonce: OrderNonce,
+    }
+
+    impl<Balance: Encode, BlockNumber: Encode> Order<Balance, BlockNumber> {
+        /// Message that the seller signs, only valid on the chain of `genesis_hash`.
+        pub fn signing_payload<Hash: Encode>(&self, genesis_hash: &Hash) -> Vec<u8> {
+            (b"order", genesis_hash, self).encode()
+        }
+    }
+
+    /// Mint of an NFT that its creator signs off-chain. The NFT is only created,
+    /// and the mint fee only paid, once someone buys it on chain.
+    #[derive(
+        Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo,
+    )]
+    #[scale_info(skip_type_params(IPFSLengthLimit))]
+    pub struct MintVoucher<Balance, BlockNumber, IPFSLengthLimit>
+    where
+        Balance: Clone + PartialEq + Debug,
+        BlockNumber: Clone + PartialEq + Debug,
+        IPFSLengthLimit: Get<u32>,
+    {
+        pub ipfs_reference: BoundedVec<u8, IPFSLengthLimit>,
+        /// Series of the NFT, completed once the NFT is minted. Without one, the
+        /// NFT gets a series of its own.
+        pub series_id: Option<crate::nfts::NFTSeriesId>,
+        /// Price in Caps. The mint fee is paid out of it.
+        pub price: Balance,
+        /// Share of the secondary sales paid back to the creator.
+        pub royalty: Permill,
+        /// Last block at which the voucher can be redeemed.
+        pub expiry: BlockNumber,
+        /// Vouchers share their nonces with the orders of their creator.
+        pub nonce: OrderNonce,
+    }
+
+    impl<Balance, BlockNumber, IPFSLengthLimit> MintVoucher<Balance, BlockNumber, IPFSLengthLimit>
+    where
+        Balance: Clone + PartialEq + Debug + Encode,
+        BlockNumber: Clone + PartialEq + Debug + Encode,
+        IPFSLengthLimit: Get<u32>,
+    {
+        /// Message that the creator signs. Its tag differs from the one of
+        /// orders, so an order signature never redeems a voucher.
+        pub fn signing_payload<Hash: Encode>(&self, genesis_hash: &Hash) -> Vec<u8> {
+            (b"mint-voucher", genesis_hash, self).encode()
+        }
+    }
+
+    #[derive(
+        Encode,
+        Decode,
+        CloneNoBound,
+        PartialEqNoBound,
+        EqNoBound,
+        RuntimeDebugNoBound,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(NameLengthLimit, URILengthLimit, DescriptionLengthLimit))]
+    #[codec(mel_bound(AccountId: MaxEncodedLen, Balance: MaxEncodedLen))]
+    pub struct MarketplaceInformation<
+        AccountId,
+        Balance,
+        NameLengthLimit,
+        URILengthLimit,
+        DescriptionLengthLimit,
+    >
+    where
+        AccountId: Clone + PartialEq + Debug,
+        Balance: Clone + PartialEq + Debug,
+        NameLengthLimit: Get<u32>,
+        URILengthLimit: Get<u32>,
+        DescriptionLengthLimit: Get<u32>,
+    {
         pub kind: MarketplaceType,
-        pub commission_fee: MarketplaceCommission,
+        pub commission_fee: MarketplaceCommission<Balance>,
         pub owner: AccountId,
-        pub allow_list: Vec<AccountId>,
-        pub disallow_list: Vec<AccountId>,
-        pub name: TextFormat,
-        pub uri: Option<TextFormat>,
-        pub logo_uri: Option<TextFormat>,
-        pub description: Option<TextFormat>,
+        pub name: BoundedVec<u8, NameLengthLimit>,
+        pub uri: Option<BoundedVec<u8, URILengthLimit>>,
+        pub logo_uri: Option<BoundedVec<u8, URILengthLimit>>,
+        pub description: Option<BoundedVec<u8, DescriptionLengthLimit>>,
     }
 
-    impl<AccountId> MarketplaceInformation<AccountId> {
+    impl<AccountId, Balance, NameLengthLimit, URILengthLimit, DescriptionLengthLimit>
+        MarketplaceInformation<
+            AccountId,
+            Balance,
+            NameLengthLimit,
+            URILengthLimit,
+            DescriptionLengthLimit,
+        >
+    where
+        AccountId: Clone + PartialEq + Debug,
+        Balance: Clone + PartialEq + Debug,
+        NameLengthLimit: Get<u32>,
+        URILengthLimit: Get<u32>,
+        DescriptionLengthLimit: Get<u32>,
+    {
         pub fn new(
             kind: MarketplaceType,
-            commission_fee: MarketplaceCommission,
+            commission_fee: MarketplaceCommission<Balance>,
             owner: AccountId,
-            allow_list: Vec<AccountId>,
-            disallow_list: Vec<AccountId>,
-            name: TextFormat,
-            uri: Option<TextFormat>,
-            logo_uri: Option<TextFormat>,
-            description: Option<TextFormat>,
-        ) -> MarketplaceInformation<AccountId> {
+            name: BoundedVec<u8, NameLengthLimit>,
+            uri: Option<BoundedVec<u8, URILengthLimit>>,
+            logo_uri: Option<BoundedVec<u8, URILengthLimit>>,
+            description: Option<BoundedVec<u8, DescriptionLengthLimit>>,
+        ) -> Self {
             Self {
                 kind,
                 commission_fee,
                 owner,
-                allow_list,
-                disallow_list,
                 name,
                 uri,
                 logo_uri,
@@ -113,77 +262,120 @@ pub mod nfts {
     /// How NFT IDs are encoded. In the JSON Types this should be "Text" and not "Vec<8>".
     pub type NFTSeriesId = Vec<u8>;
 
-    /// Data related to an NFT, such as who is its owner.
-    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+    /// Reason for which an NFT is locked. A locked NFT cannot be transferred or
+    /// burned, and it cannot be locked again until the current lock is removed.
+    #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-    pub struct NFTData<AccountId>
+    pub enum NFTLockReason {
+        /// The NFT is listed for sale on a marketplace or in an auction.
+        ListedForSale,
+        /// The NFT is held by a timed escrow until its transfer block.
+        InTransmission,
+        /// The NFT has been converted to a capsule.
+        Capsule,
+        /// The NFT is rented out to a viewer.
+        Rented,
+        /// The NFT has been split into fungible shares.
+        Fractionalized,
+        /// The NFT has been frozen by governance.
+        Frozen,
+        /// The NFT, capsule or not, waits to be handed over by a transmission.
+        PendingTransmission,
+    }
+
+    /// Data related to an NFT, such as who is its owner.
+    #[derive(
+        Encode,
+        Decode,
+        CloneNoBound,
+        PartialEqNoBound,
+        EqNoBound,
+        RuntimeDebugNoBound,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(IPFSLengthLimit, SeriesIdLengthLimit))]
+    #[codec(mel_bound(AccountId: MaxEncodedLen))]
+    pub struct NFTData<AccountId, IPFSLengthLimit, SeriesIdLengthLimit>
     where
-        AccountId: Clone,
+        AccountId: Clone + PartialEq + Debug,
+        IPFSLengthLimit: Get<u32>,
+        SeriesIdLengthLimit: Get<u32>,
     {
         // NFT owner
         pub owner: AccountId,
         // NFT creator
         pub creator: AccountId,
         // IPFS reference
-        pub ipfs_reference: TextFormat,
+        pub ipfs_reference: BoundedVec<u8, IPFSLengthLimit>,
         // Series ID
-        pub series_id: NFTSeriesId,
-        // Is listed for sale
-        pub listed_for_sale: bool,
-        // Is being transmitted
-        pub in_transmission: bool,
-        // Is NFT converted to capsule
-        pub converted_to_capsule: bool,
+        pub series_id: BoundedVec<u8, SeriesIdLengthLimit>,
+        // Why the NFT is locked, if it is
+        pub lock: Option<NFTLockReason>,
         // NFT Viewer
         pub viewer: Option<AccountId>,
+        // Share of every secondary sale that goes to the creator
+        pub royalty: Permill,
     }
 
-    impl<AccountId> NFTData<AccountId>
+    impl<AccountId, IPFSLengthLimit, SeriesIdLengthLimit>
+        NFTData<AccountId, IPFSLengthLimit, SeriesIdLengthLimit>
     where
-        AccountId: Clone,
+        AccountId: Clone + PartialEq + Debug,
+        IPFSLengthLimit: Get<u32>,
+        SeriesIdLengthLimit: Get<u32>,
     {
         pub fn new(
             owner: AccountId,
             creator: AccountId,
-            ipfs_reference: TextFormat,
-            series_id: NFTSeriesId,
-            listed_for_sale: bool,
-            in_transmission: bool,
-            converted_to_capsule: bool,
+            ipfs_reference: BoundedVec<u8, IPFSLengthLimit>,
+            series_id: BoundedVec<u8, SeriesIdLengthLimit>,
+            lock: Option<NFTLockReason>,
             viewer: Option<AccountId>,
+            royalty: Permill,
         ) -> Self {
             Self {
                 owner,
                 creator,
                 ipfs_reference,
                 series_id,
-                listed_for_sale,
-                in_transmission,
-                converted_to_capsule,
+                lock,
                 viewer,
+                royalty,
             }
         }
 
         pub fn new_default(
             owner: AccountId,
-            ipfs_reference: TextFormat,
-            series_id: NFTSeriesId,
+            ipfs_reference: BoundedVec<u8, IPFSLengthLimit>,
+            series_id: BoundedVec<u8, SeriesIdLengthLimit>,
         ) -> Self {
             Self::new(
                 owner.clone(),
                 owner,
                 ipfs_reference,
                 series_id,
-                false,
-                false,
-                false,
                 None,
+                None,
+                Permill::zero(),
             )
         }
+
+        /// Return whether the NFT is locked, whatever the reason.
+        pub fn is_locked(&self) -> bool {
+            self.lock.is_some()
+        }
+
+        /// Return whether the NFT is locked for the given reason.
+        pub fn is_locked_for(&self, reason: NFTLockReason) -> bool {
+            self.lock == Some(reason)
+        }
     }
 
     /// Data related to an NFT Series.
-    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+    #[derive(
+        Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
     #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
     pub struct NFTSeriesDetails<AccountId> {
         pub owner: AccountId, // Series Owner
@@ -195,4 +387,72 @@ pub mod nfts {
             Self { owner, draft }
         }
     }
+
+    /// Terms under which an NFT owner offers to rent it out.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct RentalTerms<AccountId, BlockNumber, Balance> {
+        // NFT owner at the time of the offer
+        pub owner: AccountId,
+        // Paid to the owner for every rented block
+        pub price_per_block: Balance,
+        // Number of blocks the rental lasts
+        pub duration: BlockNumber,
+        // Held on top of the price and given back at the end
+        pub deposit: Balance,
+    }
+
+    impl<AccountId, BlockNumber, Balance> RentalTerms<AccountId, BlockNumber, Balance> {
+        pub fn new(
+            owner: AccountId,
+            price_per_block: Balance,
+            duration: BlockNumber,
+            deposit: Balance,
+        ) -> Self {
+            Self {
+                owner,
+                price_per_block,
+                duration,
+                deposit,
+            }
+        }
+    }
+
+    /// An ongoing rental of an NFT.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct Rental<AccountId, BlockNumber, Balance> {
+        // NFT owner, receives the rent
+        pub owner: AccountId,
+        // Viewer of the NFT until the rental ends
+        pub renter: AccountId,
+        // Paid to the owner for every rented block
+        pub price_per_block: Balance,
+        // Block at which the rental started
+        pub start_block: BlockNumber,
+        // Block at which the NFT goes back to its owner
+        pub end_block: BlockNumber,
+        // Held on top of the price and given back at the end
+        pub deposit: Balance,
+    }
+
+    impl<AccountId, BlockNumber, Balance> Rental<AccountId, BlockNumber, Balance> {
+        pub fn new(
+            owner: AccountId,
+            renter: AccountId,
+            price_per_block: Balance,
+            start_block: BlockNumber,
+            end_block: BlockNumber,
+            deposit: Balance,
+        ) -> Self {
+            Self {
+                owner,
+                renter,
+                price_per_block,
+                start_block,
+                end_block,
+                deposit,
+            }
+        }
+    }
 }
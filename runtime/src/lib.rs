@@ -21,6 +21,7 @@ use sp_runtime::transaction_validity::{TransactionSource, TransactionValidity};
 use sp_runtime::{generic, ApplyExtrinsicResult};
 use sp_std::prelude::*;
 use sp_version::RuntimeVersion;
+use ternoa_primitives::nfts::{NFTId, NFTSeriesId};
 use ternoa_primitives::{AccountId, Balance, BlockNumber, Index, Signature};
 
 pub mod constants;
@@ -98,6 +99,8 @@ construct_runtime!(
 
         Multisig: pallet_multisig,
         Preimage: pallet_preimage,
+        Scheduler: pallet_scheduler,
+        Assets: pallet_assets,
 
         // Ternoa pallets.  Start indices at 100 to leave room.
         Nfts: ternoa_nfts = 100,
@@ -105,6 +108,9 @@ construct_runtime!(
         // Capsules: ternoa_capsules = 102,
         // Marketplace: ternoa_marketplace = 103,
         // Auctions: ternoa_auctions = 104,
+        Fractionalization: ternoa_fractionalization = 105,
+        Transmission: ternoa_transmission = 106,
+        TimedEscrow: ternoa_timed_escrow = 107,
     }
 );
 
@@ -316,6 +322,20 @@ impl_runtime_apis! {
         }
     }
 
+    impl ternoa_nfts_rpc_runtime_api::NFTsApi<Block, AccountId> for Runtime {
+        fn nfts_by_owner(owner: AccountId, start_after: Option<NFTId>, limit: u32) -> Vec<NFTId> {
+            Nfts::nfts_by_owner(&owner, start_after, limit)
+        }
+
+        fn nfts_by_series(
+            series_id: NFTSeriesId,
+            start_after: Option<NFTId>,
+            limit: u32,
+        ) -> Vec<NFTId> {
+            Nfts::nfts_by_series(&series_id, start_after, limit)
+        }
+    }
+
     impl sp_session::SessionKeys<Block> for Runtime {
         fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
             SessionKeys::generate(seed)
@@ -413,6 +433,9 @@ mod benches {
         // the that path resolves correctly in the generated file.
         [ternoa_nfts, Nfts]
         [ternoa_associated_accounts, AssociatedAccounts]
+        [ternoa_fractionalization, Fractionalization]
+        [ternoa_transmission, Transmission]
+        [ternoa_timed_escrow, TimedEscrow]
         // Substrate
         [pallet_babe, Babe]
         [pallet_timestamp, Timestamp]
@@ -434,6 +457,8 @@ mod benches {
         [pallet_bags_list, BagsList]
         // [pallet_multisig, Multisig]
         // [pallet_preimage, Preimage]
+        [pallet_scheduler, Scheduler]
+        [pallet_assets, Assets]
         [frame_benchmarking::baseline, Baseline::<Runtime>]
         [frame_system, SystemBench::<Runtime>]
     );
@@ -4,15 +4,16 @@ use crate::constants::time::{
 };
 use crate::{
     voter_bags, AuthorityDiscovery, Babe, BagsList, Balances, Call, ElectionProviderMultiPhase,
-    Event, Grandpa, Historical, ImOnline, Offences, Origin, OriginCaller, PalletInfo, Runtime,
-    Session, Signature, SignedPayload, Staking, System, Timestamp, TransactionPayment, Treasury,
-    UncheckedExtrinsic, VERSION,
+    Event, Fractionalization, Grandpa, Historical, ImOnline, Nfts, Offences, Origin, OriginCaller,
+    PalletInfo, Preimage, Runtime, Scheduler, Session, Signature, SignedPayload, Staking, System,
+    TimedEscrow, Timestamp, TransactionPayment, Transmission, Treasury, UncheckedExtrinsic,
+    VERSION,
 };
 use codec::{Decode, Encode};
 use frame_election_provider_support::onchain;
 use frame_support::traits::{
-    ConstU32, Currency, Imbalance, KeyOwnerProofSystem, LockIdentifier, OnUnbalanced,
-    U128CurrencyToVote,
+    ConstU32, Currency, EqualPrivilegeOnly, Imbalance, KeyOwnerProofSystem, LockIdentifier,
+    OnUnbalanced, U128CurrencyToVote,
 };
 use frame_support::weights::constants::{
     BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND,
@@ -36,6 +37,7 @@ use sp_std::vec;
 use sp_std::vec::Vec;
 use sp_version::RuntimeVersion;
 use static_assertions::const_assert;
+use ternoa_common::cid::IPFSReference;
 use ternoa_primitives::{AccountId, AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
 
 #[cfg(any(feature = "std", test))]
@@ -190,6 +192,14 @@ parameter_types! {
     // Min Max string length
     pub const NFTsMinIpfsLen: u16 = 1;
     pub const NFTsMaxIpfsLen: u16 = 256;
+    pub const NFTsMaxSeriesIdLen: u16 = 256;
+    pub const NFTsMaxBatchSize: u32 = 1000;
+    pub const NFTsBatchMintDiscount: Permill = Permill::from_percent(0);
+    pub const NFTsUseStorageDeposits: bool = true;
+    pub const NFTsDepositBase: Balance = 1 * EUROS;
+    pub const NFTsDepositPerByte: Balance = 1 * CENTS;
+    pub const NFTsDepositFollowsOwner: bool = true;
+    pub const NFTsMaxRentalsPerBlock: u32 = 1_000;
 }
 
 // NFTs
@@ -200,6 +210,18 @@ impl ternoa_nfts::Config for Runtime {
     type FeesCollector = Treasury;
     type MinIpfsLen = NFTsMinIpfsLen;
     type MaxIpfsLen = NFTsMaxIpfsLen;
+    type IPFSReferenceValidator = IPFSReference;
+    type MaxSeriesIdLen = NFTsMaxSeriesIdLen;
+    type MaxBatchSize = NFTsMaxBatchSize;
+    type BatchMintDiscount = NFTsBatchMintDiscount;
+    type UseStorageDeposits = NFTsUseStorageDeposits;
+    type DepositBase = NFTsDepositBase;
+    type DepositPerByte = NFTsDepositPerByte;
+    type DepositFollowsOwner = NFTsDepositFollowsOwner;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    // Marketplace, Auctions and Capsules need to be added once they are enabled.
+    type NFTReleaseHandler = (Fractionalization, Transmission, TimedEscrow);
+    type MaxRentalsPerBlock = NFTsMaxRentalsPerBlock;
 }
 
 /* parameter_types! {
@@ -210,6 +232,11 @@ impl ternoa_nfts::Config for Runtime {
     pub const MaxMarketplaceDescriptionLen: u16 = 512;
     pub const MinUriLen: u16 = 1;
     pub const MaxUriLen: u16 = 256;
+    pub const MaxAccountsPerCall: u32 = 1_000;
+    pub const MaxListingsPerBlock: u32 = 1_000;
+    pub const MaxOffersPerBlock: u32 = 1_000;
+    pub const MaxAcceptedAssets: u32 = 10;
+    pub const MaxBundleSize: u32 = 50;
 }
 
 // Marketplace
@@ -217,6 +244,7 @@ impl ternoa_marketplace::Config for Runtime {
     type Event = Event;
     type Currency = Balances;
     type NFTs = Nfts;
+    type MarketplaceCloseHandler = Auctions;
     type WeightInfo = ();
     type FeesCollector = Treasury;
     type MinNameLen = MinMarketplaceNameLen;
@@ -225,12 +253,41 @@ impl ternoa_marketplace::Config for Runtime {
     type MaxDescriptionLen = MaxMarketplaceDescriptionLen;
     type MinUriLen = MinUriLen;
     type MaxUriLen = MaxUriLen;
+    type MaxAccountsPerCall = MaxAccountsPerCall;
+    type MaxListingsPerBlock = MaxListingsPerBlock;
+    type MaxOffersPerBlock = MaxOffersPerBlock;
+    type AssetId = u32;
+    type Assets = Assets;
+    type MaxAcceptedAssets = MaxAcceptedAssets;
+    type MaxBundleSize = MaxBundleSize;
+    type OrderSignature = Signature;
+    type OrderSigner = <Signature as sp_runtime::traits::Verify>::Signer;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = OrderSigning;
+}
+
+/// Signs orders in benchmarks with a key of the benchmark keystore.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct OrderSigning;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl ternoa_marketplace::BenchmarkHelper<AccountId, Signature> for OrderSigning {
+    fn sign(message: &[u8]) -> (AccountId, Signature) {
+        use sp_runtime::traits::IdentifyAccount;
+
+        let key_type = KeyTypeId(*b"ordr");
+        let public = sp_io::crypto::sr25519_generate(key_type, None);
+        let signature = sp_io::crypto::sr25519_sign(key_type, &public, message).unwrap();
+        let account = sp_runtime::MultiSigner::from(public).into_account();
+        (account, signature.into())
+    }
 } */
 
 /* parameter_types! {
     pub const CapsulePalletId: PalletId = PalletId(*b"tcapsule");
     pub const CapsuleMinIpfsLen: u16 = 1;
     pub const CapsuleMaxIpfsLen: u16 = 256;
+    pub const MaxCapsulesPerAccount: u32 = 10_000;
 }
 
 // Capsules
@@ -242,16 +299,61 @@ impl ternoa_capsules::Config for Runtime {
     type PalletId = CapsulePalletId;
     type MinIpfsLen = CapsuleMinIpfsLen;
     type MaxIpfsLen = CapsuleMaxIpfsLen;
+    type IPFSReferenceValidator = IPFSReference;
+    type MaxCapsulesPerAccount = MaxCapsulesPerAccount;
 } */
 
 parameter_types! {
     pub const MinAltvrUsernameLen: u16 = 1;     // AltVR says that the minimum is 8
     pub const MaxAltvrUsernameLen: u16 = 32;    // AltVR says that the maximum is 20
+    pub const AssociatedAccountsMaxKeyLen: u16 = 32;
+    pub const AssociatedAccountsMaxValueLen: u16 = 256;
+    pub const MaxSupportedAccounts: u32 = 32;
 }
 
 impl ternoa_associated_accounts::Config for Runtime {
     type Event = Event;
     type WeightInfo = ();
+    type MaxKeyLen = AssociatedAccountsMaxKeyLen;
+    type MaxValueLen = AssociatedAccountsMaxValueLen;
+    type MaxSupportedAccounts = MaxSupportedAccounts;
+}
+
+parameter_types! {
+    pub const FractionalizationMaxShareholders: u32 = 1_000;
+}
+
+impl ternoa_fractionalization::Config for Runtime {
+    type Event = Event;
+    type WeightInfo = ();
+    type NFTs = Nfts;
+    type MaxShareholders = FractionalizationMaxShareholders;
+}
+
+parameter_types! {
+    pub const TransmissionMaxGuardians: u32 = 10;
+    pub const TransmissionMaxBlockDuration: BlockNumber = 365 * DAYS;
+    pub const TransmissionMaxPerBlock: u32 = 100;
+}
+
+impl ternoa_transmission::Config for Runtime {
+    type Event = Event;
+    type WeightInfo = ();
+    type NFTs = Nfts;
+    // Needs to be set to Capsules once they are enabled.
+    type Capsules = ();
+    type MaxGuardians = TransmissionMaxGuardians;
+    type MaxBlockDuration = TransmissionMaxBlockDuration;
+    type MaxTransmissionsPerBlock = TransmissionMaxPerBlock;
+}
+
+impl ternoa_timed_escrow::Config for Runtime {
+    type Event = Event;
+    type NFTs = Nfts;
+    type Scheduler = Scheduler;
+    type PalletsOrigin = OriginCaller;
+    type PalletsCall = Call;
+    type WeightInfo = ();
 }
 
 parameter_types! {
@@ -762,6 +864,53 @@ impl pallet_preimage::Config for Runtime {
     type ByteDeposit = PreimageByteDeposit;
 }
 
+parameter_types! {
+    pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+        RuntimeBlockWeights::get().max_block;
+    pub const MaxScheduledPerBlock: u32 = 50;
+    pub const NoPreimagePostponement: Option<u32> = Some(10);
+}
+
+impl pallet_scheduler::Config for Runtime {
+    type Event = Event;
+    type Origin = Origin;
+    type PalletsOrigin = OriginCaller;
+    type Call = Call;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type ScheduleOrigin = EnsureRoot<AccountId>;
+    type MaxScheduledPerBlock = MaxScheduledPerBlock;
+    type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+    type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type PreimageProvider = Preimage;
+    type NoPreimagePostponement = NoPreimagePostponement;
+}
+
+parameter_types! {
+    pub const AssetDeposit: Balance = 100 * EUROS;
+    pub const AssetAccountDeposit: Balance = deposit(1, 16);
+    pub const ApprovalDeposit: Balance = 1 * EUROS;
+    pub const AssetsStringLimit: u32 = 50;
+    pub const MetadataDepositBase: Balance = deposit(1, 68);
+    pub const MetadataDepositPerByte: Balance = deposit(0, 1);
+}
+
+impl pallet_assets::Config for Runtime {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type AssetAccountDeposit = AssetAccountDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = AssetsStringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
 /* parameter_types! {
     // all calculations assume blocktime of 6secs
     // min auction duration of 24 hours (24*60*60)/6
@@ -775,11 +924,15 @@ impl pallet_preimage::Config for Runtime {
     // auction ending period of 12 hr (12*60*60)/6
     pub const AuctionEndingPeriod: BlockNumber = 7200;
     pub const AuctionsPalletId: PalletId = PalletId(*b"tauction");
+    pub const MaxBidHistorySize: u32 = 25;
+    pub const MaxAuctions: u32 = 10_000;
 }
 
 impl ternoa_auctions::Config for Runtime {
     type Event = Event;
     type Currency = Balances;
+    type AssetId = u32;
+    type Assets = Assets;
     type NFTHandler = Nfts;
     type MarketplaceHandler = Marketplace;
     type MaxAuctionDelay = MaxAuctionDelay;
@@ -789,5 +942,7 @@ impl ternoa_auctions::Config for Runtime {
     type AuctionEndingPeriod = AuctionEndingPeriod;
     type PalletId = AuctionsPalletId;
     type WeightInfo = ();
+    type MaxBidHistorySize = MaxBidHistorySize;
+    type MaxAuctions = MaxAuctions;
 }
  */